@@ -0,0 +1,108 @@
+//! Calendar archives of items.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{collections::BTreeMap, string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{date::Timestamp, Error, Feed, FeedMut, FeedRef, ItemRef};
+
+fn archive_map(
+    map: &Map<String, Value>,
+) -> Result<BTreeMap<i64, BTreeMap<u32, Vec<ItemRef<'_>>>>, Error> {
+    let mut dated = Vec::new();
+    for item in crate::item_refs(map)? {
+        if let Some(Ok(published)) = item.date_published()?.map(Timestamp::parse) {
+            dated.push((published, item));
+        }
+    }
+    dated.sort_by_key(|(published, _)| *published);
+
+    let mut archive = BTreeMap::new();
+    for (published, item) in dated {
+        let (year, month, _) = published.date();
+        archive
+            .entry(year)
+            .or_insert_with(BTreeMap::new)
+            .entry(month)
+            .or_insert_with(Vec::new)
+            .push(item);
+    }
+    Ok(archive)
+}
+
+macro_rules! archive_methods {
+    () => {
+        /// Returns the items grouped by the year and month they were published.
+        ///
+        /// Years and months are in ascending order, and the items in each month are sorted by
+        /// `date_published`. The year and month are from the date as written, so an item
+        /// published at `2021-01-31T23:00:00-05:00` is in January even though it is February in
+        /// UTC. Items without a `date_published` or with an invalid date are not included.
+        ///
+        /// # Errors
+        ///
+        /// If the items or their dates are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned.
+        pub fn archive_map(&self) -> Result<BTreeMap<i64, BTreeMap<u32, Vec<ItemRef<'_>>>>, Error> {
+            archive_map(self.as_map())
+        }
+    };
+}
+
+impl Feed {
+    archive_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    archive_methods!();
+}
+
+impl<'a> FeedRef<'a> {
+    archive_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::{vec, vec::Vec};
+
+    #[test]
+    fn archive() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "items": [
+                { "id": "1", "date_published": "2021-02-10T00:00:00Z" },
+                { "id": "2", "date_published": "2021-01-31T23:00:00-05:00" },
+                { "id": "3", "date_published": "2020-12-01T00:00:00Z" },
+                { "id": "4", "date_published": "2021-02-01T00:00:00Z" },
+                { "id": "5" }
+            ]
+        }))?;
+
+        let archive = feed.archive_map()?;
+        let ids = archive
+            .iter()
+            .flat_map(|(year, months)| {
+                months.iter().flat_map(move |(month, items)| {
+                    items
+                        .iter()
+                        .map(move |item| (*year, *month, item.id().ok().flatten()))
+                })
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            vec![
+                (2020, 12, Some("3")),
+                (2021, 1, Some("2")),
+                (2021, 2, Some("4")),
+                (2021, 2, Some("1")),
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,255 @@
+//! Incremental assembly of feeds from large sources of items.
+//!
+//! Items read from a database cursor can be pushed in batches as they arrive. Only the newest
+//! `max_items` items are kept in memory, so the source does not have to be sorted or loaded at
+//! once.
+//!
+//! ```
+//! use json_feed_model::{assembler::FeedAssembler, Feed, Item};
+//!
+//! let mut metadata = Feed::new();
+//! metadata.set_title("Example");
+//!
+//! let mut assembler = FeedAssembler::with_max_items(metadata, 2);
+//! for batch in [["2021-01-03", "2021-01-01"], ["2021-01-04", "2021-01-02"]] {
+//!     assembler.push_batch(
+//!         batch
+//!             .iter()
+//!             .map(|date| {
+//!                 let mut item = Item::new();
+//!                 item.set_id(date);
+//!                 item.set_content_text("Hello");
+//!                 item.set_date_published(format!("{}T00:00:00Z", date));
+//!                 item
+//!             })
+//!             .collect(),
+//!     )?;
+//! }
+//! assert_eq!(assembler.dropped(), 2);
+//!
+//! let feed = assembler.finish();
+//! assert_eq!(feed.title()?, Some("Example"));
+//! let ids = feed
+//!     .items()?
+//!     .unwrap()
+//!     .iter()
+//!     .map(|item| item.id().map(|id| id.map(String::from)))
+//!     .collect::<Result<Vec<_>, _>>()?;
+//! assert_eq!(ids, vec![Some(String::from("2021-01-04")), Some(String::from("2021-01-03"))]);
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{collections::BinaryHeap, vec::Vec};
+#[cfg(feature = "std")]
+use std::{collections::BinaryHeap, vec::Vec};
+
+use core::cmp::{Ordering, Reverse};
+
+use crate::{date::Timestamp, Error, Feed, Item};
+
+/// The position of an item in the assembled feed.
+///
+/// Newer items have greater keys. Items with the same date are ordered by when they were
+/// pushed, with earlier items having greater keys.
+type Key = (Option<Timestamp>, Reverse<u64>);
+
+/// An item ordered by its key.
+#[derive(Debug)]
+struct Ranked {
+    key: Key,
+    item: Item,
+}
+
+impl PartialEq for Ranked {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl Eq for Ranked {}
+
+impl PartialOrd for Ranked {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ranked {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+/// Builds a feed from metadata and items pushed in arbitrary order.
+///
+/// Items are ordered from newest to oldest by their `date_published` or, if it is missing,
+/// their `date_modified`. Undated items are older than every dated item. Items with the same
+/// date keep the order they were pushed in.
+///
+/// If there is a maximum number of items, the oldest items are dropped as newer items are
+/// pushed. The assembler keeps at most `max_items` items in memory.
+#[derive(Debug)]
+pub struct FeedAssembler {
+    metadata: Feed,
+    max_items: Option<usize>,
+    // A min-heap so the oldest kept item can be replaced.
+    items: BinaryHeap<Reverse<Ranked>>,
+    pushed: u64,
+    dropped: usize,
+}
+
+impl FeedAssembler {
+    /// Instantiates an assembler which keeps every item.
+    ///
+    /// Any `items` in the metadata are removed.
+    #[must_use]
+    pub fn new(metadata: Feed) -> Self {
+        Self::with_optional_max_items(metadata, None)
+    }
+
+    /// Instantiates an assembler which keeps the `max_items` newest items.
+    ///
+    /// Any `items` in the metadata are removed.
+    #[must_use]
+    pub fn with_max_items(metadata: Feed, max_items: usize) -> Self {
+        Self::with_optional_max_items(metadata, Some(max_items))
+    }
+
+    fn with_optional_max_items(mut metadata: Feed, max_items: Option<usize>) -> Self {
+        metadata.remove_items();
+        Self {
+            metadata,
+            max_items,
+            items: BinaryHeap::with_capacity(max_items.unwrap_or(0)),
+            pushed: 0,
+            dropped: 0,
+        }
+    }
+
+    /// Adds an item.
+    ///
+    /// # Errors
+    ///
+    /// If the item's dates are not JSON strings, then `Error::UnexpectedType` is returned. If
+    /// the date is not a valid date, then `Error::InvalidDate` is returned. The item is not
+    /// added if an error is returned.
+    pub fn push(&mut self, item: Item) -> Result<(), Error> {
+        let date = match item.date_published()? {
+            Some(date) => Some(date),
+            None => item.date_modified()?,
+        };
+        let date = date.map(Timestamp::parse).transpose()?;
+        let ranked = Ranked {
+            key: (date, Reverse(self.pushed)),
+            item,
+        };
+        self.pushed += 1;
+
+        match self.max_items {
+            Some(max_items) if self.items.len() >= max_items => {
+                self.dropped += 1;
+                match self.items.peek() {
+                    Some(Reverse(oldest)) if *oldest < ranked => {
+                        self.items.pop();
+                        self.items.push(Reverse(ranked));
+                    }
+                    _ => {}
+                }
+            }
+            _ => self.items.push(Reverse(ranked)),
+        }
+        Ok(())
+    }
+
+    /// Adds a batch of items.
+    ///
+    /// # Errors
+    ///
+    /// If an item's dates are not JSON strings, then `Error::UnexpectedType` is returned. If
+    /// a date is not a valid date, then `Error::InvalidDate` is returned. The items before the
+    /// invalid item are added.
+    pub fn push_batch(&mut self, items: Vec<Item>) -> Result<(), Error> {
+        items.into_iter().try_for_each(|item| self.push(item))
+    }
+
+    /// Returns the number of items which are kept.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns true if no items are kept.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns the number of items which were dropped because of the maximum number of items.
+    #[must_use]
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Returns the feed with the metadata and the kept items from newest to oldest.
+    #[must_use]
+    pub fn finish(self) -> Feed {
+        let mut feed = self.metadata;
+        // The heap of reversed keys sorts from the newest item to the oldest.
+        feed.set_items(
+            self.items
+                .into_sorted_vec()
+                .into_iter()
+                .map(|Reverse(ranked)| ranked.item)
+                .collect::<Vec<_>>(),
+        );
+        feed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::{vec, vec::Vec};
+
+    fn item(id: &str, date: Option<&str>) -> Item {
+        let mut item = Item::new();
+        item.set_id(id);
+        if let Some(date) = date {
+            item.set_date_modified(date);
+        }
+        item
+    }
+
+    #[test]
+    fn assembly() -> Result<(), Error> {
+        let mut assembler = FeedAssembler::with_max_items(Feed::new(), 3);
+        assembler.push_batch(vec![
+            item("undated", None),
+            item("a", Some("2021-01-01T00:00:00Z")),
+            item("b", Some("2021-01-01T02:00:00+02:00")),
+        ])?;
+        assembler.push_batch(vec![item("c", Some("2021-01-02T00:00:00Z"))])?;
+        assert!(matches!(
+            assembler.push(item("invalid", Some("yesterday"))),
+            Err(Error::InvalidDate)
+        ));
+        assert_eq!(assembler.len(), 3);
+        assert_eq!(assembler.dropped(), 1);
+
+        let feed = assembler.finish();
+        let items = feed.items()?.unwrap();
+        let ids = items
+            .iter()
+            .map(|item| item.id())
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(ids, vec![Some("c"), Some("a"), Some("b")]);
+
+        let mut assembler = FeedAssembler::new(Feed::new());
+        assembler.push(item("1", None))?;
+        assembler.push(item("2", None))?;
+        assert_eq!(assembler.finish().items()?.unwrap()[1].id()?, Some("2"));
+        Ok(())
+    }
+}
@@ -0,0 +1,321 @@
+//! Synthetic feeds and throughput measurements for benchmarks.
+//!
+//! [`FeedSpec`] describes a synthetic feed by its item count, content size, and extension
+//! density. [`parse`], [`validate`], and [`serialize`] are the operations to benchmark and can
+//! be called from a `criterion` benchmark:
+//!
+//! ```ignore
+//! use criterion::{black_box, Criterion, Throughput};
+//! use json_feed_model::bench_utils::{self, FeedSpec};
+//!
+//! fn parse(c: &mut Criterion) {
+//!     let json = bench_utils::generate_json(&FeedSpec::new()).unwrap();
+//!     let mut group = c.benchmark_group("parse");
+//!     group.throughput(Throughput::Bytes(json.len() as u64));
+//!     group.bench_function("default", |b| b.iter(|| bench_utils::parse(black_box(&json))));
+//!     group.finish();
+//! }
+//! ```
+//!
+//! Without a benchmark framework, [`measure`] runs an operation repeatedly and reports its
+//! throughput:
+//!
+//! ```
+//! use core::time::Duration;
+//! use json_feed_model::bench_utils::{self, FeedSpec, Operation};
+//!
+//! let mut spec = FeedSpec::new();
+//! spec.items = 10;
+//! let measurement = bench_utils::measure(&spec, Operation::Parse, Duration::from_millis(1))?;
+//! assert!(measurement.iterations > 0);
+//! assert!(measurement.bytes_per_second() > 0.0);
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+use std::{
+    string::String,
+    time::{Duration, Instant},
+    vec::Vec,
+};
+
+use crate::{Error, Feed, Version};
+
+/// The parameters of a synthetic feed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct FeedSpec {
+    /// The number of items.
+    pub items: usize,
+    /// The approximate number of bytes in each item's `content_html`.
+    pub content_bytes: usize,
+    /// The percentage (from 0 to 100) of items with extension objects.
+    pub extension_percent: u8,
+    /// The seed for the generated data.
+    pub seed: u64,
+}
+
+impl FeedSpec {
+    /// Instantiates with the default parameters.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Default for FeedSpec {
+    fn default() -> Self {
+        Self {
+            items: 100,
+            content_bytes: 2048,
+            extension_percent: 25,
+            seed: 0,
+        }
+    }
+}
+
+/// Returns at least `content_bytes` of HTML paragraphs built from the words of `text`.
+fn content_html(text: &str, content_bytes: usize) -> String {
+    let mut html = String::with_capacity(content_bytes + 512);
+    if text.trim().is_empty() {
+        return html;
+    }
+    let mut words = text.split_whitespace().cycle();
+    while html.len() < content_bytes {
+        html.push_str("<p>");
+        for index in 0..40 {
+            if index > 0 {
+                html.push(' ');
+            }
+            html.push_str(words.next().unwrap_or_default());
+        }
+        html.push_str("</p>");
+    }
+    html
+}
+
+/// Returns a deterministic, valid version 1.1 feed with the spec's parameters.
+///
+/// The feed is generated with [`crate::testing::sample_feed`]. Each item's `content_html` is
+/// replaced with approximately `content_bytes` of HTML, and items are given a `_bench`
+/// extension object according to `extension_percent`.
+#[must_use]
+pub fn generate_feed(spec: &FeedSpec) -> Feed {
+    let mut feed = crate::testing::sample_feed(spec.items, spec.seed);
+    let extension_percent = usize::from(spec.extension_percent.min(100));
+    if let Some(serde_json::Value::Array(items)) = feed.as_map_mut().get_mut("items") {
+        for (index, item) in items.iter_mut().enumerate() {
+            let item = match item.as_object_mut() {
+                Some(item) => item,
+                None => continue,
+            };
+            let text = crate::content::plain_text(item)
+                .ok()
+                .flatten()
+                .map(|text| String::from(text.as_ref()))
+                .unwrap_or_default();
+            item.insert(
+                String::from("content_html"),
+                serde_json::Value::String(content_html(&text, spec.content_bytes)),
+            );
+            // Spreads the items with extensions evenly through the feed.
+            if (index + 1) * extension_percent / 100 > index * extension_percent / 100 {
+                item.insert(
+                    String::from("_bench"),
+                    serde_json::json!({
+                        "index": index,
+                        "score": 0.5,
+                        "flags": [true, false],
+                        "label": "synthetic",
+                        "nested": { "depth": 1 }
+                    }),
+                );
+            }
+        }
+    }
+    feed
+}
+
+/// Returns the JSON serialization of the feed generated for the spec.
+///
+/// # Errors
+///
+/// If the feed cannot be serialized, then `Error::SerdeJson(serde_json::Error)` is returned.
+pub fn generate_json(spec: &FeedSpec) -> Result<Vec<u8>, Error> {
+    serialize(&generate_feed(spec))
+}
+
+/// Parses a feed from JSON bytes.
+///
+/// # Errors
+///
+/// If the bytes are not a JSON object, then `Error::SerdeJson(serde_json::Error)` is returned.
+pub fn parse(json: &[u8]) -> Result<Feed, Error> {
+    crate::from_slice(json)
+}
+
+/// Validates a feed with the version 1.1 rules.
+#[must_use]
+pub fn validate(feed: &Feed) -> bool {
+    feed.is_valid(&Version::Version1_1)
+}
+
+/// Serializes a feed as compact JSON.
+///
+/// # Errors
+///
+/// If the feed cannot be serialized, then `Error::SerdeJson(serde_json::Error)` is returned.
+pub fn serialize(feed: &Feed) -> Result<Vec<u8>, Error> {
+    Ok(serde_json::to_vec(feed)?)
+}
+
+/// An operation which can be measured.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Operation {
+    /// [`parse`]
+    Parse,
+    /// [`validate`]
+    Validate,
+    /// [`serialize`]
+    Serialize,
+}
+
+impl Operation {
+    /// Every operation.
+    pub const ALL: &'static [Operation] =
+        &[Operation::Parse, Operation::Validate, Operation::Serialize];
+
+    /// Returns the operation's name.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Operation::Parse => "parse",
+            Operation::Validate => "validate",
+            Operation::Serialize => "serialize",
+        }
+    }
+}
+
+/// The throughput of an operation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct Measurement {
+    /// The measured operation.
+    pub operation: Operation,
+    /// The number of times the operation ran.
+    pub iterations: u64,
+    /// The total time of the iterations.
+    pub elapsed: Duration,
+    /// The size of the feed's JSON serialization processed by each iteration.
+    pub bytes: u64,
+}
+
+impl Measurement {
+    /// Returns the number of iterations per second.
+    #[must_use]
+    pub fn iterations_per_second(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds > 0.0 {
+            #[allow(clippy::cast_precision_loss)]
+            let iterations = self.iterations as f64;
+            iterations / seconds
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the number of JSON bytes processed per second.
+    #[must_use]
+    pub fn bytes_per_second(&self) -> f64 {
+        #[allow(clippy::cast_precision_loss)]
+        let bytes = self.bytes as f64;
+        self.iterations_per_second() * bytes
+    }
+}
+
+/// Runs an operation on the spec's feed repeatedly for at least `min_duration`.
+///
+/// The operation runs at least once. The feed is generated before the time is measured.
+///
+/// # Errors
+///
+/// If the feed cannot be serialized or parsed, then `Error::SerdeJson(serde_json::Error)` is
+/// returned.
+pub fn measure(
+    spec: &FeedSpec,
+    operation: Operation,
+    min_duration: Duration,
+) -> Result<Measurement, Error> {
+    let json = generate_json(spec)?;
+    let feed = parse(&json)?;
+
+    // The results are accumulated so the optimizer cannot remove the operations.
+    let mut sink = 0_usize;
+    let mut iterations = 0;
+    let start = Instant::now();
+    loop {
+        sink = sink.wrapping_add(match operation {
+            Operation::Parse => parse(&json)?.as_map().len(),
+            Operation::Validate => usize::from(validate(&feed)),
+            Operation::Serialize => serialize(&feed)?.len(),
+        });
+        iterations += 1;
+        if start.elapsed() >= min_duration {
+            break;
+        }
+    }
+    let elapsed = start.elapsed();
+    consume(sink);
+
+    Ok(Measurement {
+        operation,
+        iterations,
+        elapsed,
+        bytes: json.len() as u64,
+    })
+}
+
+/// Consumes the accumulated results of the measured operations.
+#[inline(never)]
+fn consume(_sink: usize) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn synthetic_feeds() -> Result<(), Error> {
+        let mut spec = FeedSpec::new();
+        spec.items = 20;
+        spec.content_bytes = 500;
+        spec.extension_percent = 50;
+
+        let feed = generate_feed(&spec);
+        assert!(validate(&feed));
+        let items = feed.items()?.unwrap();
+        assert_eq!(items.len(), 20);
+        assert!(items
+            .iter()
+            .all(|item| item.content_html().unwrap().unwrap().len() >= 500));
+        assert_eq!(
+            items
+                .iter()
+                .filter(|item| item.as_map().contains_key("_bench"))
+                .count(),
+            10
+        );
+        assert_eq!(generate_json(&spec)?, generate_json(&spec)?);
+
+        spec.extension_percent = 0;
+        assert!(!serialize(&generate_feed(&spec))?
+            .windows(6)
+            .any(|w| w == b"_bench"));
+
+        for operation in Operation::ALL {
+            let measurement = measure(&spec, *operation, Duration::from_millis(1))?;
+            assert_eq!(measurement.operation, *operation);
+            assert!(measurement.iterations >= 1);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,296 @@
+//! Size limits for individual items.
+//!
+//! Push notifications and WebSub fan-out often have payload limits. A single pathological item
+//! with megabytes of content should not prevent the rest of the feed from being delivered.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{Error, Feed, FeedMut, ItemRef};
+
+/// How an item which exceeds the budget is made smaller.
+///
+/// If an item still exceeds the budget after the strategy is applied, the item is removed.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum BudgetStrategy {
+    /// Drop extensions, largest first, then truncate `content_html` and `content_text`.
+    Truncate,
+    /// Drop extensions, largest first.
+    DropExtensions,
+    /// Remove the item.
+    Remove,
+}
+
+/// A change made to an item which exceeded the budget.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum BudgetAction {
+    /// A content property was truncated.
+    Truncated(String),
+    /// An extension was removed.
+    ExtensionDropped(String),
+    /// The item was removed.
+    Removed,
+}
+
+/// An item which exceeded the budget.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct BudgetViolation {
+    /// The index of the item before any items were removed.
+    pub index: usize,
+    /// The item's `id`.
+    pub id: Option<String>,
+    /// The size of the item's compact JSON encoding in bytes before any changes.
+    pub size: usize,
+    /// The changes in the order they were made.
+    pub actions: Vec<BudgetAction>,
+}
+
+const CONTENT_KEYS: &[&str] = &["content_html", "content_text"];
+
+fn encoded_len(value: &Map<String, Value>) -> Result<usize, Error> {
+    serde_json::to_vec(value)
+        .map(|bytes| bytes.len())
+        .map_err(Error::SerdeJson)
+}
+
+fn truncate_content(
+    item: &mut Map<String, Value>,
+    max_bytes: usize,
+    actions: &mut Vec<BudgetAction>,
+) -> Result<(), Error> {
+    // Do not truncate the content if the rest of the item does not fit.
+    let mut rest = item.clone();
+    for key in CONTENT_KEYS {
+        rest.remove(*key);
+    }
+    if encoded_len(&rest)? >= max_bytes {
+        return Ok(());
+    }
+
+    loop {
+        let size = encoded_len(item)?;
+        if size <= max_bytes {
+            return Ok(());
+        }
+
+        // Shorten the longest content first.
+        let longest = CONTENT_KEYS
+            .iter()
+            .filter_map(|key| match item.get(*key) {
+                Some(Value::String(content)) => Some((*key, content.len())),
+                _ => None,
+            })
+            .max_by_key(|(_, len)| *len);
+        let (key, len) = match longest {
+            Some(longest) => longest,
+            None => return Ok(()),
+        };
+        // The ellipsis and escaping take a few more bytes than the cut text.
+        let target = len.saturating_sub(size - max_bytes + 8);
+        let content = match item.get(key) {
+            Some(Value::String(content)) => content,
+            _ => return Ok(()),
+        };
+        let truncated = if key == "content_html" {
+            crate::html::truncate_html(content, target)
+        } else {
+            let mut truncated =
+                String::from(&content[..crate::html::truncate_text_len(content, target)]);
+            truncated.push('\u{2026}');
+            truncated
+        };
+        if truncated.len() >= len {
+            return Ok(());
+        }
+
+        item.insert(String::from(key), Value::String(truncated));
+        if !actions.contains(&BudgetAction::Truncated(String::from(key))) {
+            actions.push(BudgetAction::Truncated(String::from(key)));
+        }
+    }
+}
+
+fn drop_extensions(
+    item: &mut Map<String, Value>,
+    max_bytes: usize,
+    actions: &mut Vec<BudgetAction>,
+) -> Result<(), Error> {
+    let mut extensions = Vec::new();
+    for (key, value) in item.iter() {
+        if key.starts_with('_') {
+            let len = serde_json::to_vec(value).map_err(Error::SerdeJson)?.len();
+            extensions.push((key.clone(), len));
+        }
+    }
+    extensions.sort_by(|(a_key, a_len), (b_key, b_len)| b_len.cmp(a_len).then(a_key.cmp(b_key)));
+
+    for (key, _) in extensions {
+        if encoded_len(item)? <= max_bytes {
+            break;
+        }
+        item.remove(&key);
+        actions.push(BudgetAction::ExtensionDropped(key));
+    }
+    Ok(())
+}
+
+fn enforce_item_budget(
+    map: &mut Map<String, Value>,
+    max_bytes: usize,
+    strategy: BudgetStrategy,
+) -> Result<Vec<BudgetViolation>, Error> {
+    // Check the items before any changes are made.
+    crate::item_refs(map)?;
+    let items = match map.get_mut("items") {
+        Some(Value::Array(items)) => items,
+        _ => return Ok(Vec::new()),
+    };
+
+    let mut violations = Vec::new();
+    let mut kept = Vec::with_capacity(items.len());
+    for (index, item) in items.drain(..).enumerate() {
+        let mut item = match item {
+            Value::Object(item) => item,
+            _ => return Err(Error::UnexpectedType),
+        };
+        let size = encoded_len(&item)?;
+        if size <= max_bytes {
+            kept.push(Value::Object(item));
+            continue;
+        }
+
+        let mut actions = Vec::new();
+        match strategy {
+            BudgetStrategy::Truncate => {
+                drop_extensions(&mut item, max_bytes, &mut actions)?;
+                truncate_content(&mut item, max_bytes, &mut actions)?;
+            }
+            BudgetStrategy::DropExtensions => {
+                drop_extensions(&mut item, max_bytes, &mut actions)?;
+            }
+            BudgetStrategy::Remove => {}
+        }
+
+        let id = ItemRef::from(&item).id().ok().flatten().map(String::from);
+        if encoded_len(&item)? <= max_bytes {
+            kept.push(Value::Object(item));
+        } else {
+            actions.push(BudgetAction::Removed);
+        }
+        trace_event!(index, size, "item exceeds budget");
+        violations.push(BudgetViolation {
+            index,
+            id,
+            size,
+            actions,
+        });
+    }
+    *items = kept;
+    Ok(violations)
+}
+
+macro_rules! budget_methods {
+    () => {
+        /// Makes every item fit in `max_bytes` of compact JSON.
+        ///
+        /// Items which exceed the budget are changed according to the `strategy` and removed
+        /// if they still do not fit. Content is truncated at word boundaries with an ellipsis,
+        /// and truncated HTML has its open elements closed.
+        ///
+        /// Returns a violation for each item which exceeded the budget.
+        ///
+        /// # Errors
+        ///
+        /// If the items are not the expected JSON type, then `Error::UnexpectedType` is
+        /// returned and the feed is not changed.
+        pub fn enforce_item_budget(
+            &mut self,
+            max_bytes: usize,
+            strategy: BudgetStrategy,
+        ) -> Result<Vec<BudgetViolation>, Error> {
+            enforce_item_budget(self.as_map_mut(), max_bytes, strategy)
+        }
+    };
+}
+
+impl Feed {
+    budget_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    budget_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::{format, string::String, vec, vec::Vec};
+
+    fn feed() -> Feed {
+        let words = "lorem ipsum dolor sit amet ".repeat(40);
+        crate::from_value(serde_json::json!({
+            "items": [
+                { "id": "1", "content_text": "Short" },
+                { "id": "2", "content_html": format!("<p><b>{}</b></p>", words), "_small": 1 },
+                { "id": "3", "content_text": "Short", "_small": 1, "_big": words },
+                { "id": "4", "content_text": "Short", "title": words }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn truncate() -> Result<(), Error> {
+        let mut feed = feed();
+        let violations = feed.enforce_item_budget(300, BudgetStrategy::Truncate)?;
+
+        let items = feed.items()?.unwrap();
+        assert_eq!(items.len(), 3);
+        for item in &items {
+            assert!(serde_json::to_vec(item.as_map()).unwrap().len() <= 300);
+        }
+        let html = items[1].content_html()?.unwrap();
+        assert!(html.starts_with("<p><b>lorem ipsum"));
+        assert!(html.ends_with("\u{2026}</b></p>"));
+
+        assert_eq!(violations.len(), 3);
+        assert_eq!(violations[0].index, 1);
+        assert_eq!(
+            violations[0].actions,
+            vec![
+                BudgetAction::ExtensionDropped(String::from("_small")),
+                BudgetAction::Truncated(String::from("content_html"))
+            ]
+        );
+        assert_eq!(
+            violations[1].actions,
+            vec![BudgetAction::ExtensionDropped(String::from("_big"))]
+        );
+        assert_eq!(violations[2].id.as_deref(), Some("4"));
+        assert_eq!(violations[2].actions, vec![BudgetAction::Removed]);
+        Ok(())
+    }
+
+    #[test]
+    fn remove() -> Result<(), Error> {
+        let mut feed = feed();
+        let violations = feed.enforce_item_budget(300, BudgetStrategy::Remove)?;
+        assert_eq!(feed.items()?.unwrap().len(), 1);
+        assert_eq!(
+            violations
+                .iter()
+                .map(|violation| violation.index)
+                .collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,274 @@
+//! Canonical serialization of JSON data.
+//!
+//! The canonical form is compact JSON with object keys sorted by their UTF-8 bytes. The same
+//! JSON data always has the same canonical form regardless of how the `Map` orders its keys.
+//!
+//! The model types serialize their keys in the same order with [`SortedMap`], and
+//! `sorted_keys()` returns the keys in that order.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
+
+use crate::{
+    Attachment, AttachmentMut, AttachmentRef, Author, AuthorMut, AuthorRef, Error, Feed, FeedMut,
+    FeedRef, Hub, HubMut, HubRef, Item, ItemMut, ItemRef,
+};
+
+/// The feed keys defined by the spec in the spec's order.
+const FEED_SPEC_KEYS: &[&str] = &[
+    "version",
+    "title",
+    "home_page_url",
+    "feed_url",
+    "description",
+    "user_comment",
+    "next_url",
+    "icon",
+    "favicon",
+    "authors",
+    "author",
+    "language",
+    "expired",
+    "hubs",
+    "items",
+];
+
+/// Returns the entries of an object sorted by their keys' UTF-8 bytes.
+fn sorted_entries(map: &Map<String, Value>) -> Vec<(&String, &Value)> {
+    let mut entries = map.iter().collect::<Vec<_>>();
+    // A `BTreeMap` is already sorted; a map which preserves the insertion order may not be.
+    if !entries
+        .windows(2)
+        .all(|w| w[0].0.as_bytes() <= w[1].0.as_bytes())
+    {
+        entries.sort_unstable_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+    }
+    entries
+}
+
+/// Serializes a JSON object with the keys sorted by their UTF-8 bytes at every level.
+pub(crate) struct SortedMap<'a>(pub(crate) &'a Map<String, Value>);
+
+impl Serialize for SortedMap<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (key, value) in sorted_entries(self.0) {
+            map.serialize_entry(key, &SortedValue(value))?;
+        }
+        map.end()
+    }
+}
+
+struct SortedValue<'a>(&'a Value);
+
+impl Serialize for SortedValue<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.0 {
+            Value::Object(map) => SortedMap(map).serialize(serializer),
+            Value::Array(values) => {
+                let mut seq = serializer.serialize_seq(Some(values.len()))?;
+                for value in values {
+                    seq.serialize_element(&SortedValue(value))?;
+                }
+                seq.end()
+            }
+            value => value.serialize(serializer),
+        }
+    }
+}
+
+fn write_map(map: &Map<String, Value>, out: &mut Vec<u8>) -> Result<(), Error> {
+    out.push(b'{');
+    for (index, (key, value)) in sorted_entries(map).into_iter().enumerate() {
+        if index > 0 {
+            out.push(b',');
+        }
+        out.extend_from_slice(serde_json::to_string(key)?.as_bytes());
+        out.push(b':');
+        write_value(value, out)?;
+    }
+    out.push(b'}');
+    Ok(())
+}
+
+fn write_value(value: &Value, out: &mut Vec<u8>) -> Result<(), Error> {
+    match value {
+        Value::Object(map) => write_map(map, out)?,
+        Value::Array(values) => {
+            out.push(b'[');
+            for (index, value) in values.iter().enumerate() {
+                if index > 0 {
+                    out.push(b',');
+                }
+                write_value(value, out)?;
+            }
+            out.push(b']');
+        }
+        _ => out.extend_from_slice(serde_json::to_string(value)?.as_bytes()),
+    }
+    Ok(())
+}
+
+/// Returns the 64-bit FNV-1a hash of the bytes.
+///
+/// The hash is stable across platforms and releases, unlike the standard library's hasher.
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+        (hash ^ u64::from(*b)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Returns the canonical serialization of a JSON object.
+pub(crate) fn to_canonical_vec(map: &Map<String, Value>) -> Result<Vec<u8>, Error> {
+    let mut out = Vec::new();
+    write_map(map, &mut out)?;
+    Ok(out)
+}
+
+macro_rules! sorted_keys_methods {
+    () => {
+        /// Returns the keys sorted by their UTF-8 bytes.
+        ///
+        /// The keys are serialized in this order regardless of how the inner `Map` orders its
+        /// keys.
+        #[must_use]
+        pub fn sorted_keys(&self) -> Vec<&str> {
+            sorted_entries(self.as_map())
+                .into_iter()
+                .map(|(key, _)| key.as_str())
+                .collect()
+        }
+
+        /// Returns the canonical serialization of the JSON data.
+        ///
+        /// The canonical form is compact JSON with the keys sorted by their UTF-8 bytes at
+        /// every level. The same data always has the same canonical form.
+        ///
+        /// # Errors
+        ///
+        /// If the data cannot be serialized, then `Error::SerdeJson(serde_json::Error)` is
+        /// returned.
+        pub fn to_canonical_vec(&self) -> Result<Vec<u8>, Error> {
+            to_canonical_vec(self.as_map())
+        }
+    };
+}
+
+macro_rules! impl_sorted_keys {
+    ($owned:ident, $borrowed:ident, $borrowed_mut:ident) => {
+        impl $owned {
+            sorted_keys_methods!();
+        }
+
+        impl<'a> $borrowed_mut<'a> {
+            sorted_keys_methods!();
+        }
+
+        impl<'a> $borrowed<'a> {
+            sorted_keys_methods!();
+        }
+    };
+}
+
+impl_sorted_keys!(Feed, FeedRef, FeedMut);
+impl_sorted_keys!(Item, ItemRef, ItemMut);
+impl_sorted_keys!(Author, AuthorRef, AuthorMut);
+impl_sorted_keys!(Attachment, AttachmentRef, AttachmentMut);
+impl_sorted_keys!(Hub, HubRef, HubMut);
+
+fn keys_sorted_spec_first(map: &Map<String, Value>) -> Vec<&str> {
+    let mut keys = FEED_SPEC_KEYS
+        .iter()
+        .filter_map(|key| map.get_key_value(*key).map(|(key, _)| key.as_str()))
+        .collect::<Vec<_>>();
+    keys.extend(
+        sorted_entries(map)
+            .into_iter()
+            .map(|(key, _)| key.as_str())
+            .filter(|key| !FEED_SPEC_KEYS.contains(key)),
+    );
+    keys
+}
+
+macro_rules! spec_keys_methods {
+    () => {
+        /// Returns the keys defined by the spec in the order the spec lists them followed by
+        /// the other keys sorted by their UTF-8 bytes.
+        ///
+        /// The order is useful for displaying a feed to people.
+        #[must_use]
+        pub fn keys_sorted_spec_first(&self) -> Vec<&str> {
+            keys_sorted_spec_first(self.as_map())
+        }
+    };
+}
+
+impl Feed {
+    spec_keys_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    spec_keys_methods!();
+}
+
+impl<'a> FeedRef<'a> {
+    spec_keys_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::{string::String, vec};
+
+    #[test]
+    fn canonical() -> Result<(), Error> {
+        let value = serde_json::json!({
+            "b": [1, { "d": "\u{e9}\"", "c": null }],
+            "a": true
+        });
+        let canonical = to_canonical_vec(value.as_object().unwrap())?;
+        assert_eq!(
+            String::from_utf8(canonical).unwrap(),
+            "{\"a\":true,\"b\":[1,{\"c\":null,\"d\":\"\u{e9}\\\"\"}]}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn key_order() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "_ext": { "b": 1, "a": [{ "d": 1, "c": 2 }] },
+            "items": [],
+            "Z": 1,
+            "title": "T",
+            "version": "https://jsonfeed.org/version/1.1"
+        }))?;
+
+        assert_eq!(
+            feed.sorted_keys(),
+            vec!["Z", "_ext", "items", "title", "version"]
+        );
+        assert_eq!(
+            feed.keys_sorted_spec_first(),
+            vec!["version", "title", "items", "Z", "_ext"]
+        );
+
+        let canonical = String::from_utf8(feed.to_canonical_vec()?).unwrap();
+        assert_eq!(serde_json::to_string(&feed)?, canonical);
+        assert!(canonical.starts_with(r#"{"Z":1,"_ext":{"a":[{"c":2,"d":1}],"b":1}"#));
+        Ok(())
+    }
+}
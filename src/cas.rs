@@ -0,0 +1,240 @@
+//! Content-addressed attachment URLs.
+//!
+//! Publishers which upload attachments to a CDN under immutable, content-addressed paths can
+//! name each file by a hash of its payload. The crate does not read payloads or choose a hash
+//! function, so the caller supplies the hashes. [`Attachment::content_address`] returns the
+//! file name for a hash, and [`Feed::rewrite_attachments_to_cas`] points every attachment at
+//! its content-addressed URL.
+//!
+//! ```
+//! let mut feed = json_feed_model::from_str(r#"{
+//!     "items": [{
+//!         "id": "1",
+//!         "attachments": [
+//!             { "url": "https://example.org/episodes/1.MP3", "mime_type": "audio/mpeg" },
+//!             { "url": "https://example.org/episodes/1.txt", "mime_type": "text/plain" }
+//!         ]
+//!     }]
+//! }"#)?;
+//!
+//! let rewrites = feed.rewrite_attachments_to_cas("https://cdn.example.net/cas", |attachment| {
+//!     match attachment.mime_type() {
+//!         Ok(Some("audio/mpeg")) => Some(String::from("9f86d081884c7d65")),
+//!         _ => None,
+//!     }
+//! })?;
+//!
+//! assert_eq!(rewrites.len(), 1);
+//! assert_eq!(rewrites[0].path, "/items/0/attachments/0/url");
+//! assert_eq!(rewrites[0].original_url, "https://example.org/episodes/1.MP3");
+//! assert_eq!(
+//!     feed.items()?.unwrap()[0].attachments()?.unwrap()[0].url()?,
+//!     Some("https://cdn.example.net/cas/9f86d081884c7d65.mp3")
+//! );
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{format, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{format, string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{Attachment, AttachmentMut, AttachmentRef, Error, Feed, FeedMut};
+
+fn content_address(map: &Map<String, Value>, hash: &str) -> Result<String, Error> {
+    let mut address = String::with_capacity(hash.len() + 5);
+    crate::url::encode_path_segment_into(hash, &mut address);
+    let url = match map.get("url") {
+        None => None,
+        Some(Value::String(url)) => Some(url.as_str()),
+        Some(_) => return Err(Error::UnexpectedType),
+    };
+    if let Some(extension) = url.and_then(crate::url::path_extension) {
+        if extension.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            address.push('.');
+            address.push_str(&extension.to_ascii_lowercase());
+        }
+    }
+    Ok(address)
+}
+
+macro_rules! content_address_methods {
+    () => {
+        /// Returns the content-addressed file name for the attachment's payload.
+        ///
+        /// The name is the percent-encoded hash followed by the lowercased extension of the
+        /// attachment's `url`, if the URL has an alphanumeric extension.
+        ///
+        /// # Errors
+        ///
+        /// If the `url` is not a JSON string, then `Error::UnexpectedType` is returned.
+        pub fn content_address(&self, hash: &str) -> Result<String, Error> {
+            content_address(self.as_map(), hash)
+        }
+    };
+}
+
+impl Attachment {
+    content_address_methods!();
+}
+
+impl<'a> AttachmentMut<'a> {
+    content_address_methods!();
+}
+
+impl<'a> AttachmentRef<'a> {
+    content_address_methods!();
+}
+
+/// An attachment URL which was replaced with a content-addressed URL.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct CasRewrite {
+    /// The [JSON Pointer][json_pointer] to the attachment's `url`.
+    ///
+    /// [json_pointer]: https://tools.ietf.org/html/rfc6901
+    pub path: String,
+    /// The previous URL, where the payload can be fetched for uploading.
+    pub original_url: String,
+    /// The content-addressed URL.
+    pub url: String,
+}
+
+fn rewrite_attachments_to_cas<F>(
+    map: &mut Map<String, Value>,
+    base_url: &str,
+    mut hasher: F,
+) -> Result<Vec<CasRewrite>, Error>
+where
+    F: FnMut(&AttachmentRef<'_>) -> Option<String>,
+{
+    if !crate::url::is_absolute(base_url) {
+        return Err(Error::InvalidUrl);
+    }
+    let base_url = base_url.trim_end_matches('/');
+
+    let mut rewrites = Vec::new();
+    for (item_index, item) in crate::item_refs(map)?.iter().enumerate() {
+        let attachments = match item.attachments()? {
+            Some(attachments) => attachments,
+            None => continue,
+        };
+        for (index, attachment) in attachments.iter().enumerate() {
+            let original_url = match attachment.url()? {
+                Some(url) => url,
+                None => continue,
+            };
+            if let Some(hash) = hasher(attachment) {
+                let url = format!("{}/{}", base_url, attachment.content_address(&hash)?);
+                if url != original_url {
+                    rewrites.push(CasRewrite {
+                        path: format!("/items/{}/attachments/{}/url", item_index, index),
+                        original_url: String::from(original_url),
+                        url,
+                    });
+                }
+            }
+        }
+    }
+    for rewrite in &rewrites {
+        if let Some(value) = crate::pointer::get_pointer_mut(map, &rewrite.path) {
+            *value = Value::String(rewrite.url.clone());
+        }
+    }
+    Ok(rewrites)
+}
+
+macro_rules! rewrite_attachments_to_cas_methods {
+    () => {
+        /// Replaces each attachment's `url` with a content-addressed URL under `base_url`.
+        ///
+        /// `hasher` returns the hash of an attachment's payload, or `None` to keep the
+        /// attachment's URL. The new URL is `base_url` followed by the attachment's
+        /// [`content_address`][Attachment::content_address]. Returns the replaced URLs so the
+        /// payloads can be uploaded. Attachments without a `url` are skipped.
+        ///
+        /// # Errors
+        ///
+        /// If `base_url` is not an absolute URL, then `Error::InvalidUrl` is returned. If the
+        /// items, their attachments, or the attachments' URLs are not the expected JSON types,
+        /// then `Error::UnexpectedType` is returned. The feed is not changed if an error is
+        /// returned.
+        pub fn rewrite_attachments_to_cas<F>(
+            &mut self,
+            base_url: &str,
+            hasher: F,
+        ) -> Result<Vec<CasRewrite>, Error>
+        where
+            F: FnMut(&AttachmentRef<'_>) -> Option<String>,
+        {
+            rewrite_attachments_to_cas(self.as_map_mut(), base_url, hasher)
+        }
+    };
+}
+
+impl Feed {
+    rewrite_attachments_to_cas_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    rewrite_attachments_to_cas_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::{string::String, vec};
+    use serde_json::json;
+
+    #[test]
+    fn addresses() -> Result<(), Error> {
+        let mut attachment = Attachment::new();
+        assert_eq!(attachment.content_address("ab/c+")?, "ab%2Fc%2B");
+        attachment.set_url("https://example.org/a.tar.GZ?v=1");
+        assert_eq!(attachment.content_address("abc")?, "abc.gz");
+        attachment.set_url("https://example.org/a.b-c");
+        assert_eq!(attachment.content_address("abc")?, "abc");
+        Ok(())
+    }
+
+    #[test]
+    fn rewrites() -> Result<(), Error> {
+        let mut feed = crate::from_value(json!({
+            "items": [
+                { "id": "1" },
+                {
+                    "id": "2",
+                    "attachments": [
+                        { "mime_type": "audio/mpeg" },
+                        { "url": "https://cdn.example/cas/abc.mp3", "mime_type": "audio/mpeg" },
+                        { "url": "https://example.org/b.mp3", "mime_type": "audio/mpeg" }
+                    ]
+                }
+            ]
+        }))?;
+        assert!(matches!(
+            feed.rewrite_attachments_to_cas("/cas", |_| Some(String::from("abc"))),
+            Err(Error::InvalidUrl)
+        ));
+
+        let rewrites = feed.rewrite_attachments_to_cas("https://cdn.example/cas/", |_| {
+            Some(String::from("abc"))
+        })?;
+        assert_eq!(
+            rewrites,
+            vec![CasRewrite {
+                path: String::from("/items/1/attachments/2/url"),
+                original_url: String::from("https://example.org/b.mp3"),
+                url: String::from("https://cdn.example/cas/abc.mp3"),
+            }]
+        );
+        assert_eq!(
+            feed.as_map()["items"][1]["attachments"][2]["url"],
+            "https://cdn.example/cas/abc.mp3"
+        );
+        Ok(())
+    }
+}
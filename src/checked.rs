@@ -0,0 +1,447 @@
+//! Setters which check the syntax of a value before setting it.
+//!
+//! Editors can report an invalid value for a single property instead of waiting for the whole
+//! feed to be validated.
+//!
+//! ```
+//! use json_feed_model::{Error, Item};
+//!
+//! let mut item = Item::new();
+//! assert!(matches!(item.try_set_url("example.org/1"), Err(Error::InvalidUrl)));
+//! assert!(matches!(item.try_set_date_published("yesterday"), Err(Error::InvalidDate)));
+//! assert!(matches!(item.try_set_language("en_US"), Err(Error::InvalidLanguage)));
+//! assert_eq!(item.url()?, None);
+//!
+//! item.try_set_url("https://example.org/1")?;
+//! item.try_set_date_published("2021-06-01T12:00:00Z")?;
+//! item.try_set_language("en-US")?;
+//! assert_eq!(item.url()?, Some("https://example.org/1"));
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::string::{String, ToString};
+#[cfg(feature = "std")]
+use std::string::{String, ToString};
+
+use serde_json::Value;
+
+use crate::{
+    date::Timestamp, Attachment, AttachmentMut, Author, AuthorMut, Error, Feed, FeedMut, Hub,
+    HubMut, Item, ItemMut,
+};
+
+/// Checks that the value is an absolute URL without whitespace or control characters.
+pub(crate) fn check_url(url: &str) -> Result<(), Error> {
+    let has_rest = crate::url::scheme(url).map_or(false, |scheme| url.len() > scheme.len() + 1);
+    if has_rest && !url.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        Ok(())
+    } else {
+        Err(Error::InvalidUrl)
+    }
+}
+
+/// Checks that the value is a well-formed RFC 5646 language tag.
+///
+/// The primary language subtag has 2 to 8 letters, or is `x` or `i` for private use and
+/// grandfathered tags. The other subtags have 1 to 8 letters and digits.
+pub(crate) fn check_language(language: &str) -> Result<(), Error> {
+    let mut subtags = language.split('-');
+    let primary = subtags.next().unwrap_or_default();
+    let primary_valid = primary.chars().all(|c| c.is_ascii_alphabetic())
+        && (matches!(primary.len(), 2..=8)
+            || primary.eq_ignore_ascii_case("x")
+            || primary.eq_ignore_ascii_case("i"));
+    if primary_valid
+        && subtags.all(|subtag| {
+            matches!(subtag.len(), 1..=8) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+    {
+        Ok(())
+    } else {
+        Err(Error::InvalidLanguage)
+    }
+}
+
+/// Returns true if the name is an RFC 6838 type or subtype name.
+fn is_mime_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().map_or(false, |c| c.is_ascii_alphanumeric())
+        && name.len() <= 127
+        && chars.all(|c| c.is_ascii_alphanumeric() || "!#$&-^_.+".contains(c))
+}
+
+/// Checks that the value is a MIME type with optional `name=value` parameters.
+pub(crate) fn check_mime_type(mime_type: &str) -> Result<(), Error> {
+    let mut parts = mime_type.split(';');
+    let essence = parts.next().unwrap_or_default();
+    let essence_valid = essence
+        .split_once('/')
+        .map_or(false, |(ty, subty)| is_mime_name(ty) && is_mime_name(subty));
+    if essence_valid
+        && parts.all(|parameter| {
+            parameter
+                .trim_start()
+                .split_once('=')
+                .map_or(false, |(name, value)| {
+                    is_mime_name(name) && !value.is_empty()
+                })
+        })
+    {
+        Ok(())
+    } else {
+        Err(Error::InvalidMimeType)
+    }
+}
+
+/// Checks that the value is an absolute URL with one of the schemes.
+fn check_url_scheme(url: &str, schemes: &[&str]) -> Result<(), Error> {
+    check_url(url)?;
+    let scheme = crate::url::scheme(url).unwrap_or_default();
+    if schemes.iter().any(|s| scheme.eq_ignore_ascii_case(s))
+        && url[scheme.len()..].starts_with("://")
+    {
+        Ok(())
+    } else {
+        Err(Error::InvalidUrl)
+    }
+}
+
+/// Checks that the value is an RFC 3339 date.
+pub(crate) fn check_date(date: &str) -> Result<(), Error> {
+    Timestamp::parse(date).map(|_| ())
+}
+
+macro_rules! try_setters {
+    ($([$try_setter:ident, $setter:ident, $check:ident, $doc:expr]),* $(,)?) => {
+        $(
+            #[doc=$doc]
+            ///
+            /// The value is not set if an error is returned. Otherwise, the previous value is
+            /// returned.
+            ///
+            /// # Errors
+            ///
+            /// If the value is not valid, then `Error::InvalidUrl`, `Error::InvalidDate`,
+            /// `Error::InvalidLanguage`, or `Error::InvalidMimeType` is returned for URLs,
+            /// dates, languages, and MIME types respectively.
+            pub fn $try_setter<T>(&mut self, value: T) -> Result<Option<Value>, Error>
+            where
+                T: ToString,
+            {
+                let value: String = value.to_string();
+                $check(&value)?;
+                Ok(self.$setter(value))
+            }
+        )*
+    };
+}
+
+macro_rules! feed_try_setters {
+    () => {
+        try_setters!(
+            [
+                try_set_home_page_url,
+                set_home_page_url,
+                check_url,
+                "Sets the home page URL if it is an absolute URL."
+            ],
+            [
+                try_set_feed_url,
+                set_feed_url,
+                check_url,
+                "Sets the feed URL if it is an absolute URL."
+            ],
+            [
+                try_set_next_url,
+                set_next_url,
+                check_url,
+                "Sets the next URL if it is an absolute URL."
+            ],
+            [
+                try_set_icon,
+                set_icon,
+                check_url,
+                "Sets the icon if it is an absolute URL."
+            ],
+            [
+                try_set_favicon,
+                set_favicon,
+                check_url,
+                "Sets the favicon if it is an absolute URL."
+            ],
+            [
+                try_set_language,
+                set_language,
+                check_language,
+                "Sets the language if it is a well-formed language tag."
+            ],
+        );
+    };
+}
+
+macro_rules! item_try_setters {
+    () => {
+        try_setters!(
+            [
+                try_set_url,
+                set_url,
+                check_url,
+                "Sets the URL if it is an absolute URL."
+            ],
+            [
+                try_set_external_url,
+                set_external_url,
+                check_url,
+                "Sets the external URL if it is an absolute URL."
+            ],
+            [
+                try_set_image,
+                set_image,
+                check_url,
+                "Sets the image if it is an absolute URL."
+            ],
+            [
+                try_set_banner_image,
+                set_banner_image,
+                check_url,
+                "Sets the banner image if it is an absolute URL."
+            ],
+            [
+                try_set_date_published,
+                set_date_published,
+                check_date,
+                "Sets the date published if it is an RFC 3339 date."
+            ],
+            [
+                try_set_date_modified,
+                set_date_modified,
+                check_date,
+                "Sets the date modified if it is an RFC 3339 date."
+            ],
+            [
+                try_set_language,
+                set_language,
+                check_language,
+                "Sets the language if it is a well-formed language tag."
+            ],
+        );
+    };
+}
+
+macro_rules! author_try_setters {
+    () => {
+        try_setters!(
+            [
+                try_set_url,
+                set_url,
+                check_url,
+                "Sets the URL if it is an absolute URL."
+            ],
+            [
+                try_set_avatar,
+                set_avatar,
+                check_url,
+                "Sets the avatar if it is an absolute URL."
+            ],
+        );
+    };
+}
+
+macro_rules! attachment_try_setters {
+    () => {
+        try_setters!(
+            [
+                try_set_url,
+                set_url,
+                check_url,
+                "Sets the URL if it is an absolute URL."
+            ],
+            [
+                try_set_mime_type,
+                set_mime_type,
+                check_mime_type,
+                "Sets the MIME type if it is well-formed."
+            ],
+        );
+    };
+}
+
+macro_rules! hub_try_setters {
+    () => {
+        try_setters!([
+            try_set_url,
+            set_url,
+            check_url,
+            "Sets the URL if it is an absolute URL."
+        ]);
+    };
+}
+
+impl Feed {
+    feed_try_setters!();
+}
+
+impl<'a> FeedMut<'a> {
+    feed_try_setters!();
+}
+
+impl Item {
+    item_try_setters!();
+}
+
+impl<'a> ItemMut<'a> {
+    item_try_setters!();
+}
+
+impl Author {
+    author_try_setters!();
+}
+
+impl<'a> AuthorMut<'a> {
+    author_try_setters!();
+}
+
+impl Attachment {
+    attachment_try_setters!();
+}
+
+impl<'a> AttachmentMut<'a> {
+    attachment_try_setters!();
+}
+
+impl Hub {
+    hub_try_setters!();
+
+    /// Instantiates a WebSub hub if the hub's URL is an absolute `https` URL.
+    ///
+    /// # Errors
+    ///
+    /// If the URL is not an absolute `https` URL, then `Error::InvalidUrl` is returned.
+    pub fn try_websub<T>(url: T) -> Result<Self, Error>
+    where
+        T: ToString,
+    {
+        let url = url.to_string();
+        check_url_scheme(&url, &["https"])?;
+        Ok(Hub::websub(url))
+    }
+
+    /// Instantiates an rssCloud hub if the hub's URL is an absolute `http` or `https` URL.
+    ///
+    /// # Errors
+    ///
+    /// If the URL is not an absolute `http` or `https` URL, then `Error::InvalidUrl` is
+    /// returned.
+    pub fn try_rsscloud<T>(url: T) -> Result<Self, Error>
+    where
+        T: ToString,
+    {
+        let url = url.to_string();
+        check_url_scheme(&url, &["http", "https"])?;
+        Ok(Hub::rsscloud(url))
+    }
+}
+
+impl<'a> HubMut<'a> {
+    hub_try_setters!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn syntax_checks() {
+        for url in &[
+            "https://example.org/",
+            "mailto:a@example.org",
+            "urn:isbn:0451450523",
+        ] {
+            assert!(check_url(url).is_ok(), "{}", url);
+        }
+        for url in &[
+            "",
+            "/1",
+            "https:",
+            "https://example.org/a b",
+            "1http://example.org",
+        ] {
+            assert!(matches!(check_url(url), Err(Error::InvalidUrl)), "{}", url);
+        }
+
+        for language in &[
+            "en",
+            "en-US",
+            "zh-Hant-TW",
+            "x-private",
+            "i-klingon",
+            "es-419",
+        ] {
+            assert!(check_language(language).is_ok(), "{}", language);
+        }
+        for language in &["", "e", "en_US", "en-", "en-toolongsubtag", "12"] {
+            assert!(
+                matches!(check_language(language), Err(Error::InvalidLanguage)),
+                "{}",
+                language
+            );
+        }
+
+        for mime_type in &[
+            "audio/mpeg",
+            "text/html; charset=utf-8",
+            "application/feed+json",
+        ] {
+            assert!(check_mime_type(mime_type).is_ok(), "{}", mime_type);
+        }
+        for mime_type in &[
+            "",
+            "audio",
+            "audio/",
+            "/mpeg",
+            "text/html; charset",
+            "a b/c",
+        ] {
+            assert!(
+                matches!(check_mime_type(mime_type), Err(Error::InvalidMimeType)),
+                "{}",
+                mime_type
+            );
+        }
+    }
+
+    #[test]
+    fn try_setters() -> Result<(), Error> {
+        let mut feed = Feed::new();
+        assert!(feed.try_set_feed_url("feed.json").is_err());
+        assert_eq!(
+            feed.try_set_feed_url("https://example.org/feed.json")?,
+            None
+        );
+
+        let mut attachment = Attachment::new();
+        assert!(matches!(
+            attachment.try_set_mime_type("mp3"),
+            Err(Error::InvalidMimeType)
+        ));
+        attachment.try_set_mime_type("audio/mpeg")?;
+        assert_eq!(attachment.mime_type()?, Some("audio/mpeg"));
+
+        assert!(matches!(
+            Hub::try_websub("http://example.org/hub"),
+            Err(Error::InvalidUrl)
+        ));
+        assert!(matches!(
+            Hub::try_websub("https:hub"),
+            Err(Error::InvalidUrl)
+        ));
+        assert_eq!(
+            Hub::try_websub("HTTPS://example.org/hub")?.hub_type()?,
+            Some(crate::HUB_TYPE_WEBSUB)
+        );
+        assert!(Hub::try_rsscloud("http://example.org/rpc").is_ok());
+        assert!(Hub::try_rsscloud("ftp://example.org/rpc").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+//! Partial copies of feeds.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+use core::ops::{Bound, RangeBounds};
+
+use serde_json::{Map, Value};
+
+use crate::{Error, Feed, FeedMut, FeedRef};
+
+fn clone_with_items_in(map: &Map<String, Value>, items: Vec<Value>) -> Feed {
+    let mut feed = Map::new();
+    for (key, value) in map.iter().filter(|(key, _)| *key != "items") {
+        feed.insert(key.clone(), value.clone());
+    }
+    feed.insert(String::from("items"), Value::Array(items));
+    Feed::from(feed)
+}
+
+fn clone_with_items<R>(map: &Map<String, Value>, range: R) -> Result<Feed, Error>
+where
+    R: RangeBounds<usize>,
+{
+    let items = match map.get("items") {
+        None => &[][..],
+        Some(Value::Array(items)) => items.as_slice(),
+        Some(_) => return Err(Error::UnexpectedType),
+    };
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.saturating_add(1),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.saturating_add(1),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => items.len(),
+    };
+    let end = end.min(items.len());
+    let start = start.min(end);
+    Ok(clone_with_items_in(map, items[start..end].to_vec()))
+}
+
+macro_rules! clone_methods {
+    () => {
+        /// Returns a copy of the feed with all of its properties except the items.
+        ///
+        /// The copy has an empty `items` array. Unlike cloning the whole feed and removing the
+        /// items, the items are never copied.
+        #[must_use]
+        pub fn clone_metadata(&self) -> Feed {
+            clone_with_items_in(self.as_map(), Vec::new())
+        }
+
+        /// Returns a copy of the feed with only the items in the range.
+        ///
+        /// The range is limited to the items which exist, so a range past the end of the items
+        /// results in fewer or no items.
+        ///
+        /// # Errors
+        ///
+        /// If the `items` property is not a JSON array, then `Error::UnexpectedType` is returned.
+        pub fn clone_with_items<R>(&self, range: R) -> Result<Feed, Error>
+        where
+            R: RangeBounds<usize>,
+        {
+            clone_with_items(self.as_map(), range)
+        }
+    };
+}
+
+impl Feed {
+    clone_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    clone_methods!();
+}
+
+impl<'a> FeedRef<'a> {
+    clone_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::vec;
+
+    #[test]
+    fn partial_copies() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "title": "Blog",
+            "_ext": { "a": 1 },
+            "items": [{ "id": "1" }, { "id": "2" }, { "id": "3" }]
+        }))?;
+
+        assert_eq!(
+            feed.clone_metadata(),
+            crate::from_value(serde_json::json!({
+                "title": "Blog",
+                "_ext": { "a": 1 },
+                "items": []
+            }))?
+        );
+
+        let page = feed.clone_with_items(1..)?;
+        assert_eq!(
+            page.as_map().get("items"),
+            Some(&serde_json::json!([{ "id": "2" }, { "id": "3" }]))
+        );
+        assert_eq!(page.title()?, Some("Blog"));
+        assert_eq!(
+            feed.clone_with_items(2..=10)?.items()?.map(|i| i.len()),
+            Some(1)
+        );
+        assert_eq!(
+            feed.clone_with_items(5..)?.items()?.map(|i| i.len()),
+            Some(0)
+        );
+
+        let mut invalid = Feed::new();
+        invalid
+            .as_map_mut()
+            .insert(String::from("items"), Value::Bool(true));
+        assert!(matches!(
+            invalid.clone_with_items(..),
+            Err(Error::UnexpectedType)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn out_of_range() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "items": [{ "id": "1" }, { "id": "2" }, { "id": "3" }]
+        }))?;
+        let ids = |feed: Feed| -> Result<Vec<String>, Error> {
+            feed.items()?
+                .unwrap_or_default()
+                .iter()
+                .map(|item| Ok(item.id()?.map(String::from).unwrap_or_default()))
+                .collect()
+        };
+
+        assert!(ids(feed.clone_with_items(3..)?)?.is_empty());
+        assert!(ids(feed.clone_with_items(10..20)?)?.is_empty());
+        assert!(ids(feed.clone_with_items((Bound::Included(2), Bound::Excluded(1)))?)?.is_empty());
+        assert!(ids(feed.clone_with_items(..0)?)?.is_empty());
+        assert_eq!(ids(feed.clone_with_items(..=usize::MAX)?)?.len(), 3);
+        assert_eq!(
+            ids(feed.clone_with_items((Bound::Excluded(usize::MAX), Bound::Unbounded))?)?.len(),
+            0
+        );
+        assert_eq!(
+            ids(feed.clone_with_items((Bound::Excluded(0), Bound::Included(1)))?)?,
+            vec![String::from("2")]
+        );
+
+        let empty = Feed::new().clone_with_items(1..5)?;
+        assert_eq!(empty.as_map().get("items"), Some(&serde_json::json!([])));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,396 @@
+//! Tolerant reading of numbers and coercion of numbers and booleans written as JSON strings.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{format, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{format, string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use serde_json::Number;
+
+use crate::{
+    recover::{Issue, IssueKind},
+    Attachment, AttachmentMut, AttachmentRef, Author, AuthorMut, AuthorRef, CoercionOptions, Error,
+    Feed, FeedMut, FeedRef, Hub, HubMut, HubRef, Item, ItemMut, ItemRef,
+};
+
+const ATTACHMENT_NUMBER_KEYS: &[&str] = &["size_in_bytes", "duration_in_seconds"];
+
+fn parse_u64(s: &str) -> Option<u64> {
+    let s = s.trim();
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+fn parse_bool(s: &str) -> Option<bool> {
+    let s = s.trim();
+    if s.eq_ignore_ascii_case("true") {
+        Some(true)
+    } else if s.eq_ignore_ascii_case("false") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Returns the value as an unsigned integer, coercing a numeric string if allowed.
+pub(crate) fn u64_value(
+    value: Option<&Value>,
+    options: &CoercionOptions,
+) -> Result<Option<u64>, Error> {
+    match value {
+        None => Ok(None),
+        Some(Value::Number(n)) => n.as_u64().map(Some).ok_or(Error::UnexpectedType),
+        Some(Value::String(s)) if options.numeric_strings => {
+            parse_u64(s).map(Some).ok_or(Error::UnexpectedType)
+        }
+        Some(_) => Err(Error::UnexpectedType),
+    }
+}
+
+fn get_number<'a>(map: &'a Map<String, Value>, key: &str) -> Result<Option<&'a Number>, Error> {
+    match map.get(key) {
+        None => Ok(None),
+        Some(Value::Number(n)) => Ok(Some(n)),
+        Some(_) => Err(Error::UnexpectedType),
+    }
+}
+
+fn get_f64(map: &Map<String, Value>, key: &str) -> Result<Option<f64>, Error> {
+    Ok(get_number(map, key)?.and_then(Number::as_f64))
+}
+
+/// Returns the value as a boolean, coercing a `"true"` or `"false"` string if allowed.
+pub(crate) fn bool_value(
+    value: Option<&Value>,
+    options: &CoercionOptions,
+) -> Result<Option<bool>, Error> {
+    match value {
+        None => Ok(None),
+        Some(Value::Bool(b)) => Ok(Some(*b)),
+        Some(Value::String(s)) if options.boolean_strings => {
+            parse_bool(s).map(Some).ok_or(Error::UnexpectedType)
+        }
+        Some(_) => Err(Error::UnexpectedType),
+    }
+}
+
+macro_rules! attachment_coercion_methods {
+    () => {
+        /// Returns the optional `size_in_bytes`, coercing a numeric string if allowed by the
+        /// options.
+        ///
+        /// # Errors
+        ///
+        /// If the value is not a JSON number (or a numeric string when allowed), then
+        /// `Error::UnexpectedType` is returned.
+        pub fn size_in_bytes_with(&self, options: &CoercionOptions) -> Result<Option<u64>, Error> {
+            u64_value(self.as_map().get("size_in_bytes"), options)
+        }
+
+        /// Returns the optional `duration_in_seconds`, coercing a numeric string if allowed by
+        /// the options.
+        ///
+        /// # Errors
+        ///
+        /// If the value is not a JSON number (or a numeric string when allowed), then
+        /// `Error::UnexpectedType` is returned.
+        pub fn duration_in_seconds_with(
+            &self,
+            options: &CoercionOptions,
+        ) -> Result<Option<u64>, Error> {
+            u64_value(self.as_map().get("duration_in_seconds"), options)
+        }
+
+        /// Returns the optional `size_in_bytes` as a floating point number.
+        ///
+        /// Unlike `size_in_bytes()`, negative and fractional sizes are returned.
+        ///
+        /// # Errors
+        ///
+        /// If the value is not a JSON number, then `Error::UnexpectedType` is returned.
+        pub fn size_in_bytes_f64(&self) -> Result<Option<f64>, Error> {
+            get_f64(self.as_map(), "size_in_bytes")
+        }
+
+        /// Returns the optional `duration_in_seconds` as a floating point number.
+        ///
+        /// Unlike `duration_in_seconds()`, negative and fractional durations (e.g. `1234.5`)
+        /// are returned.
+        ///
+        /// # Errors
+        ///
+        /// If the value is not a JSON number, then `Error::UnexpectedType` is returned.
+        pub fn duration_in_seconds_f64(&self) -> Result<Option<f64>, Error> {
+            get_f64(self.as_map(), "duration_in_seconds")
+        }
+    };
+}
+
+impl Attachment {
+    attachment_coercion_methods!();
+}
+
+impl<'a> AttachmentMut<'a> {
+    attachment_coercion_methods!();
+}
+
+impl<'a> AttachmentRef<'a> {
+    attachment_coercion_methods!();
+}
+
+macro_rules! number_methods {
+    () => {
+        /// Returns the optional number for a key.
+        ///
+        /// Any JSON number is returned including negative, fractional, and large numbers. Use
+        /// the `Number` methods (e.g. `as_i64()` or `as_f64()`) to convert it.
+        ///
+        /// # Errors
+        ///
+        /// If the value is not a JSON number, then `Error::UnexpectedType` is returned.
+        pub fn get_number(&self, key: &str) -> Result<Option<&Number>, Error> {
+            get_number(self.as_map(), key)
+        }
+    };
+}
+
+macro_rules! impl_number {
+    ($owned:ident, $borrowed:ident, $borrowed_mut:ident) => {
+        impl $owned {
+            number_methods!();
+        }
+
+        impl<'a> $borrowed_mut<'a> {
+            number_methods!();
+        }
+
+        impl<'a> $borrowed<'a> {
+            number_methods!();
+        }
+    };
+}
+
+impl_number!(Feed, FeedRef, FeedMut);
+impl_number!(Item, ItemRef, ItemMut);
+impl_number!(Author, AuthorRef, AuthorMut);
+impl_number!(Attachment, AttachmentRef, AttachmentMut);
+impl_number!(Hub, HubRef, HubMut);
+
+/// Replaces the value with the coerced value and records the coercion.
+fn coerce_value(
+    map: &mut Map<String, Value>,
+    key: &str,
+    path: String,
+    coerced: Option<Value>,
+    message: &str,
+    issues: &mut Vec<Issue>,
+) {
+    if let Some(coerced) = coerced {
+        map.insert(String::from(key), coerced);
+        issues.push(Issue {
+            kind: IssueKind::Coerced,
+            path,
+            message: String::from(message),
+        });
+    }
+}
+
+fn coerce(map: &mut Map<String, Value>, options: &CoercionOptions) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    if options.boolean_strings {
+        let coerced = match map.get("expired") {
+            Some(Value::String(s)) => parse_bool(s).map(Value::Bool),
+            _ => None,
+        };
+        coerce_value(
+            map,
+            "expired",
+            String::from("/expired"),
+            coerced,
+            "converted to a boolean",
+            &mut issues,
+        );
+    }
+
+    if !options.numeric_strings {
+        return issues;
+    }
+    if let Some(Value::Array(items)) = map.get_mut("items") {
+        for (item_index, item) in items.iter_mut().enumerate() {
+            let attachments = match item.get_mut("attachments") {
+                Some(Value::Array(attachments)) => attachments,
+                _ => continue,
+            };
+            for (attachment_index, attachment) in attachments.iter_mut().enumerate() {
+                let attachment = match attachment.as_object_mut() {
+                    Some(attachment) => attachment,
+                    None => continue,
+                };
+                for key in ATTACHMENT_NUMBER_KEYS {
+                    let coerced = match attachment.get(*key) {
+                        Some(Value::String(s)) => parse_u64(s).map(Value::from),
+                        _ => None,
+                    };
+                    coerce_value(
+                        attachment,
+                        key,
+                        format!(
+                            "/items/{}/attachments/{}/{}",
+                            item_index, attachment_index, key
+                        ),
+                        coerced,
+                        "converted to an integer",
+                        &mut issues,
+                    );
+                }
+            }
+        }
+    }
+    issues
+}
+
+macro_rules! feed_coercion_methods {
+    () => {
+        /// Returns the optional `expired` value, coercing a `"true"` or `"false"` string if
+        /// allowed by the options.
+        ///
+        /// # Errors
+        ///
+        /// If the value is not a JSON boolean (or a boolean string when allowed), then
+        /// `Error::UnexpectedType` is returned.
+        pub fn expired_with(&self, options: &CoercionOptions) -> Result<Option<bool>, Error> {
+            bool_value(self.as_map().get("expired"), options)
+        }
+    };
+}
+
+macro_rules! feed_coerce_mut_methods {
+    () => {
+        /// Converts the numbers and booleans written as JSON strings which are allowed by the
+        /// options and returns an `Issue` for each converted value.
+        ///
+        /// The `expired` flag and each attachment's `size_in_bytes` and `duration_in_seconds`
+        /// are converted. Strings which cannot be converted are left as is.
+        pub fn coerce(&mut self, options: &CoercionOptions) -> Vec<Issue> {
+            coerce(self.as_map_mut(), options)
+        }
+    };
+}
+
+impl Feed {
+    feed_coercion_methods!();
+    feed_coerce_mut_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    feed_coercion_methods!();
+    feed_coerce_mut_methods!();
+}
+
+impl<'a> FeedRef<'a> {
+    feed_coercion_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ValidationOptions, Version};
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::{vec, vec::Vec};
+
+    #[test]
+    fn stringly_typed() -> Result<(), Error> {
+        let mut feed = crate::from_value(serde_json::json!({
+            "version": "https://jsonfeed.org/version/1.1",
+            "title": "Example",
+            "expired": "False",
+            "items": [{
+                "id": "1",
+                "content_text": "Hi",
+                "attachments": [{
+                    "url": "https://example.org/a.mp3",
+                    "mime_type": "audio/mpeg",
+                    "size_in_bytes": " 12345 ",
+                    "duration_in_seconds": "-1"
+                }]
+            }]
+        }))?;
+
+        let strict = CoercionOptions::new();
+        let lenient = CoercionOptions::all();
+        assert!(matches!(
+            feed.expired_with(&strict),
+            Err(Error::UnexpectedType)
+        ));
+        assert_eq!(feed.expired_with(&lenient)?, Some(false));
+        let items = feed.items()?.unwrap();
+        let attachments = items[0].attachments()?.unwrap();
+        assert_eq!(attachments[0].size_in_bytes_with(&lenient)?, Some(12345));
+        assert!(attachments[0].duration_in_seconds_with(&lenient).is_err());
+
+        feed.get_pointer_mut("/items/0/attachments/0")
+            .and_then(Value::as_object_mut)
+            .map(|attachment| attachment.remove("duration_in_seconds"));
+        let mut options = ValidationOptions::new();
+        assert!(!crate::validate_feed_map(
+            feed.as_map(),
+            &Version::Version1_1,
+            &options
+        ));
+        options.coercion = lenient;
+        assert!(crate::validate_feed_map(
+            feed.as_map(),
+            &Version::Version1_1,
+            &options
+        ));
+
+        let issues = feed.coerce(&lenient);
+        assert_eq!(
+            issues
+                .iter()
+                .map(|issue| issue.path.as_str())
+                .collect::<Vec<_>>(),
+            vec!["/expired", "/items/0/attachments/0/size_in_bytes"]
+        );
+        assert_eq!(feed.expired()?, Some(false));
+        assert!(feed.is_valid(&Version::Version1_1));
+        Ok(())
+    }
+
+    #[test]
+    fn tolerant_numbers() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "_count": -3,
+            "items": [{
+                "id": "1",
+                "attachments": [{
+                    "url": "https://example.org/a.mp3",
+                    "mime_type": "audio/mpeg",
+                    "size_in_bytes": -1.5,
+                    "duration_in_seconds": 1234.5,
+                    "title": "A"
+                }]
+            }]
+        }))?;
+
+        assert_eq!(
+            feed.get_number("_count")?.and_then(Number::as_i64),
+            Some(-3)
+        );
+        assert_eq!(feed.get_number("missing")?, None);
+        let items = feed.items()?.unwrap();
+        let attachments = items[0].attachments()?.unwrap();
+        assert!(attachments[0].duration_in_seconds().is_err());
+        assert_eq!(attachments[0].duration_in_seconds_f64()?, Some(1234.5));
+        assert_eq!(attachments[0].size_in_bytes_f64()?, Some(-1.5));
+        assert!(matches!(
+            attachments[0].get_number("title"),
+            Err(Error::UnexpectedType)
+        ));
+        Ok(())
+    }
+}
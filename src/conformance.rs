@@ -0,0 +1,279 @@
+//! Spec conformance test cases.
+//!
+//! The module bundles example feeds which are valid or invalid according to version 1.1 of the
+//! JSON Feed spec. The examples cover the spec's requirements and common mistakes found in
+//! published feeds.
+//!
+//! Implementations which process feeds, like converters and sanitizers, can check that they
+//! preserve validity by running the cases through their code.
+//!
+//! ```
+//! use json_feed_model::{conformance::run_conformance, Version};
+//!
+//! let report = run_conformance(|json| {
+//!     json_feed_model::from_str(json).map_or(false, |feed| feed.is_valid(&Version::Version1_1))
+//! });
+//! assert!(report.is_success(), "{:?}", report.failures);
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// An example feed with its expected validity.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Case {
+    /// A unique identifier for the case.
+    pub name: &'static str,
+    /// The JSON data.
+    pub json: &'static str,
+    /// True if the JSON data is a valid version 1.1 feed.
+    pub valid: bool,
+}
+
+const fn case(name: &'static str, valid: bool, json: &'static str) -> Case {
+    Case { name, json, valid }
+}
+
+const CASES: &[Case] = &[
+    case(
+        "minimal",
+        true,
+        r#"{"version":"https://jsonfeed.org/version/1.1","title":"Feed","items":[]}"#,
+    ),
+    case(
+        "spec_example",
+        true,
+        r#"{
+            "version": "https://jsonfeed.org/version/1.1",
+            "title": "My Example Feed",
+            "home_page_url": "https://example.org/",
+            "feed_url": "https://example.org/feed.json",
+            "items": [
+                { "id": "2", "content_text": "This is a second item.", "url": "https://example.org/second-item" },
+                { "id": "1", "content_html": "<p>Hello, world!</p>", "url": "https://example.org/initial-post" }
+            ]
+        }"#,
+    ),
+    case(
+        "version_1_feed",
+        true,
+        r#"{
+            "version": "https://jsonfeed.org/version/1",
+            "title": "Feed",
+            "author": { "name": "Alice" },
+            "items": [{ "id": "1", "content_text": "Text", "author": { "url": "https://example.org/" } }]
+        }"#,
+    ),
+    case(
+        "all_optional_properties",
+        true,
+        r#"{
+            "version": "https://jsonfeed.org/version/1.1",
+            "title": "Feed",
+            "home_page_url": "https://example.org/",
+            "feed_url": "https://example.org/feed.json",
+            "description": "A description",
+            "user_comment": "A comment",
+            "next_url": "https://example.org/feed.json?page=2",
+            "icon": "https://example.org/icon.png",
+            "favicon": "https://example.org/favicon.ico",
+            "authors": [{ "name": "Alice", "url": "https://example.org/alice", "avatar": "https://example.org/alice.png" }],
+            "language": "en-US",
+            "expired": false,
+            "hubs": [{ "type": "WebSub", "url": "https://example.org/hub" }],
+            "items": [{
+                "id": "1",
+                "url": "https://example.org/1",
+                "external_url": "https://example.com/",
+                "title": "Title",
+                "content_html": "<p>HTML</p>",
+                "content_text": "Text",
+                "summary": "Summary",
+                "image": "https://example.org/image.png",
+                "banner_image": "https://example.org/banner.png",
+                "date_published": "2021-01-01T00:00:00Z",
+                "date_modified": "2021-01-02T00:00:00+01:00",
+                "authors": [{ "name": "Bob" }],
+                "tags": ["a", "b"],
+                "language": "en",
+                "attachments": [{
+                    "url": "https://example.org/1.mp3",
+                    "mime_type": "audio/mpeg",
+                    "title": "Audio",
+                    "size_in_bytes": 1000,
+                    "duration_in_seconds": 60
+                }]
+            }]
+        }"#,
+    ),
+    case(
+        "extensions",
+        true,
+        r#"{
+            "version": "https://jsonfeed.org/version/1.1",
+            "title": "Feed",
+            "_example": { "about": "https://example.org/extension" },
+            "items": [{ "id": "1", "content_text": "Text", "_example": { "value": 1 } }]
+        }"#,
+    ),
+    case(
+        "not_json",
+        false,
+        r#"{"version":"https://jsonfeed.org/version/1.1","#,
+    ),
+    case(
+        "not_an_object",
+        false,
+        r#"["https://jsonfeed.org/version/1.1"]"#,
+    ),
+    case("missing_version", false, r#"{"title":"Feed","items":[]}"#),
+    case(
+        "unknown_version",
+        false,
+        r#"{"version":"https://jsonfeed.org/version/2","title":"Feed","items":[]}"#,
+    ),
+    case(
+        "missing_title",
+        false,
+        r#"{"version":"https://jsonfeed.org/version/1.1","items":[]}"#,
+    ),
+    case(
+        "missing_items",
+        false,
+        r#"{"version":"https://jsonfeed.org/version/1.1","title":"Feed"}"#,
+    ),
+    case(
+        "numeric_title",
+        false,
+        r#"{"version":"https://jsonfeed.org/version/1.1","title":1,"items":[]}"#,
+    ),
+    case(
+        "unknown_property",
+        false,
+        r#"{"version":"https://jsonfeed.org/version/1.1","title":"Feed","link":"https://example.org/","items":[]}"#,
+    ),
+    case(
+        "authors_object",
+        false,
+        r#"{"version":"https://jsonfeed.org/version/1.1","title":"Feed","authors":{"name":"Alice"},"items":[]}"#,
+    ),
+    case(
+        "item_missing_id",
+        false,
+        r#"{"version":"https://jsonfeed.org/version/1.1","title":"Feed","items":[{"content_text":"Text"}]}"#,
+    ),
+    case(
+        "item_numeric_id",
+        false,
+        r#"{"version":"https://jsonfeed.org/version/1.1","title":"Feed","items":[{"id":1,"content_text":"Text"}]}"#,
+    ),
+    case(
+        "item_missing_content",
+        false,
+        r#"{"version":"https://jsonfeed.org/version/1.1","title":"Feed","items":[{"id":"1","title":"Title"}]}"#,
+    ),
+    case(
+        "item_tags_string",
+        false,
+        r#"{"version":"https://jsonfeed.org/version/1.1","title":"Feed","items":[{"id":"1","content_text":"Text","tags":"a,b"}]}"#,
+    ),
+    case(
+        "attachment_missing_mime_type",
+        false,
+        r#"{"version":"https://jsonfeed.org/version/1.1","title":"Feed","items":[{"id":"1","content_text":"Text","attachments":[{"url":"https://example.org/1.mp3"}]}]}"#,
+    ),
+    case(
+        "hub_missing_url",
+        false,
+        r#"{"version":"https://jsonfeed.org/version/1.1","title":"Feed","hubs":[{"type":"WebSub"}],"items":[]}"#,
+    ),
+    case(
+        "author_without_properties",
+        false,
+        r#"{"version":"https://jsonfeed.org/version/1.1","title":"Feed","items":[{"id":"1","content_text":"Text","authors":[{}]}]}"#,
+    ),
+];
+
+/// Returns all of the conformance cases.
+#[must_use]
+pub fn cases() -> &'static [Case] {
+    CASES
+}
+
+/// A case where the checked implementation disagreed with the expected validity.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Failure {
+    /// The case's name.
+    pub name: &'static str,
+    /// True if the case's JSON data is a valid feed.
+    pub expected_valid: bool,
+}
+
+/// The results of running the conformance cases.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Report {
+    /// The number of cases where the implementation agreed with the expected validity.
+    pub passed: usize,
+    /// The cases where the implementation disagreed with the expected validity.
+    pub failures: Vec<Failure>,
+}
+
+impl Report {
+    /// Returns true if there are no failures.
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Runs every case through `f` and compares the result with the expected validity.
+///
+/// `f` receives each case's JSON data and should return true if the data is a valid feed after
+/// being processed by the implementation under test.
+pub fn run_conformance<F>(f: F) -> Report
+where
+    F: Fn(&str) -> bool,
+{
+    let mut report = Report::default();
+    for case in CASES {
+        if f(case.json) == case.valid {
+            report.passed += 1;
+        } else {
+            report.failures.push(Failure {
+                name: case.name,
+                expected_valid: case.valid,
+            });
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Version;
+
+    #[test]
+    fn crate_conforms() {
+        let report = run_conformance(|json| {
+            crate::from_str(json).map_or(false, |feed| feed.is_valid(&Version::Version1_1))
+        });
+        assert!(report.is_success(), "{:?}", report.failures);
+        assert_eq!(report.passed, cases().len());
+
+        let report = run_conformance(|_| true);
+        assert_eq!(
+            report.passed,
+            cases().iter().filter(|case| case.valid).count()
+        );
+        assert!(report
+            .failures
+            .iter()
+            .all(|failure| !failure.expected_valid));
+    }
+}
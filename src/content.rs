@@ -0,0 +1,521 @@
+//! Text content helpers for items.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{borrow::Cow, collections::BTreeSet, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{borrow::Cow, collections::BTreeSet, string::String, vec::Vec};
+
+use core::time::Duration;
+
+use serde_json::{Map, Value};
+
+use crate::{
+    ext::{__private::str_prop, reading_time::ReadingTimeMut},
+    Error, Feed, FeedMut, Item, ItemMut, ItemRef,
+};
+
+/// A common average reading speed for adults.
+pub const DEFAULT_WORDS_PER_MINUTE: u32 = 238;
+
+/// Returns the item's plain text content.
+///
+/// `content_text` is preferred. Otherwise, the text of `content_html` is used.
+pub(crate) fn plain_text(map: &Map<String, Value>) -> Result<Option<Cow<'_, str>>, Error> {
+    if let Some(text) = str_prop::get(map, None, "content_text")? {
+        return Ok(Some(Cow::Borrowed(text)));
+    }
+    Ok(str_prop::get(map, None, "content_html")?
+        .map(|html| Cow::Owned(crate::html::strip_tags(html))))
+}
+
+/// Returns a short plain text summary of the item.
+///
+/// The `summary` is preferred. Otherwise, the plain text content is used. Whitespace is
+/// collapsed and the text is truncated at a word boundary to at most `max_chars` characters
+/// (including a trailing ellipsis).
+pub(crate) fn summary_text(
+    map: &Map<String, Value>,
+    max_chars: usize,
+) -> Result<Option<String>, Error> {
+    let text = match str_prop::get(map, None, "summary")? {
+        Some(summary) => Cow::Borrowed(summary),
+        None => match plain_text(map)? {
+            Some(text) => text,
+            None => return Ok(None),
+        },
+    };
+
+    Ok(Some(truncate_words(&text, max_chars)).filter(|summary| !summary.is_empty()))
+}
+
+/// Collapses whitespace and truncates the text at a word boundary to at most `max_chars`
+/// characters (including a trailing ellipsis).
+pub(crate) fn truncate_words(text: &str, max_chars: usize) -> String {
+    let mut truncated = String::with_capacity(text.len().min(max_chars * 4));
+    let mut chars = 0;
+    let mut words = text.split_whitespace().peekable();
+    while let Some(word) = words.next() {
+        let separator = usize::from(chars > 0);
+        let word_chars = word.chars().count();
+        // Leave room for the ellipsis unless this is the last word.
+        let reserved = usize::from(words.peek().is_some());
+        if chars + separator + word_chars + reserved > max_chars {
+            if truncated.is_empty() {
+                truncated.extend(word.chars().take(max_chars.saturating_sub(1)));
+            }
+            truncated.push('\u{2026}');
+            break;
+        }
+        if separator == 1 {
+            truncated.push(' ');
+        }
+        truncated.push_str(word);
+        chars += separator + word_chars;
+    }
+    truncated
+}
+
+fn word_count(map: &Map<String, Value>) -> Result<u64, Error> {
+    Ok(plain_text(map)?.map_or(0, |text| {
+        text.split_whitespace()
+            .filter(|word| word.chars().any(char::is_alphanumeric))
+            .count() as u64
+    }))
+}
+
+fn reading_time(map: &Map<String, Value>, words_per_minute: u32) -> Result<Duration, Error> {
+    let words = word_count(map)?;
+    let words_per_minute = u64::from(words_per_minute.max(1));
+    let seconds = (words * 60 + words_per_minute - 1) / words_per_minute;
+    Ok(Duration::from_secs(seconds))
+}
+
+/// Schemes which do not link to a resource.
+const IGNORED_LINK_SCHEMES: &[&str] = &["data", "javascript"];
+
+/// Returns the bare `http` and `https` URLs in plain text.
+fn bare_urls(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    core::iter::from_fn(move || loop {
+        let start = rest.find("http")?;
+        let candidate = &rest[start..];
+        let end = candidate
+            .find(|c: char| c.is_whitespace() || c == '<' || c == '>' || c == '"')
+            .unwrap_or(candidate.len());
+        rest = &candidate[end..];
+        let mut url = &candidate[..end];
+        // Trailing punctuation usually ends the sentence instead of the URL.
+        loop {
+            let trimmed = url
+                .trim_end_matches(|c| matches!(c, '.' | ',' | ';' | ':' | '!' | '?' | '\'' | '"'));
+            let trimmed = match trimmed.strip_suffix(')') {
+                Some(without) if !without.contains('(') => without,
+                _ => trimmed,
+            };
+            if trimmed.len() == url.len() {
+                break;
+            }
+            url = trimmed;
+        }
+        let is_url = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))
+            .map_or(false, |rest| !rest.is_empty());
+        let is_word_start = text[..text.len() - candidate.len()]
+            .chars()
+            .next_back()
+            .map_or(true, |c| !c.is_alphanumeric());
+        if is_url && is_word_start {
+            return Some(url);
+        }
+    })
+}
+
+fn extract_links(map: &Map<String, Value>) -> Result<Vec<String>, Error> {
+    let base = str_prop::get(map, None, "url")?;
+    let mut seen = BTreeSet::new();
+    let mut links = Vec::new();
+    let mut add = |reference: &str| {
+        let reference = reference.trim();
+        if reference.is_empty() || reference.starts_with('#') {
+            return;
+        }
+        let link = match base {
+            Some(base) => crate::url::resolve(base, reference),
+            None if crate::url::is_absolute(reference) => crate::url::resolve(reference, ""),
+            None => None,
+        };
+        if let Some(link) = link {
+            let is_ignored = crate::url::scheme(&link).map_or(true, |scheme| {
+                IGNORED_LINK_SCHEMES
+                    .iter()
+                    .any(|ignored| ignored.eq_ignore_ascii_case(scheme))
+            });
+            if !is_ignored && seen.insert(link.clone()) {
+                links.push(link);
+            }
+        }
+    };
+
+    if let Some(html) = str_prop::get(map, None, "content_html")? {
+        let mut decoded = String::new();
+        for token in crate::html::tokens(html) {
+            if let crate::html::Token::Tag(tag) = token {
+                for value in tag.attr("href").into_iter().chain(tag.attr("src")) {
+                    decoded.clear();
+                    crate::html::decode_entities_into(value, &mut decoded);
+                    add(&decoded);
+                }
+            }
+        }
+    }
+    if let Some(text) = str_prop::get(map, None, "content_text")? {
+        bare_urls(text).for_each(&mut add);
+    }
+    Ok(links)
+}
+
+fn rewrite_content_html<F>(map: &mut Map<String, Value>, mut f: F) -> Result<usize, Error>
+where
+    F: FnMut(&str) -> String,
+{
+    // Check every item before any changes are made.
+    for item in crate::item_refs(map)? {
+        item.content_html()?;
+    }
+    let items = match map.get_mut("items") {
+        Some(Value::Array(items)) => items,
+        _ => return Ok(0),
+    };
+
+    let mut changed = 0;
+    for item in items {
+        if let Some(Value::String(html)) = item.get_mut("content_html") {
+            let rewritten = f(html);
+            if rewritten != *html {
+                *html = rewritten;
+                changed += 1;
+            }
+        }
+    }
+    Ok(changed)
+}
+
+fn mention_targets(map: &Map<String, Value>, own_host: Option<&str>) -> Result<Vec<String>, Error> {
+    let own_host = match own_host {
+        Some(own_host) => Some(own_host),
+        None => str_prop::get(map, None, "url")?.and_then(crate::url::host),
+    };
+    let mut seen = BTreeSet::new();
+    let mut targets = Vec::new();
+    for link in extract_links(map)? {
+        // The links are absolute, so only `http` and `https` links are normalized.
+        let target = match crate::subscriptions::normalize_subscription_url(&link) {
+            Some(target) => target,
+            None => continue,
+        };
+        let is_own = match (own_host, crate::url::host(&target)) {
+            (Some(own_host), Some(host)) => host.eq_ignore_ascii_case(own_host),
+            (_, None) => true,
+            (None, Some(_)) => false,
+        };
+        if !is_own && seen.insert(target.clone()) {
+            targets.push(target);
+        }
+    }
+    Ok(targets)
+}
+
+macro_rules! content_methods {
+    () => {
+        /// Returns the number of words in the item's content.
+        ///
+        /// `content_text` is preferred. Otherwise, the tags are stripped from `content_html`. An
+        /// item without content has zero words.
+        ///
+        /// # Errors
+        ///
+        /// If the content is not a JSON string, then `Error::UnexpectedType` is returned.
+        pub fn word_count(&self) -> Result<u64, Error> {
+            word_count(self.as_map())
+        }
+
+        /// Returns the estimated time to read the item's content.
+        ///
+        /// The time is rounded up to the second. See `DEFAULT_WORDS_PER_MINUTE` for a common
+        /// reading speed.
+        ///
+        /// # Errors
+        ///
+        /// If the content is not a JSON string, then `Error::UnexpectedType` is returned.
+        pub fn reading_time(&self, words_per_minute: u32) -> Result<Duration, Error> {
+            reading_time(self.as_map(), words_per_minute)
+        }
+
+        /// Returns the URLs linked from the item's content.
+        ///
+        /// The `href` and `src` attributes in `content_html` and the bare `http` and `https`
+        /// URLs in `content_text` are returned in the order they first appear. Relative URLs
+        /// are resolved against the item's `url` and are skipped if the item does not have
+        /// one. Fragment-only links, `data:` URLs, and `javascript:` URLs are skipped.
+        ///
+        /// # Errors
+        ///
+        /// If the content or `url` is not a JSON string, then `Error::UnexpectedType` is
+        /// returned.
+        pub fn extract_links(&self) -> Result<Vec<String>, Error> {
+            extract_links(self.as_map())
+        }
+
+        /// Returns the URLs linked from the item's content which should receive a
+        /// [Webmention][webmention].
+        ///
+        /// The links are found as in `extract_links`. Only `http` and `https` URLs are
+        /// returned. Each URL is normalized (the scheme and host are lowercased and the
+        /// fragment and default port are removed) and returned once. Links to `own_host` are
+        /// excluded; if it is `None`, then the host of the item's `url` is excluded.
+        ///
+        /// # Errors
+        ///
+        /// If the content or `url` is not a JSON string, then `Error::UnexpectedType` is
+        /// returned.
+        ///
+        /// [webmention]: https://www.w3.org/TR/webmention/
+        pub fn mention_targets(&self, own_host: Option<&str>) -> Result<Vec<String>, Error> {
+            mention_targets(self.as_map(), own_host)
+        }
+    };
+}
+
+macro_rules! persist_methods {
+    () => {
+        /// Computes the word count and reading time and stores them in the `_reading_time`
+        /// extension.
+        ///
+        /// The reading time is stored in whole minutes rounded up.
+        ///
+        /// # Errors
+        ///
+        /// If the content is not a JSON string, then `Error::UnexpectedType` is returned.
+        pub fn persist_reading_time(&mut self, words_per_minute: u32) -> Result<Duration, Error> {
+            let words = self.word_count()?;
+            let duration = self.reading_time(words_per_minute)?;
+            self.set_words(words);
+            self.set_minutes((duration.as_secs() + 59) / 60);
+            Ok(duration)
+        }
+    };
+}
+
+macro_rules! rewrite_methods {
+    () => {
+        /// Replaces every item's `content_html` with the result of `f`.
+        ///
+        /// Items without `content_html` are skipped. See the `rewrite` module for built-in
+        /// rewriters.
+        ///
+        /// Returns the number of items which were changed.
+        ///
+        /// # Errors
+        ///
+        /// If the items or their `content_html` are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned and the feed is not changed.
+        pub fn rewrite_content_html<F>(&mut self, f: F) -> Result<usize, Error>
+        where
+            F: FnMut(&str) -> String,
+        {
+            rewrite_content_html(self.as_map_mut(), f)
+        }
+    };
+}
+
+impl Feed {
+    rewrite_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    rewrite_methods!();
+}
+
+impl Item {
+    content_methods!();
+    persist_methods!();
+}
+
+impl<'a> ItemMut<'a> {
+    content_methods!();
+    persist_methods!();
+}
+
+impl<'a> ItemRef<'a> {
+    content_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ext::reading_time::ReadingTime;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::vec;
+
+    #[test]
+    fn word_count_from_html() -> Result<(), Error> {
+        let mut item = Item::new();
+        assert_eq!(item.word_count()?, 0);
+
+        item.set_content_html("<p>One <em>two</em> three &mdash; four.</p><script>x y z</script>");
+        assert_eq!(item.word_count()?, 4);
+
+        item.set_content_text("Just two");
+        assert_eq!(item.word_count()?, 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn persist() -> Result<(), Error> {
+        let mut item = Item::new();
+        item.set_content_text("word ".repeat(300));
+
+        assert_eq!(item.reading_time(200)?, Duration::from_secs(90));
+        assert_eq!(item.persist_reading_time(200)?, Duration::from_secs(90));
+        assert_eq!(item.words()?, Some(300));
+        assert_eq!(item.minutes()?, Some(2));
+
+        Ok(())
+    }
+
+    #[test]
+    fn links() -> Result<(), Error> {
+        let mut item = Item::new();
+        item.set_url("https://example.org/posts/1/");
+        item.set_content_html(
+            r##"<p><a href="../2/">Previous</a> <img src='/a.png'> <a href="#top">Top</a>
+            <a href="https://example.com/?a=1&amp;b=2">Ex</a> <a href="/a.png">Dup</a>
+            <a href="javascript:void(0)">JS</a></p>"##,
+        );
+        item.set_content_text(
+            "See https://example.net/page. Also (https://example.net/x) and xhttps://no",
+        );
+
+        assert_eq!(
+            item.extract_links()?,
+            vec![
+                "https://example.org/posts/2/",
+                "https://example.org/a.png",
+                "https://example.com/?a=1&b=2",
+                "https://example.net/page",
+                "https://example.net/x",
+            ]
+        );
+
+        item.remove_url();
+        assert_eq!(item.extract_links()?.len(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn non_ascii_links() -> Result<(), Error> {
+        let mut item = Item::new();
+        item.set_url("https://example.org/posts/1/");
+        item.set_content_html(
+            r#"<a href="mailto:é@example.org">Mail</a> <img src="urn:é"> <a href="ü/">Ü</a>"#,
+        );
+
+        assert_eq!(
+            item.extract_links()?,
+            vec![
+                "mailto:é@example.org",
+                "urn:é",
+                "https://example.org/posts/1/ü/"
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn non_ascii_feed_checks() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "version": "https://jsonfeed.org/version/1.1",
+            "title": "Example",
+            "items": [{
+                "id": "1",
+                "url": "https://example.org/posts/1/",
+                "image": "urn:é",
+                "content_html": "<img src=\"urn:é\"> <a href=\"mailto:é@example.org\">Mail</a>"
+            }]
+        }))?;
+
+        let _ = feed.lints();
+        let _ = feed.health_report();
+
+        Ok(())
+    }
+
+    #[test]
+    fn mentions() -> Result<(), Error> {
+        let mut item = Item::new();
+        item.set_url("https://example.org/posts/1");
+        item.set_content_html(
+            r#"<a href="/about">About</a> <a href="https://EXAMPLE.com:443/a#one">A</a>
+            <a href="https://example.com/a#two">A</a> <a href="ftp://example.net/f">F</a>
+            <a href="http://example.net/b">B</a>"#,
+        );
+
+        assert_eq!(
+            item.mention_targets(None)?,
+            vec!["https://example.com/a", "http://example.net/b"]
+        );
+        assert_eq!(
+            item.mention_targets(Some("Example.com"))?,
+            vec!["https://example.org/about", "http://example.net/b"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn summaries() -> Result<(), Error> {
+        let mut item = Item::new();
+        assert_eq!(summary_text(item.as_map(), 10)?, None);
+        item.set_content_text("one  two\nthree four");
+        assert_eq!(
+            summary_text(item.as_map(), 18)?.as_deref(),
+            Some("one two three four")
+        );
+        assert_eq!(
+            summary_text(item.as_map(), 14)?.as_deref(),
+            Some("one two three\u{2026}")
+        );
+        assert_eq!(
+            summary_text(item.as_map(), 13)?.as_deref(),
+            Some("one two\u{2026}")
+        );
+        item.set_summary("Supercalifragilistic");
+        assert_eq!(
+            summary_text(item.as_map(), 6)?.as_deref(),
+            Some("Super\u{2026}")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rewrite() -> Result<(), Error> {
+        let mut feed = crate::from_value(serde_json::json!({
+            "items": [
+                { "id": "1", "content_html": "<p>Hello</p>" },
+                { "id": "2", "content_text": "Hello" },
+                { "id": "3", "content_html": "<div>Hello</div>" }
+            ]
+        }))?;
+        assert_eq!(
+            feed.rewrite_content_html(|html| html.replace("<p>", "<p class=\"x\">"))?,
+            1
+        );
+        let items = feed.items()?.unwrap();
+        assert_eq!(items[0].content_html()?, Some("<p class=\"x\">Hello</p>"));
+        assert_eq!(items[2].content_html()?, Some("<div>Hello</div>"));
+        Ok(())
+    }
+}
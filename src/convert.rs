@@ -0,0 +1,9 @@
+//! Conversions between JSON Feed and other formats.
+//!
+//! The model types convert from JSON objects and values with `From` and `TryFrom`. The modules
+//! for other formats are grouped here and are also available from the crate root.
+
+#[cfg(feature = "feed_rs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "feed_rs")))]
+pub use crate::feed_rs;
+pub use crate::xml;
@@ -0,0 +1,195 @@
+//! Validation of properties which must be consistent with each other.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{collections::BTreeSet, string::String};
+#[cfg(feature = "std")]
+use std::{collections::BTreeSet, string::String};
+
+use serde_json::{Map, Value};
+
+use crate::{date::Timestamp, CrossFieldRules, FeedRef, ItemRef, ValidationOptions};
+
+fn is_date_order_valid(item: &ItemRef<'_>) -> bool {
+    let date = |date: Option<&str>| date.and_then(|date| Timestamp::parse(date).ok());
+    match (
+        date(item.date_published().ok().flatten()),
+        date(item.date_modified().ok().flatten()),
+    ) {
+        (Some(published), Some(modified)) => published <= modified,
+        _ => true,
+    }
+}
+
+/// Returns true if no two attachments have the same title and MIME type.
+fn are_alternate_mime_types_distinct(item: &ItemRef<'_>) -> bool {
+    let attachments = match item.attachments() {
+        Ok(Some(attachments)) => attachments,
+        Ok(None) | Err(_) => return true,
+    };
+    let mut seen = BTreeSet::new();
+    attachments.iter().all(
+        |attachment| match (attachment.title(), attachment.mime_type()) {
+            (Ok(Some(title)), Ok(Some(mime_type))) => {
+                seen.insert((title, mime_type.to_ascii_lowercase()))
+            }
+            _ => true,
+        },
+    )
+}
+
+/// Returns true if the item passes the enabled item checks.
+pub(crate) fn is_consistent_item(map: &Map<String, Value>, rules: &CrossFieldRules) -> bool {
+    let item = ItemRef::from(map);
+    (!rules.date_modified_after_published || is_date_order_valid(&item))
+        && (!rules.distinct_alternate_mime_types || are_alternate_mime_types_distinct(&item))
+}
+
+/// Returns true if the feed passes the enabled feed checks.
+///
+/// The items are checked by `is_consistent_item`.
+pub(crate) fn is_consistent_feed(map: &Map<String, Value>, options: &ValidationOptions) -> bool {
+    let rules = &options.cross_field;
+    let feed = FeedRef::from(map);
+    let next_url_valid = !rules.next_url_differs_from_feed_url
+        || match (feed.next_url(), feed.feed_url()) {
+            (Ok(Some(next_url)), Ok(Some(feed_url))) => next_url != feed_url,
+            _ => true,
+        };
+    let hubs_valid = !rules.expired_without_hubs
+        || !feed
+            .expired_with(&options.coercion)
+            .ok()
+            .flatten()
+            .unwrap_or(false)
+        || !map.contains_key("hubs");
+    next_url_valid && hubs_valid
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CrossFieldRules, Error, ValidationOptions, Version};
+    use serde_json::json;
+
+    fn is_valid(value: serde_json::Value, rules: CrossFieldRules) -> Result<bool, Error> {
+        let mut options = ValidationOptions::new();
+        options.cross_field = rules;
+        Ok(crate::validate_feed_map(
+            crate::from_value(value)?.as_map(),
+            &Version::Version1_1,
+            &options,
+        ))
+    }
+
+    #[test]
+    fn rules() -> Result<(), Error> {
+        let feed = |item: serde_json::Value, extra: serde_json::Value| {
+            let mut feed = json!({
+                "version": "https://jsonfeed.org/version/1.1",
+                "title": "Example",
+                "feed_url": "https://example.org/feed.json",
+                "items": [item]
+            });
+            if let (Some(feed), Some(extra)) = (feed.as_object_mut(), extra.as_object()) {
+                feed.extend(extra.clone());
+            }
+            feed
+        };
+        let item = json!({ "id": "1", "content_text": "Hi" });
+        let backdated = json!({
+            "id": "1",
+            "content_text": "Hi",
+            "date_published": "2021-01-02T00:00:00Z",
+            "date_modified": "2021-01-02T01:00:00+02:00"
+        });
+        let alternates = json!({
+            "id": "1",
+            "content_text": "Hi",
+            "attachments": [
+                { "url": "https://example.org/1.mp3", "mime_type": "audio/mpeg", "title": "Ep" },
+                { "url": "https://example.org/2.mp3", "mime_type": "Audio/MPEG", "title": "Ep" }
+            ]
+        });
+        let cases = [
+            (backdated, json!({})),
+            (alternates, json!({})),
+            (
+                item.clone(),
+                json!({ "next_url": "https://example.org/feed.json" }),
+            ),
+            (
+                item.clone(),
+                json!({
+                    "expired": true,
+                    "hubs": [{ "type": "WebSub", "url": "https://example.org/hub" }]
+                }),
+            ),
+        ];
+        for (item, extra) in cases.iter().cloned() {
+            assert!(is_valid(
+                feed(item.clone(), extra.clone()),
+                CrossFieldRules::new()
+            )?);
+            assert!(!is_valid(feed(item, extra), CrossFieldRules::all())?);
+        }
+
+        let mut rules = CrossFieldRules::all();
+        rules.date_modified_after_published = false;
+        assert!(is_valid(feed(cases[0].0.clone(), json!({})), rules)?);
+        assert!(is_valid(feed(item, json!({})), CrossFieldRules::all())?);
+        Ok(())
+    }
+
+    #[test]
+    fn edge_cases() -> Result<(), Error> {
+        let feed = |item: serde_json::Value| {
+            json!({
+                "version": "https://jsonfeed.org/version/1.1",
+                "title": "Example",
+                "items": [item]
+            })
+        };
+        let consistent = [
+            // Equal dates in different offsets.
+            json!({
+                "id": "1",
+                "content_text": "Hi",
+                "date_published": "2021-01-02T00:00:00Z",
+                "date_modified": "2021-01-02T02:00:00+02:00"
+            }),
+            // Dates which cannot be compared are not checked.
+            json!({
+                "id": "1",
+                "content_text": "Hi",
+                "date_published": "2021-01-02T00:00:00Z",
+                "date_modified": "yesterday"
+            }),
+            json!({ "id": "1", "content_text": "Hi", "date_modified": "2021-01-01T00:00:00Z" }),
+            // Alternates need a title and MIME type to be compared.
+            json!({
+                "id": "1",
+                "content_text": "Hi",
+                "attachments": [
+                    { "url": "https://example.org/1.mp3", "mime_type": "audio/mpeg", "title": "Ep 1" },
+                    { "url": "https://example.org/2.mp3", "mime_type": "audio/mpeg", "title": "Ep 2" },
+                    { "url": "https://example.org/3.mp3", "mime_type": "audio/mpeg" },
+                    { "url": "https://example.org/4.mp3", "mime_type": "audio/mpeg" }
+                ]
+            }),
+        ];
+        for item in consistent.iter().cloned() {
+            assert!(is_valid(feed(item), CrossFieldRules::all())?);
+        }
+
+        let feed = json!({
+            "version": "https://jsonfeed.org/version/1.1",
+            "title": "Example",
+            "feed_url": "https://example.org/feed.json",
+            "next_url": "https://example.org/feed.json?page=2",
+            "expired": false,
+            "hubs": [{ "type": "WebSub", "url": "https://example.org/hub" }],
+            "items": []
+        });
+        assert!(is_valid(feed, CrossFieldRules::all())?);
+        Ok(())
+    }
+}
@@ -0,0 +1,987 @@
+//! [RFC 3339][rfc_3339] dates.
+//!
+//! JSON Feed dates such as `date_published` are RFC 3339 strings. The crate does not depend on a
+//! date library, so `Timestamp` provides the parsing and formatting needed to compare and
+//! normalize dates.
+//!
+//! ```
+//! use json_feed_model::date::Timestamp;
+//!
+//! let a = Timestamp::parse("2021-06-01T12:00:00+02:00")?;
+//! let b = Timestamp::parse("2021-06-01T10:00:00Z")?;
+//! assert_eq!(a, b);
+//! assert_eq!(a.to_utc().to_rfc3339(), "2021-06-01T10:00:00Z");
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+//!
+//! [rfc_3339]: https://tools.ietf.org/html/rfc3339
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+use core::{fmt::Write, time::Duration};
+
+use serde_json::{Map, Value};
+
+use crate::{Error, Feed, FeedMut, FeedRef, Item, ItemMut, ItemRef};
+
+/// An instant in time with the UTC offset it was written with.
+///
+/// Timestamps are compared by the instant they represent. Two timestamps written with
+/// different offsets are equal if they represent the same instant.
+#[derive(Clone, Copy, Debug)]
+pub struct Timestamp {
+    unix_seconds: i64,
+    nanosecond: u32,
+    offset_minutes: i16,
+}
+
+/// Returns the number of days since 1970-01-01 for a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Returns the (year, month, day) for a number of days since 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = if days >= 0 { days } else { days - 146_096 } / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    (year, month as u32, day as u32)
+}
+
+fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn digits(bytes: &[u8], start: usize, len: usize) -> Result<u32, Error> {
+    let digits = bytes.get(start..start + len).ok_or(Error::InvalidDate)?;
+    digits.iter().try_fold(0, |acc, b| {
+        if b.is_ascii_digit() {
+            Ok(acc * 10 + u32::from(b - b'0'))
+        } else {
+            Err(Error::InvalidDate)
+        }
+    })
+}
+
+fn expect(bytes: &[u8], index: usize, expected: &[u8]) -> Result<(), Error> {
+    match bytes.get(index) {
+        Some(b) if expected.contains(b) => Ok(()),
+        _ => Err(Error::InvalidDate),
+    }
+}
+
+impl Timestamp {
+    /// Parses an RFC 3339 date-time string (e.g. `2021-06-01T12:00:00.5-07:00`).
+    ///
+    /// # Errors
+    ///
+    /// If the string is not an RFC 3339 date-time, then `Error::InvalidDate` is returned.
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        let bytes = s.as_bytes();
+
+        let year = digits(bytes, 0, 4)?;
+        expect(bytes, 4, b"-")?;
+        let month = digits(bytes, 5, 2)?;
+        expect(bytes, 7, b"-")?;
+        let day = digits(bytes, 8, 2)?;
+        expect(bytes, 10, b"Tt ")?;
+        let hour = digits(bytes, 11, 2)?;
+        expect(bytes, 13, b":")?;
+        let minute = digits(bytes, 14, 2)?;
+        expect(bytes, 16, b":")?;
+        let second = digits(bytes, 17, 2)?;
+
+        let year = i64::from(year);
+        if !(1..=12).contains(&month)
+            || day == 0
+            || day > days_in_month(year, month)
+            || hour > 23
+            || minute > 59
+            // A leap second is allowed by RFC 3339.
+            || second > 60
+        {
+            return Err(Error::InvalidDate);
+        }
+
+        let mut index = 19;
+        let mut nanosecond = 0;
+        if bytes.get(index) == Some(&b'.') {
+            index += 1;
+            let start = index;
+            while bytes.get(index).map_or(false, u8::is_ascii_digit) {
+                if index - start < 9 {
+                    nanosecond = nanosecond * 10 + u32::from(bytes[index] - b'0');
+                }
+                index += 1;
+            }
+            let fraction_len = index - start;
+            if fraction_len == 0 {
+                return Err(Error::InvalidDate);
+            }
+            for _ in fraction_len..9 {
+                nanosecond *= 10;
+            }
+        }
+
+        let offset_minutes = match bytes.get(index) {
+            Some(b'Z') | Some(b'z') if bytes.len() == index + 1 => 0,
+            Some(sign @ b'+') | Some(sign @ b'-') if bytes.len() == index + 6 => {
+                let offset_hour = digits(bytes, index + 1, 2)?;
+                expect(bytes, index + 3, b":")?;
+                let offset_minute = digits(bytes, index + 4, 2)?;
+                if offset_hour > 23 || offset_minute > 59 {
+                    return Err(Error::InvalidDate);
+                }
+                #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+                let offset = (offset_hour * 60 + offset_minute) as i16;
+                if *sign == b'-' {
+                    -offset
+                } else {
+                    offset
+                }
+            }
+            _ => return Err(Error::InvalidDate),
+        };
+
+        let local_seconds = days_from_civil(year, month, day) * 86_400
+            + i64::from(hour) * 3600
+            + i64::from(minute) * 60
+            + i64::from(second);
+
+        Ok(Self {
+            unix_seconds: local_seconds - i64::from(offset_minutes) * 60,
+            nanosecond,
+            offset_minutes,
+        })
+    }
+
+    /// Instantiates a UTC timestamp from the number of seconds and nanoseconds since the Unix
+    /// epoch.
+    ///
+    /// Nanoseconds greater than a second are carried into the seconds.
+    #[must_use]
+    pub fn from_unix(seconds: i64, nanosecond: u32) -> Self {
+        Self {
+            unix_seconds: seconds + i64::from(nanosecond / 1_000_000_000),
+            nanosecond: nanosecond % 1_000_000_000,
+            offset_minutes: 0,
+        }
+    }
+
+    /// Returns the number of whole seconds since the Unix epoch.
+    #[must_use]
+    pub fn unix_seconds(&self) -> i64 {
+        self.unix_seconds
+    }
+
+    /// Returns the fraction of the second in nanoseconds.
+    #[must_use]
+    pub fn nanosecond(&self) -> u32 {
+        self.nanosecond
+    }
+
+    /// Returns the UTC offset in minutes which the timestamp is written with.
+    #[must_use]
+    pub fn offset_minutes(&self) -> i16 {
+        self.offset_minutes
+    }
+
+    /// Returns the same instant written with a different UTC offset.
+    ///
+    /// The offset is clamped to the range allowed by RFC 3339 (-23:59 to +23:59).
+    #[must_use]
+    pub fn with_offset(&self, offset_minutes: i16) -> Self {
+        Self {
+            offset_minutes: offset_minutes.clamp(-1439, 1439),
+            ..*self
+        }
+    }
+
+    /// Returns the same instant written in UTC.
+    #[must_use]
+    pub fn to_utc(&self) -> Self {
+        self.with_offset(0)
+    }
+
+    /// Returns the local date (year, month, day) in the timestamp's offset.
+    #[must_use]
+    pub fn date(&self) -> (i64, u32, u32) {
+        let local = self.unix_seconds + i64::from(self.offset_minutes) * 60;
+        civil_from_days(local.div_euclid(86_400))
+    }
+
+    /// Returns the local time (hour, minute, second) in the timestamp's offset.
+    #[must_use]
+    pub fn time(&self) -> (u32, u32, u32) {
+        let local = self.unix_seconds + i64::from(self.offset_minutes) * 60;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let seconds = local.rem_euclid(86_400) as u32;
+        (seconds / 3600, seconds / 60 % 60, seconds % 60)
+    }
+
+    /// Returns the signed number of seconds from `other` to `self`.
+    #[must_use]
+    pub fn seconds_since(&self, other: &Timestamp) -> i64 {
+        self.unix_seconds - other.unix_seconds
+    }
+
+    /// Returns the duration from `earlier` to `self`.
+    ///
+    /// If `earlier` is after `self`, then a zero duration is returned.
+    #[must_use]
+    pub fn saturating_duration_since(&self, earlier: &Timestamp) -> Duration {
+        if self <= earlier {
+            return Duration::from_secs(0);
+        }
+        let (seconds, nanosecond) = if self.nanosecond >= earlier.nanosecond {
+            (
+                self.unix_seconds - earlier.unix_seconds,
+                self.nanosecond - earlier.nanosecond,
+            )
+        } else {
+            (
+                self.unix_seconds - earlier.unix_seconds - 1,
+                self.nanosecond + 1_000_000_000 - earlier.nanosecond,
+            )
+        };
+        #[allow(clippy::cast_sign_loss)]
+        Duration::new(seconds as u64, nanosecond)
+    }
+
+    /// Formats the timestamp as an RFC 3339 string in the timestamp's offset.
+    ///
+    /// Fractional seconds are written without trailing zeros. A zero offset is written as `Z`.
+    #[must_use]
+    pub fn to_rfc3339(&self) -> String {
+        let mut s = String::with_capacity(25);
+        let _ = write!(s, "{}", self);
+        s
+    }
+
+    /// Formats the timestamp as an HTTP date (e.g. `Tue, 01 Jun 2021 12:00:00 GMT`).
+    ///
+    /// The date is in UTC and fractional seconds are truncated, as in the `Last-Modified` and
+    /// `Expires` headers.
+    #[must_use]
+    pub fn to_http_date(&self) -> String {
+        const WEEKDAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+        const MONTHS: [&str; 12] = [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ];
+        let utc = self.to_utc();
+        let (year, month, day) = utc.date();
+        let (hour, minute, second) = utc.time();
+        // 1970-01-01 was a Thursday.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let weekday = utc.unix_seconds.div_euclid(86_400).rem_euclid(7) as usize;
+        let mut s = String::with_capacity(29);
+        let _ = write!(
+            s,
+            "{}, {:02} {} {:04} {:02}:{:02}:{:02} GMT",
+            WEEKDAYS[weekday],
+            day,
+            MONTHS[month as usize - 1],
+            year,
+            hour,
+            minute,
+            second
+        );
+        s
+    }
+}
+
+impl core::fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let (year, month, day) = self.date();
+        let (hour, minute, second) = self.time();
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            year, month, day, hour, minute, second
+        )?;
+        if self.nanosecond != 0 {
+            let mut fraction = self.nanosecond;
+            let mut width = 9;
+            while fraction % 10 == 0 {
+                fraction /= 10;
+                width -= 1;
+            }
+            write!(f, ".{:0width$}", fraction, width = width)?;
+        }
+        if self.offset_minutes == 0 {
+            f.write_str("Z")
+        } else {
+            let sign = if self.offset_minutes < 0 { '-' } else { '+' };
+            let offset = self.offset_minutes.unsigned_abs();
+            write!(f, "{}{:02}:{:02}", sign, offset / 60, offset % 60)
+        }
+    }
+}
+
+impl PartialEq for Timestamp {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == core::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for Timestamp {}
+
+impl PartialOrd for Timestamp {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Timestamp {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        (self.unix_seconds, self.nanosecond).cmp(&(other.unix_seconds, other.nanosecond))
+    }
+}
+
+impl core::str::FromStr for Timestamp {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Timestamp::parse(s)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::time::SystemTime> for Timestamp {
+    fn from(time: std::time::SystemTime) -> Self {
+        match time.duration_since(std::time::UNIX_EPOCH) {
+            #[allow(clippy::cast_possible_wrap)]
+            Ok(since) => Timestamp::from_unix(since.as_secs() as i64, since.subsec_nanos()),
+            Err(err) => {
+                let before = err.duration();
+                #[allow(clippy::cast_possible_wrap)]
+                let seconds = -(before.as_secs() as i64);
+                if before.subsec_nanos() == 0 {
+                    Timestamp::from_unix(seconds, 0)
+                } else {
+                    Timestamp::from_unix(seconds - 1, 1_000_000_000 - before.subsec_nanos())
+                }
+            }
+        }
+    }
+}
+
+/// A value which can be converted into a `Timestamp`.
+///
+/// Methods which compare dates against the current time accept any `ToTimestamp` so the time
+/// can be given as an RFC 3339 string, a `Timestamp`, or (with the `std` feature) a
+/// `SystemTime`.
+pub trait ToTimestamp {
+    /// Converts the value into a `Timestamp`.
+    ///
+    /// # Errors
+    ///
+    /// If the value is not a valid date, then `Error::InvalidDate` is returned.
+    fn to_timestamp(&self) -> Result<Timestamp, Error>;
+}
+
+impl ToTimestamp for Timestamp {
+    fn to_timestamp(&self) -> Result<Timestamp, Error> {
+        Ok(*self)
+    }
+}
+
+impl ToTimestamp for &Timestamp {
+    fn to_timestamp(&self) -> Result<Timestamp, Error> {
+        Ok(**self)
+    }
+}
+
+impl ToTimestamp for &str {
+    fn to_timestamp(&self) -> Result<Timestamp, Error> {
+        Timestamp::parse(self)
+    }
+}
+
+impl ToTimestamp for &String {
+    fn to_timestamp(&self) -> Result<Timestamp, Error> {
+        Timestamp::parse(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl ToTimestamp for std::time::SystemTime {
+    fn to_timestamp(&self) -> Result<Timestamp, Error> {
+        Ok(Timestamp::from(*self))
+    }
+}
+
+fn parse_date(map: &Map<String, Value>, key: &str) -> Result<Option<Timestamp>, Error> {
+    crate::ext::__private::str_prop::get(map, None, key)?
+        .map(Timestamp::parse)
+        .transpose()
+}
+
+/// Returns the item's `date_modified` or, if it is not present, its `date_published`.
+fn item_last_updated(map: &Map<String, Value>) -> Result<Option<Timestamp>, Error> {
+    match parse_date(map, "date_modified")? {
+        Some(date) => Ok(Some(date)),
+        None => parse_date(map, "date_published"),
+    }
+}
+
+fn item_age<T: ToTimestamp>(map: &Map<String, Value>, now: &T) -> Result<Option<Duration>, Error> {
+    let now = now.to_timestamp()?;
+    Ok(parse_date(map, "date_published")?
+        .map(|published| now.saturating_duration_since(&published)))
+}
+
+macro_rules! item_date_methods {
+    () => {
+        /// Returns the time elapsed from `date_published` until `now`.
+        ///
+        /// If the item does not have a `date_published`, then `None` is returned. If the item
+        /// is published after `now`, then a zero duration is returned.
+        ///
+        /// # Errors
+        ///
+        /// If `date_published` is not the expected JSON type, then `Error::UnexpectedType` is
+        /// returned. If `date_published` or `now` is not a valid date, then
+        /// `Error::InvalidDate` is returned.
+        pub fn age<T: ToTimestamp>(&self, now: T) -> Result<Option<Duration>, Error> {
+            item_age(self.as_map(), &now)
+        }
+
+        /// Returns when the item was last updated.
+        ///
+        /// The `date_modified` is used if present, otherwise the `date_published` is used.
+        ///
+        /// # Errors
+        ///
+        /// If the dates are not the expected JSON type, then `Error::UnexpectedType` is
+        /// returned. If the date is not a valid date, then `Error::InvalidDate` is returned.
+        pub fn last_updated(&self) -> Result<Option<Timestamp>, Error> {
+            item_last_updated(self.as_map())
+        }
+    };
+}
+
+impl Item {
+    item_date_methods!();
+}
+
+impl<'a> ItemMut<'a> {
+    item_date_methods!();
+}
+
+impl<'a> ItemRef<'a> {
+    item_date_methods!();
+}
+
+fn feed_last_updated(map: &Map<String, Value>) -> Result<Option<Timestamp>, Error> {
+    let mut last_updated = None;
+    for item in crate::item_refs(map)? {
+        last_updated = last_updated.max(item.last_updated()?);
+    }
+    Ok(last_updated)
+}
+
+fn stale_items<'a, T: ToTimestamp>(
+    map: &'a Map<String, Value>,
+    threshold: Duration,
+    now: &T,
+) -> Result<Vec<ItemRef<'a>>, Error> {
+    let now = now.to_timestamp()?;
+    let mut stale = Vec::new();
+    for item in crate::item_refs(map)? {
+        if let Some(last_updated) = item.last_updated()? {
+            if now.saturating_duration_since(&last_updated) > threshold {
+                stale.push(item);
+            }
+        }
+    }
+    Ok(stale)
+}
+
+fn is_stale<T: ToTimestamp>(
+    map: &Map<String, Value>,
+    threshold: Duration,
+    now: &T,
+) -> Result<bool, Error> {
+    let now = now.to_timestamp()?;
+    Ok(feed_last_updated(map)?.map_or(false, |last_updated| {
+        now.saturating_duration_since(&last_updated) > threshold
+    }))
+}
+
+/// Returns true if the item's `date_published` is after `now`.
+///
+/// Items without a valid `date_published` are not scheduled.
+pub(crate) fn is_scheduled(item: &ItemRef<'_>, now: &Timestamp) -> Result<bool, Error> {
+    Ok(item
+        .date_published()?
+        .and_then(|date| Timestamp::parse(date).ok())
+        .map_or(false, |published| published > *now))
+}
+
+fn published_view<T: ToTimestamp>(map: &Map<String, Value>, now: &T) -> Result<Feed, Error> {
+    let now = now.to_timestamp()?;
+    let mut published = Map::with_capacity(map.len());
+    for (key, value) in map {
+        if key != "items" {
+            published.insert(key.clone(), value.clone());
+        }
+    }
+    if map.contains_key("items") {
+        let mut items = Vec::new();
+        for item in crate::item_refs(map)? {
+            if !is_scheduled(&item, &now)? {
+                items.push(Value::Object(item.as_map().clone()));
+            }
+        }
+        published.insert(String::from("items"), Value::Array(items));
+    }
+    Ok(Feed::from(published))
+}
+
+macro_rules! feed_date_methods {
+    () => {
+        /// Returns the most recent time an item was updated.
+        ///
+        /// See `Item::last_updated` for how an item's last update is determined.
+        ///
+        /// # Errors
+        ///
+        /// If the items or their dates are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned. If a date is not a valid date, then
+        /// `Error::InvalidDate` is returned.
+        pub fn last_updated(&self) -> Result<Option<Timestamp>, Error> {
+            feed_last_updated(self.as_map())
+        }
+
+        /// Returns the items which were last updated more than `threshold` before `now`.
+        ///
+        /// Items without dates are not considered stale.
+        ///
+        /// # Errors
+        ///
+        /// If the items or their dates are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned. If a date or `now` is not a valid date, then
+        /// `Error::InvalidDate` is returned.
+        pub fn stale_items<T: ToTimestamp>(
+            &self,
+            threshold: Duration,
+            now: T,
+        ) -> Result<Vec<ItemRef<'_>>, Error> {
+            stale_items(self.as_map(), threshold, &now)
+        }
+
+        /// Returns true if no item has been updated within `threshold` before `now`.
+        ///
+        /// A feed without any dated items is not considered stale.
+        ///
+        /// # Errors
+        ///
+        /// If the items or their dates are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned. If a date or `now` is not a valid date, then
+        /// `Error::InvalidDate` is returned.
+        pub fn is_stale<T: ToTimestamp>(&self, threshold: Duration, now: T) -> Result<bool, Error> {
+            is_stale(self.as_map(), threshold, &now)
+        }
+
+        /// Returns a copy of the feed without the items scheduled to be published after `now`.
+        ///
+        /// Items are scheduled if their `date_published` is after `now`. Items without a
+        /// `date_published` or with an invalid date are kept. The master feed can contain
+        /// scheduled items while the published view is served to readers.
+        ///
+        /// # Errors
+        ///
+        /// If the items or their dates are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned. If `now` is not a valid date, then
+        /// `Error::InvalidDate` is returned.
+        pub fn published_view<T: ToTimestamp>(&self, now: T) -> Result<Feed, Error> {
+            published_view(self.as_map(), &now)
+        }
+    };
+}
+
+impl Feed {
+    feed_date_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    feed_date_methods!();
+}
+
+impl<'a> FeedRef<'a> {
+    feed_date_methods!();
+}
+
+fn normalize_dates(map: &mut Map<String, Value>, offset_minutes: i16) -> Result<usize, Error> {
+    let _span = trace_span!("normalize_dates", offset_minutes);
+    let items = match map.get_mut("items") {
+        None => return Ok(0),
+        Some(Value::Array(items)) => items,
+        Some(_) => return Err(Error::UnexpectedType),
+    };
+
+    let mut normalized = 0;
+    for item in items {
+        let item = item.as_object_mut().ok_or(Error::UnexpectedType)?;
+        for key in &["date_published", "date_modified"] {
+            let date = match item.get_mut(*key) {
+                None => continue,
+                Some(Value::String(date)) => date,
+                Some(_) => return Err(Error::UnexpectedType),
+            };
+            if let Ok(timestamp) = Timestamp::parse(date) {
+                let formatted = timestamp.with_offset(offset_minutes).to_rfc3339();
+                if *date != formatted {
+                    *date = formatted;
+                    normalized += 1;
+                }
+            }
+        }
+    }
+    trace_event!(normalized, "normalized item dates");
+    Ok(normalized)
+}
+
+macro_rules! normalize_methods {
+    () => {
+        /// Rewrites the items' `date_published` and `date_modified` in UTC with a `Z` suffix.
+        ///
+        /// The instants do not change. Invalid dates are left unchanged. Returns the number of
+        /// dates which were rewritten.
+        ///
+        /// # Errors
+        ///
+        /// If the items or their dates are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned.
+        pub fn normalize_dates_to_utc(&mut self) -> Result<usize, Error> {
+            normalize_dates(self.as_map_mut(), 0)
+        }
+
+        /// Rewrites the items' `date_published` and `date_modified` with a fixed UTC offset in
+        /// minutes (e.g. `-300` for `-05:00`).
+        ///
+        /// The instants do not change. Invalid dates are left unchanged. Returns the number of
+        /// dates which were rewritten. See `Timestamp::with_offset` for the allowed offsets.
+        ///
+        /// # Errors
+        ///
+        /// If the items or their dates are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned.
+        pub fn normalize_dates_to_offset(&mut self, offset_minutes: i16) -> Result<usize, Error> {
+            normalize_dates(self.as_map_mut(), offset_minutes)
+        }
+    };
+}
+
+impl Feed {
+    normalize_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    normalize_methods!();
+}
+
+/// Words for relative times in a language.
+struct RelativeWords {
+    now: &'static str,
+    past: (&'static str, &'static str),
+    future: (&'static str, &'static str),
+    /// The singular and plural names of minutes, hours, days, months, and years.
+    units: [(&'static str, &'static str); 5],
+}
+
+const ENGLISH: RelativeWords = RelativeWords {
+    now: "just now",
+    past: ("", " ago"),
+    future: ("in ", ""),
+    units: [
+        ("minute", "minutes"),
+        ("hour", "hours"),
+        ("day", "days"),
+        ("month", "months"),
+        ("year", "years"),
+    ],
+};
+
+const FRENCH: RelativeWords = RelativeWords {
+    now: "\u{e0} l\u{2019}instant",
+    past: ("il y a ", ""),
+    future: ("dans ", ""),
+    units: [
+        ("minute", "minutes"),
+        ("heure", "heures"),
+        ("jour", "jours"),
+        ("mois", "mois"),
+        ("an", "ans"),
+    ],
+};
+
+const GERMAN: RelativeWords = RelativeWords {
+    now: "gerade eben",
+    past: ("vor ", ""),
+    future: ("in ", ""),
+    units: [
+        ("Minute", "Minuten"),
+        ("Stunde", "Stunden"),
+        ("Tag", "Tagen"),
+        ("Monat", "Monaten"),
+        ("Jahr", "Jahren"),
+    ],
+};
+
+const SPANISH: RelativeWords = RelativeWords {
+    now: "ahora mismo",
+    past: ("hace ", ""),
+    future: ("dentro de ", ""),
+    units: [
+        ("minuto", "minutos"),
+        ("hora", "horas"),
+        ("d\u{ed}a", "d\u{ed}as"),
+        ("mes", "meses"),
+        ("a\u{f1}o", "a\u{f1}os"),
+    ],
+};
+
+/// Returns the time of `date` relative to `now` in words (e.g. "3 hours ago" or "in 2 days").
+///
+/// Relative times are translated for English, French, German, and Spanish. Other languages use
+/// English.
+pub(crate) fn relative(locale: &str, date: &Timestamp, now: &Timestamp) -> String {
+    let words = match locale.get(..2) {
+        Some("de") => &GERMAN,
+        Some("es") => &SPANISH,
+        Some("fr") => &FRENCH,
+        _ => &ENGLISH,
+    };
+
+    let seconds = now.seconds_since(date);
+    let is_future = seconds < 0;
+    let seconds = seconds.unsigned_abs();
+    // The thresholds round to the nearest larger unit when it reads more naturally.
+    let (unit, count) = match seconds {
+        0..=44 => return String::from(words.now),
+        45..=2_699 => (0, (seconds + 30) / 60),
+        2_700..=79_199 => (1, (seconds + 1_800) / 3_600),
+        79_200..=2_246_399 => (2, (seconds + 43_200) / 86_400),
+        2_246_400..=27_647_999 => (3, ((seconds + 1_296_000) / 2_592_000).max(1)),
+        _ => (4, ((seconds + 15_768_000) / 31_536_000).max(1)),
+    };
+    let (singular, plural) = words.units[unit];
+    let name = if count == 1 { singular } else { plural };
+    let (prefix, suffix) = if is_future { words.future } else { words.past };
+    let mut relative = String::new();
+    let _ = write!(relative, "{}{} {}{}", prefix, count, name, suffix);
+    relative
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::{string::String, vec, vec::Vec};
+
+    #[test]
+    fn parse_and_format() -> Result<(), Error> {
+        let t = Timestamp::parse("2020-02-29T23:59:59.120-08:00")?;
+        assert_eq!(t.offset_minutes(), -480);
+        assert_eq!(t.nanosecond(), 120_000_000);
+        assert_eq!(t.date(), (2020, 2, 29));
+        assert_eq!(t.to_rfc3339(), "2020-02-29T23:59:59.12-08:00");
+        assert_eq!(t.to_utc().to_rfc3339(), "2020-03-01T07:59:59.12Z");
+        assert_eq!(t.to_http_date(), "Sun, 01 Mar 2020 07:59:59 GMT");
+        assert_eq!(
+            Timestamp::from_unix(-1, 0).to_http_date(),
+            "Wed, 31 Dec 1969 23:59:59 GMT"
+        );
+
+        let epoch = Timestamp::parse("1970-01-01t00:00:00z")?;
+        assert_eq!(epoch.unix_seconds(), 0);
+        assert_eq!(
+            Timestamp::from_unix(-1, 0).to_rfc3339(),
+            "1969-12-31T23:59:59Z"
+        );
+        assert_eq!(
+            Timestamp::parse("2000-01-01T00:00:00Z")?.unix_seconds(),
+            946_684_800
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_dates() {
+        for s in &[
+            "",
+            "2021-01-01",
+            "2021-02-29T00:00:00Z",
+            "2021-13-01T00:00:00Z",
+            "2021-01-01T24:00:00Z",
+            "2021-01-01T00:00:00",
+            "2021-01-01T00:00:00.Z",
+            "2021-01-01T00:00:00+0100",
+            "2021-01-01T00:00:00Zjunk",
+            "Mon, 01 Jan 2021 00:00:00 GMT",
+        ] {
+            assert!(
+                matches!(Timestamp::parse(s), Err(Error::InvalidDate)),
+                "{}",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn age_and_staleness() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "items": [
+                { "id": "1", "date_published": "2021-01-01T00:00:00Z" },
+                {
+                    "id": "2",
+                    "date_published": "2020-01-01T00:00:00Z",
+                    "date_modified": "2021-01-02T00:00:00.5Z"
+                },
+                { "id": "3" }
+            ]
+        }))?;
+        let now = "2021-01-03T00:00:00Z";
+        let day = Duration::from_secs(86_400);
+
+        let items = feed.items()?.unwrap_or_default();
+        assert_eq!(items[0].age(now)?, Some(day * 2));
+        assert_eq!(
+            items[0].age("2020-12-31T00:00:00Z")?,
+            Some(Duration::from_secs(0))
+        );
+        assert_eq!(items[2].age(now)?, None);
+        assert!(matches!(items[0].age("yesterday"), Err(Error::InvalidDate)));
+        #[cfg(feature = "std")]
+        assert!(items[0].age(std::time::SystemTime::now())?.unwrap() > day);
+
+        assert_eq!(
+            feed.last_updated()?,
+            Some(Timestamp::parse("2021-01-02T00:00:00.5Z")?)
+        );
+        let stale = feed.stale_items(day, now)?;
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].id()?, Some("1"));
+        assert!(!feed.is_stale(day, now)?);
+        assert!(feed.is_stale(day / 4, now)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn scheduled_items() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "title": "Blog",
+            "items": [
+                { "id": "1", "date_published": "2021-01-01T00:00:00Z" },
+                { "id": "2", "date_published": "2021-01-03T00:00:00+01:00" },
+                { "id": "3" }
+            ]
+        }))?;
+
+        let view = feed.published_view("2021-01-02T22:30:00Z")?;
+        assert_eq!(view.title()?, Some("Blog"));
+        let ids = view
+            .items()?
+            .unwrap_or_default()
+            .iter()
+            .map(|item| item.id().map(|id| id.map(String::from)))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(ids, vec![Some(String::from("1")), Some(String::from("3"))]);
+        assert_eq!(feed.items()?.unwrap_or_default().len(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn normalize() -> Result<(), Error> {
+        let mut feed = crate::from_value(serde_json::json!({
+            "items": [
+                {
+                    "id": "1",
+                    "date_published": "2021-01-01T20:00:00-05:00",
+                    "date_modified": "2021-01-02T01:00:00Z"
+                },
+                { "id": "2", "date_published": "not a date" }
+            ]
+        }))?;
+
+        assert_eq!(feed.normalize_dates_to_utc()?, 1);
+        let items = feed.items()?.unwrap_or_default();
+        assert_eq!(items[0].date_published()?, Some("2021-01-02T01:00:00Z"));
+        assert_eq!(items[1].date_published()?, Some("not a date"));
+
+        assert_eq!(feed.normalize_dates_to_offset(90)?, 2);
+        let items = feed.items()?.unwrap_or_default();
+        assert_eq!(items[0].date_modified()?, Some("2021-01-02T02:30:00+01:30"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn relative_times() -> Result<(), Error> {
+        let date = Timestamp::parse("2021-06-01T12:00:00Z")?;
+        let at = |s| Timestamp::parse(s);
+
+        assert_eq!(
+            relative("en", &date, &at("2021-06-01T12:00:30Z")?),
+            "just now"
+        );
+        assert_eq!(
+            relative("en", &date, &at("2021-06-01T12:01:00Z")?),
+            "1 minute ago"
+        );
+        assert_eq!(
+            relative("en_GB", &date, &at("2021-06-03T12:00:00Z")?),
+            "2 days ago"
+        );
+        assert_eq!(
+            relative("de", &date, &at("2021-06-01T10:00:00Z")?),
+            "in 2 Stunden"
+        );
+        assert_eq!(
+            relative("fr-FR", &date, &at("2021-08-01T12:00:00Z")?),
+            "il y a 2 mois"
+        );
+        assert_eq!(
+            relative("es", &date, &at("2023-06-01T12:00:00Z")?),
+            "hace 2 a\u{f1}os"
+        );
+        assert_eq!(
+            relative("xx", &date, &at("2021-06-02T12:00:00Z")?),
+            "1 day ago"
+        );
+
+        Ok(())
+    }
+}
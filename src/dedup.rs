@@ -0,0 +1,185 @@
+//! Near-duplicate detection for items.
+//!
+//! Merged feeds often contain the same post more than once, for instance when it is syndicated
+//! to several sites with small edits. Items are compared with [MinHash][minhash] signatures of
+//! their word shingles, which estimate the Jaccard similarity of the items' text.
+//!
+//! [minhash]: https://en.wikipedia.org/wiki/MinHash
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{Error, Feed, FeedMut, FeedRef, ItemRef};
+
+/// The number of consecutive words in a shingle.
+const SHINGLE_SIZE: usize = 3;
+
+/// The number of hash functions in a signature.
+const SIGNATURE_LEN: usize = 128;
+
+/// Returns the text which is compared for an item.
+///
+/// `content_text` is preferred. Otherwise, the text of `content_html` is used.
+fn item_text(item: &ItemRef<'_>) -> Result<Option<String>, Error> {
+    if let Some(text) = item.content_text()? {
+        return Ok(Some(String::from(text)));
+    }
+    Ok(item.content_html()?.map(crate::html::strip_tags))
+}
+
+/// Mixes a hash with a seed to simulate independent hash functions.
+fn mix(hash: u64, seed: u64) -> u64 {
+    let mut z = hash ^ seed.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Returns the MinHash signature of the text or `None` if the text has no words.
+fn signature(text: &str) -> Option<Vec<u64>> {
+    let words = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>();
+    if words.is_empty() {
+        return None;
+    }
+
+    let mut signature = Vec::with_capacity(SIGNATURE_LEN);
+    signature.resize(SIGNATURE_LEN, u64::MAX);
+    let mut shingle = String::new();
+    for window in words.windows(SHINGLE_SIZE.min(words.len())) {
+        shingle.clear();
+        for word in window {
+            shingle.push_str(word);
+            shingle.push(' ');
+        }
+        let hash = crate::canonical::fnv1a(shingle.as_bytes());
+        for (seed, min) in signature.iter_mut().enumerate() {
+            *min = (*min).min(mix(hash, seed as u64));
+        }
+    }
+    Some(signature)
+}
+
+fn similarity(a: &[u64], b: &[u64]) -> f64 {
+    let equal = a.iter().zip(b).filter(|(a, b)| a == b).count();
+    equal as f64 / SIGNATURE_LEN as f64
+}
+
+fn find(parents: &mut [usize], mut index: usize) -> usize {
+    while parents[index] != index {
+        parents[index] = parents[parents[index]];
+        index = parents[index];
+    }
+    index
+}
+
+fn near_duplicate_items(
+    map: &Map<String, Value>,
+    threshold: f64,
+) -> Result<Vec<Vec<usize>>, Error> {
+    let mut signatures = Vec::new();
+    for (index, item) in crate::item_refs(map)?.iter().enumerate() {
+        if let Some(signature) = item_text(item)?.as_deref().and_then(signature) {
+            signatures.push((index, signature));
+        }
+    }
+
+    let mut parents = (0..signatures.len()).collect::<Vec<_>>();
+    for a in 0..signatures.len() {
+        for b in a + 1..signatures.len() {
+            if similarity(&signatures[a].1, &signatures[b].1) >= threshold {
+                let (root_a, root_b) = (find(&mut parents, a), find(&mut parents, b));
+                parents[root_a.max(root_b)] = root_a.min(root_b);
+            }
+        }
+    }
+
+    let mut clusters: Vec<Vec<usize>> = Vec::new();
+    let mut cluster_of_root: Vec<Option<usize>> = Vec::new();
+    cluster_of_root.resize(signatures.len(), None);
+    for (position, (item_index, _)) in signatures.iter().enumerate() {
+        let item_index = *item_index;
+        let root = find(&mut parents, position);
+        match cluster_of_root[root] {
+            Some(cluster) => clusters[cluster].push(item_index),
+            None => {
+                cluster_of_root[root] = Some(clusters.len());
+                clusters.push(core::iter::once(item_index).collect());
+            }
+        }
+    }
+    clusters.retain(|cluster| cluster.len() > 1);
+    Ok(clusters)
+}
+
+macro_rules! dedup_methods {
+    () => {
+        /// Returns clusters of items whose text is nearly the same.
+        ///
+        /// Items are compared by their `content_text` or, if they do not have one, the text of
+        /// their `content_html`. The `threshold` is the minimum estimated [Jaccard
+        /// similarity][jaccard] of the items' three-word shingles, from `0.0` to `1.0`. A
+        /// threshold around `0.8` finds copies with small edits.
+        ///
+        /// Each cluster contains the indices of two or more items in ascending order. Clusters
+        /// are ordered by their first item. Items without text are not in any cluster.
+        ///
+        /// [jaccard]: https://en.wikipedia.org/wiki/Jaccard_index
+        ///
+        /// # Errors
+        ///
+        /// If the items or their content are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned.
+        #[cfg_attr(docsrs, doc(cfg(feature = "dedup")))]
+        pub fn near_duplicate_items(&self, threshold: f64) -> Result<Vec<Vec<usize>>, Error> {
+            near_duplicate_items(self.as_map(), threshold)
+        }
+    };
+}
+
+impl Feed {
+    dedup_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    dedup_methods!();
+}
+
+impl<'a> FeedRef<'a> {
+    dedup_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clusters() -> Result<(), Error> {
+        let text = "The quick brown fox jumps over the lazy dog while the cat sleeps in the warm \
+            afternoon sun and the birds sing in the tall green trees near the quiet river bank";
+        let feed = crate::from_value(serde_json::json!({
+            "items": [
+                { "id": "1", "content_text": text },
+                { "id": "2", "content_text": "Something else entirely, about baking bread." },
+                { "id": "3", "content_html": format!("<p>{}</p>", text.to_uppercase()) },
+                { "id": "4", "content_text": text.replace("quiet river bank", "quiet river bank.") },
+                { "id": "5" },
+                { "id": "6", "content_text": "Something else entirely, about baking bread!" }
+            ]
+        }))?;
+
+        assert_eq!(
+            feed.near_duplicate_items(0.8)?,
+            vec![vec![0, 2, 3], vec![1, 5]]
+        );
+        assert!(feed.near_duplicate_items(1.1)?.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,223 @@
+//! Usage of properties deprecated by the JSON Feed spec.
+//!
+//! JSON Feed 1.1 deprecated the `author` object in favor of the `authors` array. Feeds which
+//! still declare version 1.0 or use `author` can be found with [`Feed::deprecations`] and
+//! upgraded with [`Feed::apply_fixes`].
+//!
+//! ```
+//! let mut feed = json_feed_model::from_str(r#"{
+//!     "version": "https://jsonfeed.org/version/1",
+//!     "author": { "name": "Jane" },
+//!     "items": []
+//! }"#)?;
+//!
+//! let deprecations = feed.deprecations()?;
+//! assert_eq!(deprecations.len(), 2);
+//! assert_eq!(deprecations[0].path, "/version");
+//! assert_eq!(deprecations[1].path, "/author");
+//! assert!(deprecations.iter().all(|d| d.auto_fixable));
+//!
+//! let fixed = feed.apply_fixes()?;
+//! assert_eq!(fixed.len(), 2);
+//! assert!(feed.deprecations()?.is_empty());
+//! assert_eq!(feed.authors()?.unwrap()[0].name()?, Some("Jane"));
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{format, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{format, string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{Error, Feed, FeedMut, FeedRef, VERSION_1, VERSION_1_1};
+
+/// A use of a deprecated property.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Deprecation {
+    /// The [JSON Pointer][json_pointer] to the deprecated value.
+    ///
+    /// [json_pointer]: https://tools.ietf.org/html/rfc6901
+    pub path: String,
+    /// Why the value is deprecated.
+    pub reason: &'static str,
+    /// What to use instead.
+    pub replacement: String,
+    /// If true, `apply_fixes()` replaces the value.
+    pub auto_fixable: bool,
+}
+
+/// Returns true if the `author` can be moved into the `authors` array.
+fn can_merge_author(map: &Map<String, Value>) -> bool {
+    matches!(map.get("author"), Some(Value::Object(_)))
+        && matches!(map.get("authors"), None | Some(Value::Array(_)))
+}
+
+fn author_deprecation(map: &Map<String, Value>, path: &str) -> Option<Deprecation> {
+    map.get("author")?;
+    Some(Deprecation {
+        path: format!("{}/author", path),
+        reason: "The `author` object is deprecated as of JSON Feed 1.1.",
+        replacement: format!("{}/authors", path),
+        auto_fixable: can_merge_author(map),
+    })
+}
+
+fn deprecations(map: &Map<String, Value>) -> Result<Vec<Deprecation>, Error> {
+    let mut deprecations = Vec::new();
+    if FeedRef::from(map).version()? == Some(VERSION_1) {
+        deprecations.push(Deprecation {
+            path: String::from("/version"),
+            reason: "JSON Feed 1.0 is superseded by the backwards compatible JSON Feed 1.1.",
+            replacement: String::from(VERSION_1_1),
+            auto_fixable: true,
+        });
+    }
+    deprecations.extend(author_deprecation(map, ""));
+    for (index, item) in crate::item_refs(map)?.iter().enumerate() {
+        deprecations.extend(author_deprecation(
+            item.as_map(),
+            &format!("/items/{}", index),
+        ));
+    }
+    Ok(deprecations)
+}
+
+/// Moves the `author` into the `authors` array unless an equal author is already there.
+fn merge_author(map: &mut Map<String, Value>) {
+    if !can_merge_author(map) {
+        return;
+    }
+    let author = map.remove("author").unwrap_or_default();
+    if let Value::Array(authors) = map
+        .entry("authors")
+        .or_insert_with(|| Value::Array(Vec::new()))
+    {
+        if !authors.contains(&author) {
+            authors.insert(0, author);
+        }
+    }
+}
+
+fn apply_fixes(map: &mut Map<String, Value>) -> Result<Vec<Deprecation>, Error> {
+    let fixable = deprecations(map)?
+        .into_iter()
+        .filter(|deprecation| deprecation.auto_fixable)
+        .collect::<Vec<_>>();
+    if fixable.is_empty() {
+        return Ok(fixable);
+    }
+
+    if FeedRef::from(&*map).version()? == Some(VERSION_1) {
+        map.insert(
+            String::from("version"),
+            Value::String(String::from(VERSION_1_1)),
+        );
+    }
+    merge_author(map);
+    if let Some(Value::Array(items)) = map.get_mut("items") {
+        for item in items {
+            if let Value::Object(item) = item {
+                merge_author(item);
+            }
+        }
+    }
+    Ok(fixable)
+}
+
+macro_rules! deprecations_methods {
+    () => {
+        /// Returns the uses of deprecated properties.
+        ///
+        /// A version 1.0 feed and the `author` of the feed and its items are reported. An
+        /// `author` can be fixed if it is an object and the `authors` are missing or an array.
+        ///
+        /// # Errors
+        ///
+        /// If the `version` or the items are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned.
+        pub fn deprecations(&self) -> Result<Vec<Deprecation>, Error> {
+            deprecations(self.as_map())
+        }
+    };
+}
+
+macro_rules! apply_fixes_methods {
+    () => {
+        /// Replaces the deprecated properties which can be fixed and returns what was fixed.
+        ///
+        /// The `version` is set to version 1.1. Each `author` is moved to the start of the
+        /// `authors` array unless an equal author is already in the array.
+        ///
+        /// # Errors
+        ///
+        /// If the `version` or the items are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned.
+        pub fn apply_fixes(&mut self) -> Result<Vec<Deprecation>, Error> {
+            apply_fixes(self.as_map_mut())
+        }
+    };
+}
+
+impl Feed {
+    deprecations_methods!();
+    apply_fixes_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    deprecations_methods!();
+    apply_fixes_methods!();
+}
+
+impl<'a> FeedRef<'a> {
+    deprecations_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::{vec, vec::Vec};
+
+    #[test]
+    fn author_fixes() -> Result<(), Error> {
+        let mut feed = crate::from_value(serde_json::json!({
+            "version": "https://jsonfeed.org/version/1.1",
+            "items": [
+                { "id": "1", "author": { "name": "A" }, "authors": [{ "name": "A" }] },
+                { "id": "2", "author": { "name": "B" }, "authors": [{ "name": "C" }] },
+                { "id": "3", "author": "D" }
+            ]
+        }))?;
+
+        let deprecations = feed.deprecations()?;
+        assert_eq!(
+            deprecations
+                .iter()
+                .map(|d| (d.path.as_str(), d.auto_fixable))
+                .collect::<Vec<_>>(),
+            vec![
+                ("/items/0/author", true),
+                ("/items/1/author", true),
+                ("/items/2/author", false)
+            ]
+        );
+
+        assert_eq!(feed.apply_fixes()?.len(), 2);
+        assert_eq!(
+            feed,
+            crate::from_value(serde_json::json!({
+                "version": "https://jsonfeed.org/version/1.1",
+                "items": [
+                    { "id": "1", "authors": [{ "name": "A" }] },
+                    { "id": "2", "authors": [{ "name": "B" }, { "name": "C" }] },
+                    { "id": "3", "author": "D" }
+                ]
+            }))?
+        );
+        assert!(feed.apply_fixes()?.is_empty());
+        Ok(())
+    }
+}
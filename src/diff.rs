@@ -0,0 +1,566 @@
+//! Reports of the changes between two fetches of a feed.
+//!
+//! A [`FeedDiff`] lists the new, edited, and removed items and can be rendered as plain text
+//! or Markdown for commit messages and notification emails.
+//!
+//! ```
+//! let prev = json_feed_model::from_value(serde_json::json!({
+//!     "title": "Example",
+//!     "items": [
+//!         { "id": "1", "title": "Hello", "summary": "Hi" },
+//!         { "id": "2", "title": "Old" }
+//!     ]
+//! }))?;
+//! let next = json_feed_model::from_value(serde_json::json!({
+//!     "title": "Example",
+//!     "items": [
+//!         { "id": "3", "title": "News", "url": "https://example.org/3" },
+//!         { "id": "1", "title": "Hello", "summary": "Hi!" }
+//!     ]
+//! }))?;
+//!
+//! let diff = json_feed_model::diff::diff(&prev, &next)?;
+//! assert_eq!(
+//!     diff.render_markdown(),
+//!     "**1 new, 1 edited, 1 removed**\n\
+//!      \n### New\n\
+//!      \n\
+//!      - [News](https://example.org/3)\n\
+//!      \n### Edited\n\
+//!      \n\
+//!      - Hello: `summary`\n\
+//!      \n### Removed\n\
+//!      \n\
+//!      - Old\n"
+//! );
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+//!
+//! With the `content_diff` feature, edited items also show the words which changed in their
+//! content.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+    vec::Vec,
+};
+
+use core::fmt::Write;
+
+use serde_json::{Map, Value};
+
+use crate::{ext::__private::str_prop, Error, Feed, Item, ItemRef};
+
+#[cfg(feature = "content_diff")]
+#[cfg_attr(docsrs, doc(cfg(feature = "content_diff")))]
+pub mod content;
+
+/// The identifying properties of an item.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ItemSummary {
+    /// The item's `id`.
+    pub id: String,
+    /// The item's `title`.
+    pub title: Option<String>,
+    /// The item's `url` or, if it does not have one, its `external_url`.
+    pub url: Option<String>,
+    /// A copy of the item.
+    ///
+    /// Removed items are copied from the older feed, and other items from the newer feed.
+    pub item: Item,
+}
+
+impl ItemSummary {
+    fn new(id: &str, item: &Map<String, Value>) -> Result<Self, Error> {
+        let item = ItemRef::from(item);
+        Ok(Self {
+            id: String::from(id),
+            title: item.title()?.map(String::from),
+            url: item.url()?.or(item.external_url()?).map(String::from),
+            item: Item::from(item.as_map().clone()),
+        })
+    }
+
+    /// Returns the title or, if the item does not have one, the `id`.
+    fn label(&self) -> &str {
+        self.title.as_deref().unwrap_or(&self.id)
+    }
+}
+
+/// An item which is in both feeds but is different.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct EditedItem {
+    /// The item in the newer feed.
+    pub item: ItemSummary,
+    /// The keys of the item's properties which were added, changed, or removed.
+    pub fields: Vec<String>,
+    /// The word-level changes of the text content if `content_text` or `content_html`
+    /// changed.
+    #[cfg(feature = "content_diff")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "content_diff")))]
+    pub content: Option<content::ContentDiff>,
+}
+
+impl EditedItem {
+    fn new(id: &str, prev: &Map<String, Value>, next: &Map<String, Value>) -> Result<Self, Error> {
+        let fields = changed_keys(prev, next, "");
+        #[cfg(feature = "content_diff")]
+        let content = if fields
+            .iter()
+            .any(|field| field == "content_text" || field == "content_html")
+        {
+            Some(content::word_diff(prev, next)?)
+        } else {
+            None
+        };
+        Ok(Self {
+            item: ItemSummary::new(id, next)?,
+            fields,
+            #[cfg(feature = "content_diff")]
+            content,
+        })
+    }
+}
+
+/// The changes between two fetches of a feed.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct FeedDiff {
+    /// The keys of the feed's top-level properties (other than `items`) which were added,
+    /// changed, or removed.
+    pub feed_fields: Vec<String>,
+    /// The items which are only in the newer feed in the newer feed's order.
+    pub added: Vec<ItemSummary>,
+    /// The items which are different in the newer feed's order.
+    pub edited: Vec<EditedItem>,
+    /// The items which are only in the older feed in the older feed's order.
+    pub removed: Vec<ItemSummary>,
+}
+
+/// Returns the keys which are different between two objects in order.
+fn changed_keys<'a>(
+    prev: &'a Map<String, Value>,
+    next: &'a Map<String, Value>,
+    skip: &str,
+) -> Vec<String> {
+    prev.keys()
+        .chain(next.keys())
+        .filter(|key| *key != skip && prev.get(*key) != next.get(*key))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .cloned()
+        .collect()
+}
+
+/// Items with an `id` paired with the `id`.
+type ItemsWithId<'a> = Vec<(&'a str, &'a Map<String, Value>)>;
+
+/// Returns the items with an `id` in order.
+///
+/// If multiple items have the same `id`, the first item is used.
+fn items_with_id(feed: &Feed) -> Result<ItemsWithId<'_>, Error> {
+    let mut seen = BTreeSet::new();
+    let mut items = Vec::new();
+    if let Some(values) = feed.as_map().get("items") {
+        for value in values.as_array().ok_or(Error::UnexpectedType)? {
+            let item = value.as_object().ok_or(Error::UnexpectedType)?;
+            if let Some(id) = str_prop::get(item, None, "id")? {
+                if seen.insert(id) {
+                    items.push((id, item));
+                }
+            }
+        }
+    }
+    Ok(items)
+}
+
+/// Compares two fetches of a feed.
+///
+/// Items are matched by their `id`. Items without an `id` are skipped.
+///
+/// # Errors
+///
+/// If the items or their `id`, `title`, URLs, or content are not the expected JSON type,
+/// then `Error::UnexpectedType` is returned.
+pub fn diff(prev: &Feed, next: &Feed) -> Result<FeedDiff, Error> {
+    let prev_items = items_with_id(prev)?;
+    let next_items = items_with_id(next)?;
+    let prev_by_id = prev_items.iter().copied().collect::<BTreeMap<_, _>>();
+    let next_by_id = next_items.iter().copied().collect::<BTreeMap<_, _>>();
+
+    let mut diff = FeedDiff {
+        feed_fields: changed_keys(prev.as_map(), next.as_map(), "items"),
+        ..FeedDiff::default()
+    };
+    for (id, next_item) in &next_items {
+        match prev_by_id.get(id) {
+            None => diff.added.push(ItemSummary::new(id, next_item)?),
+            Some(prev_item) if prev_item != next_item => {
+                diff.edited.push(EditedItem::new(id, prev_item, next_item)?);
+            }
+            Some(_) => {}
+        }
+    }
+    for (id, prev_item) in &prev_items {
+        if !next_by_id.contains_key(id) {
+            diff.removed.push(ItemSummary::new(id, prev_item)?);
+        }
+    }
+    Ok(diff)
+}
+
+/// Escapes the characters which have a meaning in Markdown text.
+fn escape_markdown(text: &str, out: &mut String) {
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|' | '!'
+        ) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+}
+
+/// Replaces runs of whitespace with a single space so text fits on one line.
+#[cfg(feature = "content_diff")]
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+impl FeedDiff {
+    /// Returns true if there are no changes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.feed_fields.is_empty()
+            && self.added.is_empty()
+            && self.edited.is_empty()
+            && self.removed.is_empty()
+    }
+
+    fn counts(&self) -> String {
+        let mut counts = String::new();
+        let _ = write!(
+            counts,
+            "{} new, {} edited, {} removed",
+            self.added.len(),
+            self.edited.len(),
+            self.removed.len()
+        );
+        counts
+    }
+
+    /// Renders the changes as plain text.
+    ///
+    /// The first line counts the changed items. It is followed by the changed feed properties
+    /// and a section for each kind of item change. Items are listed by their title (or `id`)
+    /// and URL. With the `content_diff` feature, an edited item's content changes are rendered
+    /// on the next line.
+    #[must_use]
+    pub fn render_text(&self) -> String {
+        if self.is_empty() {
+            return String::from("No changes\n");
+        }
+
+        let mut text = self.counts();
+        text.push('\n');
+        if !self.feed_fields.is_empty() {
+            let _ = write!(text, "\nFeed changed: {}\n", self.feed_fields.join(", "));
+        }
+
+        let item_line = |text: &mut String, item: &ItemSummary| {
+            text.push_str("  * ");
+            text.push_str(item.label());
+            if let Some(url) = &item.url {
+                let _ = write!(text, " <{}>", url);
+            }
+        };
+        if !self.added.is_empty() {
+            text.push_str("\nNew:\n");
+            for item in &self.added {
+                item_line(&mut text, item);
+                text.push('\n');
+            }
+        }
+        if !self.edited.is_empty() {
+            text.push_str("\nEdited:\n");
+            for edited in &self.edited {
+                item_line(&mut text, &edited.item);
+                let _ = writeln!(text, " ({})", edited.fields.join(", "));
+                #[cfg(feature = "content_diff")]
+                if let Some(content) = &edited.content {
+                    let _ = writeln!(text, "    {}", collapse_whitespace(&content.render_text()));
+                }
+            }
+        }
+        if !self.removed.is_empty() {
+            text.push_str("\nRemoved:\n");
+            for item in &self.removed {
+                item_line(&mut text, item);
+                text.push('\n');
+            }
+        }
+        text
+    }
+
+    /// Renders the changes as Markdown.
+    ///
+    /// The item titles are escaped and linked to the items' URLs. Changed property keys are
+    /// formatted as code. With the `content_diff` feature, an edited item's content changes are
+    /// rendered in a nested list item with deleted text struck through and inserted text in
+    /// bold.
+    #[must_use]
+    pub fn render_markdown(&self) -> String {
+        if self.is_empty() {
+            return String::from("No changes\n");
+        }
+
+        let mut markdown = String::new();
+        let _ = writeln!(markdown, "**{}**", self.counts());
+        let code_list = |markdown: &mut String, fields: &[String]| {
+            for (index, field) in fields.iter().enumerate() {
+                if index > 0 {
+                    markdown.push_str(", ");
+                }
+                let _ = write!(markdown, "`{}`", field.replace('`', "'"));
+            }
+        };
+        if !self.feed_fields.is_empty() {
+            markdown.push_str("\nFeed changed: ");
+            code_list(&mut markdown, &self.feed_fields);
+            markdown.push('\n');
+        }
+
+        let item_line = |markdown: &mut String, item: &ItemSummary| {
+            markdown.push_str("- ");
+            match &item.url {
+                Some(url) => {
+                    markdown.push('[');
+                    escape_markdown(item.label(), markdown);
+                    markdown.push_str("](");
+                    if url.contains(|c: char| c.is_whitespace() || c == '(' || c == ')') {
+                        let _ = write!(markdown, "<{}>", url);
+                    } else {
+                        markdown.push_str(url);
+                    }
+                    markdown.push(')');
+                }
+                None => escape_markdown(item.label(), markdown),
+            }
+        };
+        if !self.added.is_empty() {
+            markdown.push_str("\n### New\n\n");
+            for item in &self.added {
+                item_line(&mut markdown, item);
+                markdown.push('\n');
+            }
+        }
+        if !self.edited.is_empty() {
+            markdown.push_str("\n### Edited\n\n");
+            for edited in &self.edited {
+                item_line(&mut markdown, &edited.item);
+                markdown.push_str(": ");
+                code_list(&mut markdown, &edited.fields);
+                markdown.push('\n');
+                #[cfg(feature = "content_diff")]
+                if let Some(content) = &edited.content {
+                    let mut rendered = String::new();
+                    content.render(&mut rendered, ("~~", "~~"), ("**", "**"), escape_markdown);
+                    let _ = writeln!(markdown, "  - {}", collapse_whitespace(&rendered));
+                }
+            }
+        }
+        if !self.removed.is_empty() {
+            markdown.push_str("\n### Removed\n\n");
+            for item in &self.removed {
+                item_line(&mut markdown, item);
+                markdown.push('\n');
+            }
+        }
+        markdown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::vec;
+
+    #[test]
+    fn render() -> Result<(), Error> {
+        let prev = crate::from_value(serde_json::json!({
+            "title": "Old",
+            "items": [
+                { "id": "1", "title": "A *bold* claim", "url": "https://example.org/1" },
+                { "id": "2" }
+            ]
+        }))?;
+        let next = crate::from_value(serde_json::json!({
+            "title": "New",
+            "icon": "https://example.org/icon.png",
+            "items": [
+                { "id": "3", "url": "https://example.org/a (b)" },
+                { "id": "1", "title": "A *bold* claim", "url": "https://example.org/1", "summary": "S" },
+                { "id": "2" },
+                { "title": "No id" }
+            ]
+        }))?;
+
+        let diff = diff(&prev, &next)?;
+        assert_eq!(diff.feed_fields, vec!["icon", "title"]);
+        assert_eq!(
+            diff.render_text(),
+            "1 new, 1 edited, 0 removed\n\
+             \n\
+             Feed changed: icon, title\n\
+             \n\
+             New:\n  \
+             * 3 <https://example.org/a (b)>\n\
+             \n\
+             Edited:\n  \
+             * A *bold* claim <https://example.org/1> (summary)\n"
+        );
+        assert_eq!(
+            diff.render_markdown(),
+            "**1 new, 1 edited, 0 removed**\n\
+             \n\
+             Feed changed: `icon`, `title`\n\
+             \n\
+             ### New\n\
+             \n\
+             - [3](<https://example.org/a (b)>)\n\
+             \n\
+             ### Edited\n\
+             \n\
+             - [A \\*bold\\* claim](https://example.org/1): `summary`\n"
+        );
+
+        #[cfg(feature = "content_diff")]
+        {
+            let prev = crate::from_value(serde_json::json!({
+                "items": [{ "id": "1", "content_html": "<p>Hello *world*</p>\n<p>Bye</p>" }]
+            }))?;
+            let next = crate::from_value(serde_json::json!({
+                "items": [{ "id": "1", "content_html": "<p>Hello *everyone*</p>\n<p>Bye</p>" }]
+            }))?;
+            let diff = super::diff(&prev, &next)?;
+            assert_eq!(
+                diff.edited[0].content.as_ref().map(|c| c.deletions()),
+                Some(1)
+            );
+            assert!(diff
+                .render_text()
+                .ends_with("  * 1 (content_html)\n    Hello [-*world*-]{+*everyone*+} Bye\n"));
+            assert!(diff.render_markdown().ends_with(
+                "- 1: `content_html`\n  - Hello ~~\\*world\\*~~**\\*everyone\\*** Bye\n"
+            ));
+        }
+
+        let same = super::diff(&next, &next)?;
+        assert!(same.is_empty());
+        assert_eq!(same.render_text(), "No changes\n");
+        Ok(())
+    }
+
+    #[test]
+    fn added_and_removed() -> Result<(), Error> {
+        let prev = crate::from_value(serde_json::json!({
+            "items": [{ "id": "1", "title": "One" }, { "id": "2" }, { "id": "3" }]
+        }))?;
+        let next = crate::from_value(serde_json::json!({
+            "items": [
+                { "id": "5", "external_url": "https://example.org/5" },
+                { "id": "2" },
+                { "id": "4" }
+            ]
+        }))?;
+
+        let diff = diff(&prev, &next)?;
+        assert!(diff.feed_fields.is_empty());
+        assert!(diff.edited.is_empty());
+        assert_eq!(
+            diff.added
+                .iter()
+                .map(|item| item.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["5", "4"]
+        );
+        assert_eq!(diff.added[0].url.as_deref(), Some("https://example.org/5"));
+        assert_eq!(
+            diff.removed
+                .iter()
+                .map(|item| item.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["1", "3"]
+        );
+        assert_eq!(diff.removed[0].title.as_deref(), Some("One"));
+        assert_eq!(diff.removed[0].item.title()?, Some("One"));
+        assert!(diff
+            .render_text()
+            .starts_with("2 new, 0 edited, 2 removed\n"));
+        assert!(diff.render_text().ends_with("Removed:\n  * One\n  * 3\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn reordered() -> Result<(), Error> {
+        let prev = crate::from_value(serde_json::json!({
+            "items": [{ "id": "1" }, { "id": "2" }, { "id": "3" }]
+        }))?;
+        let next = crate::from_value(serde_json::json!({
+            "items": [{ "id": "3" }, { "id": "1" }, { "id": "2" }, { "id": "1", "title": "Dup" }]
+        }))?;
+
+        let diff = diff(&prev, &next)?;
+        assert!(diff.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn type_changes() -> Result<(), Error> {
+        let prev = crate::from_value(serde_json::json!({
+            "title": "Feed",
+            "items": [{ "id": "1", "tags": "a" }]
+        }))?;
+        let next = crate::from_value(serde_json::json!({
+            "title": ["Feed"],
+            "items": [{ "id": "1", "tags": ["a"] }]
+        }))?;
+
+        let diff = diff(&prev, &next)?;
+        assert_eq!(diff.feed_fields, vec!["title"]);
+        assert_eq!(diff.edited.len(), 1);
+        assert_eq!(diff.edited[0].fields, vec!["tags"]);
+
+        let bad_title = crate::from_value(serde_json::json!({
+            "items": [{ "id": "1", "title": 1 }]
+        }))?;
+        assert!(matches!(
+            super::diff(&prev, &bad_title),
+            Err(Error::UnexpectedType)
+        ));
+
+        let bad_items = crate::from_value(serde_json::json!({ "items": {} }))?;
+        assert!(matches!(
+            super::diff(&bad_items, &next),
+            Err(Error::UnexpectedType)
+        ));
+
+        let bad_id = crate::from_value(serde_json::json!({ "items": [{ "id": 1 }] }))?;
+        assert!(matches!(
+            super::diff(&prev, &bad_id),
+            Err(Error::UnexpectedType)
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,273 @@
+//! Diffs of the text content of an edited item.
+//!
+//! When an item is marked as edited, a [`ContentDiff`] shows which words or lines of its
+//! content changed.
+//!
+//! ```
+//! use json_feed_model::Item;
+//!
+//! let mut old = Item::new();
+//! old.set_content_text("The launch is on Monday.");
+//! let mut new = Item::new();
+//! new.set_content_html("<p>The launch is on <em>Tuesday</em>.</p>");
+//!
+//! let diff = Item::content_diff(&old, &new)?;
+//! assert_eq!(diff.insertions(), 1);
+//! assert_eq!(diff.render_text(), "The launch is on [-Monday.-]{+Tuesday.+}\n");
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+use std::{borrow::Cow, string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+use similar::TextDiff;
+
+use crate::{Error, Item};
+
+/// The number of unchanged words kept on each side of a change when rendering.
+const CONTEXT_WORDS: usize = 4;
+
+/// The kind of a change.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ChangeTag {
+    /// The text is in both versions.
+    Equal,
+    /// The text is only in the old version.
+    Delete,
+    /// The text is only in the new version.
+    Insert,
+}
+
+/// A run of text with the same kind of change.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Change {
+    /// The kind of change.
+    pub tag: ChangeTag,
+    /// The text including any whitespace.
+    pub text: String,
+}
+
+/// The changes between the text content of two versions of an item.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ContentDiff {
+    /// The runs of text in order. Adjacent runs have different tags.
+    pub changes: Vec<Change>,
+}
+
+impl ContentDiff {
+    fn new<'a>(diff: &TextDiff<'a, 'a, 'a, str>) -> Self {
+        let mut changes: Vec<Change> = Vec::new();
+        for change in diff.iter_all_changes() {
+            let tag = match change.tag() {
+                similar::ChangeTag::Equal => ChangeTag::Equal,
+                similar::ChangeTag::Delete => ChangeTag::Delete,
+                similar::ChangeTag::Insert => ChangeTag::Insert,
+            };
+            match changes.last_mut() {
+                Some(last) if last.tag == tag => last.text.push_str(change.value()),
+                _ => changes.push(Change {
+                    tag,
+                    text: String::from(change.value()),
+                }),
+            }
+        }
+        Self { changes }
+    }
+
+    /// Returns true if the content did not change other than whitespace.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.insertions() == 0 && self.deletions() == 0
+    }
+
+    /// Returns the number of inserted runs of text.
+    ///
+    /// Runs of only whitespace are not counted.
+    #[must_use]
+    pub fn insertions(&self) -> usize {
+        self.count(ChangeTag::Insert)
+    }
+
+    /// Returns the number of deleted runs of text.
+    ///
+    /// Runs of only whitespace are not counted.
+    #[must_use]
+    pub fn deletions(&self) -> usize {
+        self.count(ChangeTag::Delete)
+    }
+
+    fn count(&self, tag: ChangeTag) -> usize {
+        self.changes
+            .iter()
+            .filter(|change| change.tag == tag && !change.text.trim().is_empty())
+            .count()
+    }
+
+    /// Renders the changes with deleted text as `[-text-]` and inserted text as `{+text+}`.
+    ///
+    /// Long runs of unchanged text are shortened to the few words around the changes, with
+    /// `…` in place of the removed words.
+    #[must_use]
+    pub fn render_text(&self) -> String {
+        let mut text = String::new();
+        self.render(&mut text, ("[-", "-]"), ("{+", "+}"), |t, out| {
+            out.push_str(t)
+        });
+        text
+    }
+
+    pub(super) fn render(
+        &self,
+        out: &mut String,
+        delete: (&str, &str),
+        insert: (&str, &str),
+        push: fn(&str, &mut String),
+    ) {
+        let last = self.changes.len().saturating_sub(1);
+        for (index, change) in self.changes.iter().enumerate() {
+            let (open, close) = match change.tag {
+                ChangeTag::Equal => {
+                    push(&elide(&change.text, index > 0, index < last), out);
+                    continue;
+                }
+                ChangeTag::Delete => delete,
+                ChangeTag::Insert => insert,
+            };
+            // Whitespace is kept outside of the markers.
+            let trimmed = change.text.trim();
+            if trimmed.is_empty() {
+                out.push_str(&change.text);
+                continue;
+            }
+            let start = change.text.len() - change.text.trim_start().len();
+            out.push_str(&change.text[..start]);
+            out.push_str(open);
+            push(trimmed, out);
+            out.push_str(close);
+            out.push_str(&change.text[start + trimmed.len()..]);
+        }
+    }
+}
+
+/// Shortens unchanged text to the words next to the changes before and after it.
+fn elide(text: &str, before: bool, after: bool) -> Cow<'_, str> {
+    let words = text.split_whitespace().collect::<Vec<_>>();
+    let head = if before { CONTEXT_WORDS } else { 0 };
+    let tail = if after { CONTEXT_WORDS } else { 0 };
+    if words.len() <= head + tail {
+        return Cow::Borrowed(text);
+    }
+
+    let mut elided = String::new();
+    if head > 0 {
+        if text.starts_with(char::is_whitespace) {
+            elided.push(' ');
+        }
+        elided.push_str(&words[..head].join(" "));
+        elided.push(' ');
+    }
+    elided.push('…');
+    if tail > 0 {
+        elided.push(' ');
+        elided.push_str(&words[words.len() - tail..].join(" "));
+        if text.ends_with(char::is_whitespace) {
+            elided.push(' ');
+        }
+    }
+    Cow::Owned(elided)
+}
+
+/// Returns the plain text content with surrounding whitespace replaced by a final newline.
+fn text(map: &Map<String, Value>) -> Result<String, Error> {
+    let mut text = crate::content::plain_text(map)?
+        .map(|text| String::from(text.trim()))
+        .unwrap_or_default();
+    if !text.is_empty() {
+        text.push('\n');
+    }
+    Ok(text)
+}
+
+/// Returns the word-level changes between the text content of two items.
+pub(super) fn word_diff(
+    old: &Map<String, Value>,
+    new: &Map<String, Value>,
+) -> Result<ContentDiff, Error> {
+    let (old, new) = (text(old)?, text(new)?);
+    Ok(ContentDiff::new(&TextDiff::from_words(&old, &new)))
+}
+
+fn line_diff(old: &Map<String, Value>, new: &Map<String, Value>) -> Result<ContentDiff, Error> {
+    let (old, new) = (text(old)?, text(new)?);
+    Ok(ContentDiff::new(&TextDiff::from_lines(&old, &new)))
+}
+
+impl Item {
+    /// Returns the word-level changes between the text content of two versions of an item.
+    ///
+    /// The `content_text` is compared if it is present. Otherwise, the text of the
+    /// `content_html` is compared. Missing content is compared as empty text.
+    ///
+    /// # Errors
+    ///
+    /// If the content is not a JSON string, then `Error::UnexpectedType` is returned.
+    pub fn content_diff(old: &Item, new: &Item) -> Result<ContentDiff, Error> {
+        word_diff(old.as_map(), new.as_map())
+    }
+
+    /// Returns the line-level changes between the text content of two versions of an item.
+    ///
+    /// The content is compared as in [`Item::content_diff`].
+    ///
+    /// # Errors
+    ///
+    /// If the content is not a JSON string, then `Error::UnexpectedType` is returned.
+    pub fn content_line_diff(old: &Item, new: &Item) -> Result<ContentDiff, Error> {
+        line_diff(old.as_map(), new.as_map())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(text: &str) -> Item {
+        let mut item = Item::new();
+        item.set_content_text(text);
+        item
+    }
+
+    #[test]
+    fn diffs() -> Result<(), Error> {
+        let old = item("one two three four five six seven eight nine ten\nsame line\n");
+        let new = item("one two three four five six seven eight nine TEN\nsame line\nadded\n");
+
+        let diff = Item::content_diff(&old, &new)?;
+        assert_eq!(diff.deletions(), 1);
+        assert_eq!(
+            diff.render_text(),
+            "… six seven eight nine [-ten-]{+TEN+}\nsame line\n{+added+}\n"
+        );
+
+        let diff = Item::content_line_diff(&old, &new)?;
+        assert_eq!(diff.insertions(), 2);
+        assert_eq!(
+            diff.changes
+                .iter()
+                .map(|change| change.tag)
+                .collect::<Vec<_>>(),
+            vec![
+                ChangeTag::Delete,
+                ChangeTag::Insert,
+                ChangeTag::Equal,
+                ChangeTag::Insert
+            ]
+        );
+
+        assert!(Item::content_diff(&old, &old)?.is_empty());
+        assert!(!Item::content_diff(&Item::new(), &old)?.is_empty());
+        Ok(())
+    }
+}
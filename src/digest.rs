@@ -0,0 +1,205 @@
+//! Digests of feeds for verifying their integrity.
+//!
+//! Digests are computed over the canonical serialization of a feed: compact JSON with object
+//! keys sorted by their UTF-8 bytes. Mirrors can compare the digest of the data they received
+//! with a published digest.
+//!
+//! ```
+//! use json_feed_model::digest::{verify_digest, DigestAlgorithm};
+//!
+//! let feed = json_feed_model::from_str(r#"{ "title": "My Blog", "items": [] }"#)?;
+//! let digest = feed.digest(DigestAlgorithm::Sha256)?;
+//! assert!(verify_digest(br#"{"items":[],"title":"My Blog"}"#, &digest));
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::string::String;
+#[cfg(feature = "std")]
+use std::string::String;
+
+use core::fmt::Write;
+
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256, Sha512};
+
+use crate::{Error, Feed, FeedMut, FeedRef};
+
+/// A hash algorithm used for digests.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum DigestAlgorithm {
+    /// SHA-256
+    Sha256,
+    /// SHA-512
+    Sha512,
+}
+
+impl DigestAlgorithm {
+    /// Returns the conventional file extension for digest files using the algorithm.
+    #[must_use]
+    pub fn extension(&self) -> &'static str {
+        match self {
+            DigestAlgorithm::Sha256 => "sha256",
+            DigestAlgorithm::Sha512 => "sha512",
+        }
+    }
+
+    /// Returns the number of hexadecimal digits in a digest.
+    fn hex_len(self) -> usize {
+        match self {
+            DigestAlgorithm::Sha256 => 64,
+            DigestAlgorithm::Sha512 => 128,
+        }
+    }
+
+    /// Returns the lowercase hexadecimal digest of the bytes.
+    #[must_use]
+    pub fn hex_digest(&self, bytes: &[u8]) -> String {
+        fn to_hex(hash: &[u8]) -> String {
+            let mut hex = String::with_capacity(hash.len() * 2);
+            for b in hash {
+                let _ = write!(hex, "{:02x}", b);
+            }
+            hex
+        }
+
+        match self {
+            DigestAlgorithm::Sha256 => to_hex(&Sha256::digest(bytes)),
+            DigestAlgorithm::Sha512 => to_hex(&Sha512::digest(bytes)),
+        }
+    }
+}
+
+/// Returns true if the digest of the bytes matches the expected digest.
+///
+/// The algorithm is determined by the length of the expected hexadecimal digest. Only the first
+/// word of `expected` is used, so the contents of a `sha256sum` style file can be passed
+/// directly. Hexadecimal digits are compared case-insensitively.
+#[must_use]
+pub fn verify_digest(bytes: &[u8], expected: &str) -> bool {
+    let expected = match expected.split_whitespace().next() {
+        Some(expected) => expected,
+        None => return false,
+    };
+    [DigestAlgorithm::Sha256, DigestAlgorithm::Sha512]
+        .iter()
+        .find(|algorithm| algorithm.hex_len() == expected.len())
+        .map_or(false, |algorithm| {
+            algorithm.hex_digest(bytes).eq_ignore_ascii_case(expected)
+        })
+}
+
+fn digest(map: &Map<String, Value>, algorithm: DigestAlgorithm) -> Result<String, Error> {
+    Ok(algorithm.hex_digest(&crate::canonical::to_canonical_vec(map)?))
+}
+
+#[cfg(feature = "std")]
+fn write_with_digest(map: &Map<String, Value>, path: &std::path::Path) -> Result<(), Error> {
+    let algorithm = DigestAlgorithm::Sha256;
+    let bytes = crate::canonical::to_canonical_vec(map)?;
+
+    let mut sidecar = algorithm.hex_digest(&bytes);
+    if let Some(file_name) = path.file_name() {
+        sidecar.push_str("  ");
+        sidecar.push_str(&file_name.to_string_lossy());
+    }
+    sidecar.push('\n');
+
+    let mut sidecar_path = path.as_os_str().to_owned();
+    sidecar_path.push(".");
+    sidecar_path.push(algorithm.extension());
+
+    crate::write::write_bytes_atomic(path, &bytes)?;
+    crate::write::write_bytes_atomic(std::path::Path::new(&sidecar_path), sidecar.as_bytes())?;
+    Ok(())
+}
+
+macro_rules! digest_methods {
+    () => {
+        /// Returns the lowercase hexadecimal digest of the feed's canonical serialization.
+        ///
+        /// # Errors
+        ///
+        /// If the feed cannot be serialized, then `Error::SerdeJson(serde_json::Error)` is
+        /// returned.
+        pub fn digest(&self, algorithm: DigestAlgorithm) -> Result<String, Error> {
+            digest(self.as_map(), algorithm)
+        }
+
+        /// Writes the feed's canonical serialization to a file with a SHA-256 digest file next
+        /// to it.
+        ///
+        /// The digest file has the same path with a `.sha256` extension appended and the same
+        /// format as the output of `sha256sum`. Both files are written atomically like
+        /// `write_to_path_atomic()`. The feed file is written first.
+        ///
+        /// # Errors
+        ///
+        /// If a file cannot be written or renamed, then `Error::Io(std::io::Error)` is
+        /// returned.
+        ///
+        /// If the feed cannot be serialized, then `Error::SerdeJson(serde_json::Error)` is
+        /// returned.
+        #[cfg(feature = "std")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+        pub fn write_with_digest<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), Error> {
+            write_with_digest(self.as_map(), path.as_ref())
+        }
+    };
+}
+
+impl Feed {
+    digest_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    digest_methods!();
+}
+
+impl<'a> FeedRef<'a> {
+    digest_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digests() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({ "title": "a" }))?;
+        let digest = feed.digest(DigestAlgorithm::Sha256)?;
+        assert_eq!(
+            digest,
+            DigestAlgorithm::Sha256.hex_digest(br#"{"title":"a"}"#)
+        );
+        assert!(verify_digest(br#"{"title":"a"}"#, &digest.to_uppercase()));
+        assert!(!verify_digest(br#"{"title":"b"}"#, &digest));
+
+        let digest = feed.digest(DigestAlgorithm::Sha512)?;
+        assert_eq!(digest.len(), 128);
+        assert!(verify_digest(br#"{"title":"a"}"#, &digest));
+        assert!(!verify_digest(b"", ""));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn sidecar() -> Result<(), Error> {
+        let dir =
+            std::env::temp_dir().join(format!("json-feed-model-digest-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join("feed.json");
+
+        let feed = crate::from_value(serde_json::json!({ "title": "a" }))?;
+        feed.write_with_digest(&path)?;
+        let bytes = std::fs::read(&path)?;
+        let sidecar = std::fs::read_to_string(dir.join("feed.json.sha256"))?;
+        assert!(sidecar.ends_with("  feed.json\n"));
+        assert!(verify_digest(&bytes, &sidecar));
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+//! Short human readable descriptions of feeds and items for logging.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::string::String;
+#[cfg(feature = "std")]
+use std::string::String;
+
+use core::fmt;
+
+use serde_json::{Map, Value};
+
+use crate::{summary::FeedSummary, Feed, FeedMut, FeedRef, Item, ItemMut, ItemRef};
+
+fn fmt_feed(map: &Map<String, Value>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let summary = FeedSummary::from(&FeedRef::from(map));
+    match summary.title {
+        Some(title) => write!(f, "Feed '{}'", title)?,
+        None => f.write_str("Feed (untitled)")?,
+    }
+    let noun = if summary.item_count == 1 {
+        "item"
+    } else {
+        "items"
+    };
+    write!(f, " \u{2013} {} {}", summary.item_count, noun)?;
+    if let Some(updated) = summary.latest_item_date {
+        let (year, month, day) = updated.to_utc().date();
+        write!(f, ", updated {:04}-{:02}-{:02}", year, month, day)?;
+    }
+    Ok(())
+}
+
+fn fmt_item(map: &Map<String, Value>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let item = ItemRef::from(map);
+    match item.id() {
+        Ok(Some(id)) => write!(f, "Item <{}>", id)?,
+        Ok(None) | Err(_) => f.write_str("Item (no id)")?,
+    }
+    if let Ok(Some(title)) = item.title() {
+        write!(f, " '{}'", title)?;
+    }
+    Ok(())
+}
+
+macro_rules! impl_display {
+    ($fmt:ident, $($ty:ty),+) => {
+        $(
+            /// Writes a short summary for logging.
+            ///
+            /// Properties which are missing or are not the expected JSON type are omitted. Dates
+            /// are written in UTC.
+            impl fmt::Display for $ty {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    $fmt(self.as_map(), f)
+                }
+            }
+        )+
+    };
+}
+
+impl_display!(fmt_feed, Feed, FeedMut<'_>, FeedRef<'_>);
+impl_display!(fmt_item, Item, ItemMut<'_>, ItemRef<'_>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::string::ToString;
+
+    #[test]
+    fn summaries() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "title": "Blog",
+            "items": [
+                { "id": "1", "title": "First", "date_published": "2024-05-01T10:00:00Z" },
+                { "id": "2", "date_modified": "2024-06-01T23:30:00-02:00" }
+            ]
+        }))?;
+        assert_eq!(
+            feed.to_string(),
+            "Feed 'Blog' \u{2013} 2 items, updated 2024-06-02"
+        );
+
+        let items = feed.items()?.unwrap_or_default();
+        assert_eq!(items[0].to_string(), "Item <1> 'First'");
+        assert_eq!(items[1].to_string(), "Item <2>");
+
+        assert_eq!(Feed::new().to_string(), "Feed (untitled) \u{2013} 0 items");
+        assert_eq!(Item::new().to_string(), "Item (no id)");
+
+        Ok(())
+    }
+}
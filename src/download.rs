@@ -0,0 +1,190 @@
+//! Helpers for downloading attachments.
+//!
+//! The helpers do not perform any I/O. They plan [HTTP range requests][rfc_7233] so large
+//! attachments such as podcast audio can be downloaded in chunks and resumed, and they verify
+//! the downloaded size against the attachment's metadata.
+//!
+//! ```
+//! use json_feed_model::{download::ByteRange, Attachment};
+//!
+//! let mut attachment = Attachment::new();
+//! attachment.set_size_in_bytes::<()>(2500);
+//!
+//! let plan = attachment.range_request_plan(1000)?.unwrap();
+//! assert_eq!(plan, vec![ByteRange::new(0, 999), ByteRange::new(1000, 1999), ByteRange::new(2000, 2499)]);
+//! assert_eq!(plan[2].to_string(), "bytes=2000-2499");
+//!
+//! assert!(attachment.verify_size(2500).is_ok());
+//! assert!(attachment.verify_size(2000).is_err());
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+//!
+//! [rfc_7233]: https://tools.ietf.org/html/rfc7233
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{ext::__private::u64_prop, Attachment, AttachmentMut, AttachmentRef, Error};
+
+/// An inclusive range of byte offsets.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ByteRange {
+    /// The offset of the first byte.
+    pub start: u64,
+    /// The offset of the last byte.
+    pub end: u64,
+}
+
+impl ByteRange {
+    /// Instantiates a range from the first to the last byte offset (inclusive).
+    #[must_use]
+    pub fn new(start: u64, end: u64) -> Self {
+        Self { start, end }
+    }
+
+    /// Returns the number of bytes in the range.
+    #[must_use]
+    pub fn len(&self) -> u64 {
+        if self.is_empty() {
+            0
+        } else {
+            (self.end - self.start).saturating_add(1)
+        }
+    }
+
+    /// Returns true if the range does not contain any bytes (the end is before the start).
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.end < self.start
+    }
+}
+
+impl core::fmt::Display for ByteRange {
+    /// Formats the range as the value of an HTTP `Range` header.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "bytes={}-{}", self.start, self.end)
+    }
+}
+
+fn range_request_plan(
+    map: &Map<String, Value>,
+    chunk_size: u64,
+) -> Result<Option<Vec<ByteRange>>, Error> {
+    let size = match u64_prop::get(map, None, "size_in_bytes")? {
+        Some(size) => size,
+        None => return Ok(None),
+    };
+    let chunk_size = if chunk_size == 0 { size } else { chunk_size };
+
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    while start < size {
+        let end = start.saturating_add(chunk_size - 1).min(size - 1);
+        ranges.push(ByteRange::new(start, end));
+        start = end + 1;
+    }
+    Ok(Some(ranges))
+}
+
+fn verify_size(map: &Map<String, Value>, actual: u64) -> Result<(), Error> {
+    match u64_prop::get(map, None, "size_in_bytes")? {
+        Some(expected) if expected != actual => Err(Error::SizeMismatch { expected, actual }),
+        Some(_) | None => Ok(()),
+    }
+}
+
+macro_rules! download_methods {
+    () => {
+        /// Returns the byte ranges to request to download the attachment in chunks.
+        ///
+        /// Each range has `chunk_size` bytes except the last range, which may be shorter. A
+        /// `chunk_size` of 0 returns a single range for the whole attachment. If the attachment
+        /// does not have a `size_in_bytes`, then `None` is returned. If the size is 0, then no
+        /// ranges are returned.
+        ///
+        /// # Errors
+        ///
+        /// If `size_in_bytes` is not a JSON unsigned integer, then `Error::UnexpectedType` is
+        /// returned.
+        pub fn range_request_plan(&self, chunk_size: u64) -> Result<Option<Vec<ByteRange>>, Error> {
+            range_request_plan(self.as_map(), chunk_size)
+        }
+
+        /// Verifies the size of the downloaded data matches the `size_in_bytes`.
+        ///
+        /// If the attachment does not have a `size_in_bytes`, then any size is accepted.
+        ///
+        /// # Errors
+        ///
+        /// If the sizes do not match, then `Error::SizeMismatch` is returned. If
+        /// `size_in_bytes` is not a JSON unsigned integer, then `Error::UnexpectedType` is
+        /// returned.
+        pub fn verify_size(&self, actual: u64) -> Result<(), Error> {
+            verify_size(self.as_map(), actual)
+        }
+    };
+}
+
+impl Attachment {
+    download_methods!();
+}
+
+impl<'a> AttachmentMut<'a> {
+    download_methods!();
+}
+
+impl<'a> AttachmentRef<'a> {
+    download_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::vec;
+
+    #[test]
+    fn plans() -> Result<(), Error> {
+        let mut attachment = Attachment::new();
+        assert_eq!(attachment.range_request_plan(10)?, None);
+
+        attachment.set_size_in_bytes::<()>(20);
+        assert_eq!(
+            attachment.range_request_plan(10)?,
+            Some(vec![ByteRange::new(0, 9), ByteRange::new(10, 19)])
+        );
+        assert_eq!(
+            attachment.range_request_plan(0)?,
+            Some(vec![ByteRange::new(0, 19)])
+        );
+        assert_eq!(
+            attachment.range_request_plan(u64::MAX)?,
+            Some(vec![ByteRange::new(0, 19)])
+        );
+
+        attachment.set_size_in_bytes::<()>(0);
+        assert_eq!(attachment.range_request_plan(10)?, Some(vec![]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn sizes() {
+        let mut attachment = Attachment::new();
+        assert!(attachment.verify_size(5).is_ok());
+
+        attachment.set_size_in_bytes::<()>(4);
+        assert!(attachment.verify_size(4).is_ok());
+        assert!(matches!(
+            attachment.verify_size(5),
+            Err(Error::SizeMismatch {
+                expected: 4,
+                actual: 5
+            })
+        ));
+    }
+}
@@ -0,0 +1,188 @@
+//! Entity tags for serving feeds over HTTP.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{format, string::String};
+#[cfg(feature = "std")]
+use std::{format, string::String};
+
+use serde_json::{Map, Value};
+
+use crate::{Error, Feed, FeedMut, FeedRef};
+
+/// Item properties which are ignored by weak entity tags.
+///
+/// Some generators update the modification date of every item when a feed is regenerated even
+/// if the content did not change.
+const VOLATILE_ITEM_KEYS: &[&str] = &["date_modified"];
+
+fn etag(map: &Map<String, Value>) -> Result<String, Error> {
+    let hash = crate::canonical::fnv1a(&crate::canonical::to_canonical_vec(map)?);
+    Ok(format!("\"{:016x}\"", hash))
+}
+
+fn weak_etag(map: &Map<String, Value>) -> Result<String, Error> {
+    let mut stable = map.clone();
+    if let Some(Value::Array(items)) = stable.get_mut("items") {
+        for item in items {
+            if let Value::Object(item) = item {
+                for key in VOLATILE_ITEM_KEYS {
+                    item.remove(*key);
+                }
+            }
+        }
+    }
+    let hash = crate::canonical::fnv1a(&crate::canonical::to_canonical_vec(&stable)?);
+    Ok(format!("W/\"{:016x}\"", hash))
+}
+
+macro_rules! etag_methods {
+    () => {
+        /// Returns a strong entity tag for the feed, including the surrounding quotes.
+        ///
+        /// The tag is derived from a hash of the feed's canonical serialization (compact JSON
+        /// with sorted object keys), so it changes whenever any property changes and does not
+        /// depend on key order. The hash is not cryptographic.
+        ///
+        /// # Errors
+        ///
+        /// If the feed cannot be serialized, then `Error::SerdeJson(serde_json::Error)` is
+        /// returned.
+        pub fn etag(&self) -> Result<String, Error> {
+            etag(self.as_map())
+        }
+
+        /// Returns a weak entity tag for the feed, including the `W/` prefix and quotes.
+        ///
+        /// Like `etag()`, but the items' `date_modified` is ignored. The tag changes when the
+        /// content of the feed changes, including extension properties, but not when only the
+        /// modification dates do.
+        ///
+        /// # Errors
+        ///
+        /// If the feed cannot be serialized, then `Error::SerdeJson(serde_json::Error)` is
+        /// returned.
+        pub fn weak_etag(&self) -> Result<String, Error> {
+            weak_etag(self.as_map())
+        }
+    };
+}
+
+impl Feed {
+    etag_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    etag_methods!();
+}
+
+impl<'a> FeedRef<'a> {
+    etag_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::vec;
+
+    #[test]
+    fn etags() -> Result<(), Error> {
+        let a = crate::from_value(serde_json::json!({
+            "title": "Blog",
+            "_generator": { "version": 1 },
+            "items": [{ "id": "1", "date_modified": "2021-01-01T00:00:00Z" }]
+        }))?;
+        let b = crate::from_value(serde_json::json!({
+            "title": "Blog",
+            "_generator": { "version": 1 },
+            "items": [{ "id": "1", "date_modified": "2021-01-02T00:00:00Z" }]
+        }))?;
+
+        let etag = a.etag()?;
+        assert_eq!(etag.len(), 18);
+        assert!(etag.starts_with('"') && etag.ends_with('"'));
+        assert_eq!(etag, a.clone().etag()?);
+        assert_ne!(etag, b.etag()?);
+
+        assert!(a.weak_etag()?.starts_with("W/\""));
+        assert_eq!(a.weak_etag()?, b.weak_etag()?);
+
+        let mut c = b.clone();
+        c.set_title("Other");
+        assert_ne!(b.weak_etag()?, c.weak_etag()?);
+
+        let mut d = b.clone();
+        d.as_map_mut().insert(
+            String::from("_generator"),
+            serde_json::json!({ "version": 2 }),
+        );
+        assert_ne!(b.weak_etag()?, d.weak_etag()?);
+
+        let mut e = b.clone();
+        e.as_map_mut()["items"][0]
+            .as_object_mut()
+            .unwrap()
+            .insert(String::from("_rating"), Value::from(5));
+        assert_ne!(b.weak_etag()?, e.weak_etag()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn key_order() -> Result<(), Error> {
+        let mut item_a = Map::new();
+        item_a.insert(String::from("id"), Value::from("1"));
+        item_a.insert(String::from("content_text"), Value::from("Hi"));
+        let mut a = Map::new();
+        a.insert(String::from("title"), Value::from("Blog"));
+        a.insert(
+            String::from("items"),
+            Value::Array(vec![Value::Object(item_a)]),
+        );
+
+        let mut item_b = Map::new();
+        item_b.insert(String::from("content_text"), Value::from("Hi"));
+        item_b.insert(String::from("id"), Value::from("1"));
+        let mut b = Map::new();
+        b.insert(
+            String::from("items"),
+            Value::Array(vec![Value::Object(item_b)]),
+        );
+        b.insert(String::from("title"), Value::from("Blog"));
+
+        let (a, b) = (Feed::from(a), Feed::from(b));
+        assert_eq!(a.etag()?, b.etag()?);
+        assert_eq!(a.weak_etag()?, b.weak_etag()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn content_changes() -> Result<(), Error> {
+        let a = crate::from_value(serde_json::json!({
+            "items": [{ "id": "1", "content_text": "Hi" }]
+        }))?;
+        let b = crate::from_value(serde_json::json!({
+            "items": [{ "id": "1", "content_text": "Hello" }]
+        }))?;
+
+        assert_ne!(a.etag()?, b.etag()?);
+        assert_ne!(a.weak_etag()?, b.weak_etag()?);
+        Ok(())
+    }
+
+    #[test]
+    fn formats() -> Result<(), Error> {
+        let feed = Feed::new();
+        let strong = feed.etag()?;
+        let weak = feed.weak_etag()?;
+
+        assert_eq!(weak, format!("W/{}", strong));
+        let hash = strong.trim_matches('"');
+        assert_eq!(hash.len(), 16);
+        assert!(hash
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b)));
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+//! The example feeds published with the JSON Feed spec.
+//!
+//! Doctests, demos, and downstream tests can start from the canonical data instead of copying
+//! JSON into each test. Every call returns a new `Feed` which can be modified freely.
+//!
+//! ```
+//! use json_feed_model::{examples, Version};
+//!
+//! let feed = examples::podcast();
+//! assert!(feed.is_valid(&Version::Version1_1));
+//! assert_eq!(feed.title()?, Some("The Record"));
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+use serde_json::{json, Value};
+
+use crate::Feed;
+
+fn feed(value: Value) -> Feed {
+    match value {
+        Value::Object(map) => Feed::from(map),
+        _ => Feed::new(),
+    }
+}
+
+/// Returns the simple example feed from the [version 1.1 spec][spec].
+///
+/// The feed has a title, URLs, and two items with text and HTML content.
+///
+/// [spec]: https://www.jsonfeed.org/version/1.1/
+#[must_use]
+pub fn spec_example_1_1() -> Feed {
+    feed(json!({
+        "version": "https://jsonfeed.org/version/1.1",
+        "title": "My Example Feed",
+        "home_page_url": "https://example.org/",
+        "feed_url": "https://example.org/feed.json",
+        "items": [
+            {
+                "id": "2",
+                "content_text": "This is a second item.",
+                "url": "https://example.org/second-item"
+            },
+            {
+                "id": "1",
+                "content_html": "<p>Hello, world!</p>",
+                "url": "https://example.org/initial-post"
+            }
+        ]
+    }))
+}
+
+/// Returns the podcast example feed from the [version 1.1 spec][spec].
+///
+/// The feed has a `user_comment` and an episode with text and HTML content, a summary, a
+/// publication date, and an audio attachment.
+///
+/// [spec]: https://www.jsonfeed.org/version/1.1/
+#[must_use]
+pub fn podcast() -> Feed {
+    feed(json!({
+        "version": "https://jsonfeed.org/version/1.1",
+        "user_comment": "This is a podcast feed. You can add this feed to your podcast client using the following URL: http://therecord.co/feed.json",
+        "title": "The Record",
+        "home_page_url": "http://therecord.co/",
+        "feed_url": "http://therecord.co/feed.json",
+        "items": [
+            {
+                "id": "http://therecord.co/chris-parrish",
+                "title": "Special #1 - Chris Parrish",
+                "url": "http://therecord.co/chris-parrish",
+                "content_text": "Chris has worked at Adobe and as a founder of Rogue Sheep, which won an Apple Design Award for Postage. Chris\u{2019}s new company is Aged & Distilled with Guy English \u{2014} which shipped Napkin, a Mac app for visual collaboration. Chris is also the co-host of The Record. He lives on Bainbridge Island, a quick ferry ride from Seattle.",
+                "content_html": "Chris has worked at <a href=\"http://adobe.com/\">Adobe</a> and as a founder of Rogue Sheep, which won an Apple Design Award for Postage. Chris\u{2019}s new company is Aged & Distilled with Guy English \u{2014} which shipped <a href=\"http://aged-and-distilled.com/napkin/\">Napkin</a>, a Mac app for visual collaboration. Chris is also the co-host of The Record. He lives on <a href=\"http://www.ci.bainbridge-isl.wa.us/\">Bainbridge Island</a>, a quick ferry ride from Seattle.",
+                "summary": "Brent interviews Chris Parrish, co-host of The Record and one-half of Aged & Distilled.",
+                "date_published": "2014-05-09T14:04:00-07:00",
+                "attachments": [
+                    {
+                        "url": "http://therecord.co/downloads/The-Record-sp1e1-ChrisParrish.m4a",
+                        "mime_type": "audio/x-m4a",
+                        "size_in_bytes": 89_970_236,
+                        "duration_in_seconds": 6629
+                    }
+                ]
+            }
+        ]
+    }))
+}
+
+/// Returns the microblog example feed from the [version 1.1 spec][spec].
+///
+/// The feed has an author with an avatar and an untitled text item with a publication date.
+///
+/// [spec]: https://www.jsonfeed.org/version/1.1/
+#[must_use]
+pub fn microblog() -> Feed {
+    feed(json!({
+        "version": "https://jsonfeed.org/version/1.1",
+        "user_comment": "This is a microblog feed. You can add this to your feed reader using the following URL: https://example.org/feed.json",
+        "title": "Brent Simmons\u{2019}s Microblog",
+        "home_page_url": "https://example.org/",
+        "feed_url": "https://example.org/feed.json",
+        "authors": [
+            {
+                "name": "Brent Simmons",
+                "url": "http://example.org/",
+                "avatar": "https://example.org/avatar.png"
+            }
+        ],
+        "items": [
+            {
+                "id": "2347259",
+                "url": "https://example.org/2347259",
+                "content_text": "Cats are neat. \n\nhttps://example.org/cats",
+                "date_published": "2016-02-09T14:22:00-07:00"
+            }
+        ]
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Version;
+
+    #[test]
+    fn examples_are_valid() -> Result<(), crate::Error> {
+        for feed in &[spec_example_1_1(), podcast(), microblog()] {
+            assert!(feed.is_valid(&Version::Version1_1));
+            assert!(!feed.items()?.unwrap_or_default().is_empty());
+        }
+        Ok(())
+    }
+}
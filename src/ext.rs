@@ -0,0 +1,515 @@
+//! Extensions to the JSON Feed model.
+//!
+//! The JSON Feed spec allows publishers to add custom properties to the feed, items, authors,
+//! attachments, and hubs as long as the keys start with an underscore (`_`). By convention,
+//! an extension is a JSON object stored under a single key (e.g. `"_geo": { "lat": 1.0 }`).
+//!
+//! The [`define_extension!`][crate::define_extension] macro generates traits with typed
+//! accessors for an extension so that the extension values can be read and written like the
+//! standard properties.
+
+pub mod geo;
+pub mod indexing;
+pub mod interactions;
+pub mod media;
+pub mod podcast;
+pub mod reading_time;
+pub mod roles;
+pub mod series;
+pub mod source;
+pub mod visibility;
+
+/// Declares traits with typed accessors for a custom extension.
+///
+/// Two traits are declared. The first trait contains the "getter" methods and is meant to be
+/// implemented for the owned, borrowed, and borrowed mutable types (e.g. `Item`, `ItemRef`, and
+/// `ItemMut`). The second trait contains the "setter" and "remove" methods and is meant to be
+/// implemented for the owned and borrowed mutable types.
+///
+/// If a `key` is given, the properties are stored in a JSON object under the key. Setting a
+/// property creates the object if it does not exist, and removing the last property removes
+/// the object. If the key's value is not a JSON object, then setters do not modify it and
+/// return `None`. Without a `key`, the properties are stored directly in the model's JSON
+/// object.
+///
+/// The properties are listed in a `props` block with the same form as the crate's standard
+/// properties: the property kind, the JSON key, and the names and documentation of the getter,
+/// setter, and remove methods.
+///
+/// If `valid` is given, a method with the given name is added to the first trait which verifies
+/// that every declared property has the expected JSON type. An optional `with` function of
+/// type `fn(&Map<String, Value>) -> bool` is called as an additional validation hook on the
+/// model's JSON object.
+///
+/// The supported property kinds are `str_prop`, `str_array_prop`, `bool_prop`, `u64_prop`,
+/// `i64_prop`, and `f64_prop`. JSON cannot represent non-finite numbers, so `f64_prop` setters
+/// do not modify the property and return `None` if the value is NaN or infinite.
+///
+/// # Example
+///
+/// ```
+/// use json_feed_model::{Item, ItemMut, ItemRef};
+///
+/// json_feed_model::define_extension! {
+///     /// Reads the `_example` extension.
+///     pub trait ExampleExt for Item, ItemRef<'_>, ItemMut<'_>;
+///     /// Writes the `_example` extension.
+///     pub trait ExampleExtMut for Item, ItemMut<'_>;
+///     key "_example";
+///     valid is_example_valid;
+///     props {
+///         [str_prop, "about", about, "The extension's documentation URL.", set_about, "Sets the URL.", remove_about, "Removes the URL."],
+///         [u64_prop, "count", count, "A count.", set_count, "Sets the count.", remove_count, "Removes the count."]
+///     }
+/// }
+///
+/// let mut item = Item::new();
+/// item.set_count(3);
+/// assert_eq!(item.count()?, Some(3));
+/// assert_eq!(item.about()?, None);
+/// assert!(item.is_example_valid());
+/// assert_eq!(
+///     item.as_map().get("_example"),
+///     Some(&serde_json::json!({ "count": 3 }))
+/// );
+///
+/// item.remove_count();
+/// assert_eq!(item.as_map().get("_example"), None);
+/// # Ok::<(), json_feed_model::Error>(())
+/// ```
+#[macro_export]
+macro_rules! define_extension {
+    (
+        $(#[$read_meta:meta])*
+        $read_vis:vis trait $read:ident for $($read_ty:ty),+;
+        $(#[$write_meta:meta])*
+        $write_vis:vis trait $write:ident for $($write_ty:ty),+;
+        $(key $ns:expr;)?
+        $(valid $valid:ident $(with $hook:path)?;)?
+        props $props:tt
+    ) => {
+        $crate::define_extension!(
+            @define
+            [$(#[$read_meta])*] [$read_vis] $read [$($read_ty),+]
+            [$(#[$write_meta])*] [$write_vis] $write [$($write_ty),+]
+            [$($ns)?] [$($valid $(with $hook)?)?] $props
+        );
+    };
+
+    (
+        @define
+        [$(#[$read_meta:meta])*] [$read_vis:vis] $read:ident [$($read_ty:ty),+]
+        [$(#[$write_meta:meta])*] [$write_vis:vis] $write:ident [$($write_ty:ty),+]
+        $ns:tt $valid:tt $props:tt
+    ) => {
+        $(#[$read_meta])*
+        $read_vis trait $read {
+            $crate::define_extension!(@read sig $ns $props);
+            $crate::define_extension!(@is_valid sig $valid $ns $props);
+        }
+
+        $(
+            impl $read for $read_ty {
+                $crate::define_extension!(@read impl $ns $props);
+                $crate::define_extension!(@is_valid impl $valid $ns $props);
+            }
+        )+
+
+        $(#[$write_meta])*
+        $write_vis trait $write {
+            $crate::define_extension!(@write sig $ns $props);
+        }
+
+        $(
+            impl $write for $write_ty {
+                $crate::define_extension!(@write impl $ns $props);
+            }
+        )+
+    };
+
+    (@is_valid $mode:ident [] $ns:tt $props:tt) => {};
+    (@is_valid sig [$valid:ident $(with $hook:path)?] $ns:tt $props:tt) => {
+        /// Verifies the extension's properties have the expected JSON types.
+        fn $valid(&self) -> bool;
+    };
+    (@is_valid impl [$valid:ident $(with $hook:path)?] $ns:tt $props:tt) => {
+        fn $valid(&self) -> bool {
+            let map = self.as_map();
+            $crate::define_extension!(@valid map $ns $props) $(&& $hook(map))?
+        }
+    };
+
+    (@ns []) => { None };
+    (@ns [$ns:expr]) => { Some($ns) };
+
+    (@read $mode:ident $ns:tt {}) => {};
+    (@read $mode:ident $ns:tt {
+        [$kind:ident, $key:expr, $getter:ident, $getter_doc:expr, $setter:ident, $setter_doc:expr, $remover:ident, $remover_doc:expr]
+        $(, $($rest:tt)*)?
+    }) => {
+        $crate::define_extension!(@getter $mode $kind $ns $key, $getter, $getter_doc);
+        $crate::define_extension!(@read $mode $ns { $($($rest)*)? });
+    };
+
+    (@write $mode:ident $ns:tt {}) => {};
+    (@write $mode:ident $ns:tt {
+        [$kind:ident, $key:expr, $getter:ident, $getter_doc:expr, $setter:ident, $setter_doc:expr, $remover:ident, $remover_doc:expr]
+        $(, $($rest:tt)*)?
+    }) => {
+        $crate::define_extension!(@setter $mode $kind $ns $key, $setter, $setter_doc);
+        $crate::define_extension!(@remover $mode $ns $key, $remover, $remover_doc);
+        $crate::define_extension!(@write $mode $ns { $($($rest)*)? });
+    };
+
+    (@valid $map:ident $ns:tt {}) => { true };
+    (@valid $map:ident $ns:tt {
+        [$kind:ident, $key:expr, $getter:ident, $getter_doc:expr, $setter:ident, $setter_doc:expr, $remover:ident, $remover_doc:expr]
+        $(, $($rest:tt)*)?
+    }) => {
+        $crate::ext::__private::$kind::get($map, $crate::define_extension!(@ns $ns), $key).is_ok()
+            && $crate::define_extension!(@valid $map $ns { $($($rest)*)? })
+    };
+
+    (@getter sig $kind:ident $ns:tt $key:expr, $getter:ident, $getter_doc:expr) => {
+        #[doc=$getter_doc]
+        fn $getter(&self) -> Result<Option<$crate::define_extension!(@get_ty $kind)>, $crate::Error>;
+    };
+    (@getter impl $kind:ident $ns:tt $key:expr, $getter:ident, $getter_doc:expr) => {
+        fn $getter(&self) -> Result<Option<$crate::define_extension!(@get_ty $kind)>, $crate::Error> {
+            $crate::ext::__private::$kind::get(self.as_map(), $crate::define_extension!(@ns $ns), $key)
+        }
+    };
+
+    (@setter sig $kind:ident $ns:tt $key:expr, $setter:ident, $setter_doc:expr) => {
+        #[doc=$setter_doc]
+        fn $setter(&mut self, value: $crate::define_extension!(@set_ty $kind)) -> Option<$crate::ext::__private::Value>;
+    };
+    (@setter impl $kind:ident $ns:tt $key:expr, $setter:ident, $setter_doc:expr) => {
+        fn $setter(&mut self, value: $crate::define_extension!(@set_ty $kind)) -> Option<$crate::ext::__private::Value> {
+            let value = $crate::ext::__private::$kind::to_value(value)?;
+            $crate::ext::__private::insert(
+                self.as_map_mut(),
+                $crate::define_extension!(@ns $ns),
+                $key,
+                value,
+            )
+        }
+    };
+
+    (@remover sig $ns:tt $key:expr, $remover:ident, $remover_doc:expr) => {
+        #[doc=$remover_doc]
+        fn $remover(&mut self) -> Option<$crate::ext::__private::Value>;
+    };
+    (@remover impl $ns:tt $key:expr, $remover:ident, $remover_doc:expr) => {
+        fn $remover(&mut self) -> Option<$crate::ext::__private::Value> {
+            $crate::ext::__private::remove(self.as_map_mut(), $crate::define_extension!(@ns $ns), $key)
+        }
+    };
+
+    (@get_ty str_prop) => { &str };
+    (@get_ty str_array_prop) => { $crate::ext::__private::Vec<&str> };
+    (@get_ty bool_prop) => { bool };
+    (@get_ty u64_prop) => { u64 };
+    (@get_ty i64_prop) => { i64 };
+    (@get_ty f64_prop) => { f64 };
+
+    (@set_ty str_prop) => { impl $crate::ext::__private::ToString };
+    (@set_ty str_array_prop) => { impl IntoIterator<Item = $crate::ext::__private::String> };
+    (@set_ty bool_prop) => { bool };
+    (@set_ty u64_prop) => { u64 };
+    (@set_ty i64_prop) => { i64 };
+    (@set_ty f64_prop) => { f64 };
+}
+
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    pub use alloc::{
+        string::{String, ToString},
+        vec::Vec,
+    };
+    #[cfg(feature = "std")]
+    pub use std::{
+        string::{String, ToString},
+        vec::Vec,
+    };
+
+    pub use serde_json::{Map, Value};
+
+    use crate::Error;
+
+    pub fn get<'a>(
+        map: &'a Map<String, Value>,
+        ns: Option<&str>,
+        key: &str,
+    ) -> Result<Option<&'a Value>, Error> {
+        match ns {
+            None => Ok(map.get(key)),
+            Some(ns) => match map.get(ns) {
+                None => Ok(None),
+                Some(Value::Object(obj)) => Ok(obj.get(key)),
+                Some(_) => Err(Error::UnexpectedType),
+            },
+        }
+    }
+
+    /// Inserts the value, or returns `None` without inserting if the namespace is not an object.
+    pub fn insert(
+        map: &mut Map<String, Value>,
+        ns: Option<&str>,
+        key: &str,
+        value: Value,
+    ) -> Option<Value> {
+        match ns {
+            None => map.insert(String::from(key), value),
+            Some(ns) => match map
+                .entry(String::from(ns))
+                .or_insert_with(|| Value::Object(Map::new()))
+            {
+                Value::Object(obj) => obj.insert(String::from(key), value),
+                _ => None,
+            },
+        }
+    }
+
+    pub fn remove(map: &mut Map<String, Value>, ns: Option<&str>, key: &str) -> Option<Value> {
+        match ns {
+            None => map.remove(key),
+            Some(ns) => {
+                let (removed, is_empty) = match map.get_mut(ns) {
+                    Some(Value::Object(obj)) => (obj.remove(key), obj.is_empty()),
+                    _ => return None,
+                };
+                if is_empty {
+                    map.remove(ns);
+                }
+                removed
+            }
+        }
+    }
+
+    pub mod str_prop {
+        use super::{Map, String, ToString, Value};
+        use crate::Error;
+
+        pub fn get<'a>(
+            map: &'a Map<String, Value>,
+            ns: Option<&str>,
+            key: &str,
+        ) -> Result<Option<&'a str>, Error> {
+            super::get(map, ns, key)?.map_or_else(
+                || Ok(None),
+                |value| match value {
+                    Value::String(s) => Ok(Some(s.as_str())),
+                    _ => Err(Error::UnexpectedType),
+                },
+            )
+        }
+
+        pub fn to_value(value: impl ToString) -> Option<Value> {
+            Some(Value::String(value.to_string()))
+        }
+    }
+
+    pub mod str_array_prop {
+        use super::{Map, String, Value, Vec};
+        use crate::Error;
+
+        pub fn get<'a>(
+            map: &'a Map<String, Value>,
+            ns: Option<&str>,
+            key: &str,
+        ) -> Result<Option<Vec<&'a str>>, Error> {
+            super::get(map, ns, key)?.map_or_else(
+                || Ok(None),
+                |value| match value {
+                    Value::Array(arr) => arr
+                        .iter()
+                        .map(|value| match value {
+                            Value::String(s) => Ok(s.as_str()),
+                            _ => Err(Error::UnexpectedType),
+                        })
+                        .collect::<Result<Vec<&str>, Error>>()
+                        .map(Some),
+                    _ => Err(Error::UnexpectedType),
+                },
+            )
+        }
+
+        pub fn to_value(values: impl IntoIterator<Item = String>) -> Option<Value> {
+            Some(Value::Array(
+                values.into_iter().map(Value::String).collect(),
+            ))
+        }
+    }
+
+    pub mod bool_prop {
+        use super::{Map, String, Value};
+        use crate::Error;
+
+        pub fn get(
+            map: &Map<String, Value>,
+            ns: Option<&str>,
+            key: &str,
+        ) -> Result<Option<bool>, Error> {
+            super::get(map, ns, key)?.map_or_else(
+                || Ok(None),
+                |value| match value {
+                    Value::Bool(b) => Ok(Some(*b)),
+                    _ => Err(Error::UnexpectedType),
+                },
+            )
+        }
+
+        pub fn to_value(value: bool) -> Option<Value> {
+            Some(Value::Bool(value))
+        }
+    }
+
+    pub mod u64_prop {
+        use super::{Map, String, Value};
+        use crate::Error;
+
+        pub fn get(
+            map: &Map<String, Value>,
+            ns: Option<&str>,
+            key: &str,
+        ) -> Result<Option<u64>, Error> {
+            super::get(map, ns, key)?.map_or_else(
+                || Ok(None),
+                |value| value.as_u64().map(Some).ok_or(Error::UnexpectedType),
+            )
+        }
+
+        pub fn to_value(value: u64) -> Option<Value> {
+            Some(Value::Number(serde_json::Number::from(value)))
+        }
+    }
+
+    pub mod i64_prop {
+        use super::{Map, String, Value};
+        use crate::Error;
+
+        pub fn get(
+            map: &Map<String, Value>,
+            ns: Option<&str>,
+            key: &str,
+        ) -> Result<Option<i64>, Error> {
+            super::get(map, ns, key)?.map_or_else(
+                || Ok(None),
+                |value| value.as_i64().map(Some).ok_or(Error::UnexpectedType),
+            )
+        }
+
+        pub fn to_value(value: i64) -> Option<Value> {
+            Some(Value::Number(serde_json::Number::from(value)))
+        }
+    }
+
+    pub mod f64_prop {
+        use super::{Map, String, Value};
+        use crate::Error;
+
+        pub fn get(
+            map: &Map<String, Value>,
+            ns: Option<&str>,
+            key: &str,
+        ) -> Result<Option<f64>, Error> {
+            super::get(map, ns, key)?.map_or_else(
+                || Ok(None),
+                |value| value.as_f64().map(Some).ok_or(Error::UnexpectedType),
+            )
+        }
+
+        /// Returns `None` for non-finite values which cannot be represented in JSON.
+        pub fn to_value(value: f64) -> Option<Value> {
+            serde_json::Number::from_f64(value).map(Value::Number)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, Feed, FeedMut, FeedRef};
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::{string::String, vec};
+    use serde_json::{Map, Value};
+
+    fn has_about(map: &Map<String, Value>) -> bool {
+        map.get("_about").is_some()
+    }
+
+    crate::define_extension! {
+        /// Reads the test extension.
+        trait TestExt for Feed, FeedRef<'_>, FeedMut<'_>;
+        /// Writes the test extension.
+        trait TestExtMut for Feed, FeedMut<'_>;
+        valid is_test_valid with has_about;
+        props {
+            [str_prop, "_about", about, "About.", set_about, "Sets about.", remove_about, "Removes about."],
+            [f64_prop, "_score", score, "Score.", set_score, "Sets score.", remove_score, "Removes score."],
+            [str_array_prop, "_labels", labels, "Labels.", set_labels, "Sets labels.", remove_labels, "Removes labels."]
+        }
+    }
+
+    #[test]
+    fn top_level_properties() -> Result<(), Error> {
+        let mut feed = Feed::new();
+        assert!(!feed.is_test_valid());
+
+        feed.set_about("https://example.org/about");
+        feed.set_score(0.5);
+        feed.set_labels(vec![String::from("a"), String::from("b")]);
+        assert!(feed.is_test_valid());
+
+        let feed_ref = FeedRef::from(feed.as_map());
+        assert_eq!(feed_ref.about()?, Some("https://example.org/about"));
+        assert_eq!(feed_ref.score()?, Some(0.5));
+        assert_eq!(feed_ref.labels()?, Some(vec!["a", "b"]));
+
+        feed.as_map_mut()
+            .insert(String::from("_score"), Value::Bool(true));
+        assert!(matches!(feed.score(), Err(Error::UnexpectedType)));
+        assert!(!feed.is_test_valid());
+
+        assert_eq!(feed.remove_score(), Some(Value::Bool(true)));
+        assert_eq!(feed.score()?, None);
+
+        feed.remove_about();
+        feed.remove_labels();
+        assert!(feed.as_map().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn namespaced_properties_with_non_object() {
+        let mut map = Map::new();
+        map.insert(String::from("_geo"), Value::Bool(true));
+        assert!(matches!(
+            super::__private::str_prop::get(&map, Some("_geo"), "name"),
+            Err(Error::UnexpectedType)
+        ));
+
+        assert_eq!(
+            super::__private::insert(&mut map, Some("_geo"), "name", Value::from("Home")),
+            None
+        );
+        assert_eq!(map.get("_geo"), Some(&Value::Bool(true)));
+    }
+
+    #[test]
+    fn non_finite_numbers() -> Result<(), Error> {
+        let mut feed = Feed::new();
+        assert_eq!(feed.set_score(f64::NAN), None);
+        assert_eq!(feed.score()?, None);
+        assert!(feed.as_map().is_empty());
+
+        feed.set_score(1.5);
+        assert_eq!(feed.set_score(f64::INFINITY), None);
+        assert_eq!(feed.set_score(f64::NEG_INFINITY), None);
+        assert_eq!(feed.score()?, Some(1.5));
+
+        Ok(())
+    }
+}
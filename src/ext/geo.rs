@@ -0,0 +1,229 @@
+//! Geographic location of items stored in the `_geo` extension.
+//!
+//! Check-in and photo feeds may carry the location where an item was created. The location is
+//! stored in a `_geo` JSON object on the item with `lat`, `lon`, and an optional `name`.
+//!
+//! ```
+//! use json_feed_model::{ext::geo::{BoundingBox, Geo, GeoMut}, Feed, Item};
+//!
+//! let mut item = Item::new();
+//! item.set_id("1");
+//! item.set_content_text("Lunch");
+//! item.set_lat(37.7749);
+//! item.set_lon(-122.4194);
+//! item.set_location_name("San Francisco");
+//! assert!(item.is_geo_valid());
+//!
+//! let mut feed = Feed::new();
+//! feed.set_items(vec![item]);
+//!
+//! let bbox = BoundingBox::new(37.0, -123.0, 38.0, -122.0);
+//! assert_eq!(feed.items_within_bbox(&bbox)?.len(), 1);
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{Error, Feed, FeedMut, FeedRef, Item, ItemMut, ItemRef};
+
+/// The key of the geo extension object.
+pub const KEY: &str = "_geo";
+
+fn are_coordinates_in_range(map: &Map<String, Value>) -> bool {
+    let lat = crate::ext::__private::f64_prop::get(map, Some(KEY), "lat");
+    let lon = crate::ext::__private::f64_prop::get(map, Some(KEY), "lon");
+    match (lat, lon) {
+        (Ok(None), Ok(None)) => true,
+        (Ok(Some(lat)), Ok(Some(lon))) => {
+            (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lon)
+        }
+        _ => false,
+    }
+}
+
+crate::define_extension! {
+    /// Reads the location of an item.
+    pub trait Geo for Item, ItemRef<'_>, ItemMut<'_>;
+    /// Writes the location of an item.
+    pub trait GeoMut for Item, ItemMut<'_>;
+    key KEY;
+    valid is_geo_valid with are_coordinates_in_range;
+    props {
+        [
+            f64_prop,
+            "lat",
+            lat,
+            "The optional latitude in degrees.
+
+Valid values are between -90 and 90 inclusive.",
+            set_lat,
+            "Sets the latitude.",
+            remove_lat,
+            "Removes the latitude."
+        ],
+        [
+            f64_prop,
+            "lon",
+            lon,
+            "The optional longitude in degrees.
+
+Valid values are between -180 and 180 inclusive.",
+            set_lon,
+            "Sets the longitude.",
+            remove_lon,
+            "Removes the longitude."
+        ],
+        [
+            str_prop,
+            "name",
+            location_name,
+            "An optional human readable name of the location.",
+            set_location_name,
+            "Sets the location name.",
+            remove_location_name,
+            "Removes the location name."
+        ]
+    }
+}
+
+/// A geographic area bounded by minimum and maximum coordinates.
+///
+/// If `min_lon` is greater than `max_lon`, the box crosses the antimeridian.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoundingBox {
+    /// The southern latitude.
+    pub min_lat: f64,
+    /// The western longitude.
+    pub min_lon: f64,
+    /// The northern latitude.
+    pub max_lat: f64,
+    /// The eastern longitude.
+    pub max_lon: f64,
+}
+
+impl BoundingBox {
+    /// Instantiates a bounding box from the south-west and north-east corners.
+    #[must_use]
+    pub fn new(min_lat: f64, min_lon: f64, max_lat: f64, max_lon: f64) -> Self {
+        Self {
+            min_lat,
+            min_lon,
+            max_lat,
+            max_lon,
+        }
+    }
+
+    /// Returns true if the coordinates are inside the bounding box.
+    #[must_use]
+    pub fn contains(&self, lat: f64, lon: f64) -> bool {
+        let lon_in_range = if self.min_lon <= self.max_lon {
+            self.min_lon <= lon && lon <= self.max_lon
+        } else {
+            self.min_lon <= lon || lon <= self.max_lon
+        };
+        self.min_lat <= lat && lat <= self.max_lat && lon_in_range
+    }
+}
+
+fn items_within_bbox<'a>(
+    map: &'a Map<String, Value>,
+    bbox: &BoundingBox,
+) -> Result<Vec<ItemRef<'a>>, Error> {
+    let mut matches = Vec::new();
+    for item in crate::item_refs(map)? {
+        if let (Some(lat), Some(lon)) = (item.lat()?, item.lon()?) {
+            if bbox.contains(lat, lon) {
+                matches.push(item);
+            }
+        }
+    }
+    Ok(matches)
+}
+
+impl Feed {
+    /// Returns the items with a location inside the bounding box.
+    ///
+    /// Items without a location are skipped.
+    ///
+    /// # Errors
+    ///
+    /// If the items or an item's location is not the expected JSON type, then
+    /// `Error::UnexpectedType` is returned.
+    pub fn items_within_bbox(&self, bbox: &BoundingBox) -> Result<Vec<ItemRef<'_>>, Error> {
+        items_within_bbox(&self.value, bbox)
+    }
+}
+
+impl<'a> FeedMut<'a> {
+    /// Returns the items with a location inside the bounding box.
+    ///
+    /// Items without a location are skipped.
+    ///
+    /// # Errors
+    ///
+    /// If the items or an item's location is not the expected JSON type, then
+    /// `Error::UnexpectedType` is returned.
+    pub fn items_within_bbox(&self, bbox: &BoundingBox) -> Result<Vec<ItemRef<'_>>, Error> {
+        items_within_bbox(self.value, bbox)
+    }
+}
+
+impl<'a> FeedRef<'a> {
+    /// Returns the items with a location inside the bounding box.
+    ///
+    /// Items without a location are skipped.
+    ///
+    /// # Errors
+    ///
+    /// If the items or an item's location is not the expected JSON type, then
+    /// `Error::UnexpectedType` is returned.
+    pub fn items_within_bbox(&self, bbox: &BoundingBox) -> Result<Vec<ItemRef<'_>>, Error> {
+        items_within_bbox(self.value, bbox)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coordinate_ranges() {
+        let mut item = Item::new();
+        assert!(item.is_geo_valid());
+
+        item.set_lat(91.0);
+        item.set_lon(0.0);
+        assert!(!item.is_geo_valid());
+
+        item.set_lat(-45.0);
+        assert!(item.is_geo_valid());
+
+        item.remove_lon();
+        assert!(!item.is_geo_valid());
+    }
+
+    #[test]
+    fn bbox_across_antimeridian() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "items": [
+                { "id": "1", "_geo": { "lat": 0.0, "lon": 179.5 } },
+                { "id": "2", "_geo": { "lat": 0.0, "lon": -179.5 } },
+                { "id": "3", "_geo": { "lat": 0.0, "lon": 0.0 } },
+                { "id": "4" }
+            ]
+        }))?;
+
+        let bbox = BoundingBox::new(-1.0, 179.0, 1.0, -179.0);
+        let items = feed.items_within_bbox(&bbox)?;
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].id()?, Some("1"));
+        assert_eq!(items[1].id()?, Some("2"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,220 @@
+//! Search engine indexing flags stored in the `_indexing` extension.
+//!
+//! Items which should not be indexed or whose links should not be followed have an `_indexing`
+//! JSON object with `noindex` and `nofollow` booleans, like the `robots` meta tag.
+//! [`Feed::indexable_items`], [`Feed::to_sitemap`], and [`Item::to_meta_tags`] honor the flags.
+//!
+//! ```
+//! use json_feed_model::{ext::indexing::IndexingMut, Feed, Item};
+//!
+//! let mut hidden = Item::new();
+//! hidden.set_id("1");
+//! hidden.set_url("https://example.org/1");
+//! hidden.set_noindex(true);
+//!
+//! let mut post = Item::new();
+//! post.set_id("2");
+//! post.set_url("https://example.org/2");
+//! post.set_date_modified("2021-06-01T12:00:00Z");
+//!
+//! let mut feed = Feed::new();
+//! feed.set_items(vec![hidden, post]);
+//!
+//! assert_eq!(feed.indexable_items()?.len(), 1);
+//! assert_eq!(
+//!     feed.to_sitemap()?,
+//!     "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+//!      <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n\
+//!      <url><loc>https://example.org/2</loc><lastmod>2021-06-01T12:00:00Z</lastmod></url>\n\
+//!      </urlset>\n"
+//! );
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{
+    ext::visibility::ItemVisibility, Error, Feed, FeedMut, FeedRef, Item, ItemMut, ItemRef,
+};
+
+/// The key of the indexing extension object.
+pub const KEY: &str = "_indexing";
+
+crate::define_extension! {
+    /// Reads the indexing flags of an item.
+    pub trait Indexing for Item, ItemRef<'_>, ItemMut<'_>;
+    /// Writes the indexing flags of an item.
+    pub trait IndexingMut for Item, ItemMut<'_>;
+    key KEY;
+    valid is_indexing_valid;
+    props {
+        [
+            bool_prop,
+            "noindex",
+            noindex,
+            "If true, search engines should not index the item's page.",
+            set_noindex,
+            "Sets whether the item's page should not be indexed.",
+            remove_noindex,
+            "Removes the `noindex` flag."
+        ],
+        [
+            bool_prop,
+            "nofollow",
+            nofollow,
+            "If true, search engines should not follow the links on the item's page.",
+            set_nofollow,
+            "Sets whether the links on the item's page should not be followed.",
+            remove_nofollow,
+            "Removes the `nofollow` flag."
+        ]
+    }
+}
+
+/// Returns the `content` of a `robots` meta tag for the item's flags.
+///
+/// If the item can be indexed and its links followed, then `None` is returned.
+pub(crate) fn robots(item: &ItemRef<'_>) -> Result<Option<&'static str>, Error> {
+    Ok(
+        match (
+            item.noindex()?.unwrap_or(false),
+            item.nofollow()?.unwrap_or(false),
+        ) {
+            (false, false) => None,
+            (true, false) => Some("noindex"),
+            (false, true) => Some("nofollow"),
+            (true, true) => Some("noindex, nofollow"),
+        },
+    )
+}
+
+fn indexable_items(map: &Map<String, Value>) -> Result<Vec<ItemRef<'_>>, Error> {
+    let mut items = Vec::new();
+    for item in crate::item_refs(map)? {
+        if item.is_public() && !item.noindex()?.unwrap_or(false) {
+            items.push(item);
+        }
+    }
+    Ok(items)
+}
+
+fn to_sitemap(map: &Map<String, Value>) -> Result<String, Error> {
+    let mut sitemap = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for item in indexable_items(map)? {
+        let url = match item.url()? {
+            Some(url) => url,
+            None => continue,
+        };
+        sitemap.push_str("<url><loc>");
+        crate::xml::escape_into(url, &mut sitemap);
+        sitemap.push_str("</loc>");
+        if let Some(date) = item.date_modified()?.or(item.date_published()?) {
+            sitemap.push_str("<lastmod>");
+            crate::xml::escape_into(date, &mut sitemap);
+            sitemap.push_str("</lastmod>");
+        }
+        sitemap.push_str("</url>\n");
+    }
+    sitemap.push_str("</urlset>\n");
+    Ok(sitemap)
+}
+
+macro_rules! indexing_methods {
+    () => {
+        /// Returns the items which search engines may index.
+        ///
+        /// Items with the `noindex` flag are excluded, as are items which are not public
+        /// according to the `_visibility` extension.
+        ///
+        /// # Errors
+        ///
+        /// If the items or an item's indexing flags are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned.
+        pub fn indexable_items(&self) -> Result<Vec<ItemRef<'_>>, Error> {
+            indexable_items(self.as_map())
+        }
+
+        /// Returns a [sitemap][sitemap] listing the `url` of each indexable item.
+        ///
+        /// The `date_modified` or, if it is missing, the `date_published` is the `lastmod`
+        /// date. Items without a `url` are skipped.
+        ///
+        /// # Errors
+        ///
+        /// If the items or an item's properties are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned.
+        ///
+        /// [sitemap]: https://www.sitemaps.org/protocol.html
+        pub fn to_sitemap(&self) -> Result<String, Error> {
+            to_sitemap(self.as_map())
+        }
+    };
+}
+
+impl Feed {
+    indexing_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    indexing_methods!();
+}
+
+impl<'a> FeedRef<'a> {
+    indexing_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::{vec, vec::Vec};
+
+    #[test]
+    fn indexable() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "items": [
+                { "id": "1", "url": "https://example.org/?a=1&b=2", "_indexing": { "nofollow": true } },
+                { "id": "2", "url": "https://example.org/2", "_visibility": "unlisted" },
+                { "id": "3", "url": "https://example.org/3", "_indexing": { "noindex": false } },
+                { "id": "4", "date_published": "2021-06-01T12:00:00Z" }
+            ]
+        }))?;
+
+        let items = feed.indexable_items()?;
+        let ids = items
+            .iter()
+            .map(|item| item.id())
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(ids, vec![Some("1"), Some("3"), Some("4")]);
+        assert!(feed
+            .to_sitemap()?
+            .contains("<url><loc>https://example.org/?a=1&amp;b=2</loc></url>\n"));
+
+        assert_eq!(robots(&items[0])?, Some("nofollow"));
+        assert_eq!(robots(&items[2])?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn sitemap_removes_invalid_characters() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "items": [{ "id": "1", "url": "https://example.org/\u{1}a<b" }]
+        }))?;
+        assert_eq!(
+            feed.to_sitemap()?,
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n\
+             <url><loc>https://example.org/a&lt;b</loc></url>\n\
+             </urlset>\n"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,174 @@
+//! Comments and interaction counts stored in the `_interactions` extension.
+//!
+//! Feeds bridged from social platforms often carry a link to the item's comments and counts of
+//! comments, likes, and boosts. The values are stored in an `_interactions` JSON object on the
+//! item.
+//!
+//! ```
+//! use json_feed_model::{
+//!     ext::interactions::{IncrementInteractions, Interactions, InteractionsMut},
+//!     Item,
+//! };
+//!
+//! let mut item = Item::new();
+//! item.set_comments_url("https://example.org/1#comments");
+//! item.increment_like_count(1)?;
+//! item.increment_like_count(2)?;
+//!
+//! assert_eq!(item.like_count()?, Some(3));
+//! assert_eq!(item.boost_count()?, None);
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{format, vec::Vec};
+#[cfg(feature = "std")]
+use std::{format, vec::Vec};
+
+use crate::{
+    lint::{Lint, LintCode},
+    Error, FeedRef, Item, ItemMut, ItemRef,
+};
+
+/// The key of the interactions extension object.
+pub const KEY: &str = "_interactions";
+
+crate::define_extension! {
+    /// Reads the comments and interaction counts of an item.
+    pub trait Interactions for Item, ItemRef<'_>, ItemMut<'_>;
+    /// Writes the comments and interaction counts of an item.
+    pub trait InteractionsMut for Item, ItemMut<'_>;
+    key KEY;
+    valid is_interactions_valid;
+    props {
+        [
+            str_prop,
+            "comments_url",
+            comments_url,
+            "An optional absolute URL of the item's comments.",
+            set_comments_url,
+            "Sets the comments URL.",
+            remove_comments_url,
+            "Removes the comments URL."
+        ],
+        [
+            u64_prop,
+            "comments",
+            comment_count,
+            "The optional number of comments.",
+            set_comment_count,
+            "Sets the number of comments.",
+            remove_comment_count,
+            "Removes the number of comments."
+        ],
+        [
+            u64_prop,
+            "likes",
+            like_count,
+            "The optional number of likes.",
+            set_like_count,
+            "Sets the number of likes.",
+            remove_like_count,
+            "Removes the number of likes."
+        ],
+        [
+            u64_prop,
+            "boosts",
+            boost_count,
+            "The optional number of boosts (reposts).",
+            set_boost_count,
+            "Sets the number of boosts.",
+            remove_boost_count,
+            "Removes the number of boosts."
+        ]
+    }
+}
+
+/// Incrementally updates the interaction counts of an item.
+///
+/// A missing count is treated as zero. Counts saturate at `u64::MAX`.
+pub trait IncrementInteractions: Interactions + InteractionsMut {
+    /// Adds to the number of comments and returns the new count.
+    ///
+    /// # Errors
+    ///
+    /// If the existing count is not a JSON unsigned integer, then `Error::UnexpectedType` is
+    /// returned.
+    fn increment_comment_count(&mut self, n: u64) -> Result<u64, Error> {
+        let count = self.comment_count()?.unwrap_or(0).saturating_add(n);
+        self.set_comment_count(count);
+        Ok(count)
+    }
+
+    /// Adds to the number of likes and returns the new count.
+    ///
+    /// # Errors
+    ///
+    /// If the existing count is not a JSON unsigned integer, then `Error::UnexpectedType` is
+    /// returned.
+    fn increment_like_count(&mut self, n: u64) -> Result<u64, Error> {
+        let count = self.like_count()?.unwrap_or(0).saturating_add(n);
+        self.set_like_count(count);
+        Ok(count)
+    }
+
+    /// Adds to the number of boosts and returns the new count.
+    ///
+    /// # Errors
+    ///
+    /// If the existing count is not a JSON unsigned integer, then `Error::UnexpectedType` is
+    /// returned.
+    fn increment_boost_count(&mut self, n: u64) -> Result<u64, Error> {
+        let count = self.boost_count()?.unwrap_or(0).saturating_add(n);
+        self.set_boost_count(count);
+        Ok(count)
+    }
+}
+
+impl IncrementInteractions for Item {}
+
+impl<'a> IncrementInteractions for ItemMut<'a> {}
+
+/// Lints the interactions of each item.
+///
+/// The comments URL should be an absolute URL.
+pub(crate) fn lint(feed: &FeedRef<'_>, lints: &mut Vec<Lint>) {
+    let items = match feed.items() {
+        Ok(Some(items)) => items,
+        Ok(None) | Err(_) => return,
+    };
+
+    for (item_index, item) in items.iter().enumerate() {
+        if let Ok(Some(comments_url)) = item.comments_url() {
+            if !crate::url::is_absolute(comments_url) {
+                lints.push(Lint::new(
+                    LintCode::RelativeCommentsUrl,
+                    format!("/items/{}/{}/comments_url", item_index, KEY),
+                    "comments URL is not an absolute URL",
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_comments_url_lint() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "items": [
+                { "id": "1", "_interactions": { "comments_url": "https://example.org/1" } },
+                { "id": "2", "_interactions": { "comments_url": "/2#comments" } }
+            ]
+        }))?;
+
+        let lints = feed.lints();
+        assert_eq!(lints.len(), 1);
+        assert_eq!(lints[0].code, LintCode::RelativeCommentsUrl);
+        assert_eq!(lints[0].path, "/items/1/_interactions/comments_url");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,414 @@
+//! Sized thumbnails of items stored in the `_media` extension.
+//!
+//! Clients which display a list of items usually want an image of a known size for each item.
+//! Similar to Media RSS, the thumbnails are stored in a `thumbnails` array in a `_media` JSON
+//! object on the item. Each thumbnail has a `url` and an optional `width` and `height` in
+//! pixels.
+//!
+//! ```
+//! use json_feed_model::{ext::media::{Media, MediaMut, Thumbnail}, Item};
+//!
+//! let mut item = Item::new();
+//! item.set_thumbnails(vec![
+//!     Thumbnail::with_size("https://example.org/small.jpg", 160, 90),
+//!     Thumbnail::with_size("https://example.org/large.jpg", 1280, 720),
+//! ]);
+//!
+//! let thumbnail = item.thumbnail_for_width(320)?.unwrap();
+//! assert_eq!(thumbnail.url, "https://example.org/large.jpg");
+//! assert_eq!(
+//!     item.as_map().get("_media"),
+//!     Some(&serde_json::json!({
+//!         "thumbnails": [
+//!             { "url": "https://example.org/small.jpg", "width": 160, "height": 90 },
+//!             { "url": "https://example.org/large.jpg", "width": 1280, "height": 720 }
+//!         ]
+//!     }))
+//! );
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{Error, Feed, FeedMut, Item, ItemMut, ItemRef};
+
+/// The key of the media extension object.
+pub const KEY: &str = "_media";
+
+const THUMBNAILS: &str = "thumbnails";
+
+/// An image which represents an item.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub struct Thumbnail {
+    /// The URL of the image.
+    pub url: String,
+    /// The optional width in pixels.
+    pub width: Option<u64>,
+    /// The optional height in pixels.
+    pub height: Option<u64>,
+}
+
+impl Thumbnail {
+    /// Instantiates a thumbnail with an unknown size.
+    pub fn new<S: Into<String>>(url: S) -> Self {
+        Self {
+            url: url.into(),
+            width: None,
+            height: None,
+        }
+    }
+
+    /// Instantiates a thumbnail with a width and height in pixels.
+    pub fn with_size<S: Into<String>>(url: S, width: u64, height: u64) -> Self {
+        Self {
+            url: url.into(),
+            width: Some(width),
+            height: Some(height),
+        }
+    }
+
+    fn from_value(value: &Value) -> Result<Self, Error> {
+        let obj = match value {
+            Value::Object(obj) => obj,
+            _ => return Err(Error::UnexpectedType),
+        };
+        let url = match obj.get("url") {
+            Some(Value::String(url)) => url.clone(),
+            _ => return Err(Error::UnexpectedType),
+        };
+        let dimension = |key| match obj.get(key) {
+            None => Ok(None),
+            Some(value) => value.as_u64().map(Some).ok_or(Error::UnexpectedType),
+        };
+        Ok(Self {
+            url,
+            width: dimension("width")?,
+            height: dimension("height")?,
+        })
+    }
+
+    fn into_value(self) -> Value {
+        let mut obj = Map::new();
+        obj.insert(String::from("url"), Value::String(self.url));
+        if let Some(width) = self.width {
+            obj.insert(String::from("width"), Value::from(width));
+        }
+        if let Some(height) = self.height {
+            obj.insert(String::from("height"), Value::from(height));
+        }
+        Value::Object(obj)
+    }
+}
+
+fn thumbnails(map: &Map<String, Value>) -> Result<Vec<Thumbnail>, Error> {
+    match crate::ext::__private::get(map, Some(KEY), THUMBNAILS)? {
+        None => Ok(Vec::new()),
+        Some(Value::Array(values)) => values.iter().map(Thumbnail::from_value).collect(),
+        Some(_) => Err(Error::UnexpectedType),
+    }
+}
+
+fn thumbnail_for_width(
+    map: &Map<String, Value>,
+    min_width: u64,
+) -> Result<Option<Thumbnail>, Error> {
+    let mut best: Option<Thumbnail> = None;
+    for thumbnail in thumbnails(map)? {
+        let is_better = match (&best, thumbnail.width) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(best), Some(width)) => match best.width {
+                None => true,
+                // Prefer the narrowest thumbnail which is wide enough, else the widest.
+                Some(best_width) if best_width >= min_width => {
+                    width >= min_width && width < best_width
+                }
+                Some(best_width) => width > best_width,
+            },
+        };
+        if is_better {
+            best = Some(thumbnail);
+        }
+    }
+    Ok(best)
+}
+
+fn is_image_mime_type(mime_type: &str) -> bool {
+    mime_type
+        .get(..6)
+        .map_or(false, |prefix| prefix.eq_ignore_ascii_case("image/"))
+}
+
+fn derived_thumbnails(item: &ItemRef<'_>) -> Result<Vec<Thumbnail>, Error> {
+    let mut urls: Vec<String> = Vec::new();
+    for url in [item.image()?, item.banner_image()?].iter().flatten() {
+        urls.push(String::from(*url));
+    }
+    for attachment in item.attachments()?.unwrap_or_default() {
+        if attachment.mime_type()?.map_or(false, is_image_mime_type) {
+            if let Some(url) = attachment.url()? {
+                urls.push(String::from(url));
+            }
+        }
+    }
+
+    let mut thumbnails: Vec<Thumbnail> = Vec::with_capacity(urls.len());
+    for url in urls {
+        if !thumbnails.iter().any(|thumbnail| thumbnail.url == url) {
+            thumbnails.push(Thumbnail::new(url));
+        }
+    }
+    Ok(thumbnails)
+}
+
+/// Sets the derived thumbnails if the item does not have any thumbnails.
+fn derive_thumbnails(map: &mut Map<String, Value>) -> Result<usize, Error> {
+    if !thumbnails(map)?.is_empty() {
+        return Ok(0);
+    }
+    let derived = derived_thumbnails(&ItemRef::from(&*map))?;
+    let count = derived.len();
+    if count > 0 {
+        set_thumbnails(map, derived);
+    }
+    Ok(count)
+}
+
+fn set_thumbnails<I>(map: &mut Map<String, Value>, thumbnails: I) -> Option<Value>
+where
+    I: IntoIterator<Item = Thumbnail>,
+{
+    let values = thumbnails.into_iter().map(Thumbnail::into_value).collect();
+    crate::ext::__private::insert(map, Some(KEY), THUMBNAILS, Value::Array(values))
+}
+
+/// Reads the thumbnails of an item.
+pub trait Media {
+    /// Returns the item's thumbnails.
+    ///
+    /// An item without the extension has no thumbnails.
+    ///
+    /// # Errors
+    ///
+    /// If the thumbnails are not an array of objects with a string `url` and unsigned integer
+    /// `width` and `height`, then `Error::UnexpectedType` is returned.
+    fn thumbnails(&self) -> Result<Vec<Thumbnail>, Error>;
+
+    /// Returns the thumbnail which best fits a display width in pixels.
+    ///
+    /// The narrowest thumbnail which is at least `min_width` wide is preferred. Otherwise, the
+    /// widest thumbnail is returned. Thumbnails without a width are only returned if no
+    /// thumbnail has a width.
+    ///
+    /// # Errors
+    ///
+    /// If the thumbnails are not the expected JSON type, then `Error::UnexpectedType` is
+    /// returned.
+    fn thumbnail_for_width(&self, min_width: u64) -> Result<Option<Thumbnail>, Error>;
+
+    /// Verifies the extension's properties have the expected JSON types.
+    fn is_media_valid(&self) -> bool;
+}
+
+/// Writes the thumbnails of an item.
+pub trait MediaMut {
+    /// Sets the thumbnails.
+    fn set_thumbnails<I>(&mut self, thumbnails: I) -> Option<Value>
+    where
+        I: IntoIterator<Item = Thumbnail>;
+
+    /// Removes the thumbnails.
+    fn remove_thumbnails(&mut self) -> Option<Value>;
+
+    /// Sets thumbnails derived from the item's `image`, `banner_image`, and image attachments
+    /// if the item does not have any thumbnails.
+    ///
+    /// The sizes of the derived thumbnails are unknown. Returns the number of thumbnails which
+    /// were set.
+    ///
+    /// # Errors
+    ///
+    /// If the thumbnails, images, or attachments are not the expected JSON type, then
+    /// `Error::UnexpectedType` is returned.
+    fn derive_thumbnails(&mut self) -> Result<usize, Error>;
+}
+
+macro_rules! impl_media {
+    ($($ty:ty),+) => {
+        $(
+            impl Media for $ty {
+                fn thumbnails(&self) -> Result<Vec<Thumbnail>, Error> {
+                    thumbnails(self.as_map())
+                }
+
+                fn thumbnail_for_width(&self, min_width: u64) -> Result<Option<Thumbnail>, Error> {
+                    thumbnail_for_width(self.as_map(), min_width)
+                }
+
+                fn is_media_valid(&self) -> bool {
+                    thumbnails(self.as_map()).is_ok()
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_media_mut {
+    ($($ty:ty),+) => {
+        $(
+            impl MediaMut for $ty {
+                fn set_thumbnails<I>(&mut self, thumbnails: I) -> Option<Value>
+                where
+                    I: IntoIterator<Item = Thumbnail>,
+                {
+                    set_thumbnails(self.as_map_mut(), thumbnails)
+                }
+
+                fn remove_thumbnails(&mut self) -> Option<Value> {
+                    crate::ext::__private::remove(self.as_map_mut(), Some(KEY), THUMBNAILS)
+                }
+
+                fn derive_thumbnails(&mut self) -> Result<usize, Error> {
+                    derive_thumbnails(self.as_map_mut())
+                }
+            }
+        )+
+    };
+}
+
+impl_media!(Item, ItemRef<'_>, ItemMut<'_>);
+impl_media_mut!(Item, ItemMut<'_>);
+
+fn derive_item_thumbnails(map: &mut Map<String, Value>) -> Result<usize, Error> {
+    // Check every item before any changes are made.
+    let mut derived = Vec::new();
+    for item in crate::item_refs(map)? {
+        derived.push(if item.thumbnails()?.is_empty() {
+            derived_thumbnails(&item)?
+        } else {
+            Vec::new()
+        });
+    }
+
+    let mut changed = 0;
+    if let Some(Value::Array(items)) = map.get_mut("items") {
+        for (item, thumbnails) in items.iter_mut().zip(derived) {
+            if let (Value::Object(item), false) = (item, thumbnails.is_empty()) {
+                set_thumbnails(item, thumbnails);
+                changed += 1;
+            }
+        }
+    }
+    Ok(changed)
+}
+
+macro_rules! feed_media_methods {
+    () => {
+        /// Sets thumbnails derived from each item's images and image attachments.
+        ///
+        /// Items which already have thumbnails are not changed. See
+        /// [`MediaMut::derive_thumbnails`].
+        ///
+        /// Returns the number of items which were changed.
+        ///
+        /// # Errors
+        ///
+        /// If the items, thumbnails, images, or attachments are not the expected JSON type,
+        /// then `Error::UnexpectedType` is returned and the feed is not changed.
+        pub fn derive_item_thumbnails(&mut self) -> Result<usize, Error> {
+            derive_item_thumbnails(self.as_map_mut())
+        }
+    };
+}
+
+impl Feed {
+    feed_media_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    feed_media_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::{string::String, vec};
+
+    #[test]
+    fn derive() -> Result<(), Error> {
+        let mut feed = crate::from_value(serde_json::json!({
+            "items": [
+                {
+                    "id": "1",
+                    "image": "https://example.org/1.jpg",
+                    "attachments": [
+                        { "url": "https://example.org/1.jpg", "mime_type": "image/jpeg" },
+                        { "url": "https://example.org/2.png", "mime_type": "image/png" },
+                        { "url": "https://example.org/1.mp3", "mime_type": "audio/mpeg" }
+                    ]
+                },
+                {
+                    "id": "2",
+                    "image": "https://example.org/3.jpg",
+                    "_media": { "thumbnails": [{ "url": "https://example.org/3-small.jpg", "width": 100 }] }
+                },
+                { "id": "3", "content_text": "No images" }
+            ]
+        }))?;
+
+        assert_eq!(feed.derive_item_thumbnails()?, 1);
+        let items = feed.items()?.unwrap();
+        assert_eq!(
+            items[0].thumbnails()?,
+            vec![
+                Thumbnail::new("https://example.org/1.jpg"),
+                Thumbnail::new("https://example.org/2.png")
+            ]
+        );
+        assert_eq!(
+            items[1].thumbnails()?,
+            vec![Thumbnail {
+                url: String::from("https://example.org/3-small.jpg"),
+                width: Some(100),
+                height: None,
+            }]
+        );
+        assert!(items[2].thumbnails()?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn best_fit() -> Result<(), Error> {
+        let mut item = Item::new();
+        assert!(item.is_media_valid());
+        assert_eq!(item.thumbnail_for_width(100)?, None);
+
+        item.set_thumbnails(vec![
+            Thumbnail::new("https://example.org/unknown.jpg"),
+            Thumbnail::with_size("https://example.org/200.jpg", 200, 200),
+            Thumbnail::with_size("https://example.org/800.jpg", 800, 800),
+            Thumbnail::with_size("https://example.org/400.jpg", 400, 400),
+        ]);
+        let url = |width| item.thumbnail_for_width(width).map(|t| t.unwrap().url);
+        assert_eq!(url(300)?, "https://example.org/400.jpg");
+        assert_eq!(url(100)?, "https://example.org/200.jpg");
+        assert_eq!(url(1000)?, "https://example.org/800.jpg");
+
+        item.remove_thumbnails();
+        assert_eq!(item.as_map().get(KEY), None);
+
+        item.as_map_mut().insert(
+            String::from(KEY),
+            serde_json::json!({ "thumbnails": [{ "width": 1 }] }),
+        );
+        assert!(!item.is_media_valid());
+        Ok(())
+    }
+}
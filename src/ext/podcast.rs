@@ -0,0 +1,244 @@
+//! Podcast properties stored in the `_itunes` extension.
+//!
+//! Podcast feeds commonly carry metadata used by podcast directories such as episode and season
+//! numbers. The properties are stored in an `_itunes` JSON object on the feed and on each item.
+//!
+//! ```
+//! use json_feed_model::{ext::podcast::{PodcastItem, PodcastItemMut}, Item};
+//!
+//! let mut item = Item::new();
+//! item.set_episode(12);
+//! item.set_explicit(false);
+//!
+//! assert_eq!(item.episode()?, Some(12));
+//! assert_eq!(
+//!     item.as_map().get("_itunes"),
+//!     Some(&serde_json::json!({ "episode": 12, "explicit": false }))
+//! );
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{format, vec::Vec};
+#[cfg(feature = "std")]
+use std::{format, vec::Vec};
+
+use crate::{
+    lint::{Lint, LintCode},
+    Feed, FeedMut, FeedRef, Item, ItemMut, ItemRef,
+};
+
+/// The key of the podcast extension object.
+pub const KEY: &str = "_itunes";
+
+crate::define_extension! {
+    /// Reads podcast properties from a feed.
+    pub trait PodcastFeed for Feed, FeedRef<'_>, FeedMut<'_>;
+    /// Writes podcast properties to a feed.
+    pub trait PodcastFeedMut for Feed, FeedMut<'_>;
+    key KEY;
+    valid is_podcast_valid;
+    props {
+        [
+            bool_prop,
+            "explicit",
+            explicit,
+            "Optionally determines if the podcast contains explicit content.",
+            set_explicit,
+            "Sets the explicit flag.",
+            remove_explicit,
+            "Removes the explicit flag."
+        ],
+        [
+            str_prop,
+            "artwork",
+            artwork,
+            "An optional URL of the podcast's cover art.",
+            set_artwork,
+            "Sets the artwork URL.",
+            remove_artwork,
+            "Removes the artwork URL."
+        ]
+    }
+}
+
+crate::define_extension! {
+    /// Reads podcast episode properties from an item.
+    pub trait PodcastItem for Item, ItemRef<'_>, ItemMut<'_>;
+    /// Writes podcast episode properties to an item.
+    pub trait PodcastItemMut for Item, ItemMut<'_>;
+    key KEY;
+    valid is_podcast_valid;
+    props {
+        [
+            u64_prop,
+            "episode",
+            episode,
+            "The optional episode number.",
+            set_episode,
+            "Sets the episode number.",
+            remove_episode,
+            "Removes the episode number."
+        ],
+        [
+            u64_prop,
+            "season",
+            season,
+            "The optional season number.",
+            set_season,
+            "Sets the season number.",
+            remove_season,
+            "Removes the season number."
+        ],
+        [
+            bool_prop,
+            "explicit",
+            explicit,
+            "Optionally determines if the episode contains explicit content.",
+            set_explicit,
+            "Sets the explicit flag.",
+            remove_explicit,
+            "Removes the explicit flag."
+        ],
+        [
+            str_prop,
+            "artwork",
+            artwork,
+            "An optional URL of the episode's cover art.",
+            set_artwork,
+            "Sets the artwork URL.",
+            remove_artwork,
+            "Removes the artwork URL."
+        ]
+    }
+}
+
+/// Returns true if the feed or any of its items have the podcast extension.
+#[must_use]
+pub fn is_podcast(feed: &FeedRef<'_>) -> bool {
+    feed.as_map().contains_key(KEY)
+        || feed.items().map_or(false, |items| {
+            items.map_or(false, |items| {
+                items.iter().any(|item| item.as_map().contains_key(KEY))
+            })
+        })
+}
+
+fn is_audio_mime_type(mime_type: &str) -> bool {
+    mime_type
+        .get(..6)
+        .map_or(false, |prefix| prefix.eq_ignore_ascii_case("audio/"))
+}
+
+/// Lints podcast feeds.
+///
+/// Every item should have an attachment with an audio MIME type and every audio attachment
+/// should have a duration.
+pub(crate) fn lint(feed: &FeedRef<'_>, lints: &mut Vec<Lint>) {
+    if !is_podcast(feed) {
+        return;
+    }
+
+    let items = match feed.items() {
+        Ok(Some(items)) => items,
+        Ok(None) | Err(_) => return,
+    };
+
+    for (item_index, item) in items.iter().enumerate() {
+        let attachments = match item.attachments() {
+            Ok(Some(attachments)) => attachments,
+            Ok(None) => Vec::new(),
+            Err(_) => continue,
+        };
+
+        let mut has_audio = false;
+        for (attachment_index, attachment) in attachments.iter().enumerate() {
+            if !attachment.mime_type().map_or(false, |mime_type| {
+                mime_type.map_or(false, is_audio_mime_type)
+            }) {
+                continue;
+            }
+            has_audio = true;
+
+            if let Ok(None) = attachment.duration_in_seconds() {
+                lints.push(Lint::new(
+                    LintCode::PodcastMissingDuration,
+                    format!("/items/{}/attachments/{}", item_index, attachment_index),
+                    "audio attachment does not have a duration_in_seconds",
+                ));
+            }
+        }
+
+        if !has_audio {
+            lints.push(Lint::new(
+                LintCode::PodcastMissingAudio,
+                format!("/items/{}", item_index),
+                "podcast item does not have an audio attachment",
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    #[test]
+    fn podcast_lints() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "version": "https://jsonfeed.org/version/1.1",
+            "title": "Podcast",
+            "_itunes": { "explicit": false },
+            "items": [
+                {
+                    "id": "1",
+                    "content_text": "Episode 1",
+                    "_itunes": { "episode": 1 },
+                    "attachments": [
+                        {
+                            "url": "https://example.org/1.mp3",
+                            "mime_type": "audio/mpeg",
+                            "duration_in_seconds": 3600
+                        }
+                    ]
+                },
+                {
+                    "id": "2",
+                    "content_text": "Episode 2",
+                    "attachments": [
+                        { "url": "https://example.org/2.mp3", "mime_type": "audio/mpeg" }
+                    ]
+                },
+                {
+                    "id": "3",
+                    "content_text": "Episode 3"
+                }
+            ]
+        }))?;
+
+        assert_eq!(feed.explicit()?, Some(false));
+        assert!(feed.is_podcast_valid());
+        assert_eq!(feed.items()?.unwrap()[0].episode()?, Some(1));
+
+        let lints = feed.lints();
+        assert_eq!(lints.len(), 2);
+        assert_eq!(lints[0].code, LintCode::PodcastMissingDuration);
+        assert_eq!(lints[0].path, "/items/1/attachments/0");
+        assert_eq!(lints[1].code, LintCode::PodcastMissingAudio);
+        assert_eq!(lints[1].path, "/items/2");
+
+        Ok(())
+    }
+
+    #[test]
+    fn not_a_podcast() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "version": "https://jsonfeed.org/version/1.1",
+            "title": "Blog",
+            "items": [{ "id": "1", "content_text": "Post" }]
+        }))?;
+        assert!(feed.lints().is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+//! Word count and reading time stored in the `_reading_time` extension.
+//!
+//! Front-ends usually display the estimated reading time of an item. Publishers can store the
+//! values computed with `Item::persist_reading_time` so readers do not recompute them.
+
+use crate::{Item, ItemMut, ItemRef};
+
+/// The key of the reading time extension object.
+pub const KEY: &str = "_reading_time";
+
+crate::define_extension! {
+    /// Reads the word count and reading time of an item.
+    pub trait ReadingTime for Item, ItemRef<'_>, ItemMut<'_>;
+    /// Writes the word count and reading time of an item.
+    pub trait ReadingTimeMut for Item, ItemMut<'_>;
+    key KEY;
+    valid is_reading_time_valid;
+    props {
+        [
+            u64_prop,
+            "words",
+            words,
+            "The optional number of words in the content.",
+            set_words,
+            "Sets the number of words.",
+            remove_words,
+            "Removes the number of words."
+        ],
+        [
+            u64_prop,
+            "minutes",
+            minutes,
+            "The optional estimated reading time in minutes.",
+            set_minutes,
+            "Sets the reading time in minutes.",
+            remove_minutes,
+            "Removes the reading time in minutes."
+        ]
+    }
+}
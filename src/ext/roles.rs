@@ -0,0 +1,261 @@
+//! Contribution roles of authors stored in the `_role` extension.
+//!
+//! Items with several contributors can describe what each contributor did. The role is stored
+//! in a `_role` string on the author object, such as `editor`, `translator`, or
+//! `photographer`.
+//!
+//! ```
+//! use json_feed_model::ext::roles::{AuthorRole, DEFAULT_ROLES};
+//!
+//! let feed = json_feed_model::from_value(serde_json::json!({
+//!     "items": [
+//!         {
+//!             "id": "1",
+//!             "authors": [
+//!                 { "name": "Jane" },
+//!                 { "name": "Ana", "_role": "translator" }
+//!             ]
+//!         },
+//!         { "id": "2", "authors": [{ "name": "Ana", "_role": "editor" }] }
+//!     ]
+//! }))?;
+//!
+//! let translated = feed.items_by_contributor("ana", Some("translator"))?;
+//! assert_eq!(translated.len(), 1);
+//! assert_eq!(translated[0].id()?, Some("1"));
+//! assert_eq!(feed.items_by_contributor("Ana", None)?.len(), 2);
+//!
+//! let authors = translated[0].authors()?.unwrap();
+//! assert_eq!(authors[1].role()?, Some("translator"));
+//! assert!(feed.role_lints(DEFAULT_ROLES).is_empty());
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{format, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{format, string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{
+    lint::{Lint, LintCode},
+    Author, AuthorMut, AuthorRef, Error, Feed, FeedMut, FeedRef, ItemRef,
+};
+
+/// The key of the role extension string.
+pub const KEY: &str = "_role";
+
+/// Common contribution roles.
+pub const DEFAULT_ROLES: &[&str] = &[
+    "author",
+    "contributor",
+    "editor",
+    "illustrator",
+    "narrator",
+    "photographer",
+    "translator",
+];
+
+crate::define_extension! {
+    /// Reads the contribution role of an author.
+    pub trait AuthorRole for Author, AuthorRef<'_>, AuthorMut<'_>;
+    /// Writes the contribution role of an author.
+    pub trait AuthorRoleMut for Author, AuthorMut<'_>;
+    valid is_role_valid;
+    props {
+        [
+            str_prop,
+            KEY,
+            role,
+            "The optional role of the author in the item such as `editor` or `translator`.",
+            set_role,
+            "Sets the role.",
+            remove_role,
+            "Removes the role."
+        ]
+    }
+}
+
+/// Calls `f` with each author object of an object and its JSON Pointer.
+fn for_each_author<F>(map: &Map<String, Value>, path: &str, f: &mut F)
+where
+    F: FnMut(AuthorRef<'_>, String),
+{
+    if let Some(Value::Object(author)) = map.get("author") {
+        f(AuthorRef::from(author), format!("{}/author", path));
+    }
+    if let Some(Value::Array(authors)) = map.get("authors") {
+        for (author_index, author) in authors.iter().enumerate() {
+            if let Value::Object(author) = author {
+                f(
+                    AuthorRef::from(author),
+                    format!("{}/authors/{}", path, author_index),
+                );
+            }
+        }
+    }
+}
+
+fn names_match(a: &str, b: &str) -> bool {
+    let mut a = a.split_whitespace();
+    let mut b = b.split_whitespace();
+    loop {
+        match (a.next(), b.next()) {
+            (None, None) => return true,
+            (Some(a), Some(b)) if a.to_lowercase() == b.to_lowercase() => {}
+            _ => return false,
+        }
+    }
+}
+
+fn items_by_contributor<'a>(
+    map: &'a Map<String, Value>,
+    name: &str,
+    role: Option<&str>,
+) -> Result<Vec<ItemRef<'a>>, Error> {
+    let mut matches = Vec::new();
+    for item in crate::item_refs(map)? {
+        let authors = item
+            .authors()?
+            .unwrap_or_default()
+            .into_iter()
+            .chain(item.author()?);
+        let mut is_match = false;
+        for author in authors {
+            if !author
+                .name()?
+                .map_or(false, |author| names_match(author, name))
+            {
+                continue;
+            }
+            if let Some(role) = role {
+                if !author
+                    .role()?
+                    .map_or(false, |author| author.eq_ignore_ascii_case(role))
+                {
+                    continue;
+                }
+            }
+            is_match = true;
+        }
+        if is_match {
+            matches.push(item);
+        }
+    }
+    Ok(matches)
+}
+
+fn role_lints(map: &Map<String, Value>, roles: &[&str]) -> Vec<Lint> {
+    let mut lints = Vec::new();
+    let mut lint_author = |author: AuthorRef<'_>, path: String| {
+        if let Ok(Some(role)) = author.role() {
+            if !roles.iter().any(|known| known.eq_ignore_ascii_case(role)) {
+                lints.push(Lint::new(
+                    LintCode::UnknownRole,
+                    format!("{}/{}", path, KEY),
+                    format!("unknown contribution role: {}", role),
+                ));
+            }
+        }
+    };
+
+    for_each_author(map, "", &mut lint_author);
+    if let Some(Value::Array(items)) = map.get("items") {
+        for (item_index, item) in items.iter().enumerate() {
+            if let Value::Object(item) = item {
+                for_each_author(item, &format!("/items/{}", item_index), &mut lint_author);
+            }
+        }
+    }
+    lints
+}
+
+macro_rules! roles_methods {
+    () => {
+        /// Returns the items with a contributor with the given name and, optionally, role.
+        ///
+        /// The item's `authors` and `author` are searched. Names are compared
+        /// case-insensitively with whitespace collapsed, and roles are compared
+        /// case-insensitively.
+        ///
+        /// # Errors
+        ///
+        /// If the items or an item's authors are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned.
+        pub fn items_by_contributor(
+            &self,
+            name: &str,
+            role: Option<&str>,
+        ) -> Result<Vec<ItemRef<'_>>, Error> {
+            items_by_contributor(self.as_map(), name, role)
+        }
+
+        /// Returns a lint for each author with a role which is not in `roles`.
+        ///
+        /// Roles are compared case-insensitively. See [`DEFAULT_ROLES`] for a common
+        /// vocabulary.
+        #[must_use]
+        pub fn role_lints(&self, roles: &[&str]) -> Vec<Lint> {
+            role_lints(self.as_map(), roles)
+        }
+    };
+}
+
+impl Feed {
+    roles_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    roles_methods!();
+}
+
+impl<'a> FeedRef<'a> {
+    roles_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::{vec, vec::Vec};
+
+    #[test]
+    fn vocabulary() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "authors": [{ "name": "Jane", "_role": "Publisher" }],
+            "items": [
+                {
+                    "id": "1",
+                    "author": { "name": "Ana  Lima", "_role": "Photographer" },
+                    "authors": [{ "name": "Jane" }, { "name": "Sam", "_role": "colorist" }]
+                }
+            ]
+        }))?;
+
+        assert_eq!(
+            feed.items_by_contributor("ana lima", Some("photographer"))?
+                .len(),
+            1
+        );
+        assert!(feed
+            .items_by_contributor("ana lima", Some("editor"))?
+            .is_empty());
+        assert!(feed.items_by_contributor("Ana", None)?.is_empty());
+
+        let lints = feed.role_lints(DEFAULT_ROLES);
+        assert_eq!(
+            lints
+                .iter()
+                .map(|lint| lint.path.as_str())
+                .collect::<Vec<_>>(),
+            vec!["/authors/0/_role", "/items/0/authors/1/_role"]
+        );
+        assert!(lints.iter().all(|lint| lint.code == LintCode::UnknownRole));
+
+        let mut roles = DEFAULT_ROLES.to_vec();
+        roles.extend(["publisher", "colorist"]);
+        assert!(feed.role_lints(&roles).is_empty());
+        Ok(())
+    }
+}
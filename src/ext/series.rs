@@ -0,0 +1,211 @@
+//! Series and reply threading stored in the `_series` and `_in_reply_to` extensions.
+//!
+//! Multi-part posts identify their series and their part number in a `_series` JSON object on
+//! the item. Replies and follow-ups identify the `id` of the item they respond to in an
+//! `_in_reply_to` string on the item.
+//!
+//! ```
+//! use json_feed_model::{ext::series::{Series, SeriesMut, Thread, ThreadMut}, Feed, Item};
+//!
+//! let mut part_2 = Item::new();
+//! part_2.set_id("2");
+//! part_2.set_series_id("rust-basics");
+//! part_2.set_series_part(2);
+//! part_2.set_in_reply_to("1");
+//!
+//! let mut part_1 = Item::new();
+//! part_1.set_id("1");
+//! part_1.set_series_id("rust-basics");
+//! part_1.set_series_part(1);
+//!
+//! let mut feed = Feed::new();
+//! feed.set_items(vec![part_2, part_1]);
+//!
+//! let series = feed.series("rust-basics")?;
+//! assert_eq!(series[0].id()?, Some("1"));
+//! assert_eq!(series[1].id()?, Some("2"));
+//! assert_eq!(series[1].in_reply_to()?, Some("1"));
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{collections::BTreeMap, format, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{collections::BTreeMap, format, string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{
+    lint::{Lint, LintCode},
+    Error, Feed, FeedMut, FeedRef, Item, ItemMut, ItemRef,
+};
+
+/// The key of the series extension object.
+pub const KEY: &str = "_series";
+
+/// The key of the reply extension string.
+pub const IN_REPLY_TO_KEY: &str = "_in_reply_to";
+
+crate::define_extension! {
+    /// Reads the series of an item.
+    pub trait Series for Item, ItemRef<'_>, ItemMut<'_>;
+    /// Writes the series of an item.
+    pub trait SeriesMut for Item, ItemMut<'_>;
+    key KEY;
+    valid is_series_valid;
+    props {
+        [
+            str_prop,
+            "id",
+            series_id,
+            "The optional identifier of the series which the item is part of.",
+            set_series_id,
+            "Sets the series identifier.",
+            remove_series_id,
+            "Removes the series identifier."
+        ],
+        [
+            u64_prop,
+            "part",
+            series_part,
+            "The optional part number of the item in the series.",
+            set_series_part,
+            "Sets the part number.",
+            remove_series_part,
+            "Removes the part number."
+        ]
+    }
+}
+
+crate::define_extension! {
+    /// Reads the item which an item replies to.
+    pub trait Thread for Item, ItemRef<'_>, ItemMut<'_>;
+    /// Writes the item which an item replies to.
+    pub trait ThreadMut for Item, ItemMut<'_>;
+    valid is_thread_valid;
+    props {
+        [
+            str_prop,
+            IN_REPLY_TO_KEY,
+            in_reply_to,
+            "The optional `id` of the item which this item replies to or follows up on.",
+            set_in_reply_to,
+            "Sets the parent item's `id`.",
+            remove_in_reply_to,
+            "Removes the parent item's `id`."
+        ]
+    }
+}
+
+fn series<'a>(map: &'a Map<String, Value>, series_id: &str) -> Result<Vec<ItemRef<'a>>, Error> {
+    let mut parts = Vec::new();
+    for item in crate::item_refs(map)? {
+        if item.series_id()? == Some(series_id) {
+            parts.push((item.series_part()?, item));
+        }
+    }
+    // Items without a part number are last and keep their order in the feed.
+    parts.sort_by_key(|(part, _)| (part.is_none(), *part));
+    Ok(parts.into_iter().map(|(_, item)| item).collect())
+}
+
+macro_rules! series_methods {
+    () => {
+        /// Returns the items in a series ordered by their part number.
+        ///
+        /// Items without a part number are returned after the numbered parts in the order
+        /// they appear in the feed.
+        ///
+        /// # Errors
+        ///
+        /// If the items or an item's series is not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned.
+        pub fn series(&self, series_id: &str) -> Result<Vec<ItemRef<'_>>, Error> {
+            series(self.as_map(), series_id)
+        }
+    };
+}
+
+impl Feed {
+    series_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    series_methods!();
+}
+
+impl<'a> FeedRef<'a> {
+    series_methods!();
+}
+
+/// Lints the series of each item.
+///
+/// The part numbers should be unique within a series, and an item should not reply to itself.
+pub(crate) fn lint(feed: &FeedRef<'_>, lints: &mut Vec<Lint>) {
+    let items = match feed.items() {
+        Ok(Some(items)) => items,
+        Ok(None) | Err(_) => return,
+    };
+
+    let mut parts = BTreeMap::new();
+    for (item_index, item) in items.iter().enumerate() {
+        if let (Ok(Some(series_id)), Ok(Some(part))) = (item.series_id(), item.series_part()) {
+            let first_index = *parts.entry((series_id, part)).or_insert(item_index);
+            if first_index != item_index {
+                lints.push(Lint::new(
+                    LintCode::DuplicateSeriesPart,
+                    format!("/items/{}/{}/part", item_index, KEY),
+                    format!(
+                        "part {} of series {:?} is also item {}",
+                        part, series_id, first_index
+                    ),
+                ));
+            }
+        }
+
+        if let (Ok(Some(id)), Ok(Some(parent))) = (item.id(), item.in_reply_to()) {
+            if id == parent {
+                lints.push(Lint::new(
+                    LintCode::ReplyToSelf,
+                    format!("/items/{}/{}", item_index, IN_REPLY_TO_KEY),
+                    "item replies to itself",
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::{vec, vec::Vec};
+
+    #[test]
+    fn series_lints() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "items": [
+                { "id": "1", "_series": { "id": "a", "part": 1 } },
+                { "id": "2", "_series": { "id": "a" } },
+                { "id": "3", "_series": { "id": "a", "part": 1 }, "_in_reply_to": "3" },
+                { "id": "4", "_series": { "id": "b", "part": 1 }, "_in_reply_to": "1" },
+                { "id": "5", "_series": { "id": "a", "part": 0 } }
+            ]
+        }))?;
+
+        let series = feed.series("a")?;
+        let ids = series
+            .iter()
+            .map(|item| item.id())
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(ids, vec![Some("5"), Some("1"), Some("3"), Some("2")]);
+
+        let lints = feed.lints();
+        assert_eq!(lints.len(), 2);
+        assert_eq!(lints[0].code, LintCode::DuplicateSeriesPart);
+        assert_eq!(lints[0].path, "/items/2/_series/part");
+        assert_eq!(lints[1].code, LintCode::ReplyToSelf);
+        assert_eq!(lints[1].path, "/items/2/_in_reply_to");
+        Ok(())
+    }
+}
@@ -0,0 +1,300 @@
+//! Attribution of aggregated items stored in the `_source` extension.
+//!
+//! An aggregator which combines items from several feeds can record where each item came from.
+//! The attribution is stored in a `_source` JSON object on the item with the originating
+//! `feed_url`, feed `title`, `item_url`, and the `retrieved_at` date.
+//!
+//! The crate does not merge feeds, so the attribution is not added automatically. An aggregator
+//! calls `attribute_source()` on each fetched feed before copying its items into the combined
+//! feed.
+//!
+//! ```
+//! use json_feed_model::{ext::source::Source, Feed};
+//!
+//! let mut upstream = json_feed_model::from_value(serde_json::json!({
+//!     "version": "https://jsonfeed.org/version/1.1",
+//!     "title": "Upstream",
+//!     "feed_url": "https://example.org/feed.json",
+//!     "items": [
+//!         { "id": "1", "content_text": "Hello", "url": "https://example.org/1" }
+//!     ]
+//! }))?;
+//! upstream.attribute_source("2021-01-01T00:00:00Z")?;
+//!
+//! let mut aggregate = Feed::new();
+//! aggregate.set_items(upstream.items()?.unwrap().iter().map(|item| item.to_item()));
+//!
+//! let items = aggregate.items_from_source("https://example.org/feed.json")?;
+//! assert_eq!(items.len(), 1);
+//! assert_eq!(items[0].source_item_url()?, Some("https://example.org/1"));
+//! assert_eq!(items[0].retrieved_at()?, Some("2021-01-01T00:00:00Z"));
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{date::Timestamp, Error, Feed, FeedMut, FeedRef, Item, ItemMut, ItemRef};
+
+/// The key of the source extension object.
+pub const KEY: &str = "_source";
+
+crate::define_extension! {
+    /// Reads the source attribution of an item.
+    pub trait Source for Item, ItemRef<'_>, ItemMut<'_>;
+    /// Writes the source attribution of an item.
+    pub trait SourceMut for Item, ItemMut<'_>;
+    key KEY;
+    valid is_source_valid;
+    props {
+        [
+            str_prop,
+            "feed_url",
+            source_feed_url,
+            "The optional URL of the feed which the item was retrieved from.",
+            set_source_feed_url,
+            "Sets the source feed URL.",
+            remove_source_feed_url,
+            "Removes the source feed URL."
+        ],
+        [
+            str_prop,
+            "title",
+            source_title,
+            "The optional title of the feed which the item was retrieved from.",
+            set_source_title,
+            "Sets the source feed title.",
+            remove_source_title,
+            "Removes the source feed title."
+        ],
+        [
+            str_prop,
+            "item_url",
+            source_item_url,
+            "The optional URL of the item in the source feed.",
+            set_source_item_url,
+            "Sets the source item URL.",
+            remove_source_item_url,
+            "Removes the source item URL."
+        ],
+        [
+            str_prop,
+            "retrieved_at",
+            retrieved_at,
+            "The date which the item was retrieved in [RFC 3339][rfc_3339] format.
+
+[rfc_3339]: https://tools.ietf.org/html/rfc3339
+",
+            set_retrieved_at,
+            "Sets the date retrieved.",
+            remove_retrieved_at,
+            "Removes the date retrieved."
+        ]
+    }
+}
+
+fn attribute_source(map: &mut Map<String, Value>, retrieved_at: &str) -> Result<(), Error> {
+    Timestamp::parse(retrieved_at)?;
+
+    // Read every property before modifying any items so an error leaves the feed unchanged.
+    let feed_ref = FeedRef::from(&*map);
+    let feed_url = feed_ref.feed_url()?.map(String::from);
+    let title = feed_ref.title()?.map(String::from);
+    let item_urls = crate::item_refs(map)?
+        .iter()
+        .map(|item| Ok(item.url()?.map(String::from)))
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let mut feed_mut = FeedMut::from(map);
+    let items = feed_mut.items_mut()?.unwrap_or_default();
+    for (mut item, url) in items.into_iter().zip(item_urls) {
+        if item.as_map().contains_key(KEY) {
+            continue;
+        }
+        if let Some(feed_url) = &feed_url {
+            item.set_source_feed_url(feed_url);
+        }
+        if let Some(title) = &title {
+            item.set_source_title(title);
+        }
+        if let Some(url) = url {
+            item.set_source_item_url(url);
+        }
+        item.set_retrieved_at(retrieved_at);
+    }
+    Ok(())
+}
+
+fn items_from_source<'a>(
+    map: &'a Map<String, Value>,
+    feed_url: &str,
+) -> Result<Vec<ItemRef<'a>>, Error> {
+    let items = match map.get("items") {
+        None => return Ok(Vec::new()),
+        Some(Value::Array(items)) => items,
+        Some(_) => return Err(Error::UnexpectedType),
+    };
+
+    let mut matches = Vec::new();
+    for item in items {
+        let item = match item {
+            Value::Object(obj) => ItemRef::from(obj),
+            _ => return Err(Error::UnexpectedType),
+        };
+        if item.source_feed_url()? == Some(feed_url) {
+            matches.push(item);
+        }
+    }
+    Ok(matches)
+}
+
+impl Feed {
+    /// Records the feed as the source of each of its items.
+    ///
+    /// The feed's `feed_url` and `title`, the item's `url`, and the `retrieved_at` date are
+    /// stored in each item's `_source` extension. Items which already have a source are not
+    /// modified so attribution survives repeated aggregation.
+    ///
+    /// Call this on each fetched feed before its items are copied into an aggregated feed.
+    ///
+    /// # Errors
+    ///
+    /// If a property is not the expected JSON type, then `Error::UnexpectedType` is returned.
+    /// If `retrieved_at` is not a valid date, then `Error::InvalidDate` is returned. The feed is
+    /// not modified if an error is returned.
+    pub fn attribute_source(&mut self, retrieved_at: &str) -> Result<(), Error> {
+        attribute_source(&mut self.value, retrieved_at)
+    }
+
+    /// Returns the items which were retrieved from the feed URL.
+    ///
+    /// # Errors
+    ///
+    /// If the items or an item's source is not the expected JSON type, then
+    /// `Error::UnexpectedType` is returned.
+    pub fn items_from_source(&self, feed_url: &str) -> Result<Vec<ItemRef<'_>>, Error> {
+        items_from_source(&self.value, feed_url)
+    }
+}
+
+impl<'a> FeedMut<'a> {
+    /// Records the feed as the source of each of its items.
+    ///
+    /// The feed's `feed_url` and `title`, the item's `url`, and the `retrieved_at` date are
+    /// stored in each item's `_source` extension. Items which already have a source are not
+    /// modified so attribution survives repeated aggregation.
+    ///
+    /// Call this on each fetched feed before its items are copied into an aggregated feed.
+    ///
+    /// # Errors
+    ///
+    /// If a property is not the expected JSON type, then `Error::UnexpectedType` is returned.
+    /// If `retrieved_at` is not a valid date, then `Error::InvalidDate` is returned. The feed is
+    /// not modified if an error is returned.
+    pub fn attribute_source(&mut self, retrieved_at: &str) -> Result<(), Error> {
+        attribute_source(self.value, retrieved_at)
+    }
+
+    /// Returns the items which were retrieved from the feed URL.
+    ///
+    /// # Errors
+    ///
+    /// If the items or an item's source is not the expected JSON type, then
+    /// `Error::UnexpectedType` is returned.
+    pub fn items_from_source(&self, feed_url: &str) -> Result<Vec<ItemRef<'_>>, Error> {
+        items_from_source(self.value, feed_url)
+    }
+}
+
+impl<'a> FeedRef<'a> {
+    /// Returns the items which were retrieved from the feed URL.
+    ///
+    /// # Errors
+    ///
+    /// If the items or an item's source is not the expected JSON type, then
+    /// `Error::UnexpectedType` is returned.
+    pub fn items_from_source(&self, feed_url: &str) -> Result<Vec<ItemRef<'_>>, Error> {
+        items_from_source(self.value, feed_url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn existing_source_is_kept() -> Result<(), Error> {
+        let mut feed = crate::from_value(serde_json::json!({
+            "title": "Relay",
+            "feed_url": "https://relay.example.org/feed.json",
+            "items": [
+                {
+                    "id": "1",
+                    "_source": { "feed_url": "https://example.org/feed.json" }
+                },
+                { "id": "2" }
+            ]
+        }))?;
+        feed.attribute_source("2021-01-01T00:00:00Z")?;
+
+        let items = feed.items()?.unwrap();
+        assert_eq!(
+            items[0].source_feed_url()?,
+            Some("https://example.org/feed.json")
+        );
+        assert_eq!(items[0].retrieved_at()?, None);
+        assert_eq!(
+            items[1].source_feed_url()?,
+            Some("https://relay.example.org/feed.json")
+        );
+        assert_eq!(items[1].source_title()?, Some("Relay"));
+        assert_eq!(items[1].source_item_url()?, None);
+        assert!(items[1].is_source_valid());
+
+        Ok(())
+    }
+
+    #[test]
+    fn errors_leave_feed_unchanged() -> Result<(), Error> {
+        let value = serde_json::json!({
+            "title": "Upstream",
+            "feed_url": "https://example.org/feed.json",
+            "items": [
+                { "id": "1", "url": "https://example.org/1" },
+                { "id": "2", "url": 2 }
+            ]
+        });
+
+        let mut feed = crate::from_value(value.clone())?;
+        assert!(matches!(
+            feed.attribute_source("2021-01-01T00:00:00Z"),
+            Err(Error::UnexpectedType)
+        ));
+        assert_eq!(feed, crate::from_value(value)?);
+
+        let mut feed = crate::from_value(serde_json::json!({
+            "items": [{ "id": "1", "url": "https://example.org/1" }]
+        }))?;
+        assert!(matches!(
+            feed.attribute_source("yesterday"),
+            Err(Error::InvalidDate)
+        ));
+        assert_eq!(feed.items()?.unwrap()[0].retrieved_at()?, None);
+
+        let mut feed = crate::from_value(serde_json::json!({
+            "title": 1,
+            "items": [{ "id": "1" }]
+        }))?;
+        assert!(matches!(
+            feed.attribute_source("2021-01-01T00:00:00Z"),
+            Err(Error::UnexpectedType)
+        ));
+        assert_eq!(feed.items()?.unwrap()[0].retrieved_at()?, None);
+
+        Ok(())
+    }
+}
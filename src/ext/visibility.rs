@@ -0,0 +1,248 @@
+//! Publishing state of items stored in the `_visibility` extension.
+//!
+//! A single feed can drive both a preview endpoint and a public endpoint. Each item may have a
+//! `_visibility` string of `draft`, `unlisted`, or `public`. Items without the extension are
+//! public. [`Feed::public_view`] returns the feed which should be served publicly.
+//!
+//! ```
+//! use json_feed_model::{ext::visibility::{ItemVisibility, ItemVisibilityMut, Visibility}, Feed, Item};
+//!
+//! let mut draft = Item::new();
+//! draft.set_id("2");
+//! draft.set_visibility(Visibility::Draft);
+//!
+//! let mut published = Item::new();
+//! published.set_id("1");
+//! published.set_visibility(Visibility::Public);
+//!
+//! let mut feed = Feed::new();
+//! feed.set_items(vec![draft, published]);
+//!
+//! let public = feed.public_view()?;
+//! let items = public.items()?.unwrap();
+//! assert_eq!(items.len(), 1);
+//! assert_eq!(items[0].id()?, Some("1"));
+//! assert_eq!(items[0].visibility()?, None);
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{Error, Feed, FeedMut, FeedRef, Item, ItemMut, ItemRef};
+
+/// The key of the visibility extension string.
+pub const KEY: &str = "_visibility";
+
+/// The publishing state of an item.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Visibility {
+    /// The item is not ready to be published.
+    Draft,
+    /// The item is published but should not be listed in the public feed.
+    Unlisted,
+    /// The item is published.
+    Public,
+}
+
+impl Visibility {
+    /// Returns the extension's string value.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Visibility::Draft => "draft",
+            Visibility::Unlisted => "unlisted",
+            Visibility::Public => "public",
+        }
+    }
+
+    /// Parses an extension's string value.
+    ///
+    /// The values are matched case-insensitively. If the value is unknown, then `None` is
+    /// returned.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        [Visibility::Draft, Visibility::Unlisted, Visibility::Public]
+            .iter()
+            .copied()
+            .find(|visibility| value.eq_ignore_ascii_case(visibility.as_str()))
+    }
+}
+
+impl AsRef<str> for Visibility {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl core::fmt::Display for Visibility {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+fn visibility(map: &Map<String, Value>) -> Result<Option<Visibility>, Error> {
+    crate::ext::__private::str_prop::get(map, None, KEY)?.map_or(Ok(None), |value| {
+        Visibility::parse(value)
+            .map(Some)
+            .ok_or(Error::UnexpectedType)
+    })
+}
+
+/// Reads the publishing state of an item.
+pub trait ItemVisibility {
+    /// Returns the optional publishing state.
+    ///
+    /// # Errors
+    ///
+    /// If the value is not one of the known JSON strings, then `Error::UnexpectedType` is
+    /// returned.
+    fn visibility(&self) -> Result<Option<Visibility>, Error>;
+
+    /// Returns true if the item should be included in the public feed.
+    ///
+    /// Items without a publishing state are public. Items with an unknown publishing state are
+    /// not public.
+    fn is_public(&self) -> bool;
+
+    /// Verifies the extension's properties have the expected JSON types.
+    fn is_visibility_valid(&self) -> bool;
+}
+
+/// Writes the publishing state of an item.
+pub trait ItemVisibilityMut {
+    /// Sets the publishing state.
+    fn set_visibility(&mut self, visibility: Visibility) -> Option<Value>;
+
+    /// Removes the publishing state.
+    fn remove_visibility(&mut self) -> Option<Value>;
+}
+
+macro_rules! impl_visibility {
+    ($($ty:ty),+) => {
+        $(
+            impl ItemVisibility for $ty {
+                fn visibility(&self) -> Result<Option<Visibility>, Error> {
+                    visibility(self.as_map())
+                }
+
+                fn is_public(&self) -> bool {
+                    matches!(visibility(self.as_map()), Ok(None | Some(Visibility::Public)))
+                }
+
+                fn is_visibility_valid(&self) -> bool {
+                    visibility(self.as_map()).is_ok()
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_visibility_mut {
+    ($($ty:ty),+) => {
+        $(
+            impl ItemVisibilityMut for $ty {
+                fn set_visibility(&mut self, visibility: Visibility) -> Option<Value> {
+                    self.as_map_mut()
+                        .insert(String::from(KEY), Value::String(String::from(visibility.as_str())))
+                }
+
+                fn remove_visibility(&mut self) -> Option<Value> {
+                    self.as_map_mut().remove(KEY)
+                }
+            }
+        )+
+    };
+}
+
+impl_visibility!(Item, ItemRef<'_>, ItemMut<'_>);
+impl_visibility_mut!(Item, ItemMut<'_>);
+
+fn public_view(map: &Map<String, Value>) -> Result<Feed, Error> {
+    let items = crate::item_refs(map)?;
+    let mut public = Map::new();
+    for (key, value) in map {
+        if key != "items" {
+            public.insert(key.clone(), value.clone());
+        }
+    }
+    if map.contains_key("items") {
+        let items = items
+            .into_iter()
+            .filter(ItemVisibility::is_public)
+            .map(|item| {
+                let mut item = item.as_map().clone();
+                item.remove(KEY);
+                Value::Object(item)
+            })
+            .collect::<Vec<_>>();
+        public.insert(String::from("items"), Value::Array(items));
+    }
+    Ok(Feed::from(public))
+}
+
+macro_rules! visibility_methods {
+    () => {
+        /// Returns a copy of the feed for public endpoints.
+        ///
+        /// Draft and unlisted items are removed, as are items with an unknown publishing
+        /// state. The `_visibility` extension is removed from the remaining items.
+        ///
+        /// # Errors
+        ///
+        /// If the items are not the expected JSON type, then `Error::UnexpectedType` is
+        /// returned.
+        pub fn public_view(&self) -> Result<Feed, Error> {
+            public_view(self.as_map())
+        }
+    };
+}
+
+impl Feed {
+    visibility_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    visibility_methods!();
+}
+
+impl<'a> FeedRef<'a> {
+    visibility_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn public_items() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "title": "Example",
+            "items": [
+                { "id": "1", "_visibility": "public" },
+                { "id": "2", "_visibility": "draft" },
+                { "id": "3", "_visibility": "Unlisted" },
+                { "id": "4" },
+                { "id": "5", "_visibility": "scheduled" }
+            ]
+        }))?;
+
+        let items = feed.items()?.unwrap();
+        assert_eq!(items[2].visibility()?, Some(Visibility::Unlisted));
+        assert!(!items[4].is_visibility_valid());
+        assert!(!items[4].is_public());
+
+        assert_eq!(
+            feed.public_view()?,
+            crate::from_value(serde_json::json!({
+                "title": "Example",
+                "items": [{ "id": "1" }, { "id": "4" }]
+            }))?
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+//! The optional features which the crate was compiled with.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+use crate::{VERSION_1, VERSION_1_1};
+
+/// The version of the crate.
+pub const CRATE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The JSON Feed spec versions which the crate supports.
+pub const SPEC_VERSIONS: &[&str] = &[VERSION_1, VERSION_1_1];
+
+/// The optional features which the crate was compiled with and the supported versions.
+///
+/// Each field is true if the Cargo feature with the same name is enabled. Features which only
+/// enable an optional dependency of another feature, such as `sha2`, are not included.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Features {
+    /// The version of the crate.
+    pub crate_version: &'static str,
+    /// The JSON Feed spec versions which the crate supports.
+    pub spec_versions: &'static [&'static str],
+    /// The `std` library is used.
+    pub std: bool,
+    /// The `alloc` feature is enabled. Without `std`, only the `alloc` library is used.
+    pub alloc: bool,
+    /// Synthetic feeds for benchmarks are available.
+    pub bench_utils: bool,
+    /// Diffs of item content are available.
+    pub content_diff: bool,
+    /// Near-duplicate detection for items is available.
+    pub dedup: bool,
+    /// Digests of feeds are available.
+    pub digest: bool,
+    /// Conversions to and from the `feed-rs` model are available.
+    pub feed_rs: bool,
+    /// Gravatar avatar URLs are available.
+    pub gravatar: bool,
+    /// HTTP responses for serving feeds are available.
+    pub http_types: bool,
+    /// Locale aware formatting of dates is available.
+    pub locale: bool,
+    /// Email digests of items are available.
+    pub newsletter: bool,
+    /// Regular expression patterns are available for redaction.
+    pub regex_lite: bool,
+    /// Rewriters for `content_html` are available.
+    pub rewrite: bool,
+    /// Full-text search is available.
+    pub search: bool,
+    /// Helpers for testing code which builds feeds are available.
+    pub testing: bool,
+    /// Events are emitted with `tracing`.
+    pub tracing: bool,
+    /// Transliteration of non-ASCII text in slugs is available.
+    pub transliterate: bool,
+    /// Language detection is available.
+    pub whatlang: bool,
+}
+
+impl Features {
+    fn flags(&self) -> [(&'static str, bool); 18] {
+        [
+            ("std", self.std),
+            ("alloc", self.alloc),
+            ("bench_utils", self.bench_utils),
+            ("content_diff", self.content_diff),
+            ("dedup", self.dedup),
+            ("digest", self.digest),
+            ("feed_rs", self.feed_rs),
+            ("gravatar", self.gravatar),
+            ("http_types", self.http_types),
+            ("locale", self.locale),
+            ("newsletter", self.newsletter),
+            ("regex_lite", self.regex_lite),
+            ("rewrite", self.rewrite),
+            ("search", self.search),
+            ("testing", self.testing),
+            ("tracing", self.tracing),
+            ("transliterate", self.transliterate),
+            ("whatlang", self.whatlang),
+        ]
+    }
+
+    /// Returns the names of the enabled Cargo features.
+    #[must_use]
+    pub fn enabled(&self) -> Vec<&'static str> {
+        self.flags()
+            .iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(name, _)| *name)
+            .collect()
+    }
+
+    /// Returns true if the Cargo feature is enabled.
+    ///
+    /// Unknown feature names are not enabled.
+    #[must_use]
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.flags()
+            .iter()
+            .any(|(feature, enabled)| *enabled && *feature == name)
+    }
+}
+
+/// Returns the optional features which the crate was compiled with.
+///
+/// Applications which load code built against the crate, such as plugins, can check that an
+/// optional feature is available before relying on it.
+///
+/// ```
+/// let features = json_feed_model::features();
+/// assert_eq!(features.crate_version, env!("CARGO_PKG_VERSION"));
+/// assert!(features.spec_versions.contains(&json_feed_model::VERSION_1_1));
+/// assert_eq!(features.is_enabled("std"), features.std);
+/// assert!(!features.is_enabled("unknown"));
+/// ```
+#[must_use]
+pub const fn features() -> Features {
+    Features {
+        crate_version: CRATE_VERSION,
+        spec_versions: SPEC_VERSIONS,
+        std: cfg!(feature = "std"),
+        alloc: cfg!(feature = "alloc"),
+        bench_utils: cfg!(feature = "bench_utils"),
+        content_diff: cfg!(feature = "content_diff"),
+        dedup: cfg!(feature = "dedup"),
+        digest: cfg!(feature = "digest"),
+        feed_rs: cfg!(feature = "feed_rs"),
+        gravatar: cfg!(feature = "gravatar"),
+        http_types: cfg!(feature = "http_types"),
+        locale: cfg!(feature = "locale"),
+        newsletter: cfg!(feature = "newsletter"),
+        regex_lite: cfg!(feature = "regex_lite"),
+        rewrite: cfg!(feature = "rewrite"),
+        search: cfg!(feature = "search"),
+        testing: cfg!(feature = "testing"),
+        tracing: cfg!(feature = "tracing"),
+        transliterate: cfg!(feature = "transliterate"),
+        whatlang: cfg!(feature = "whatlang"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enabled_features() {
+        let features = features();
+        assert_eq!(features.enabled().contains(&"std"), cfg!(feature = "std"));
+        assert_eq!(features.is_enabled("feed_rs"), cfg!(feature = "feed_rs"));
+        assert_eq!(
+            features.is_enabled("regex_lite"),
+            cfg!(feature = "regex_lite")
+        );
+        assert_eq!(features.is_enabled("tracing"), cfg!(feature = "tracing"));
+        assert_eq!(features.is_enabled("whatlang"), cfg!(feature = "whatlang"));
+    }
+}
@@ -0,0 +1,610 @@
+//! Conversions between [`Feed`] and the [`feed-rs`][feed_rs] model.
+//!
+//! Applications which parse RSS and Atom with `feed-rs` can convert its [`model::Feed`] into
+//! a JSON Feed and back, so a single model passes through the application while this crate
+//! handles JSON Feed specifics.
+//!
+//! ```
+//! use json_feed_model::{Feed, Item};
+//!
+//! let mut item = Item::new();
+//! item.set_id("1");
+//! item.set_url("https://example.org/1");
+//! item.set_content_html("<p>Hello</p>");
+//! item.set_date_published("2021-06-01T12:00:00Z");
+//!
+//! let mut feed = Feed::new();
+//! feed.set_title("Example");
+//! feed.set_feed_url("https://example.org/feed.json");
+//! feed.set_items(vec![item]);
+//!
+//! let model = feed_rs::model::Feed::try_from(&feed)?;
+//! assert_eq!(model.entries[0].links[0].href, "https://example.org/1");
+//!
+//! let round_trip = Feed::from(&model);
+//! assert_eq!(round_trip.feed_url()?, Some("https://example.org/feed.json"));
+//! let items = round_trip.items()?.unwrap();
+//! assert_eq!(items[0].content_html()?, Some("<p>Hello</p>"));
+//! assert_eq!(items[0].date_published()?, Some("2021-06-01T12:00:00Z"));
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+//!
+//! # Mapping
+//!
+//! The mapping follows the `feed-rs` JSON Feed parser with links distinguished by their Atom
+//! relation:
+//!
+//! | JSON Feed                         | `feed-rs`                                 |
+//! |-----------------------------------|-------------------------------------------|
+//! | `home_page_url`, item `url`       | link with `rel` of `alternate`            |
+//! | `feed_url`                        | link with `rel` of `self`                 |
+//! | `next_url`                        | link with `rel` of `next`                 |
+//! | `hubs`                            | links with `rel` of `hub`                 |
+//! | item `external_url`               | link with `rel` of `related`              |
+//! | item `attachments`                | links with `rel` of `enclosure`           |
+//! | `icon`                            | `logo`                                    |
+//! | `favicon`                         | `icon`                                    |
+//! | item `content_html`               | `content` with a `text/html` type         |
+//! | item `content_text`               | `content` with a `text/plain` type        |
+//! | item `image`                      | first media thumbnail                     |
+//! | item `tags`                       | `categories`                              |
+//! | item `date_published`             | `published`                               |
+//! | item `date_modified`              | `updated`                                 |
+//!
+//! Properties without an equivalent, such as `user_comment`, `expired`, `banner_image`,
+//! author avatars, attachment durations, and extensions, are not converted. An item with both
+//! `content_html` and `content_text` keeps only the HTML. Authors without a name are skipped
+//! because a `feed-rs` person requires one.
+//!
+//! `feed-rs` copies the feed's authors to entries without authors, so entry authors which are
+//! the same as the feed's authors are not repeated in the converted items. Media content of
+//! entries, such as RSS enclosures, are also converted into attachments.
+//!
+//! [feed_rs]: https://docs.rs/feed-rs
+
+use std::{string::String, vec::Vec};
+
+use ::feed_rs::model;
+use chrono::{DateTime, Utc};
+use mediatype::{names, MediaTypeBuf};
+use serde_json::{Map, Value};
+
+use crate::{
+    date::Timestamp, Attachment, Author, AuthorRef, Error, Feed, FeedMut, FeedRef, Hub, Item,
+    ItemRef, Version,
+};
+
+/// Parses a JSON Feed date.
+fn date_time(date: &str) -> Result<DateTime<Utc>, Error> {
+    let timestamp = Timestamp::parse(date)?;
+    DateTime::from_timestamp(timestamp.unix_seconds(), timestamp.nanosecond())
+        .ok_or(Error::InvalidDate)
+}
+
+/// Formats a date as a JSON Feed date.
+fn date_string(date: &DateTime<Utc>) -> String {
+    Timestamp::from_unix(date.timestamp(), date.timestamp_subsec_nanos()).to_rfc3339()
+}
+
+fn text(content: &str, content_type: MediaTypeBuf) -> model::Text {
+    model::Text {
+        content_type,
+        src: None,
+        content: String::from(content),
+    }
+}
+
+fn plain(content: &str) -> model::Text {
+    text(content, MediaTypeBuf::new(names::TEXT, names::PLAIN))
+}
+
+/// Returns true if the media type is HTML or XHTML.
+fn is_html(content_type: &MediaTypeBuf) -> bool {
+    let (ty, subty) = (content_type.ty(), content_type.subty());
+    (ty == names::TEXT && subty == names::HTML)
+        || (ty == names::APPLICATION && subty == names::XHTML)
+}
+
+/// Returns the text content of a `feed-rs` text.
+fn plain_text(text: &model::Text) -> String {
+    if !is_html(&text.content_type) {
+        String::from(text.content.trim())
+    } else {
+        String::from(crate::html::strip_tags(&text.content).trim())
+    }
+}
+
+fn link(href: &str, rel: &str) -> model::Link {
+    model::Link {
+        href: String::from(href),
+        rel: Some(String::from(rel)),
+        media_type: None,
+        href_lang: None,
+        title: None,
+        length: None,
+    }
+}
+
+fn image(uri: &str) -> model::Image {
+    model::Image {
+        uri: String::from(uri),
+        title: None,
+        link: None,
+        width: None,
+        height: None,
+        description: None,
+    }
+}
+
+fn people(
+    author: Option<AuthorRef<'_>>,
+    authors: Option<Vec<AuthorRef<'_>>>,
+) -> Result<Vec<model::Person>, Error> {
+    let mut people = Vec::new();
+    for author in author.into_iter().chain(authors.into_iter().flatten()) {
+        if let Some(name) = author.name()? {
+            people.push(model::Person {
+                name: String::from(name),
+                uri: author.url()?.map(String::from),
+                email: None,
+            });
+        }
+    }
+    Ok(people)
+}
+
+fn entry(item: &ItemRef<'_>) -> Result<model::Entry, Error> {
+    let mut entry = model::Entry {
+        id: item.id()?.map(String::from).unwrap_or_default(),
+        title: item.title()?.map(plain),
+        summary: item.summary()?.map(plain),
+        published: item.date_published()?.map(date_time).transpose()?,
+        updated: item.date_modified()?.map(date_time).transpose()?,
+        authors: people(item.author()?, item.authors()?)?,
+        language: item.language()?.map(String::from),
+        ..model::Entry::default()
+    };
+
+    if let Some(url) = item.url()? {
+        entry.links.push(link(url, "alternate"));
+    }
+    if let Some(external_url) = item.external_url()? {
+        entry.links.push(link(external_url, "related"));
+    }
+    for attachment in item.attachments()?.unwrap_or_default() {
+        if let Some(url) = attachment.url()? {
+            let mut link = link(url, "enclosure");
+            link.media_type = attachment.mime_type()?.map(String::from);
+            link.title = attachment.title()?.map(String::from);
+            link.length = attachment.size_in_bytes()?;
+            entry.links.push(link);
+        }
+    }
+
+    let content = match (item.content_html()?, item.content_text()?) {
+        (Some(html), _) => Some((html, MediaTypeBuf::new(names::TEXT, names::HTML))),
+        (None, Some(text)) => Some((text, MediaTypeBuf::new(names::TEXT, names::PLAIN))),
+        (None, None) => None,
+    };
+    entry.content = content.map(|(body, content_type)| model::Content {
+        body: Some(String::from(body)),
+        content_type,
+        length: Some(body.len() as u64),
+        src: None,
+    });
+
+    if let Some(uri) = item.image()? {
+        entry.media.push(model::MediaObject {
+            thumbnails: vec![model::MediaThumbnail {
+                image: image(uri),
+                time: None,
+            }],
+            ..model::MediaObject::default()
+        });
+    }
+
+    entry.categories = item
+        .tags()?
+        .unwrap_or_default()
+        .into_iter()
+        .map(|tag| model::Category {
+            term: String::from(tag),
+            scheme: None,
+            label: None,
+            subcategories: Vec::new(),
+        })
+        .collect();
+
+    Ok(entry)
+}
+
+fn to_feed_rs(map: &Map<String, Value>) -> Result<model::Feed, Error> {
+    let feed = FeedRef::from(map);
+    let home_page_url = feed.home_page_url()?;
+    let feed_url = feed.feed_url()?;
+
+    let mut links = Vec::new();
+    if let Some(home_page_url) = home_page_url {
+        links.push(link(home_page_url, "alternate"));
+    }
+    if let Some(feed_url) = feed_url {
+        links.push(link(feed_url, "self"));
+    }
+    if let Some(next_url) = feed.next_url()? {
+        links.push(link(next_url, "next"));
+    }
+    for hub in feed.hubs()?.unwrap_or_default() {
+        if let Some(url) = hub.url()? {
+            links.push(link(url, "hub"));
+        }
+    }
+
+    Ok(model::Feed {
+        feed_type: model::FeedType::JSON,
+        id: String::from(feed_url.or(home_page_url).unwrap_or_default()),
+        title: feed.title()?.map(plain),
+        updated: None,
+        authors: people(feed.author()?, feed.authors()?)?,
+        description: feed.description()?.map(plain),
+        links,
+        categories: Vec::new(),
+        contributors: Vec::new(),
+        generator: None,
+        icon: feed.favicon()?.map(image),
+        language: feed.language()?.map(String::from),
+        logo: feed.icon()?.map(image),
+        published: None,
+        rating: None,
+        rights: None,
+        ttl: None,
+        entries: crate::item_refs(map)?
+            .iter()
+            .map(entry)
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+/// Returns the link with the relation or, for `alternate`, without a relation.
+fn find_link<'a>(links: &'a [model::Link], rel: &str) -> Option<&'a str> {
+    links
+        .iter()
+        .find(|link| match &link.rel {
+            Some(link_rel) => link_rel.eq_ignore_ascii_case(rel),
+            None => rel == "alternate",
+        })
+        .map(|link| link.href.as_str())
+}
+
+fn authors(people: &[model::Person]) -> Vec<Author> {
+    people
+        .iter()
+        .map(|person| {
+            let mut author = Author::new();
+            author.set_name(&person.name);
+            if let Some(uri) = &person.uri {
+                author.set_url(uri);
+            }
+            author
+        })
+        .collect()
+}
+
+fn item(entry: &model::Entry, feed_authors: &[model::Person]) -> Item {
+    let mut item = Item::new();
+    if !entry.id.is_empty() {
+        item.set_id(&entry.id);
+    }
+    if let Some(url) = find_link(&entry.links, "alternate") {
+        item.set_url(url);
+    }
+    if let Some(external_url) = find_link(&entry.links, "related") {
+        item.set_external_url(external_url);
+    }
+    if let Some(title) = &entry.title {
+        item.set_title(plain_text(title));
+    }
+    if let Some(content) = &entry.content {
+        if let Some(body) = &content.body {
+            if is_html(&content.content_type) {
+                item.set_content_html(body);
+            } else {
+                item.set_content_text(body);
+            }
+        }
+    }
+    if let Some(summary) = &entry.summary {
+        item.set_summary(plain_text(summary));
+    }
+    if let Some(image) = entry
+        .media
+        .iter()
+        .flat_map(|media| &media.thumbnails)
+        .next()
+    {
+        item.set_image(&image.image.uri);
+    }
+    if let Some(published) = &entry.published {
+        item.set_date_published(date_string(published));
+    }
+    if let Some(updated) = &entry.updated {
+        item.set_date_modified(date_string(updated));
+    }
+    if !entry.authors.is_empty() && entry.authors != feed_authors {
+        item.set_authors(authors(&entry.authors));
+    }
+    if !entry.categories.is_empty() {
+        item.set_tags(
+            entry
+                .categories
+                .iter()
+                .map(|category| category.term.clone()),
+        );
+    }
+    if let Some(language) = &entry.language {
+        item.set_language(language);
+    }
+
+    let mut attachments: Vec<Attachment> = Vec::new();
+    let enclosures = entry
+        .links
+        .iter()
+        .filter(|link| {
+            link.rel
+                .as_deref()
+                .map_or(false, |rel| rel.eq_ignore_ascii_case("enclosure"))
+        })
+        .map(|link| {
+            (
+                link.href.as_str(),
+                link.media_type.as_deref(),
+                link.length,
+                link.title.as_deref(),
+            )
+        });
+    let media = entry
+        .media
+        .iter()
+        .flat_map(|media| &media.content)
+        .filter_map(|content| {
+            content.url.as_ref().map(|url| {
+                (
+                    url.as_str(),
+                    content.content_type.as_ref().map(MediaTypeBuf::as_str),
+                    content.size,
+                    None,
+                )
+            })
+        });
+    for (url, mime_type, size, title) in enclosures.chain(media) {
+        if attachments
+            .iter()
+            .any(|attachment| attachment.url().ok().flatten() == Some(url))
+        {
+            continue;
+        }
+        let mut attachment = Attachment::new();
+        attachment.set_url(url);
+        attachment.set_mime_type(mime_type.unwrap_or("application/octet-stream"));
+        if let Some(title) = title {
+            attachment.set_title(title);
+        }
+        if let Some(size) = size {
+            attachment.set_size_in_bytes::<()>(size);
+        }
+        attachments.push(attachment);
+    }
+    if !attachments.is_empty() {
+        item.set_attachments(attachments);
+    }
+
+    item
+}
+
+/// Converts a `feed-rs` feed into a version 1.1 JSON Feed.
+///
+/// See the [module documentation](self) for the mapping.
+#[must_use]
+pub fn from_feed_rs(feed: &model::Feed) -> Feed {
+    let mut json_feed = Feed::new();
+    json_feed.set_version(Version::Version1_1);
+    if let Some(title) = &feed.title {
+        json_feed.set_title(plain_text(title));
+    }
+    if let Some(home_page_url) = find_link(&feed.links, "alternate") {
+        json_feed.set_home_page_url(home_page_url);
+    }
+    if let Some(feed_url) = find_link(&feed.links, "self") {
+        json_feed.set_feed_url(feed_url);
+    }
+    if let Some(next_url) = find_link(&feed.links, "next") {
+        json_feed.set_next_url(next_url);
+    }
+    if let Some(description) = &feed.description {
+        json_feed.set_description(plain_text(description));
+    }
+    if let Some(logo) = &feed.logo {
+        json_feed.set_icon(&logo.uri);
+    }
+    if let Some(icon) = &feed.icon {
+        json_feed.set_favicon(&icon.uri);
+    }
+    if !feed.authors.is_empty() {
+        json_feed.set_authors(authors(&feed.authors));
+    }
+    if let Some(language) = &feed.language {
+        json_feed.set_language(language);
+    }
+    let hubs = feed
+        .links
+        .iter()
+        .filter(|link| {
+            link.rel
+                .as_deref()
+                .map_or(false, |rel| rel.eq_ignore_ascii_case("hub"))
+        })
+        .map(|link| {
+            let mut hub = Hub::new();
+            hub.set_hub_type("WebSub");
+            hub.set_url(&link.href);
+            hub
+        })
+        .collect::<Vec<_>>();
+    if !hubs.is_empty() {
+        json_feed.set_hubs(hubs);
+    }
+    json_feed.set_items(
+        feed.entries
+            .iter()
+            .map(|entry| item(entry, &feed.authors))
+            .collect::<Vec<_>>(),
+    );
+    json_feed
+}
+
+impl From<&model::Feed> for Feed {
+    fn from(feed: &model::Feed) -> Self {
+        from_feed_rs(feed)
+    }
+}
+
+impl TryFrom<&Feed> for model::Feed {
+    type Error = Error;
+
+    fn try_from(feed: &Feed) -> Result<Self, Self::Error> {
+        feed.to_feed_rs()
+    }
+}
+
+macro_rules! feed_rs_methods {
+    () => {
+        /// Converts the feed into a `feed-rs` feed.
+        ///
+        /// See the [module documentation](crate::feed_rs) for the mapping.
+        ///
+        /// # Errors
+        ///
+        /// If the feed's properties are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned. If an item's date is not a valid date, then
+        /// `Error::InvalidDate` is returned.
+        pub fn to_feed_rs(&self) -> Result<model::Feed, Error> {
+            to_feed_rs(self.as_map())
+        }
+    };
+}
+
+impl Feed {
+    feed_rs_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    feed_rs_methods!();
+}
+
+impl<'a> FeedRef<'a> {
+    feed_rs_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "version": "https://jsonfeed.org/version/1.1",
+            "title": "Example",
+            "home_page_url": "https://example.org/",
+            "feed_url": "https://example.org/feed.json",
+            "next_url": "https://example.org/feed.json?page=2",
+            "icon": "https://example.org/icon.png",
+            "favicon": "https://example.org/favicon.ico",
+            "authors": [{ "name": "Jane", "url": "https://example.org/jane" }],
+            "language": "en",
+            "hubs": [{ "type": "WebSub", "url": "https://hub.example.org/" }],
+            "items": [{
+                "id": "1",
+                "url": "https://example.org/1",
+                "external_url": "https://example.com/a",
+                "title": "First",
+                "content_text": "Hello",
+                "summary": "Greeting",
+                "image": "https://example.org/1.png",
+                "date_published": "2021-06-01T12:00:00.5-04:00",
+                "tags": ["a", "b"],
+                "attachments": [{
+                    "url": "https://example.org/1.mp3",
+                    "mime_type": "audio/mpeg",
+                    "size_in_bytes": 100
+                }]
+            }, {
+                "id": "2",
+                "content_html": "<p>Bye</p>",
+                "authors": [{ "name": "Joe" }]
+            }]
+        }))?;
+
+        let model = feed.to_feed_rs()?;
+        assert_eq!(model.id, "https://example.org/feed.json");
+        assert_eq!(model.entries.len(), 2);
+
+        let round_trip = Feed::from(&model);
+        let mut expected = feed.clone();
+        let items = expected.items_mut()?.unwrap();
+        for mut item in items {
+            if item.id()? == Some("1") {
+                item.set_date_published("2021-06-01T16:00:00.5Z");
+            }
+        }
+        assert_eq!(round_trip, expected);
+
+        let mut invalid = Item::new();
+        invalid.set_date_modified("yesterday");
+        let mut feed = Feed::new();
+        feed.set_items(vec![invalid]);
+        assert!(matches!(feed.to_feed_rs(), Err(Error::InvalidDate)));
+        Ok(())
+    }
+
+    #[test]
+    fn atom() -> Result<(), Error> {
+        let atom = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title type="html">A &lt;b&gt;bold&lt;/b&gt; feed</title>
+  <link href="https://example.org/"/>
+  <link rel="self" href="https://example.org/atom.xml"/>
+  <id>urn:uuid:60a76c80-d399-11d9-b93C-0003939e0af6</id>
+  <updated>2003-12-13T18:30:02Z</updated>
+  <author><name>John Doe</name></author>
+  <entry>
+    <title>Atom-Powered Robots Run Amok</title>
+    <link href="https://example.org/2003/12/13/atom03"/>
+    <link rel="enclosure" type="audio/mpeg" length="1337" href="https://example.org/audio.mp3"/>
+    <id>urn:uuid:1225c695-cfb8-4ebb-aaaa-80da344efa6a</id>
+    <updated>2003-12-13T18:30:02Z</updated>
+    <content type="html">&lt;p&gt;Some text.&lt;/p&gt;</content>
+  </entry>
+</feed>"#;
+        let model = ::feed_rs::parser::parse(atom.as_bytes()).unwrap();
+        let feed = Feed::from(&model);
+
+        assert_eq!(feed.title()?, Some("A bold feed"));
+        assert_eq!(feed.home_page_url()?, Some("https://example.org/"));
+        assert_eq!(feed.feed_url()?, Some("https://example.org/atom.xml"));
+        let items = feed.items()?.unwrap();
+        let item = &items[0];
+        assert_eq!(
+            item.id()?,
+            Some("urn:uuid:1225c695-cfb8-4ebb-aaaa-80da344efa6a")
+        );
+        assert_eq!(item.url()?, Some("https://example.org/2003/12/13/atom03"));
+        assert_eq!(item.content_html()?, Some("<p>Some text.</p>"));
+        assert_eq!(item.date_modified()?, Some("2003-12-13T18:30:02Z"));
+        assert!(item.authors()?.is_none());
+        let attachments = item.attachments()?.unwrap();
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].mime_type()?, Some("audio/mpeg"));
+        assert_eq!(attachments[0].size_in_bytes()?, Some(1337));
+        assert!(feed.is_valid(&Version::Version1_1));
+        Ok(())
+    }
+}
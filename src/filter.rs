@@ -0,0 +1,411 @@
+//! Item filtering while a feed is decoded.
+//!
+//! Incremental pollers are often only interested in a few new items. The functions in this
+//! module call a predicate with an item's metadata as the item is decoded. Once the predicate
+//! rejects an item, the item's remaining members are skipped instead of being decoded into JSON
+//! values, and the item is not included in the returned `Feed`.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+use core::fmt;
+
+use serde::de::{
+    value::MapAccessDeserializer, Deserialize, DeserializeSeed, Deserializer, IgnoredAny,
+    MapAccess, SeqAccess, Visitor,
+};
+use serde_json::{Map, Value};
+
+use crate::{Error, Feed};
+
+/// The metadata of an item which is known while the item is decoded.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ItemMeta<'a> {
+    /// The item's `id` if it has been decoded.
+    pub id: Option<&'a str>,
+    /// The item's `date_published` if it has been decoded.
+    pub date_published: Option<&'a str>,
+    /// The item's `date_modified` if it has been decoded.
+    pub date_modified: Option<&'a str>,
+    /// True if the whole item has been decoded.
+    ///
+    /// If true, members which are `None` are not in the item.
+    pub complete: bool,
+}
+
+#[derive(Default)]
+struct OwnedMeta {
+    id: Option<String>,
+    date_published: Option<String>,
+    date_modified: Option<String>,
+}
+
+impl OwnedMeta {
+    fn slot(&mut self, key: &str) -> Option<&mut Option<String>> {
+        match key {
+            "id" => Some(&mut self.id),
+            "date_published" => Some(&mut self.date_published),
+            "date_modified" => Some(&mut self.date_modified),
+            _ => None,
+        }
+    }
+
+    fn as_meta(&self, complete: bool) -> ItemMeta<'_> {
+        ItemMeta {
+            id: self.id.as_deref(),
+            date_published: self.date_published.as_deref(),
+            date_modified: self.date_modified.as_deref(),
+            complete,
+        }
+    }
+}
+
+// Implements the scalar visitor methods for visitors whose value is either `Value` or
+// `Option<Value>`.
+macro_rules! forward_to_value {
+    () => {
+        fn visit_bool<E: serde::de::Error>(self, v: bool) -> Result<Self::Value, E> {
+            Ok(Self::Value::from(Value::Bool(v)))
+        }
+
+        fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<Self::Value, E> {
+            Ok(Self::Value::from(Value::from(v)))
+        }
+
+        fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            Ok(Self::Value::from(Value::from(v)))
+        }
+
+        fn visit_f64<E: serde::de::Error>(self, v: f64) -> Result<Self::Value, E> {
+            Ok(Self::Value::from(Value::from(v)))
+        }
+
+        fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            Ok(Self::Value::from(Value::String(String::from(v))))
+        }
+
+        fn visit_string<E: serde::de::Error>(self, v: String) -> Result<Self::Value, E> {
+            Ok(Self::Value::from(Value::String(v)))
+        }
+
+        fn visit_unit<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+            Ok(Self::Value::from(Value::Null))
+        }
+    };
+}
+
+struct ItemSeed<'p, P> {
+    predicate: &'p mut P,
+}
+
+impl<'de, 'p, P> DeserializeSeed<'de> for ItemSeed<'p, P>
+where
+    P: FnMut(&ItemMeta<'_>) -> bool,
+{
+    type Value = Option<Value>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, 'p, P> Visitor<'de> for ItemSeed<'p, P>
+where
+    P: FnMut(&ItemMeta<'_>) -> bool,
+{
+    type Value = Option<Value>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a JSON value")
+    }
+
+    forward_to_value!();
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut values = Vec::new();
+        while let Some(value) = seq.next_element()? {
+            values.push(value);
+        }
+        Ok(Some(Value::Array(values)))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut item = Map::new();
+        let mut meta = OwnedMeta::default();
+        while let Some(key) = map.next_key::<String>()? {
+            let value: Value = map.next_value()?;
+            if let (Some(slot), Value::String(s)) = (meta.slot(&key), &value) {
+                *slot = Some(s.clone());
+                if !(self.predicate)(&meta.as_meta(false)) {
+                    while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+                    return Ok(None);
+                }
+            }
+            item.insert(key, value);
+        }
+
+        if (self.predicate)(&meta.as_meta(true)) {
+            Ok(Some(Value::Object(item)))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+struct ItemsSeed<'p, P> {
+    predicate: &'p mut P,
+}
+
+impl<'de, 'p, P> DeserializeSeed<'de> for ItemsSeed<'p, P>
+where
+    P: FnMut(&ItemMeta<'_>) -> bool,
+{
+    type Value = Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, 'p, P> Visitor<'de> for ItemsSeed<'p, P>
+where
+    P: FnMut(&ItemMeta<'_>) -> bool,
+{
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a JSON value")
+    }
+
+    forward_to_value!();
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut items = Vec::new();
+        while let Some(item) = seq.next_element_seed(ItemSeed {
+            predicate: &mut *self.predicate,
+        })? {
+            items.extend(item);
+        }
+        Ok(Value::Array(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        Value::deserialize(MapAccessDeserializer::new(map))
+    }
+}
+
+struct FeedVisitor<P> {
+    predicate: P,
+}
+
+impl<'de, P> Visitor<'de> for FeedVisitor<P>
+where
+    P: FnMut(&ItemMeta<'_>) -> bool,
+{
+    type Value = Option<Map<String, Value>>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a JSON value")
+    }
+
+    fn visit_bool<E: serde::de::Error>(self, _: bool) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, _: i64) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, _: u64) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_f64<E: serde::de::Error>(self, _: f64) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_str<E: serde::de::Error>(self, _: &str) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_unit<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        while seq.next_element::<IgnoredAny>()?.is_some() {}
+        Ok(None)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(mut self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut feed = Map::new();
+        while let Some(key) = map.next_key::<String>()? {
+            let value = if key == "items" {
+                map.next_value_seed(ItemsSeed {
+                    predicate: &mut self.predicate,
+                })?
+            } else {
+                map.next_value()?
+            };
+            feed.insert(key, value);
+        }
+        Ok(Some(feed))
+    }
+}
+
+fn deserialize_filtered<'de, R, P>(
+    mut deserializer: serde_json::Deserializer<R>,
+    predicate: P,
+) -> Result<Feed, Error>
+where
+    R: serde_json::de::Read<'de>,
+    P: FnMut(&ItemMeta<'_>) -> bool,
+{
+    let map = (&mut deserializer)
+        .deserialize_any(FeedVisitor { predicate })
+        .and_then(|map| deserializer.end().map(|()| map));
+    match map {
+        Ok(Some(map)) => {
+            trace_event!(
+                items = map
+                    .get("items")
+                    .and_then(|items| items.as_array())
+                    .map(Vec::len),
+                "parsed filtered feed"
+            );
+            Ok(Feed::from(map))
+        }
+        Ok(None) => {
+            trace_event!("feed is not a JSON object");
+            Err(Error::UnexpectedType)
+        }
+        Err(error) => {
+            trace_event!(%error, "feed cannot be decoded");
+            Err(Error::SerdeJson(error))
+        }
+    }
+}
+
+/// Attempts to JSON decode a byte slice and return a `Feed` with only the items accepted by a
+/// predicate.
+///
+/// The predicate is called with the item's metadata each time the item's `id`,
+/// `date_published`, or `date_modified` is decoded and once more after the whole item is decoded.
+/// Members may be in any order, so the predicate should return `true` if the metadata it needs is
+/// not known yet and `ItemMeta::complete` is false. If the predicate returns `false`, the item's
+/// remaining members are skipped and the item is removed.
+///
+/// Items which are not JSON objects are kept.
+///
+/// # Errors
+///
+/// If the byte slice cannot be JSON decoded, then `Error::SerdeJson(serde_json::Error)` is returned.
+///
+/// If the decoded JSON value is not an Object, then `Error::UnexpectedType` is returned.
+///
+/// # Example
+///
+/// ```
+/// let json = br#"{
+///     "items": [
+///         { "id": "1", "date_published": "2021-01-01T00:00:00Z", "content_text": "Old" },
+///         { "id": "2", "date_published": "2021-06-01T00:00:00Z", "content_text": "New" }
+///     ]
+/// }"#;
+///
+/// // RFC 3339 dates in UTC can be compared as strings.
+/// let feed = json_feed_model::filter::from_slice_filtered(json, |meta| {
+///     meta.date_published
+///         .map_or(!meta.complete, |date| date >= "2021-03-01")
+/// })?;
+///
+/// let items = feed.items()?.unwrap();
+/// assert_eq!(items.len(), 1);
+/// assert_eq!(items[0].id()?, Some("2"));
+/// # Ok::<(), json_feed_model::Error>(())
+/// ```
+pub fn from_slice_filtered<P>(v: &[u8], predicate: P) -> Result<Feed, Error>
+where
+    P: FnMut(&ItemMeta<'_>) -> bool,
+{
+    let _span = trace_span!("parse_feed_filtered", bytes = v.len());
+    deserialize_filtered(serde_json::Deserializer::from_slice(v), predicate)
+}
+
+/// Attempts to JSON decode a `std::io::Read` and return a `Feed` with only the items accepted
+/// by a predicate.
+///
+/// See `from_slice_filtered` for how the predicate is called.
+///
+/// # Errors
+///
+/// If the data cannot be JSON decoded, then `Error::SerdeJson(serde_json::Error)` is returned.
+///
+/// If the decoded JSON value is not an Object, then `Error::UnexpectedType` is returned.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn from_reader_filtered<R, P>(reader: R, predicate: P) -> Result<Feed, Error>
+where
+    R: std::io::Read,
+    P: FnMut(&ItemMeta<'_>) -> bool,
+{
+    let _span = trace_span!("parse_feed_filtered");
+    deserialize_filtered(serde_json::Deserializer::from_reader(reader), predicate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn skips_rejected_items() -> Result<(), Error> {
+        let json = r#"{
+            "title": "Example",
+            "items": [
+                { "id": "1", "content_text": "Kept" },
+                { "id": "2", "content_text": "Skipped", "attachments": [{ "url": 1 }] },
+                { "content_text": "Late id", "id": "3" },
+                "not an item"
+            ]
+        }"#;
+        let mut calls = Vec::new();
+        let feed = from_reader_filtered(json.as_bytes(), |meta| {
+            calls.push((meta.id.map(String::from), meta.complete));
+            meta.id != Some("2") && meta.id != Some("3")
+        })?;
+
+        assert_eq!(
+            feed,
+            crate::from_value(serde_json::json!({
+                "title": "Example",
+                "items": [{ "id": "1", "content_text": "Kept" }, "not an item"]
+            }))?
+        );
+        assert_eq!(
+            calls,
+            vec![
+                (Some(String::from("1")), false),
+                (Some(String::from("1")), true),
+                (Some(String::from("2")), false),
+                (Some(String::from("3")), false),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn not_an_object() {
+        assert!(matches!(
+            from_slice_filtered(b"[]", |_| true),
+            Err(Error::UnexpectedType)
+        ));
+        assert!(matches!(
+            from_slice_filtered(b"{} {}", |_| true),
+            Err(Error::SerdeJson(_))
+        ));
+    }
+}
@@ -0,0 +1,199 @@
+//! Links between items in the same feed.
+//!
+//! Series posts link to the previous posts, and updates link to the posts they correct. The
+//! links can be used to show "previous post" or "referenced by" lists without fetching other
+//! feeds.
+//!
+//! ```
+//! let feed = json_feed_model::from_value(serde_json::json!({
+//!     "items": [
+//!         {
+//!             "id": "3",
+//!             "url": "https://example.org/posts/3",
+//!             "content_html": "<p>Corrects <a href=\"/posts/1#intro\">part 1</a>.</p>"
+//!         },
+//!         {
+//!             "id": "2",
+//!             "url": "https://example.org/posts/2",
+//!             "content_html": "<p>Continues <a href=\"1\">part 1</a>.</p>"
+//!         },
+//!         { "id": "1", "url": "https://example.org/posts/1", "content_text": "Part 1" }
+//!     ]
+//! }))?;
+//!
+//! let graph = feed.reference_graph()?;
+//! assert_eq!(graph.outgoing, vec![vec![2], vec![2], vec![]]);
+//! assert_eq!(graph.incoming, vec![vec![], vec![], vec![0, 1]]);
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{collections::BTreeMap, string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{Error, Feed, FeedMut, FeedRef};
+
+/// A directed graph of the items which link to other items in the same feed.
+///
+/// The items are identified by their index in the feed's `items`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ReferenceGraph {
+    /// For each item, the items it links to in the order the links first appear.
+    pub outgoing: Vec<Vec<usize>>,
+    /// For each item, the items which link to it in ascending order.
+    pub incoming: Vec<Vec<usize>>,
+}
+
+impl ReferenceGraph {
+    /// Returns the number of links between items.
+    #[must_use]
+    pub fn edge_count(&self) -> usize {
+        self.outgoing.iter().map(Vec::len).sum()
+    }
+
+    /// Returns the links as pairs of the linking item and the linked item.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.outgoing
+            .iter()
+            .enumerate()
+            .flat_map(|(from, targets)| targets.iter().map(move |to| (from, *to)))
+    }
+}
+
+/// Returns a key which is the same for URLs which likely refer to the same item.
+///
+/// Fragments, the case of the scheme and host, default ports, and trailing slashes are
+/// ignored.
+fn url_key(url: &str) -> Option<String> {
+    let mut key = crate::subscriptions::normalize_subscription_url(url)?;
+    if !key.contains('?') {
+        while key.ends_with('/') {
+            key.pop();
+        }
+    }
+    Some(key)
+}
+
+fn reference_graph(map: &Map<String, Value>) -> Result<ReferenceGraph, Error> {
+    let items = crate::item_refs(map)?;
+
+    let mut indexes = BTreeMap::new();
+    for (index, item) in items.iter().enumerate() {
+        if let Some(key) = item.url()?.and_then(url_key) {
+            // The first item with a URL is the target if items share a URL.
+            indexes.entry(key).or_insert(index);
+        }
+    }
+
+    let mut graph = ReferenceGraph {
+        outgoing: Vec::with_capacity(items.len()),
+        incoming: Vec::new(),
+    };
+    graph.incoming.resize(items.len(), Vec::new());
+    for (from, item) in items.iter().enumerate() {
+        let mut targets = Vec::new();
+        for link in item.extract_links()? {
+            let to = match url_key(&link).and_then(|key| indexes.get(&key)) {
+                Some(to) => *to,
+                None => continue,
+            };
+            if to != from && !targets.contains(&to) {
+                targets.push(to);
+                graph.incoming[to].push(from);
+            }
+        }
+        graph.outgoing.push(targets);
+    }
+    Ok(graph)
+}
+
+macro_rules! graph_methods {
+    () => {
+        /// Returns a graph of the items which link to other items in the feed.
+        ///
+        /// The links are found with `Item::extract_links` and matched against the items'
+        /// `url`s. Fragments, trailing slashes, and the case of the scheme and host are
+        /// ignored when matching. Links from an item to itself are skipped.
+        ///
+        /// # Errors
+        ///
+        /// If the items or an item's URL or content is not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned.
+        pub fn reference_graph(&self) -> Result<ReferenceGraph, Error> {
+            reference_graph(self.as_map())
+        }
+    };
+}
+
+impl Feed {
+    graph_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    graph_methods!();
+}
+
+impl<'a> FeedRef<'a> {
+    graph_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::{vec, vec::Vec};
+
+    #[test]
+    fn links() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "items": [
+                {
+                    "id": "1",
+                    "url": "https://example.org/1/",
+                    "content_text": "See https://EXAMPLE.org/2 and https://example.org/1 and https://other.example/2"
+                },
+                {
+                    "id": "2",
+                    "url": "https://example.org/2",
+                    "content_html": "<a href=\"/1\">1</a><a href=\"/3?page=2\">3</a><a href=\"/1/#top\">1</a>"
+                },
+                { "id": "3", "url": "https://example.org/3?page=2", "content_text": "Three" },
+                { "id": "4", "content_html": "<a href=\"https://example.org/3?page=2\">3</a>" }
+            ]
+        }))?;
+
+        let graph = feed.reference_graph()?;
+        assert_eq!(graph.outgoing, vec![vec![1], vec![0, 2], vec![], vec![2]]);
+        assert_eq!(graph.incoming, vec![vec![1], vec![0], vec![1, 3], vec![]]);
+        assert_eq!(graph.edge_count(), 4);
+        assert_eq!(
+            graph.edges().collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0), (1, 2), (3, 2)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn non_ascii_urls() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "items": [
+                { "id": "1", "url": "urn:😀", "content_html": "<a href=\"urn:😀\">1</a>" },
+                {
+                    "id": "2",
+                    "url": "https://ß.example/é/",
+                    "content_html": "<a href=\"urn:😀\">1</a><a href=\"/é\">2</a>"
+                },
+                { "id": "3", "content_html": "<a href=\"https://ß.example/é\">2</a>" }
+            ]
+        }))?;
+
+        let graph = feed.reference_graph()?;
+        assert_eq!(graph.outgoing, vec![vec![], vec![], vec![1]]);
+        assert_eq!(graph.incoming, vec![vec![], vec![2], vec![]]);
+        Ok(())
+    }
+}
@@ -0,0 +1,366 @@
+//! Feed health reports.
+//!
+//! A health report combines spec validation, lints, and other quality checks into a single
+//! score. Feed directories can use the score to rank submissions and the findings to tell
+//! publishers what to fix.
+//!
+//! ```
+//! let feed = json_feed_model::from_str(r#"{
+//!     "version": "https://jsonfeed.org/version/1.1",
+//!     "title": "My Blog",
+//!     "items": [{ "id": "1", "content_text": "Hi" }, { "id": "1", "content_text": "Again" }]
+//! }"#)?;
+//!
+//! let report = feed.health_report();
+//! assert!(report.score < 100);
+//! assert!(report
+//!     .findings
+//!     .iter()
+//!     .any(|finding| finding.category == json_feed_model::health::Category::DuplicateIds));
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{collections::BTreeMap, format, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{collections::BTreeMap, format, string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{Feed, FeedMut, FeedRef, Version};
+
+/// A kind of problem found by a health report.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub enum Category {
+    /// The feed does not comply with the JSON Feed spec.
+    Validity,
+    /// A lint was reported. See `Feed::lints`.
+    Lint,
+    /// The feed has not been updated recently.
+    Freshness,
+    /// Multiple items have the same `id`.
+    DuplicateIds,
+    /// A URL is not an absolute URL or contains whitespace.
+    InvalidUrl,
+    /// A property recommended by the spec is missing.
+    MissingRecommended,
+}
+
+impl Category {
+    /// Returns the number of points deducted from the score for a finding in the category.
+    #[must_use]
+    pub fn penalty(&self) -> u32 {
+        match self {
+            Category::Validity => 40,
+            Category::Freshness | Category::DuplicateIds => 10,
+            Category::InvalidUrl => 5,
+            Category::Lint => 3,
+            Category::MissingRecommended => 2,
+        }
+    }
+}
+
+/// A problem found by a health report.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Finding {
+    /// The kind of problem.
+    pub category: Category,
+    /// A [JSON Pointer][json_pointer] to the value which the finding applies to.
+    ///
+    /// [json_pointer]: https://tools.ietf.org/html/rfc6901
+    pub path: String,
+    /// A human readable description of the problem.
+    pub message: String,
+}
+
+/// The result of checking a feed's health.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct HealthReport {
+    /// A score from 0 to 100 where 100 is a feed without any findings.
+    pub score: u8,
+    /// The problems found.
+    pub findings: Vec<Finding>,
+}
+
+impl HealthReport {
+    /// Returns the findings in a category.
+    pub fn findings_in(&self, category: Category) -> impl Iterator<Item = &Finding> {
+        self.findings
+            .iter()
+            .filter(move |finding| finding.category == category)
+    }
+}
+
+/// Feeds which have not been updated for longer are reported as stale.
+#[cfg(feature = "std")]
+const STALE_AFTER: core::time::Duration = core::time::Duration::from_secs(365 * 24 * 60 * 60);
+
+const FEED_URL_KEYS: &[&str] = &["home_page_url", "feed_url", "next_url", "icon", "favicon"];
+const ITEM_URL_KEYS: &[&str] = &["url", "external_url", "image", "banner_image"];
+const AUTHOR_URL_KEYS: &[&str] = &["url", "avatar"];
+
+const FEED_RECOMMENDED_KEYS: &[&str] = &["home_page_url", "feed_url", "description", "icon"];
+const ITEM_RECOMMENDED_KEYS: &[&str] = &["url", "date_published"];
+
+struct Findings(Vec<Finding>);
+
+impl Findings {
+    fn push<P, M>(&mut self, category: Category, path: P, message: M)
+    where
+        P: Into<String>,
+        M: Into<String>,
+    {
+        self.0.push(Finding {
+            category,
+            path: path.into(),
+            message: message.into(),
+        });
+    }
+
+    fn check_urls(&mut self, map: &Map<String, Value>, keys: &[&str], path: &str) {
+        for key in keys {
+            if let Some(Value::String(url)) = map.get(*key) {
+                if !crate::url::is_absolute(url) || url.chars().any(char::is_whitespace) {
+                    self.push(
+                        Category::InvalidUrl,
+                        format!("{}/{}", path, key),
+                        format!("{} is not a valid absolute URL", key),
+                    );
+                }
+            }
+        }
+    }
+
+    fn check_author_urls(&mut self, map: &Map<String, Value>, path: &str) {
+        if let Some(Value::Object(author)) = map.get("author") {
+            self.check_urls(author, AUTHOR_URL_KEYS, &format!("{}/author", path));
+        }
+        if let Some(Value::Array(authors)) = map.get("authors") {
+            for (index, author) in authors.iter().enumerate() {
+                if let Value::Object(author) = author {
+                    let path = format!("{}/authors/{}", path, index);
+                    self.check_urls(author, AUTHOR_URL_KEYS, &path);
+                }
+            }
+        }
+    }
+
+    fn check_recommended(&mut self, map: &Map<String, Value>, keys: &[&str], path: &str) {
+        for key in keys {
+            if !map.contains_key(*key) {
+                self.push(
+                    Category::MissingRecommended,
+                    format!("{}/{}", path, key),
+                    format!("{} is recommended", key),
+                );
+            }
+        }
+    }
+}
+
+fn health_report(map: &Map<String, Value>) -> HealthReport {
+    let feed = FeedRef::from(map);
+    let mut findings = Findings(Vec::new());
+
+    match feed.version() {
+        Ok(Some(version)) => {
+            let version = Version::from(version);
+            if let Version::Unknown(version) = version {
+                findings.push(
+                    Category::Validity,
+                    "/version",
+                    format!("unknown version: {}", version),
+                );
+            } else if !feed.is_valid(&version) {
+                findings.push(Category::Validity, "", "feed is not valid");
+            }
+        }
+        Ok(None) | Err(_) => findings.push(Category::Validity, "/version", "version is missing"),
+    }
+
+    for lint in feed.lints() {
+        findings.push(Category::Lint, lint.path, lint.message);
+    }
+
+    #[cfg(feature = "std")]
+    if !feed.is_expired().unwrap_or(false)
+        && feed
+            .is_stale(STALE_AFTER, std::time::SystemTime::now())
+            .unwrap_or(false)
+    {
+        findings.push(
+            Category::Freshness,
+            "/items",
+            "no item has been updated in over a year",
+        );
+    }
+
+    findings.check_urls(map, FEED_URL_KEYS, "");
+    findings.check_author_urls(map, "");
+    if let Some(Value::Array(hubs)) = map.get("hubs") {
+        for (index, hub) in hubs.iter().enumerate() {
+            if let Value::Object(hub) = hub {
+                findings.check_urls(hub, &["url"], &format!("/hubs/{}", index));
+            }
+        }
+    }
+    findings.check_recommended(map, FEED_RECOMMENDED_KEYS, "");
+
+    let mut first_index_by_id = BTreeMap::new();
+    if let Some(Value::Array(items)) = map.get("items") {
+        for (index, item) in items.iter().enumerate() {
+            let item = match item {
+                Value::Object(item) => item,
+                _ => continue,
+            };
+            let path = format!("/items/{}", index);
+
+            if let Some(Value::String(id)) = item.get("id") {
+                if let Some(first_index) = first_index_by_id.insert(id.as_str(), index) {
+                    first_index_by_id.insert(id.as_str(), first_index);
+                    findings.push(
+                        Category::DuplicateIds,
+                        format!("{}/id", path),
+                        format!("id is the same as /items/{}/id", first_index),
+                    );
+                }
+            }
+
+            findings.check_urls(item, ITEM_URL_KEYS, &path);
+            findings.check_author_urls(item, &path);
+            if let Some(Value::Array(attachments)) = item.get("attachments") {
+                for (attachment_index, attachment) in attachments.iter().enumerate() {
+                    if let Value::Object(attachment) = attachment {
+                        let path = format!("{}/attachments/{}", path, attachment_index);
+                        findings.check_urls(attachment, &["url"], &path);
+                    }
+                }
+            }
+            findings.check_recommended(item, ITEM_RECOMMENDED_KEYS, &path);
+        }
+    }
+
+    let penalty = findings
+        .0
+        .iter()
+        .map(|finding| finding.category.penalty())
+        .sum::<u32>();
+    let score = 100u32.saturating_sub(penalty);
+    HealthReport {
+        score: u8::try_from(score).unwrap_or(0),
+        findings: findings.0,
+    }
+}
+
+macro_rules! health_methods {
+    () => {
+        /// Returns a report of the feed's health.
+        ///
+        /// The report includes spec validation, lints, duplicate item ids, URLs which are not
+        /// absolute, and missing recommended properties. With the `std` feature, a feed which
+        /// has not been updated in over a year is reported as well. Each finding deducts its
+        /// category's penalty from a score of 100.
+        #[must_use]
+        pub fn health_report(&self) -> HealthReport {
+            health_report(self.as_map())
+        }
+    };
+}
+
+impl Feed {
+    health_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    health_methods!();
+}
+
+impl<'a> FeedRef<'a> {
+    health_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::{vec, vec::Vec};
+
+    #[test]
+    fn healthy_feed() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "version": "https://jsonfeed.org/version/1.1",
+            "title": "Blog",
+            "home_page_url": "https://example.org/",
+            "feed_url": "https://example.org/feed.json",
+            "description": "A blog",
+            "icon": "https://example.org/icon.png",
+            "items": [{
+                "id": "1",
+                "url": "https://example.org/1",
+                "content_text": "Hi",
+                "date_published": "9000-01-01T00:00:00Z"
+            }]
+        }))?;
+
+        let report = feed.health_report();
+        // Future dated items are not reported because the report does not depend on the time.
+        assert!(report.findings.is_empty());
+        assert_eq!(report.score, 100);
+
+        Ok(())
+    }
+
+    #[test]
+    fn unhealthy_feed() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "version": "https://jsonfeed.org/version/1.1",
+            "title": "Blog",
+            "home_page_url": "example.org",
+            "items": [
+                { "id": "1", "content_text": "Hi", "url": "https://example.org/a b" },
+                { "id": "1", "content_text": "Hi" }
+            ]
+        }))?;
+
+        let report = feed.health_report();
+        let paths = |category| {
+            report
+                .findings_in(category)
+                .map(|finding| finding.path.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(paths(Category::Validity), Vec::<&str>::new());
+        assert_eq!(paths(Category::DuplicateIds), vec!["/items/1/id"]);
+        assert_eq!(
+            paths(Category::InvalidUrl),
+            vec!["/home_page_url", "/items/0/url"]
+        );
+        assert_eq!(
+            paths(Category::MissingRecommended),
+            vec![
+                "/feed_url",
+                "/description",
+                "/icon",
+                "/items/0/date_published",
+                "/items/1/url",
+                "/items/1/date_published"
+            ]
+        );
+        assert_eq!(report.score, 100 - 10 - 5 * 2 - 2 * 6);
+
+        let report = Feed::new().health_report();
+        assert_eq!(
+            report
+                .findings_in(Category::Validity)
+                .map(|finding| finding.path.as_str())
+                .collect::<Vec<_>>(),
+            vec!["/version"]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,477 @@
+//! HTML helpers.
+//!
+//! The crate does not depend on an HTML parsing library. The helpers are lenient and only
+//! intended for deriving plain text and simple values from `content_html`.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+use core::ops::Range;
+
+/// Tags whose content is not displayed text.
+const SKIPPED_CONTENT_TAGS: &[&str] = &["script", "style", "template"];
+
+/// Tags which do not have content or a closing tag.
+const VOID_TAGS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Tags which separate blocks of text.
+const BLOCK_TAGS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "br",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "figcaption",
+    "figure",
+    "footer",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "li",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "td",
+    "th",
+    "tr",
+    "ul",
+];
+
+/// A tag found in an HTML string.
+pub(crate) struct Tag<'a> {
+    /// The name as written in the HTML. Use `is` to compare names case-insensitively.
+    pub(crate) name: &'a str,
+    pub(crate) is_closing: bool,
+    /// The text between the name and the closing `>`.
+    pub(crate) attrs: &'a str,
+}
+
+impl<'a> Tag<'a> {
+    pub(crate) fn is(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name)
+    }
+
+    /// Returns the raw value of an attribute.
+    pub(crate) fn attr(&self, name: &str) -> Option<&'a str> {
+        self.attr_span(name).map(|(_, value)| value)
+    }
+
+    /// Returns the byte range of a whole attribute in `attrs` and its raw value.
+    pub(crate) fn attr_span(&self, name: &str) -> Option<(Range<usize>, &'a str)> {
+        let bytes = self.attrs.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            while i < bytes.len() && (bytes[i].is_ascii_whitespace() || bytes[i] == b'/') {
+                i += 1;
+            }
+            let name_start = i;
+            while i < bytes.len()
+                && !bytes[i].is_ascii_whitespace()
+                && bytes[i] != b'='
+                && bytes[i] != b'/'
+            {
+                i += 1;
+            }
+            let attr_name = &self.attrs[name_start..i];
+            let mut attr_end = i;
+            while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+                i += 1;
+            }
+            let mut value = "";
+            if i < bytes.len() && bytes[i] == b'=' {
+                i += 1;
+                while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+                    i += 1;
+                }
+                if i < bytes.len() && (bytes[i] == b'"' || bytes[i] == b'\'') {
+                    let quote = bytes[i];
+                    let value_start = i + 1;
+                    i = value_start;
+                    while i < bytes.len() && bytes[i] != quote {
+                        i += 1;
+                    }
+                    value = &self.attrs[value_start..i];
+                    i += 1;
+                } else {
+                    let value_start = i;
+                    while i < bytes.len() && !bytes[i].is_ascii_whitespace() {
+                        i += 1;
+                    }
+                    value = &self.attrs[value_start..i];
+                }
+                attr_end = i.min(bytes.len());
+            }
+            if !attr_name.is_empty() && attr_name.eq_ignore_ascii_case(name) {
+                return Some((name_start..attr_end, value));
+            }
+            if attr_name.is_empty() && i == name_start {
+                i += 1;
+            }
+        }
+        None
+    }
+}
+
+/// A piece of an HTML string.
+pub(crate) enum Token<'a> {
+    /// Text which may contain character references.
+    Text(&'a str),
+    Tag(Tag<'a>),
+    /// Comments, doctypes, and processing instructions.
+    Other,
+}
+
+/// Splits an HTML string into text and tags.
+pub(crate) struct Tokens<'a> {
+    html: &'a str,
+    pos: usize,
+}
+
+pub(crate) fn tokens(html: &str) -> Tokens<'_> {
+    Tokens { html, pos: 0 }
+}
+
+impl<'a> Tokens<'a> {
+    /// Returns the byte offset of the next token.
+    pub(crate) fn offset(&self) -> usize {
+        self.pos
+    }
+
+    /// Skips to the end of the element's closing tag (used for raw text elements).
+    fn skip_to_closing(&mut self, name: &str) {
+        let rest = &self.html[self.pos..];
+        let mut search = 0;
+        while let Some(offset) = rest[search..].find("</") {
+            let start = search + offset + 2;
+            let candidate = &rest[start..];
+            if candidate.len() >= name.len()
+                && candidate.as_bytes()[..name.len()].eq_ignore_ascii_case(name.as_bytes())
+            {
+                let end = candidate.find('>').map_or(rest.len(), |i| start + i + 1);
+                self.pos += end;
+                return;
+            }
+            search = start;
+        }
+        self.pos = self.html.len();
+    }
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        let html = self.html;
+        if self.pos >= html.len() {
+            return None;
+        }
+        let rest = &html[self.pos..];
+
+        if !rest.starts_with('<') {
+            let end = rest.find('<').unwrap_or(rest.len());
+            self.pos += end;
+            return Some(Token::Text(&rest[..end]));
+        }
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            let end = comment.find("-->").map_or(rest.len(), |i| i + 7);
+            self.pos += end;
+            return Some(Token::Other);
+        }
+
+        let after = &rest[1..];
+        let is_closing = after.starts_with('/');
+        let name_start = if is_closing { 2 } else { 1 };
+        let name_len = rest[name_start..]
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '-' && c != ':')
+            .unwrap_or(rest.len() - name_start);
+
+        if name_len == 0 {
+            if after.starts_with('!') || after.starts_with('?') {
+                let end = rest.find('>').map_or(rest.len(), |i| i + 1);
+                self.pos += end;
+                return Some(Token::Other);
+            }
+            // A lone `<` is text.
+            let end = rest[1..].find('<').map_or(rest.len(), |i| i + 1);
+            self.pos += end;
+            return Some(Token::Text(&rest[..end]));
+        }
+
+        // Find the end of the tag while skipping quoted attribute values.
+        let bytes = rest.as_bytes();
+        let mut i = name_start + name_len;
+        let mut quote = None;
+        while i < bytes.len() {
+            match (quote, bytes[i]) {
+                (None, b'"') | (None, b'\'') => quote = Some(bytes[i]),
+                (Some(q), b) if q == b => quote = None,
+                (None, b'>') => break,
+                _ => {}
+            }
+            i += 1;
+        }
+        let attrs_end = i.min(bytes.len());
+        self.pos += (i + 1).min(bytes.len());
+
+        let tag = Tag {
+            name: &rest[name_start..name_start + name_len],
+            is_closing,
+            attrs: rest[name_start + name_len..attrs_end].trim_end_matches('/'),
+        };
+        if !tag.is_closing {
+            if let Some(name) = SKIPPED_CONTENT_TAGS.iter().find(|name| tag.is(name)) {
+                self.skip_to_closing(name);
+            }
+        }
+        Some(Token::Tag(tag))
+    }
+}
+
+/// Appends text with character references decoded.
+pub(crate) fn decode_entities_into(text: &str, out: &mut String) {
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let end = match rest[1..].find(|c: char| c == ';' || c == '&' || c.is_whitespace()) {
+            Some(i) if rest.as_bytes()[i + 1] == b';' && i <= 10 => i + 1,
+            _ => {
+                out.push('&');
+                rest = &rest[1..];
+                continue;
+            }
+        };
+        let entity = &rest[1..end];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some('\u{a0}'),
+            "ndash" => Some('\u{2013}'),
+            "mdash" => Some('\u{2014}'),
+            "lsquo" => Some('\u{2018}'),
+            "rsquo" => Some('\u{2019}'),
+            "ldquo" => Some('\u{201c}'),
+            "rdquo" => Some('\u{201d}'),
+            "hellip" => Some('\u{2026}'),
+            "copy" => Some('\u{a9}'),
+            "reg" => Some('\u{ae}'),
+            "trade" => Some('\u{2122}'),
+            _ => {
+                let code = if let Some(hex) = entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                {
+                    u32::from_str_radix(hex, 16).ok()
+                } else if let Some(dec) = entity.strip_prefix('#') {
+                    dec.parse::<u32>().ok()
+                } else {
+                    None
+                };
+                code.and_then(core::char::from_u32)
+            }
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+}
+
+/// Returns the text content of an HTML string.
+///
+/// Tags are removed, the content of `script` and `style` elements is dropped, character
+/// references are decoded, and block level elements are separated by whitespace.
+pub(crate) fn strip_tags(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    for token in tokens(html) {
+        match token {
+            Token::Text(text) => decode_entities_into(text, &mut out),
+            Token::Tag(tag) => {
+                if BLOCK_TAGS.iter().any(|name| tag.is(name)) && !out.ends_with('\n') {
+                    out.push('\n');
+                }
+            }
+            Token::Other => {}
+        }
+    }
+    out
+}
+
+/// Returns the largest char boundary in `text` which is at most `max`.
+fn floor_char_boundary(text: &str, max: usize) -> usize {
+    if max >= text.len() {
+        return text.len();
+    }
+    (0..=max)
+        .rev()
+        .find(|i| text.is_char_boundary(*i))
+        .unwrap_or(0)
+}
+
+/// Returns the length of the text cut at a word boundary to at most `max` bytes.
+///
+/// If the text cannot be cut at whitespace, it is cut at a char boundary.
+pub(crate) fn truncate_text_len(text: &str, max: usize) -> usize {
+    if text.len() <= max {
+        return text.len();
+    }
+    let end = floor_char_boundary(text, max);
+    match text[..end].rfind(char::is_whitespace) {
+        Some(space) if space > 0 => text[..space].trim_end().len(),
+        _ => end,
+    }
+}
+
+/// Returns the HTML cut to at most `max` bytes of the original HTML followed by an ellipsis
+/// and closing tags for the elements which are still open.
+///
+/// The HTML is only cut between tags, at word boundaries in text, and outside of character
+/// references. The closing tags are not counted in `max`.
+pub(crate) fn truncate_html(html: &str, max: usize) -> String {
+    if html.len() <= max {
+        return String::from(html);
+    }
+
+    let mut tokens = tokens(html);
+    let mut open: Vec<&str> = Vec::new();
+    let mut end = 0;
+    loop {
+        let start = tokens.offset();
+        let token = match tokens.next() {
+            Some(token) => token,
+            None => break,
+        };
+        if tokens.offset() > max {
+            if let Token::Text(text) = token {
+                let mut len = truncate_text_len(text, max - start);
+                // Do not cut a character reference in half.
+                if let Some(amp) = text[..len].rfind('&') {
+                    if !text[amp..len].contains(';') {
+                        len = truncate_text_len(text, amp);
+                    }
+                }
+                end = start + len;
+            }
+            break;
+        }
+        if let Token::Tag(tag) = &token {
+            if tag.is_closing {
+                if let Some(index) = open.iter().rposition(|name| tag.is(name)) {
+                    open.truncate(index);
+                }
+            } else if !VOID_TAGS.iter().any(|name| tag.is(name))
+                && !SKIPPED_CONTENT_TAGS.iter().any(|name| tag.is(name))
+            {
+                open.push(tag.name);
+            }
+        }
+        end = tokens.offset();
+    }
+
+    let mut truncated = String::with_capacity(end + 3 + open.len() * 8);
+    truncated.push_str(&html[..end]);
+    truncated.push('\u{2026}');
+    for name in open.iter().rev() {
+        truncated.push_str("</");
+        truncated.push_str(name);
+        truncated.push('>');
+    }
+    truncated
+}
+
+/// Escapes text for use in HTML content and attribute values.
+pub(crate) fn escape_into(text: &str, out: &mut String) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip() {
+        assert_eq!(
+            strip_tags("<p>Hello <b>world</b> &amp; friends</p><p>Bye&#33;</p>"),
+            "\nHello world & friends\nBye!\n"
+        );
+        assert_eq!(
+            strip_tags("a<script>if (a < b) {}</script>b<!-- c --><STYLE>p {}</STYLE>"),
+            "ab"
+        );
+        assert_eq!(strip_tags("1 < 2 &unknown; &"), "1 < 2 &unknown; &");
+    }
+
+    #[test]
+    fn truncate() {
+        let html = "<p>Hello <b>brave new</b> world &amp; friends</p><p>Bye</p>";
+        assert_eq!(truncate_html(html, 200), html);
+        assert_eq!(truncate_html(html, 20), "<p>Hello <b>brave\u{2026}</b></p>");
+        assert_eq!(
+            truncate_html(html, 37),
+            "<p>Hello <b>brave new</b> world\u{2026}</p>"
+        );
+        assert_eq!(truncate_html(html, 5), "<p>He\u{2026}</p>");
+        assert_eq!(truncate_html(html, 2), "\u{2026}");
+    }
+
+    #[test]
+    fn escape() {
+        let mut out = String::new();
+        escape_into(r#"<a href="x">Tom & Jerry's</a>"#, &mut out);
+        assert_eq!(
+            out,
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn attributes() {
+        let tag = tokens(r#"<a class=x HREF="https://example.org/?a=1&amp;b=2" data-y='>'>"#)
+            .find_map(|t| match t {
+                Token::Tag(tag) => Some(tag),
+                _ => None,
+            })
+            .unwrap();
+        assert!(tag.is("A"));
+        assert_eq!(tag.attr("href"), Some("https://example.org/?a=1&amp;b=2"));
+        assert_eq!(tag.attr("class"), Some("x"));
+        assert_eq!(tag.attr("data-y"), Some(">"));
+        assert_eq!(tag.attr("src"), None);
+    }
+}
@@ -0,0 +1,152 @@
+//! HTTP responses for serving feeds.
+//!
+//! [`Feed::into_http_response_parts`] returns the status, headers, and body of a response as
+//! plain types, so they can be converted into any web framework's response type:
+//!
+//! ```
+//! use json_feed_model::{Feed, Item, SerializeOptions};
+//!
+//! let mut item = Item::new();
+//! item.set_id("1");
+//! item.set_content_text("Hello");
+//! item.set_date_published("2021-06-01T12:00:00+02:00");
+//!
+//! let mut feed = Feed::new();
+//! feed.set_items(vec![item]);
+//! let etag = feed.etag()?;
+//!
+//! let (status, headers, body) = feed.into_http_response_parts(SerializeOptions::new())?;
+//! assert_eq!(status, 200);
+//! assert_eq!(
+//!     headers,
+//!     vec![
+//!         ("content-type", String::from("application/feed+json; charset=utf-8")),
+//!         ("etag", etag),
+//!         ("last-modified", String::from("Tue, 01 Jun 2021 10:00:00 GMT")),
+//!     ]
+//! );
+//! assert!(body.starts_with(b"{\"items\":"));
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{format, string::String, vec, vec::Vec};
+#[cfg(feature = "std")]
+use std::{format, string::String, vec, vec::Vec};
+
+use crate::{Error, Feed, SerializeOptions};
+
+/// The `Content-Type` of a JSON Feed.
+pub const CONTENT_TYPE: &str = "application/feed+json; charset=utf-8";
+
+/// The status code, headers, and body of an HTTP response.
+///
+/// Header names are lowercase so they can be used with HTTP/2 and with header types which
+/// require lowercase names (e.g. `http::HeaderName::from_static`).
+pub type HttpResponseParts = (u16, Vec<(&'static str, String)>, Vec<u8>);
+
+impl Feed {
+    /// Serializes the feed into the parts of a `200 OK` HTTP response.
+    ///
+    /// The headers are:
+    ///
+    /// * `content-type`: [`CONTENT_TYPE`]
+    /// * `etag`: a strong entity tag of the body, which is the same as [`Feed::etag`] for
+    ///   compact JSON
+    /// * `last-modified`: the [`Feed::last_updated`] date, if any item has a date
+    ///
+    /// # Errors
+    ///
+    /// If the feed cannot be serialized, then `Error::SerdeJson(serde_json::Error)` is
+    /// returned. If the items or their dates are not the expected JSON type, then
+    /// `Error::UnexpectedType` is returned. If a date is not a valid date, then
+    /// `Error::InvalidDate` is returned.
+    pub fn into_http_response_parts(
+        self,
+        options: SerializeOptions,
+    ) -> Result<HttpResponseParts, Error> {
+        let last_modified = self.last_updated()?;
+        let map = crate::canonical::SortedMap(self.as_map());
+        let body = if options.pretty {
+            serde_json::to_vec_pretty(&map)?
+        } else {
+            serde_json::to_vec(&map)?
+        };
+
+        let mut headers = vec![
+            ("content-type", String::from(CONTENT_TYPE)),
+            (
+                "etag",
+                format!("\"{:016x}\"", crate::canonical::fnv1a(&body)),
+            ),
+        ];
+        if let Some(last_modified) = last_modified {
+            headers.push(("last-modified", last_modified.to_http_date()));
+        }
+        Ok((200, headers, body))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn response_parts() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({ "title": "Undated", "items": [] }))?;
+        let etag = feed.etag()?;
+
+        let (_, headers, body) = feed
+            .clone()
+            .into_http_response_parts(SerializeOptions::new())?;
+        assert_eq!(headers.len(), 2);
+        assert_eq!(headers[1], ("etag", etag.clone()));
+        assert_eq!(crate::from_slice(&body)?, feed);
+
+        let (_, headers, body) = feed.into_http_response_parts(SerializeOptions::pretty())?;
+        assert!(body.contains(&b'\n'));
+        assert_ne!(headers[1], ("etag", etag));
+        Ok(())
+    }
+
+    #[test]
+    fn last_modified() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "items": [
+                { "id": "1", "date_published": "2021-06-01T12:00:00Z" },
+                { "id": "2", "date_modified": "2021-06-02T00:00:00-01:00" }
+            ]
+        }))?;
+        let (status, headers, _) = feed.into_http_response_parts(SerializeOptions::new())?;
+        assert_eq!(status, 200);
+        assert_eq!(
+            headers[2],
+            (
+                "last-modified",
+                String::from("Wed, 02 Jun 2021 01:00:00 GMT")
+            )
+        );
+
+        let (_, headers, _) = Feed::new().into_http_response_parts(SerializeOptions::new())?;
+        assert_eq!(headers.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn errors() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({ "items": {} }))?;
+        assert!(matches!(
+            feed.into_http_response_parts(SerializeOptions::new()),
+            Err(Error::UnexpectedType)
+        ));
+
+        let feed = crate::from_value(serde_json::json!({
+            "items": [{ "id": "1", "date_published": "June 1, 2021" }]
+        }))?;
+        assert!(matches!(
+            feed.into_http_response_parts(SerializeOptions::new()),
+            Err(Error::InvalidDate)
+        ));
+        Ok(())
+    }
+}
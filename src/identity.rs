@@ -0,0 +1,415 @@
+//! Author identities across feeds.
+//!
+//! Aggregators which build author pages need to decide if authors in different feeds or items
+//! are the same person. Author objects rarely have identifiers, so the comparison is fuzzy.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{format, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{format, string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{Author, AuthorMut, AuthorRef, Error, Feed};
+
+/// Options which change how authors are compared.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MatchOptions {
+    /// If true, names are compared case-insensitively with whitespace collapsed.
+    pub fold_case: bool,
+    /// If true, only the hosts of the authors' URLs are compared.
+    ///
+    /// Otherwise, URLs are compared with `subscriptions::feed_url_equivalent`.
+    pub url_host_only: bool,
+    /// If true, avatars are ignored.
+    ///
+    /// The same person often uses different avatar URLs in different feeds.
+    pub ignore_avatar: bool,
+}
+
+impl MatchOptions {
+    /// Instantiates the default options.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Default for MatchOptions {
+    fn default() -> Self {
+        Self {
+            fold_case: true,
+            url_host_only: false,
+            ignore_avatar: true,
+        }
+    }
+}
+
+fn normalize_name(name: &str, options: &MatchOptions) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    for word in name.split_whitespace() {
+        if !normalized.is_empty() {
+            normalized.push(' ');
+        }
+        if options.fold_case {
+            normalized.push_str(&word.to_lowercase());
+        } else {
+            normalized.push_str(word);
+        }
+    }
+    normalized
+}
+
+fn url_host(url: &str) -> Option<String> {
+    let url = crate::subscriptions::normalize_subscription_url(url)?;
+    let rest = &url[url.find("://")? + 3..];
+    let authority = &rest[..rest.find('/').unwrap_or(rest.len())];
+    let host = &authority[authority.rfind('@').map_or(0, |i| i + 1)..];
+    Some(String::from(host.strip_prefix("www.").unwrap_or(host)))
+}
+
+/// Compares an optional attribute of two authors.
+///
+/// Returns `None` if either author does not have the attribute.
+fn compare<T, F>(a: Option<T>, b: Option<T>, eq: F) -> Option<bool>
+where
+    F: FnOnce(T, T) -> bool,
+{
+    Some(eq(a?, b?))
+}
+
+fn matches(
+    a: &Map<String, Value>,
+    b: &Map<String, Value>,
+    options: &MatchOptions,
+) -> Result<bool, Error> {
+    let (a, b) = (AuthorRef::from(a), AuthorRef::from(b));
+    let comparisons = [
+        compare(a.name()?, b.name()?, |a, b| {
+            normalize_name(a, options) == normalize_name(b, options)
+        }),
+        compare(a.url()?, b.url()?, |a, b| {
+            if options.url_host_only {
+                match (url_host(a), url_host(b)) {
+                    (Some(a), Some(b)) => a == b,
+                    _ => a.trim() == b.trim(),
+                }
+            } else {
+                crate::subscriptions::feed_url_equivalent(a, b)
+            }
+        }),
+        if options.ignore_avatar {
+            None
+        } else {
+            compare(a.avatar()?, b.avatar()?, |a, b| a.trim() == b.trim())
+        },
+    ];
+    Ok(comparisons.iter().any(Option::is_some)
+        && comparisons.iter().all(|matched| matched.unwrap_or(true)))
+}
+
+macro_rules! author_match_methods {
+    () => {
+        /// Returns true if the authors are likely the same person.
+        ///
+        /// Each attribute which both authors have (the name, the URL, and, unless ignored, the
+        /// avatar) must match. Authors without any attribute in common do not match.
+        ///
+        /// # Errors
+        ///
+        /// If an attribute is not the expected JSON type, then `Error::UnexpectedType` is
+        /// returned.
+        pub fn matches<O>(&self, other: &O, options: &MatchOptions) -> Result<bool, Error>
+        where
+            O: AsRef<Map<String, Value>>,
+        {
+            matches(self.as_map(), other.as_ref(), options)
+        }
+    };
+}
+
+impl Author {
+    author_match_methods!();
+}
+
+impl<'a> AuthorMut<'a> {
+    author_match_methods!();
+}
+
+impl<'a> AuthorRef<'a> {
+    author_match_methods!();
+}
+
+/// Where an author object is in a `FeedSet`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub struct AuthorLocation {
+    /// The index of the feed.
+    pub feed: usize,
+    /// The index of the item or `None` for the feed's authors.
+    pub item: Option<usize>,
+    /// The index in `authors` or `None` for the deprecated `author` member.
+    pub author: Option<usize>,
+}
+
+/// An author who appears one or more times in a `FeedSet`.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct UnifiedAuthor {
+    /// A key for the author.
+    ///
+    /// The key is derived from the first occurrence of the author, so it is stable as long as
+    /// that occurrence does not change.
+    pub key: String,
+    /// The author's attributes.
+    ///
+    /// Each attribute is from the first occurrence which has the attribute.
+    pub author: Author,
+    /// Where the author appears.
+    pub locations: Vec<AuthorLocation>,
+}
+
+/// An author object and where it is.
+type Occurrence<'a> = (AuthorLocation, &'a Map<String, Value>);
+
+/// A collection of feeds which are processed together.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FeedSet {
+    feeds: Vec<Feed>,
+}
+
+impl FeedSet {
+    /// Instantiates an empty set.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a feed to the set and returns its index.
+    pub fn push(&mut self, feed: Feed) -> usize {
+        self.feeds.push(feed);
+        self.feeds.len() - 1
+    }
+
+    /// Returns the feeds.
+    #[must_use]
+    pub fn feeds(&self) -> &[Feed] {
+        &self.feeds
+    }
+
+    /// Returns the feeds.
+    #[must_use]
+    pub fn into_feeds(self) -> Vec<Feed> {
+        self.feeds
+    }
+
+    /// Groups the authors in every feed and item by identity.
+    ///
+    /// Authors are compared with `Author::matches`. An author joins the first group which has a
+    /// matching member. Groups are returned in the order of their first occurrence.
+    ///
+    /// # Errors
+    ///
+    /// If the authors or items are not the expected JSON type, then `Error::UnexpectedType` is
+    /// returned.
+    pub fn unify_authors(&self, options: &MatchOptions) -> Result<Vec<UnifiedAuthor>, Error> {
+        let mut groups: Vec<(Vec<&Map<String, Value>>, UnifiedAuthor)> = Vec::new();
+        for (location, author) in self.author_occurrences()? {
+            let mut group_index = None;
+            for (index, (members, _)) in groups.iter().enumerate() {
+                if any_matches(members, author, options)? {
+                    group_index = Some(index);
+                    break;
+                }
+            }
+
+            match group_index {
+                Some(index) => {
+                    let (members, unified) = &mut groups[index];
+                    members.push(author);
+                    for key in ["name", "url", "avatar"] {
+                        if !unified.author.as_map().contains_key(key) {
+                            if let Some(value) = author.get(key) {
+                                unified
+                                    .author
+                                    .as_map_mut()
+                                    .insert(String::from(key), value.clone());
+                            }
+                        }
+                    }
+                    unified.locations.push(location);
+                }
+                None => {
+                    let unified = UnifiedAuthor {
+                        key: author_key(author, options)?,
+                        author: Author::from(author.clone()),
+                        locations: core::iter::once(location).collect(),
+                    };
+                    groups.push((core::iter::once(author).collect(), unified));
+                }
+            }
+        }
+        Ok(groups.into_iter().map(|(_, unified)| unified).collect())
+    }
+
+    fn author_occurrences(&self) -> Result<Vec<Occurrence<'_>>, Error> {
+        fn push<'a>(
+            map: &'a Map<String, Value>,
+            feed: usize,
+            item: Option<usize>,
+            occurrences: &mut Vec<Occurrence<'a>>,
+        ) -> Result<(), Error> {
+            if let Some(authors) = map.get("authors") {
+                let authors = authors.as_array().ok_or(Error::UnexpectedType)?;
+                for (index, author) in authors.iter().enumerate() {
+                    let author = author.as_object().ok_or(Error::UnexpectedType)?;
+                    let location = AuthorLocation {
+                        feed,
+                        item,
+                        author: Some(index),
+                    };
+                    occurrences.push((location, author));
+                }
+            }
+            if let Some(author) = map.get("author") {
+                let author = author.as_object().ok_or(Error::UnexpectedType)?;
+                let location = AuthorLocation {
+                    feed,
+                    item,
+                    author: None,
+                };
+                occurrences.push((location, author));
+            }
+            Ok(())
+        }
+
+        let mut occurrences = Vec::new();
+        for (feed_index, feed) in self.feeds.iter().enumerate() {
+            push(feed.as_map(), feed_index, None, &mut occurrences)?;
+            let items = match feed.as_map().get("items") {
+                Some(items) => items.as_array().ok_or(Error::UnexpectedType)?.as_slice(),
+                None => &[],
+            };
+            for (item_index, item) in items.iter().enumerate() {
+                let item = item.as_object().ok_or(Error::UnexpectedType)?;
+                push(item, feed_index, Some(item_index), &mut occurrences)?;
+            }
+        }
+        Ok(occurrences)
+    }
+}
+
+impl From<Vec<Feed>> for FeedSet {
+    fn from(feeds: Vec<Feed>) -> Self {
+        Self { feeds }
+    }
+}
+
+fn any_matches(
+    members: &[&Map<String, Value>],
+    author: &Map<String, Value>,
+    options: &MatchOptions,
+) -> Result<bool, Error> {
+    for member in members {
+        if matches(member, author, options)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+fn author_key(author: &Map<String, Value>, options: &MatchOptions) -> Result<String, Error> {
+    let author = AuthorRef::from(author);
+    let mut identity = String::new();
+    if let Some(name) = author.name()? {
+        identity.push_str(&normalize_name(name, options));
+    }
+    identity.push('\n');
+    if let Some(url) = author.url()? {
+        match url_host(url) {
+            Some(host) if options.url_host_only => identity.push_str(&host),
+            _ => identity.push_str(url.trim()),
+        }
+    }
+    Ok(format!(
+        "{:016x}",
+        crate::canonical::fnv1a(identity.as_bytes())
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::vec;
+
+    fn author(value: Value) -> Author {
+        match value {
+            Value::Object(map) => Author::from(map),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn matching() -> Result<(), Error> {
+        let options = MatchOptions::new();
+        let jane =
+            author(serde_json::json!({ "name": "Jane  Doe", "url": "https://jane.example/" }));
+        assert!(jane.matches(&author(serde_json::json!({ "name": "jane doe" })), &options)?);
+        assert!(jane.matches(
+            &author(serde_json::json!({ "url": "http://www.jane.example", "avatar": "a.png" })),
+            &options
+        )?);
+        assert!(!jane.matches(
+            &author(serde_json::json!({ "name": "Jane Doe", "url": "https://other.example/" })),
+            &options
+        )?);
+        assert!(!jane.matches(&author(serde_json::json!({ "avatar": "a.png" })), &options)?);
+
+        let mut options = MatchOptions::new();
+        options.url_host_only = true;
+        assert!(jane.matches(
+            &author(serde_json::json!({ "url": "https://jane.example/about" })),
+            &options
+        )?);
+        Ok(())
+    }
+
+    #[test]
+    fn unify_authors() -> Result<(), Error> {
+        let set = FeedSet::from(vec![
+            crate::from_value(serde_json::json!({
+                "authors": [{ "name": "Jane Doe" }],
+                "items": [{ "id": "1", "authors": [{ "name": "John" }] }]
+            }))?,
+            crate::from_value(serde_json::json!({
+                "items": [{ "id": "1", "author": { "name": "JANE DOE", "url": "https://jane.example/" } }]
+            }))?,
+        ]);
+
+        let unified = set.unify_authors(&MatchOptions::new())?;
+        assert_eq!(unified.len(), 2);
+        assert_eq!(unified[0].author.name()?, Some("Jane Doe"));
+        assert_eq!(unified[0].author.url()?, Some("https://jane.example/"));
+        assert_eq!(
+            unified[0].locations,
+            vec![
+                AuthorLocation {
+                    feed: 0,
+                    item: None,
+                    author: Some(0)
+                },
+                AuthorLocation {
+                    feed: 1,
+                    item: Some(0),
+                    author: None
+                },
+            ]
+        );
+        assert_eq!(unified[1].author.name()?, Some("John"));
+        assert_ne!(unified[0].key, unified[1].key);
+        assert_eq!(unified, set.unify_authors(&MatchOptions::new())?);
+        Ok(())
+    }
+}
@@ -0,0 +1,731 @@
+//! Views of a feed's items with a slice-like API.
+//!
+//! The `items()` accessor allocates a `Vec` of wrappers. The views returned by `items_view()`
+//! and `items_view_mut()` wrap the JSON array directly.
+//!
+//! ```
+//! let feed = json_feed_model::from_str(r#"{
+//!     "items": [{ "id": "1" }, { "id": "2" }]
+//! }"#)?;
+//!
+//! let items = feed.items_view()?.unwrap();
+//! assert_eq!(items.len(), 2);
+//! assert_eq!(items.get(1).unwrap().id()?, Some("2"));
+//! assert_eq!(items[0]["id"], "1");
+//!
+//! let empty = json_feed_model::from_str("{}")?;
+//! assert!(empty.items_or_empty()?.is_empty());
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+//!
+//! Reader UIs can show the valid items of a feed with malformed entries using `items_lossy()`:
+//!
+//! ```
+//! let feed = json_feed_model::from_str(r#"{
+//!     "items": [{ "id": "1" }, "stray", null, { "id": "2" }]
+//! }"#)?;
+//! assert!(feed.items().is_err());
+//!
+//! let lossy = feed.items_lossy()?;
+//! assert_eq!(lossy.items.len(), 2);
+//! assert_eq!(lossy.items[1].id()?, Some("2"));
+//! assert_eq!(lossy.skipped[0].index, 1);
+//! assert_eq!(lossy.skipped[1].reason, "item is null");
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+//!
+//! A multi-step change can be rolled back with a snapshot of the items:
+//!
+//! ```
+//! use json_feed_model::{Error, Feed};
+//!
+//! fn retitle_all(feed: &mut Feed) -> Result<(), Error> {
+//!     let mut items = feed.items_view_mut()?.ok_or(Error::UnexpectedType)?;
+//!     for mut item in items.iter_mut() {
+//!         let title = item.id()?.ok_or(Error::UnexpectedType)?.to_uppercase();
+//!         item.set_title(title);
+//!     }
+//!     Ok(())
+//! }
+//!
+//! let mut feed = json_feed_model::from_str(r#"{
+//!     "items": [{ "id": "a" }, { "title": "No id" }]
+//! }"#)?;
+//!
+//! let snapshot = feed.snapshot_items();
+//! if retitle_all(&mut feed).is_err() {
+//!     feed.restore_items(snapshot);
+//! }
+//! assert_eq!(feed.items()?.unwrap()[0].title()?, None);
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+use core::{
+    cmp::Ordering,
+    ops::{Index, IndexMut},
+};
+
+use serde_json::{Map, Value};
+
+use crate::{Error, Feed, FeedMut, FeedRef, Item, ItemMut, ItemRef};
+
+const NOT_AN_OBJECT: &str = "items are verified to be JSON objects";
+
+fn as_object(value: &Value) -> &Map<String, Value> {
+    match value {
+        Value::Object(obj) => obj,
+        _ => unreachable!("{}", NOT_AN_OBJECT),
+    }
+}
+
+fn as_object_mut(value: &mut Value) -> &mut Map<String, Value> {
+    match value {
+        Value::Object(obj) => obj,
+        _ => unreachable!("{}", NOT_AN_OBJECT),
+    }
+}
+
+fn verify_objects(values: &[Value]) -> Result<(), Error> {
+    if values.iter().all(Value::is_object) {
+        Ok(())
+    } else {
+        Err(Error::UnexpectedType)
+    }
+}
+
+/// A read-only view of a feed's `items` array.
+///
+/// Indexing returns the item's JSON object.
+#[derive(Clone, Copy, Debug)]
+pub struct ItemsRef<'a> {
+    values: &'a [Value],
+}
+
+impl<'a> ItemsRef<'a> {
+    /// Returns the number of items.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns true if there are no items.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the item at the index.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<ItemRef<'a>> {
+        self.values
+            .get(index)
+            .map(|value| ItemRef::from(as_object(value)))
+    }
+
+    /// Returns the first item.
+    #[must_use]
+    pub fn first(&self) -> Option<ItemRef<'a>> {
+        self.get(0)
+    }
+
+    /// Returns the last item.
+    #[must_use]
+    pub fn last(&self) -> Option<ItemRef<'a>> {
+        self.len().checked_sub(1).and_then(|index| self.get(index))
+    }
+
+    /// Returns an iterator over the items.
+    #[must_use]
+    pub fn iter(&self) -> Iter<'a> {
+        Iter {
+            inner: self.values.iter(),
+        }
+    }
+}
+
+impl<'a> Index<usize> for ItemsRef<'a> {
+    type Output = Map<String, Value>;
+
+    fn index(&self, index: usize) -> &Map<String, Value> {
+        as_object(&self.values[index])
+    }
+}
+
+impl<'a> IntoIterator for ItemsRef<'a> {
+    type Item = ItemRef<'a>;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+/// An iterator over borrowed items.
+#[derive(Clone, Debug)]
+pub struct Iter<'a> {
+    inner: core::slice::Iter<'a, Value>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = ItemRef<'a>;
+
+    fn next(&mut self) -> Option<ItemRef<'a>> {
+        self.inner
+            .next()
+            .map(|value| ItemRef::from(as_object(value)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for Iter<'a> {
+    fn next_back(&mut self) -> Option<ItemRef<'a>> {
+        self.inner
+            .next_back()
+            .map(|value| ItemRef::from(as_object(value)))
+    }
+}
+
+impl<'a> ExactSizeIterator for Iter<'a> {}
+
+/// A mutable view of a feed's `items` array.
+///
+/// Indexing returns the item's JSON object.
+#[derive(Debug)]
+pub struct ItemsMut<'a> {
+    values: &'a mut Vec<Value>,
+}
+
+impl<'a> ItemsMut<'a> {
+    /// Returns the number of items.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns true if there are no items.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns a read-only view of the items.
+    #[must_use]
+    pub fn as_items_ref(&self) -> ItemsRef<'_> {
+        ItemsRef {
+            values: self.values,
+        }
+    }
+
+    /// Returns the item at the index.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<ItemRef<'_>> {
+        self.values
+            .get(index)
+            .map(|value| ItemRef::from(as_object(value)))
+    }
+
+    /// Returns the item at the index as a mutable reference.
+    pub fn get_mut(&mut self, index: usize) -> Option<ItemMut<'_>> {
+        self.values
+            .get_mut(index)
+            .map(|value| ItemMut::from(as_object_mut(value)))
+    }
+
+    /// Returns an iterator over the items.
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            inner: self.values.iter(),
+        }
+    }
+
+    /// Returns an iterator over the items as mutable references.
+    pub fn iter_mut(&mut self) -> IterMut<'_> {
+        IterMut {
+            inner: self.values.iter_mut(),
+        }
+    }
+
+    /// Appends an item.
+    pub fn push(&mut self, item: Item) {
+        self.values.push(Value::Object(item.into_inner()));
+    }
+
+    /// Removes and returns the item at the index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Item {
+        match self.values.remove(index) {
+            Value::Object(obj) => Item::from(obj),
+            _ => unreachable!("{}", NOT_AN_OBJECT),
+        }
+    }
+
+    /// Swaps two items.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is out of bounds.
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.values.swap(a, b);
+    }
+
+    /// Sorts the items with a comparator function.
+    ///
+    /// The sort is stable.
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&ItemRef<'_>, &ItemRef<'_>) -> Ordering,
+    {
+        self.values
+            .sort_by(|a, b| compare(&ItemRef::from(as_object(a)), &ItemRef::from(as_object(b))));
+    }
+
+    /// Retains only the items for which the function returns true.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&ItemRef<'_>) -> bool,
+    {
+        self.values
+            .retain(|value| f(&ItemRef::from(as_object(value))));
+    }
+}
+
+impl<'a> Index<usize> for ItemsMut<'a> {
+    type Output = Map<String, Value>;
+
+    fn index(&self, index: usize) -> &Map<String, Value> {
+        as_object(&self.values[index])
+    }
+}
+
+impl<'a> IndexMut<usize> for ItemsMut<'a> {
+    fn index_mut(&mut self, index: usize) -> &mut Map<String, Value> {
+        as_object_mut(&mut self.values[index])
+    }
+}
+
+/// An iterator over mutably borrowed items.
+#[derive(Debug)]
+pub struct IterMut<'a> {
+    inner: core::slice::IterMut<'a, Value>,
+}
+
+impl<'a> Iterator for IterMut<'a> {
+    type Item = ItemMut<'a>;
+
+    fn next(&mut self) -> Option<ItemMut<'a>> {
+        self.inner
+            .next()
+            .map(|value| ItemMut::from(as_object_mut(value)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for IterMut<'a> {
+    fn next_back(&mut self) -> Option<ItemMut<'a>> {
+        self.inner
+            .next_back()
+            .map(|value| ItemMut::from(as_object_mut(value)))
+    }
+}
+
+impl<'a> ExactSizeIterator for IterMut<'a> {}
+
+/// An entry of the `items` array which is not a JSON object.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct SkippedItem {
+    /// The index of the entry in the `items` array.
+    pub index: usize,
+    /// Why the entry was skipped.
+    pub reason: &'static str,
+}
+
+/// The items of an `items` array which may contain entries that are not items.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct LossyItems<'a> {
+    /// The entries which are JSON objects in order.
+    pub items: Vec<ItemRef<'a>>,
+    /// The entries which are not JSON objects in order.
+    pub skipped: Vec<SkippedItem>,
+}
+
+fn not_an_object_reason(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "item is null",
+        Value::Bool(_) => "item is a JSON boolean",
+        Value::Number(_) => "item is a JSON number",
+        Value::String(_) => "item is a JSON string",
+        Value::Array(_) => "item is a JSON array",
+        Value::Object(_) => "item is a JSON object",
+    }
+}
+
+fn items_lossy(map: &Map<String, Value>) -> Result<LossyItems<'_>, Error> {
+    let mut lossy = LossyItems {
+        items: Vec::new(),
+        skipped: Vec::new(),
+    };
+    let values = match map.get("items") {
+        None => return Ok(lossy),
+        Some(Value::Array(values)) => values,
+        Some(_) => return Err(Error::UnexpectedType),
+    };
+    for (index, value) in values.iter().enumerate() {
+        match value {
+            Value::Object(obj) => lossy.items.push(ItemRef::from(obj)),
+            _ => lossy.skipped.push(SkippedItem {
+                index,
+                reason: not_an_object_reason(value),
+            }),
+        }
+    }
+    Ok(lossy)
+}
+
+/// A copy of a feed's `items` which can be restored after a failed change.
+///
+/// The snapshot also records if there was no `items` property.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ItemsSnapshot {
+    items: Option<Value>,
+}
+
+fn restore_items(map: &mut Map<String, Value>, snapshot: ItemsSnapshot) -> Option<Value> {
+    match snapshot.items {
+        Some(items) => map.insert(String::from("items"), items),
+        None => map.remove("items"),
+    }
+}
+
+fn items_view(map: &Map<String, Value>) -> Result<Option<ItemsRef<'_>>, Error> {
+    match map.get("items") {
+        None => Ok(None),
+        Some(Value::Array(values)) => {
+            verify_objects(values)?;
+            Ok(Some(ItemsRef { values }))
+        }
+        Some(_) => Err(Error::UnexpectedType),
+    }
+}
+
+fn items_view_mut(map: &mut Map<String, Value>) -> Result<Option<ItemsMut<'_>>, Error> {
+    match map.get_mut("items") {
+        None => Ok(None),
+        Some(Value::Array(values)) => {
+            verify_objects(values)?;
+            Ok(Some(ItemsMut { values }))
+        }
+        Some(_) => Err(Error::UnexpectedType),
+    }
+}
+
+macro_rules! items_view_methods {
+    () => {
+        /// Returns a read-only view of the items.
+        ///
+        /// # Errors
+        ///
+        /// If the `items` property is not a JSON array of JSON objects, then
+        /// `Error::UnexpectedType` is returned.
+        pub fn items_view(&self) -> Result<Option<ItemsRef<'_>>, Error> {
+            items_view(self.as_map())
+        }
+
+        /// Returns a read-only view of the items which is empty if there is no `items`
+        /// property.
+        ///
+        /// # Errors
+        ///
+        /// If the `items` property is not a JSON array of JSON objects, then
+        /// `Error::UnexpectedType` is returned.
+        pub fn items_or_empty(&self) -> Result<ItemsRef<'_>, Error> {
+            Ok(items_view(self.as_map())?.unwrap_or(ItemsRef { values: &[] }))
+        }
+
+        /// Returns the items which are JSON objects and the entries of the `items` array
+        /// which were skipped.
+        ///
+        /// Unlike `items()`, a stray string or `null` in the array does not cause an error.
+        /// If there is no `items` property, there are no items.
+        ///
+        /// # Errors
+        ///
+        /// If the `items` property is not a JSON array, then `Error::UnexpectedType` is
+        /// returned.
+        pub fn items_lossy(&self) -> Result<LossyItems<'_>, Error> {
+            items_lossy(self.as_map())
+        }
+    };
+}
+
+macro_rules! snapshot_items_methods {
+    () => {
+        /// Returns a copy of the `items` which can be restored with `restore_items()`.
+        ///
+        /// The `items` are copied as they are, even if they are not the expected JSON type.
+        #[must_use]
+        pub fn snapshot_items(&self) -> ItemsSnapshot {
+            ItemsSnapshot {
+                items: self.as_map().get("items").cloned(),
+            }
+        }
+    };
+}
+
+macro_rules! items_view_mut_methods {
+    () => {
+        /// Replaces the `items` with a snapshot and returns the replaced `items`.
+        ///
+        /// If there was no `items` property when the snapshot was taken, the property is
+        /// removed.
+        pub fn restore_items(&mut self, snapshot: ItemsSnapshot) -> Option<Value> {
+            restore_items(self.as_map_mut(), snapshot)
+        }
+
+        /// Returns a mutable view of the items.
+        ///
+        /// # Errors
+        ///
+        /// If the `items` property is not a JSON array of JSON objects, then
+        /// `Error::UnexpectedType` is returned.
+        pub fn items_view_mut(&mut self) -> Result<Option<ItemsMut<'_>>, Error> {
+            items_view_mut(self.as_map_mut())
+        }
+    };
+}
+
+impl Feed {
+    items_view_methods!();
+    snapshot_items_methods!();
+    items_view_mut_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    items_view_methods!();
+    snapshot_items_methods!();
+    items_view_mut_methods!();
+}
+
+impl<'a> FeedRef<'a> {
+    items_view_methods!();
+    snapshot_items_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::{string::String, vec};
+
+    #[test]
+    fn views() -> Result<(), Error> {
+        let mut feed = crate::from_value(serde_json::json!({
+            "items": [{ "id": "b" }, { "id": "c" }, { "id": "a" }]
+        }))?;
+
+        let items = feed.items_view()?.unwrap();
+        assert_eq!(items.len(), 3);
+        assert_eq!(items.last().unwrap().id()?, Some("a"));
+        assert_eq!(items.iter().rev().count(), 3);
+
+        let mut items = feed.items_view_mut()?.unwrap();
+        items.sort_by(|a, b| a.id().ok().cmp(&b.id().ok()));
+        items.swap(0, 2);
+        items.retain(|item| item.id().ok().flatten() != Some("b"));
+        items.get_mut(0).unwrap().set_title("C");
+        for mut item in items.iter_mut() {
+            item.set_content_text("Text");
+        }
+        items.push(Item::new());
+        assert_eq!(items.remove(2), Item::new());
+
+        assert_eq!(
+            feed.as_map().get("items"),
+            Some(&serde_json::json!([
+                { "id": "c", "title": "C", "content_text": "Text" },
+                { "id": "a", "content_text": "Text" }
+            ]))
+        );
+
+        feed.as_map_mut()
+            .insert(String::from("items"), serde_json::json!([{}, 1]));
+        assert!(matches!(feed.items_view(), Err(Error::UnexpectedType)));
+        assert!(matches!(feed.items_or_empty(), Err(Error::UnexpectedType)));
+
+        feed.remove_items();
+        assert!(feed.items_view()?.is_none());
+        assert!(feed.items_or_empty()?.is_empty());
+        assert_eq!(feed.items_or_empty()?.iter().count(), 0);
+        assert!(feed.items_lossy()?.items.is_empty());
+
+        let missing = feed.snapshot_items();
+        feed.set_items(vec![Item::new()]);
+        let one = feed.snapshot_items();
+        feed.remove_items();
+        assert_eq!(feed.restore_items(one), None);
+        assert_eq!(feed.items_view()?.map(|items| items.len()), Some(1));
+        assert!(feed.restore_items(missing).is_some());
+        assert!(feed.items_view()?.is_none());
+
+        feed.as_map_mut()
+            .insert(String::from("items"), serde_json::json!({}));
+        assert!(matches!(feed.items_lossy(), Err(Error::UnexpectedType)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn items_ref() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "items": [{ "id": "1" }, { "id": "2" }, { "id": "3" }]
+        }))?;
+
+        let items = feed.items_view()?.unwrap();
+        assert!(!items.is_empty());
+        assert_eq!(items.first().unwrap().id()?, Some("1"));
+        assert_eq!(items.last().unwrap().id()?, Some("3"));
+        assert!(items.get(3).is_none());
+        assert_eq!(items[1]["id"], "2");
+
+        let mut iter = items.iter();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next_back().unwrap().id()?, Some("3"));
+        assert_eq!(iter.next().unwrap().id()?, Some("1"));
+        assert_eq!(iter.len(), 1);
+
+        let ids = items
+            .into_iter()
+            .map(|item| Ok(item.id()?.map(String::from)))
+            .collect::<Result<Vec<_>, Error>>()?;
+        assert_eq!(
+            ids,
+            vec![
+                Some(String::from("1")),
+                Some(String::from("2")),
+                Some(String::from("3"))
+            ]
+        );
+
+        let feed_ref = FeedRef::from(feed.as_map());
+        assert_eq!(feed_ref.items_or_empty()?.len(), 3);
+
+        let empty = crate::from_value(serde_json::json!({ "items": [] }))?;
+        let items = empty.items_view()?.unwrap();
+        assert!(items.is_empty());
+        assert!(items.first().is_none());
+        assert!(items.last().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn items_mut() -> Result<(), Error> {
+        let mut feed = crate::from_value(serde_json::json!({
+            "items": [{ "id": "1" }, { "id": "2" }]
+        }))?;
+
+        let mut feed_mut = FeedMut::from(&mut feed);
+        let mut items = feed_mut.items_view_mut()?.unwrap();
+        assert_eq!(items.len(), 2);
+        assert!(!items.is_empty());
+        assert_eq!(items.as_items_ref().last().unwrap().id()?, Some("2"));
+        assert_eq!(items.get(0).unwrap().id()?, Some("1"));
+        assert!(items.get(2).is_none());
+        assert!(items.get_mut(2).is_none());
+        assert_eq!(items.iter().len(), 2);
+        assert_eq!(items.iter_mut().next_back().unwrap().id()?, Some("2"));
+
+        items[0].insert(String::from("title"), Value::from("One"));
+        assert_eq!(items[0]["title"], "One");
+
+        items.retain(|_| false);
+        assert!(items.is_empty());
+        assert_eq!(feed.as_map()["items"], serde_json::json!([]));
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn items_mut_remove_out_of_bounds() {
+        let mut feed = Feed::new();
+        feed.set_items(vec![Item::new()]);
+        feed.items_view_mut().unwrap().unwrap().remove(1);
+    }
+
+    #[test]
+    fn items_view_errors() -> Result<(), Error> {
+        let mut feed = crate::from_value(serde_json::json!({ "items": "not an array" }))?;
+        assert!(matches!(feed.items_view(), Err(Error::UnexpectedType)));
+        assert!(matches!(feed.items_view_mut(), Err(Error::UnexpectedType)));
+        assert!(matches!(feed.items_or_empty(), Err(Error::UnexpectedType)));
+        assert!(matches!(feed.items_lossy(), Err(Error::UnexpectedType)));
+
+        feed.as_map_mut().insert(
+            String::from("items"),
+            serde_json::json!([{ "id": "1" }, null]),
+        );
+        assert!(matches!(feed.items_view_mut(), Err(Error::UnexpectedType)));
+
+        feed.remove_items();
+        assert!(feed.items_view_mut()?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn lossy_reasons() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "items": [null, true, 1, "a", [], { "id": "1" }]
+        }))?;
+        let lossy = feed.items_lossy()?;
+        assert_eq!(lossy.items.len(), 1);
+        assert_eq!(lossy.items[0].id()?, Some("1"));
+        assert_eq!(
+            lossy
+                .skipped
+                .iter()
+                .map(|skipped| (skipped.index, skipped.reason))
+                .collect::<Vec<_>>(),
+            vec![
+                (0, "item is null"),
+                (1, "item is a JSON boolean"),
+                (2, "item is a JSON number"),
+                (3, "item is a JSON string"),
+                (4, "item is a JSON array"),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn snapshots() -> Result<(), Error> {
+        let mut feed = crate::from_value(serde_json::json!({ "items": "malformed" }))?;
+
+        let snapshot = FeedRef::from(feed.as_map()).snapshot_items();
+        assert_eq!(snapshot, feed.snapshot_items());
+        feed.set_items(vec![Item::new()]);
+
+        let mut feed_mut = FeedMut::from(&mut feed);
+        assert_eq!(
+            feed_mut.restore_items(snapshot.clone()),
+            Some(serde_json::json!([{}]))
+        );
+        assert_eq!(feed.as_map()["items"], "malformed");
+        assert_eq!(feed.restore_items(snapshot), Some(Value::from("malformed")));
+        Ok(())
+    }
+}
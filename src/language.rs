@@ -0,0 +1,282 @@
+//! Language detection for feeds which do not declare a language.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{collections::BTreeMap, string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use whatlang::Lang;
+
+use crate::{Error, Feed, FeedMut, FeedRef, Item, ItemMut, ItemRef};
+
+/// Returns the [RFC 5646][rfc_5646] tag for a detected language.
+///
+/// The two letter ISO 639-1 code is used when the language has one.
+///
+/// [rfc_5646]: https://tools.ietf.org/html/rfc5646
+fn language_tag(lang: Lang) -> &'static str {
+    match lang {
+        Lang::Epo => "eo",
+        Lang::Eng => "en",
+        Lang::Rus => "ru",
+        Lang::Cmn => "zh",
+        Lang::Spa => "es",
+        Lang::Por => "pt",
+        Lang::Ita => "it",
+        Lang::Ben => "bn",
+        Lang::Fra => "fr",
+        Lang::Deu => "de",
+        Lang::Ukr => "uk",
+        Lang::Kat => "ka",
+        Lang::Ara => "ar",
+        Lang::Hin => "hi",
+        Lang::Jpn => "ja",
+        Lang::Heb => "he",
+        Lang::Yid => "yi",
+        Lang::Pol => "pl",
+        Lang::Amh => "am",
+        Lang::Jav => "jv",
+        Lang::Kor => "ko",
+        Lang::Nob => "nb",
+        Lang::Dan => "da",
+        Lang::Swe => "sv",
+        Lang::Fin => "fi",
+        Lang::Tur => "tr",
+        Lang::Nld => "nl",
+        Lang::Hun => "hu",
+        Lang::Ces => "cs",
+        Lang::Ell => "el",
+        Lang::Bul => "bg",
+        Lang::Bel => "be",
+        Lang::Mar => "mr",
+        Lang::Kan => "kn",
+        Lang::Ron => "ro",
+        Lang::Slv => "sl",
+        Lang::Hrv => "hr",
+        Lang::Srp => "sr",
+        Lang::Mkd => "mk",
+        Lang::Lit => "lt",
+        Lang::Lav => "lv",
+        Lang::Est => "et",
+        Lang::Tam => "ta",
+        Lang::Vie => "vi",
+        Lang::Urd => "ur",
+        Lang::Tha => "th",
+        Lang::Guj => "gu",
+        Lang::Uzb => "uz",
+        Lang::Pan => "pa",
+        Lang::Aze => "az",
+        Lang::Ind => "id",
+        Lang::Tel => "te",
+        Lang::Pes => "fa",
+        Lang::Mal => "ml",
+        Lang::Ori => "or",
+        Lang::Mya => "my",
+        Lang::Nep => "ne",
+        Lang::Sin => "si",
+        Lang::Khm => "km",
+        Lang::Tuk => "tk",
+        Lang::Aka => "ak",
+        Lang::Zul => "zu",
+        Lang::Sna => "sn",
+        Lang::Afr => "af",
+        Lang::Lat => "la",
+        Lang::Slk => "sk",
+        Lang::Cat => "ca",
+        Lang::Tgl => "tl",
+        Lang::Hye => "hy",
+    }
+}
+
+/// Returns the language tag of the text if it can be reliably detected.
+fn detect(text: &str) -> Option<&'static str> {
+    whatlang::detect(text)
+        .filter(whatlang::Info::is_reliable)
+        .map(|info| language_tag(info.lang()))
+}
+
+fn push_text(text: &mut String, part: Option<&str>) {
+    if let Some(part) = part {
+        text.push_str(part);
+        text.push('\n');
+    }
+}
+
+fn detect_item_language(map: &Map<String, Value>) -> Result<Option<&'static str>, Error> {
+    let item = ItemRef::from(map);
+    let mut text = String::new();
+    push_text(&mut text, item.title()?);
+    push_text(&mut text, item.summary()?);
+    match item.content_text()? {
+        Some(content_text) => push_text(&mut text, Some(content_text)),
+        None => push_text(
+            &mut text,
+            item.content_html()?.map(crate::html::strip_tags).as_deref(),
+        ),
+    }
+    Ok(detect(&text))
+}
+
+fn infer_language(map: &mut Map<String, Value>) -> Result<usize, Error> {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    let mut detected = Vec::new();
+    for item in crate::item_refs(map)? {
+        let language = match item.language()? {
+            Some(language) => {
+                detected.push(None);
+                Some(String::from(language))
+            }
+            None => {
+                let language = detect_item_language(item.as_map())?;
+                detected.push(language);
+                language.map(String::from)
+            }
+        };
+        if let Some(language) = language {
+            *counts.entry(language).or_insert(0) += 1;
+        }
+    }
+
+    let feed = FeedRef::from(&*map);
+    let (feed_language, set_feed_language) = match feed.language()? {
+        Some(language) => (Some(String::from(language)), false),
+        None => {
+            // The most common item language or, without any, the language of the feed's text.
+            let mut most_common: Option<(&String, usize)> = None;
+            for (language, count) in &counts {
+                if most_common.map_or(true, |(_, most)| *count > most) {
+                    most_common = Some((language, *count));
+                }
+            }
+            let language = match most_common {
+                Some((language, _)) => Some(language.clone()),
+                None => {
+                    let mut text = String::new();
+                    push_text(&mut text, feed.title()?);
+                    push_text(&mut text, feed.description()?);
+                    detect(&text).map(String::from)
+                }
+            };
+            (language, true)
+        }
+    };
+
+    let mut filled = 0;
+    if set_feed_language {
+        if let Some(language) = &feed_language {
+            map.insert(String::from("language"), Value::String(language.clone()));
+            filled += 1;
+        }
+    }
+    if let Some(Value::Array(items)) = map.get_mut("items") {
+        for (item, language) in items.iter_mut().zip(detected) {
+            if let (Value::Object(item), Some(language)) = (item, language) {
+                // Items inherit the feed's language.
+                if feed_language.as_deref() != Some(language) {
+                    item.insert(
+                        String::from("language"),
+                        Value::String(String::from(language)),
+                    );
+                    filled += 1;
+                }
+            }
+        }
+    }
+    Ok(filled)
+}
+
+macro_rules! item_language_methods {
+    () => {
+        /// Detects the language of the item's title, summary, and content.
+        ///
+        /// The `content_text` is used if present; otherwise, the text of the `content_html` is
+        /// used. The item's `language` is not considered. If the language cannot be reliably
+        /// detected, then `None` is returned.
+        ///
+        /// The returned value is an [RFC 5646][rfc_5646] tag such as `en`.
+        ///
+        /// [rfc_5646]: https://tools.ietf.org/html/rfc5646
+        ///
+        /// # Errors
+        ///
+        /// If the item's text properties are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned.
+        #[cfg_attr(docsrs, doc(cfg(feature = "whatlang")))]
+        pub fn detect_language(&self) -> Result<Option<&'static str>, Error> {
+            detect_item_language(self.as_map())
+        }
+    };
+}
+
+impl Item {
+    item_language_methods!();
+}
+
+impl<'a> ItemMut<'a> {
+    item_language_methods!();
+}
+
+impl<'a> ItemRef<'a> {
+    item_language_methods!();
+}
+
+macro_rules! feed_language_methods {
+    () => {
+        /// Sets the `language` of the feed and its items when absent using language detection.
+        ///
+        /// Each item without a `language` is detected with `Item::detect_language`. If the feed
+        /// does not have a `language`, it is set to the most common item language or, if the
+        /// feed has no items with a language, the language detected from the feed's title and
+        /// description. Items inherit the feed's language, so an item's `language` is only set
+        /// if its detected language differs from the feed's.
+        ///
+        /// Returns the number of `language` properties which were set.
+        ///
+        /// # Errors
+        ///
+        /// If the items or the text properties are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned. The feed is not changed if an error is returned.
+        #[cfg_attr(docsrs, doc(cfg(feature = "whatlang")))]
+        pub fn infer_language(&mut self) -> Result<usize, Error> {
+            infer_language(self.as_map_mut())
+        }
+    };
+}
+
+impl Feed {
+    feed_language_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    feed_language_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infer_language() -> Result<(), Error> {
+        let mut feed = crate::from_value(serde_json::json!({
+            "title": "Example",
+            "items": [
+                { "id": "1", "content_text": "The weather is nice today and we are going for a long walk in the park." },
+                { "id": "2", "content_html": "<p>Nous allons au marché ce matin pour acheter du pain et des fromages.</p>" },
+                { "id": "3", "title": "This is a short post about the things that happened during the week." },
+                { "id": "4", "language": "de", "content_text": "Hello" }
+            ]
+        }))?;
+
+        assert_eq!(feed.infer_language()?, 2);
+        assert_eq!(feed.language()?, Some("en"));
+        let items = feed.items()?.unwrap();
+        assert_eq!(items[0].language()?, None);
+        assert_eq!(items[1].language()?, Some("fr"));
+        assert_eq!(items[3].language()?, Some("de"));
+        assert_eq!(items[0].detect_language()?, Some("en"));
+        assert_eq!(feed.infer_language()?, 0);
+        Ok(())
+    }
+}
@@ -0,0 +1,417 @@
+//! Structural limits for hosted feeds.
+//!
+//! Platforms which host feeds for many publishers can enforce quotas on the number of items,
+//! attachments, tags, and authors and on the size of extensions. The limits are checked during
+//! validation with [`ValidationOptions::limits`][crate::ValidationOptions::limits], and each
+//! exceeded limit can be reported with [`Feed::limit_violations`].
+//!
+//! ```
+//! use json_feed_model::{limits::{LimitKind, StructuralLimits}, ValidationOptions, Version};
+//!
+//! let feed = json_feed_model::from_str(r#"{
+//!     "version": "https://jsonfeed.org/version/1.1",
+//!     "title": "Example",
+//!     "items": [{ "id": "1", "content_text": "Hi", "tags": ["a", "b", "c"] }]
+//! }"#)?;
+//!
+//! let mut limits = StructuralLimits::new();
+//! limits.max_tags = Some(2);
+//!
+//! let violations = feed.limit_violations(&limits);
+//! assert_eq!(violations.len(), 1);
+//! assert_eq!(violations[0].kind, LimitKind::Tags);
+//! assert_eq!(violations[0].path, "/items/0/tags");
+//! assert_eq!((violations[0].limit, violations[0].actual), (2, 3));
+//!
+//! let mut options = ValidationOptions::new();
+//! options.limits = limits;
+//! assert!(!json_feed_model::validate_feed_map(feed.as_map(), &Version::Version1_1, &options));
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{format, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{format, string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{Feed, FeedMut, FeedRef};
+
+/// The maximum sizes of a feed's structures.
+///
+/// By default, there are no limits.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct StructuralLimits {
+    /// The maximum number of items in the feed.
+    pub max_items: Option<usize>,
+    /// The maximum number of attachments in an item.
+    pub max_attachments: Option<usize>,
+    /// The maximum number of tags in an item.
+    pub max_tags: Option<usize>,
+    /// The maximum number of `authors` in the feed or in an item.
+    pub max_authors: Option<usize>,
+    /// The maximum size of an extension's compact JSON encoding in bytes.
+    ///
+    /// Each extension of the feed, its items, and the items' authors and attachments is
+    /// checked separately.
+    pub max_extension_bytes: Option<usize>,
+}
+
+impl StructuralLimits {
+    /// Instantiates limits which allow any size.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// The kind of an exceeded limit.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub enum LimitKind {
+    /// The feed has too many items.
+    Items,
+    /// An item has too many attachments.
+    Attachments,
+    /// An item has too many tags.
+    Tags,
+    /// The feed or an item has too many authors.
+    Authors,
+    /// An extension is too large.
+    ExtensionBytes,
+}
+
+impl LimitKind {
+    /// Returns a stable string identifier for the limit.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LimitKind::Items => "max_items",
+            LimitKind::Attachments => "max_attachments",
+            LimitKind::Tags => "max_tags",
+            LimitKind::Authors => "max_authors",
+            LimitKind::ExtensionBytes => "max_extension_bytes",
+        }
+    }
+}
+
+impl core::fmt::Display for LimitKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An exceeded limit.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct LimitViolation {
+    /// The kind of limit.
+    pub kind: LimitKind,
+    /// A [JSON Pointer][json_pointer] to the array or extension which exceeds the limit.
+    ///
+    /// [json_pointer]: https://tools.ietf.org/html/rfc6901
+    pub path: String,
+    /// The limit.
+    pub limit: usize,
+    /// The number of values or bytes.
+    pub actual: usize,
+}
+
+fn check_len(
+    map: &Map<String, Value>,
+    path: &str,
+    key: &str,
+    kind: LimitKind,
+    limit: Option<usize>,
+    violations: &mut Vec<LimitViolation>,
+) {
+    if let (Some(limit), Some(Value::Array(values))) = (limit, map.get(key)) {
+        if values.len() > limit {
+            violations.push(LimitViolation {
+                kind,
+                path: format!("{}/{}", path, key),
+                limit,
+                actual: values.len(),
+            });
+        }
+    }
+}
+
+fn check_extensions(
+    map: &Map<String, Value>,
+    path: &str,
+    limits: &StructuralLimits,
+    violations: &mut Vec<LimitViolation>,
+) {
+    let limit = match limits.max_extension_bytes {
+        Some(limit) => limit,
+        None => return,
+    };
+    for (key, value) in map {
+        if !crate::is_extension_key(key) {
+            continue;
+        }
+        let actual = serde_json::to_vec(value).map_or(0, |bytes| bytes.len());
+        if actual > limit {
+            violations.push(LimitViolation {
+                kind: LimitKind::ExtensionBytes,
+                path: format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1")),
+                limit,
+                actual,
+            });
+        }
+    }
+}
+
+/// Checks the extensions of the objects in an array property.
+fn check_nested_extensions(
+    map: &Map<String, Value>,
+    path: &str,
+    key: &str,
+    limits: &StructuralLimits,
+    violations: &mut Vec<LimitViolation>,
+) {
+    match map.get(key) {
+        Some(Value::Object(obj)) => {
+            check_extensions(obj, &format!("{}/{}", path, key), limits, violations);
+        }
+        Some(Value::Array(values)) => {
+            for (index, value) in values.iter().enumerate() {
+                if let Value::Object(obj) = value {
+                    let path = format!("{}/{}/{}", path, key, index);
+                    check_extensions(obj, &path, limits, violations);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn item_violations(
+    map: &Map<String, Value>,
+    path: &str,
+    limits: &StructuralLimits,
+    violations: &mut Vec<LimitViolation>,
+) {
+    check_len(
+        map,
+        path,
+        "attachments",
+        LimitKind::Attachments,
+        limits.max_attachments,
+        violations,
+    );
+    check_len(
+        map,
+        path,
+        "tags",
+        LimitKind::Tags,
+        limits.max_tags,
+        violations,
+    );
+    check_len(
+        map,
+        path,
+        "authors",
+        LimitKind::Authors,
+        limits.max_authors,
+        violations,
+    );
+    check_extensions(map, path, limits, violations);
+    for key in &["author", "authors", "attachments"] {
+        check_nested_extensions(map, path, key, limits, violations);
+    }
+}
+
+/// Checks the feed's limits without checking the items' limits.
+fn feed_violations(
+    map: &Map<String, Value>,
+    limits: &StructuralLimits,
+    violations: &mut Vec<LimitViolation>,
+) {
+    check_len(
+        map,
+        "",
+        "items",
+        LimitKind::Items,
+        limits.max_items,
+        violations,
+    );
+    check_len(
+        map,
+        "",
+        "authors",
+        LimitKind::Authors,
+        limits.max_authors,
+        violations,
+    );
+    check_extensions(map, "", limits, violations);
+    for key in &["author", "authors", "hubs"] {
+        check_nested_extensions(map, "", key, limits, violations);
+    }
+}
+
+/// Returns true if the item does not exceed any limit.
+pub(crate) fn is_within_item_limits(map: &Map<String, Value>, limits: &StructuralLimits) -> bool {
+    let mut violations = Vec::new();
+    item_violations(map, "", limits, &mut violations);
+    violations.is_empty()
+}
+
+/// Returns true if the feed does not exceed any limit.
+///
+/// The items are checked by `is_within_item_limits`.
+pub(crate) fn is_within_feed_limits(map: &Map<String, Value>, limits: &StructuralLimits) -> bool {
+    let mut violations = Vec::new();
+    feed_violations(map, limits, &mut violations);
+    violations.is_empty()
+}
+
+fn limit_violations(map: &Map<String, Value>, limits: &StructuralLimits) -> Vec<LimitViolation> {
+    let mut violations = Vec::new();
+    feed_violations(map, limits, &mut violations);
+    if let Some(Value::Array(items)) = map.get("items") {
+        for (index, item) in items.iter().enumerate() {
+            if let Value::Object(item) = item {
+                let path = format!("/items/{}", index);
+                item_violations(item, &path, limits, &mut violations);
+            }
+        }
+    }
+    violations
+}
+
+macro_rules! limit_violations_methods {
+    () => {
+        /// Returns the limits which the feed and its items exceed.
+        ///
+        /// The feed's limits are reported before the items' limits. Values which are not the
+        /// expected JSON type are skipped.
+        #[must_use]
+        pub fn limit_violations(&self, limits: &StructuralLimits) -> Vec<LimitViolation> {
+            limit_violations(self.as_map(), limits)
+        }
+    };
+}
+
+impl Feed {
+    limit_violations_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    limit_violations_methods!();
+}
+
+impl<'a> FeedRef<'a> {
+    limit_violations_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::{vec, vec::Vec};
+    use serde_json::json;
+
+    #[test]
+    fn violations() -> Result<(), crate::Error> {
+        let feed = crate::from_value(json!({
+            "_big": "0123456789",
+            "authors": [{ "name": "A" }, { "name": "B" }],
+            "items": [
+                {
+                    "id": "1",
+                    "attachments": [
+                        { "url": "https://example.org/1", "_big": [1, 2, 3, 4, 5] },
+                        { "url": "https://example.org/2" }
+                    ],
+                    "_small": 1
+                },
+                { "id": "2" }
+            ]
+        }))?;
+        assert!(feed.limit_violations(&StructuralLimits::new()).is_empty());
+
+        let mut limits = StructuralLimits::new();
+        limits.max_items = Some(1);
+        limits.max_attachments = Some(1);
+        limits.max_authors = Some(1);
+        limits.max_extension_bytes = Some(8);
+        assert_eq!(
+            feed.limit_violations(&limits)
+                .iter()
+                .map(|v| (v.kind, v.path.as_str(), v.actual))
+                .collect::<Vec<_>>(),
+            vec![
+                (LimitKind::Items, "/items", 2),
+                (LimitKind::Authors, "/authors", 2),
+                (LimitKind::ExtensionBytes, "/_big", 12),
+                (LimitKind::Attachments, "/items/0/attachments", 2),
+                (LimitKind::ExtensionBytes, "/items/0/attachments/0/_big", 11),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn each_level() -> Result<(), crate::Error> {
+        let feed = crate::from_value(json!({
+            "version": "https://jsonfeed.org/version/1.1",
+            "title": "Example",
+            "author": { "name": "A", "_x/y": "0123456789" },
+            "hubs": [{ "type": "WebSub", "url": "https://example.org/hub", "_x~": "0123456789" }],
+            "items": [{
+                "id": "1",
+                "content_text": "Hi",
+                "tags": ["a", "b", "c"],
+                "authors": [{ "name": "A", "_x": "0123456789" }, { "name": "B" }, { "name": "C" }],
+                "author": { "name": "A", "_x": "0123456789" },
+                "_x": "0123456789"
+            }]
+        }))?;
+
+        let mut limits = StructuralLimits::new();
+        limits.max_items = Some(1);
+        limits.max_tags = Some(2);
+        limits.max_authors = Some(2);
+        limits.max_extension_bytes = Some(11);
+        assert_eq!(
+            feed.limit_violations(&limits)
+                .iter()
+                .map(|v| (v.kind, v.path.as_str(), v.limit, v.actual))
+                .collect::<Vec<_>>(),
+            vec![
+                (LimitKind::ExtensionBytes, "/author/_x~1y", 11, 12),
+                (LimitKind::ExtensionBytes, "/hubs/0/_x~0", 11, 12),
+                (LimitKind::Tags, "/items/0/tags", 2, 3),
+                (LimitKind::Authors, "/items/0/authors", 2, 3),
+                (LimitKind::ExtensionBytes, "/items/0/_x", 11, 12),
+                (LimitKind::ExtensionBytes, "/items/0/author/_x", 11, 12),
+                (LimitKind::ExtensionBytes, "/items/0/authors/0/_x", 11, 12),
+            ]
+        );
+
+        let mut options = crate::ValidationOptions::new();
+        options.limits = limits;
+        assert!(!crate::validate_feed_map(
+            feed.as_map(),
+            &crate::Version::Version1_1,
+            &options
+        ));
+
+        // Limits which are equal to the sizes are not exceeded.
+        limits.max_tags = Some(3);
+        limits.max_authors = Some(3);
+        limits.max_extension_bytes = Some(12);
+        assert!(feed.limit_violations(&limits).is_empty());
+        options.limits = limits;
+        assert!(crate::validate_feed_map(
+            feed.as_map(),
+            &crate::Version::Version1_1,
+            &options
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,623 @@
+//! Lints for JSON Feed data.
+//!
+//! A lint is a potential problem with the data which does not necessarily make the feed invalid
+//! according to the spec. For instance, a podcast episode without an audio attachment is a
+//! valid JSON Feed item but is probably a mistake.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{format, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{format, string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{
+    date::{Timestamp, ToTimestamp},
+    AuthorRef, Error, Feed, FeedMut, FeedRef, HubType, ItemRef, Version,
+};
+
+/// An identifier for a kind of lint.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub enum LintCode {
+    /// A podcast item does not have an attachment with an audio MIME type.
+    PodcastMissingAudio,
+    /// A podcast item's audio attachment does not have a duration.
+    PodcastMissingDuration,
+    /// An item's comments URL is not an absolute URL.
+    RelativeCommentsUrl,
+    /// A hub's type is not a known subscription protocol.
+    UnknownHubType,
+    /// An expired feed has hubs for update notifications.
+    ExpiredWithHubs,
+    /// An expired feed has a pagination URL.
+    ExpiredWithNextUrl,
+    /// An author's avatar URL does not use `https`.
+    InsecureAvatarUrl,
+    /// An author's avatar URL does not have an image file extension.
+    NonImageAvatarUrl,
+    /// An item's external URL is the same as its URL.
+    ExternalUrlSameAsUrl,
+    /// An item's `image` or `banner_image` is the URL of one of its attachments.
+    ImageIsAttachment,
+    /// An attachment's `size_in_bytes` is 0.
+    EmptyAttachment,
+    /// An item's `content_html` displays the item's `image`, which readers may also display.
+    ContentRepeatsImage,
+    /// An item's `date_published` is in the future. Only reported by `lints_at`.
+    FutureDated,
+    /// The feed's `version` is not a known JSON Feed version.
+    UnknownVersion,
+    /// Two items in the same series have the same part number.
+    DuplicateSeriesPart,
+    /// An item's `_in_reply_to` is its own `id`.
+    ReplyToSelf,
+    /// An author's `_role` is not in the expected vocabulary.
+    UnknownRole,
+}
+
+impl LintCode {
+    /// Returns a stable string identifier for the lint.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LintCode::PodcastMissingAudio => "podcast_missing_audio",
+            LintCode::PodcastMissingDuration => "podcast_missing_duration",
+            LintCode::RelativeCommentsUrl => "relative_comments_url",
+            LintCode::UnknownHubType => "unknown_hub_type",
+            LintCode::ExpiredWithHubs => "expired_with_hubs",
+            LintCode::ExpiredWithNextUrl => "expired_with_next_url",
+            LintCode::InsecureAvatarUrl => "insecure_avatar_url",
+            LintCode::NonImageAvatarUrl => "non_image_avatar_url",
+            LintCode::ExternalUrlSameAsUrl => "external_url_same_as_url",
+            LintCode::ImageIsAttachment => "image_is_attachment",
+            LintCode::EmptyAttachment => "empty_attachment",
+            LintCode::ContentRepeatsImage => "content_repeats_image",
+            LintCode::FutureDated => "future_dated",
+            LintCode::UnknownVersion => "unknown_version",
+            LintCode::DuplicateSeriesPart => "duplicate_series_part",
+            LintCode::ReplyToSelf => "reply_to_self",
+            LintCode::UnknownRole => "unknown_role",
+        }
+    }
+}
+
+impl core::fmt::Display for LintCode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A potential problem found in the JSON data.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Lint {
+    /// The kind of lint.
+    pub code: LintCode,
+    /// A [JSON Pointer][json_pointer] to the value which the lint applies to.
+    ///
+    /// [json_pointer]: https://tools.ietf.org/html/rfc6901
+    pub path: String,
+    /// A human readable description of the problem.
+    pub message: String,
+}
+
+impl Lint {
+    pub(crate) fn new<P, M>(code: LintCode, path: P, message: M) -> Self
+    where
+        P: Into<String>,
+        M: Into<String>,
+    {
+        Self {
+            code,
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+
+fn lint_version(feed: &FeedRef<'_>, lints: &mut Vec<Lint>) {
+    if let Ok(Some(version)) = feed.version() {
+        if let Version::Unknown(version) = Version::from(version) {
+            lints.push(Lint::new(
+                LintCode::UnknownVersion,
+                "/version",
+                format!("unknown version: {}", version),
+            ));
+        }
+    }
+}
+
+fn lint_hubs(feed: &FeedRef<'_>, lints: &mut Vec<Lint>) {
+    let hubs = match feed.hubs() {
+        Ok(Some(hubs)) => hubs,
+        Ok(None) | Err(_) => return,
+    };
+
+    for (hub_index, hub) in hubs.iter().enumerate() {
+        if let Ok(Some(HubType::Other(hub_type))) = hub.hub_type_enum() {
+            lints.push(Lint::new(
+                LintCode::UnknownHubType,
+                format!("/hubs/{}/type", hub_index),
+                format!("unknown hub type: {}", hub_type),
+            ));
+        }
+    }
+}
+
+fn lint_expired(feed: &FeedRef<'_>, lints: &mut Vec<Lint>) {
+    if !feed.is_expired().unwrap_or(false) {
+        return;
+    }
+
+    if feed.as_map().contains_key("hubs") {
+        lints.push(Lint::new(
+            LintCode::ExpiredWithHubs,
+            "/hubs",
+            "expired feed will not be updated but has hubs",
+        ));
+    }
+    if feed.as_map().contains_key("next_url") {
+        lints.push(Lint::new(
+            LintCode::ExpiredWithNextUrl,
+            "/next_url",
+            "expired feed has a next URL",
+        ));
+    }
+}
+
+const IMAGE_EXTENSIONS: &[&str] = &[
+    "apng", "avif", "bmp", "gif", "ico", "jpeg", "jpg", "png", "svg", "webp",
+];
+
+fn lint_avatar(author: &AuthorRef<'_>, path: &str, lints: &mut Vec<Lint>) {
+    let avatar = match author.avatar() {
+        Ok(Some(avatar)) => avatar,
+        Ok(None) | Err(_) => return,
+    };
+
+    if !crate::url::scheme(avatar).map_or(false, |scheme| scheme.eq_ignore_ascii_case("https")) {
+        lints.push(Lint::new(
+            LintCode::InsecureAvatarUrl,
+            format!("{}/avatar", path),
+            "avatar URL does not use https",
+        ));
+    }
+    // Avatar services often serve images from URLs without an extension.
+    if let Some(ext) = crate::url::path_extension(avatar) {
+        if !IMAGE_EXTENSIONS
+            .iter()
+            .any(|image_ext| image_ext.eq_ignore_ascii_case(ext))
+        {
+            lints.push(Lint::new(
+                LintCode::NonImageAvatarUrl,
+                format!("{}/avatar", path),
+                format!("avatar URL has a non-image extension: {}", ext),
+            ));
+        }
+    }
+}
+
+fn lint_authors(map: &Map<String, Value>, path: &str, lints: &mut Vec<Lint>) {
+    if let Some(Value::Object(author)) = map.get("author") {
+        lint_avatar(&AuthorRef::from(author), &format!("{}/author", path), lints);
+    }
+    if let Some(Value::Array(authors)) = map.get("authors") {
+        for (author_index, author) in authors.iter().enumerate() {
+            if let Value::Object(author) = author {
+                lint_avatar(
+                    &AuthorRef::from(author),
+                    &format!("{}/authors/{}", path, author_index),
+                    lints,
+                );
+            }
+        }
+    }
+}
+
+fn lint_all_authors(feed: &FeedRef<'_>, lints: &mut Vec<Lint>) {
+    lint_authors(feed.as_map(), "", lints);
+    if let Ok(Some(items)) = feed.items() {
+        for (item_index, item) in items.iter().enumerate() {
+            lint_authors(item.as_map(), &format!("/items/{}", item_index), lints);
+        }
+    }
+}
+
+fn lint_external_urls(feed: &FeedRef<'_>, lints: &mut Vec<Lint>) {
+    let items = match feed.items() {
+        Ok(Some(items)) => items,
+        Ok(None) | Err(_) => return,
+    };
+
+    for (item_index, item) in items.iter().enumerate() {
+        if let (Ok(Some(url)), Ok(Some(external_url))) = (item.url(), item.external_url()) {
+            if url == external_url {
+                lints.push(Lint::new(
+                    LintCode::ExternalUrlSameAsUrl,
+                    format!("/items/{}/external_url", item_index),
+                    "external URL should link to a different page than the item's URL",
+                ));
+            }
+        }
+    }
+}
+
+/// Returns true if the item's `content_html` has an `img` element showing the image.
+fn content_shows_image(item: &ItemRef<'_>, image: &str) -> bool {
+    let html = match item.content_html() {
+        Ok(Some(html)) => html,
+        Ok(None) | Err(_) => return false,
+    };
+    let base = item.url().ok().flatten();
+    let resolve = |url: &str| match base {
+        Some(base) => crate::url::resolve(base, url),
+        None => Some(String::from(url)),
+    };
+    let image = resolve(image);
+
+    let mut src = String::new();
+    crate::html::tokens(html).any(|token| match token {
+        crate::html::Token::Tag(tag) if tag.is("img") && !tag.is_closing => {
+            tag.attr("src").map_or(false, |value| {
+                src.clear();
+                crate::html::decode_entities_into(value.trim(), &mut src);
+                image.is_some() && resolve(&src) == image
+            })
+        }
+        _ => false,
+    })
+}
+
+fn lint_item_references(item: &ItemRef<'_>, path: &str, lints: &mut Vec<Lint>) {
+    let attachments = item.attachments().ok().flatten().unwrap_or_default();
+    let attachment_urls = attachments
+        .iter()
+        .filter_map(|attachment| attachment.url().ok().flatten())
+        .collect::<Vec<_>>();
+
+    for (key, image) in [
+        ("image", item.image()),
+        ("banner_image", item.banner_image()),
+    ] {
+        if let Ok(Some(image)) = image {
+            if attachment_urls.contains(&image) {
+                lints.push(Lint::new(
+                    LintCode::ImageIsAttachment,
+                    format!("{}/{}", path, key),
+                    format!("{} is also an attachment URL", key),
+                ));
+            }
+        }
+    }
+
+    for (attachment_index, attachment) in attachments.iter().enumerate() {
+        if let Ok(Some(0)) = attachment.size_in_bytes() {
+            lints.push(Lint::new(
+                LintCode::EmptyAttachment,
+                format!("{}/attachments/{}/size_in_bytes", path, attachment_index),
+                "attachment size is 0 bytes",
+            ));
+        }
+    }
+
+    if let Ok(Some(image)) = item.image() {
+        if content_shows_image(item, image) {
+            lints.push(Lint::new(
+                LintCode::ContentRepeatsImage,
+                format!("{}/content_html", path),
+                "content displays the item's image which readers may show as well",
+            ));
+        }
+    }
+}
+
+fn lint_references(feed: &FeedRef<'_>, lints: &mut Vec<Lint>) {
+    if let Ok(Some(items)) = feed.items() {
+        for (item_index, item) in items.iter().enumerate() {
+            lint_item_references(item, &format!("/items/{}", item_index), lints);
+        }
+    }
+}
+
+/// Reports scheduled items which should not be served to readers yet.
+fn lint_future_dates(feed: &FeedRef<'_>, now: &Timestamp, lints: &mut Vec<Lint>) {
+    if let Ok(Some(items)) = feed.items() {
+        for (item_index, item) in items.iter().enumerate() {
+            if let Ok(true) = crate::date::is_scheduled(item, now) {
+                lints.push(Lint::new(
+                    LintCode::FutureDated,
+                    format!("/items/{}/date_published", item_index),
+                    "item is published in the future; see `published_view`",
+                ));
+            }
+        }
+    }
+}
+
+fn lint_feed(map: &Map<String, Value>) -> Vec<Lint> {
+    let mut lints = Vec::new();
+    let feed_ref = FeedRef::from(map);
+    lint_version(&feed_ref, &mut lints);
+    lint_expired(&feed_ref, &mut lints);
+    lint_hubs(&feed_ref, &mut lints);
+    lint_all_authors(&feed_ref, &mut lints);
+    lint_external_urls(&feed_ref, &mut lints);
+    lint_references(&feed_ref, &mut lints);
+    crate::ext::podcast::lint(&feed_ref, &mut lints);
+    crate::ext::interactions::lint(&feed_ref, &mut lints);
+    crate::ext::series::lint(&feed_ref, &mut lints);
+    lints
+}
+
+fn lint_feed_at<T: ToTimestamp>(map: &Map<String, Value>, now: &T) -> Result<Vec<Lint>, Error> {
+    let now = now.to_timestamp()?;
+    let mut lints = lint_feed(map);
+    lint_future_dates(&FeedRef::from(map), &now, &mut lints);
+    Ok(lints)
+}
+
+impl Feed {
+    /// Returns the potential problems found in the feed.
+    ///
+    /// Lints do not affect whether the feed is valid. See `is_valid` for spec conformance.
+    #[must_use]
+    pub fn lints(&self) -> Vec<Lint> {
+        lint_feed(&self.value)
+    }
+
+    /// Returns the potential problems found in the feed, including the items published after
+    /// `now`.
+    ///
+    /// The lints depend on the current time, so they are not part of `lints`.
+    ///
+    /// # Errors
+    ///
+    /// If `now` is not a valid date, then `Error::InvalidDate` is returned.
+    pub fn lints_at<T: ToTimestamp>(&self, now: T) -> Result<Vec<Lint>, Error> {
+        lint_feed_at(&self.value, &now)
+    }
+}
+
+impl<'a> FeedMut<'a> {
+    /// Returns the potential problems found in the feed.
+    ///
+    /// Lints do not affect whether the feed is valid. See `is_valid` for spec conformance.
+    #[must_use]
+    pub fn lints(&self) -> Vec<Lint> {
+        lint_feed(self.value)
+    }
+
+    /// Returns the potential problems found in the feed, including the items published after
+    /// `now`.
+    ///
+    /// The lints depend on the current time, so they are not part of `lints`.
+    ///
+    /// # Errors
+    ///
+    /// If `now` is not a valid date, then `Error::InvalidDate` is returned.
+    pub fn lints_at<T: ToTimestamp>(&self, now: T) -> Result<Vec<Lint>, Error> {
+        lint_feed_at(self.value, &now)
+    }
+}
+
+impl<'a> FeedRef<'a> {
+    /// Returns the potential problems found in the feed.
+    ///
+    /// Lints do not affect whether the feed is valid. See `is_valid` for spec conformance.
+    #[must_use]
+    pub fn lints(&self) -> Vec<Lint> {
+        lint_feed(self.value)
+    }
+
+    /// Returns the potential problems found in the feed, including the items published after
+    /// `now`.
+    ///
+    /// The lints depend on the current time, so they are not part of `lints`.
+    ///
+    /// # Errors
+    ///
+    /// If `now` is not a valid date, then `Error::InvalidDate` is returned.
+    pub fn lints_at<T: ToTimestamp>(&self, now: T) -> Result<Vec<Lint>, Error> {
+        lint_feed_at(self.value, &now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::{string::String, vec, vec::Vec};
+
+    #[test]
+    fn unknown_hub_type() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "hubs": [
+                { "type": "WebSub", "url": "https://example.org/hub" },
+                { "type": "pubsubhubbub", "url": "https://example.org/pshb" }
+            ]
+        }))?;
+
+        let lints = feed.lints();
+        assert_eq!(lints.len(), 1);
+        assert_eq!(lints[0].code, LintCode::UnknownHubType);
+        assert_eq!(lints[0].path, "/hubs/1/type");
+
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_version() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "version": "https://jsonfeed.org/version/2"
+        }))?;
+
+        let lints = feed.lints();
+        assert_eq!(lints.len(), 1);
+        assert_eq!(lints[0].code, LintCode::UnknownVersion);
+        assert_eq!(lints[0].path, "/version");
+
+        Ok(())
+    }
+
+    #[test]
+    fn expired_contradictions() -> Result<(), Error> {
+        let mut feed = crate::from_value(serde_json::json!({
+            "next_url": "https://example.org/feed.json?page=2",
+            "hubs": [{ "type": "WebSub", "url": "https://example.org/hub" }]
+        }))?;
+        assert!(feed.lints().is_empty());
+
+        feed.expire();
+        let codes = feed.lints().iter().map(|l| l.code).collect::<Vec<_>>();
+        assert_eq!(
+            codes,
+            vec![LintCode::ExpiredWithHubs, LintCode::ExpiredWithNextUrl]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn avatar_urls() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "authors": [
+                { "avatar": "https://example.org/me.png" },
+                { "avatar": "http://example.org/me.jpg" }
+            ],
+            "items": [
+                { "id": "1", "author": { "avatar": "https://example.org/me.html" } },
+                { "id": "2", "authors": [{ "avatar": "https://example.org/avatar?s=512" }] }
+            ]
+        }))?;
+
+        let lints = feed.lints();
+        assert_eq!(lints.len(), 2);
+        assert_eq!(lints[0].code, LintCode::InsecureAvatarUrl);
+        assert_eq!(lints[0].path, "/authors/1/avatar");
+        assert_eq!(lints[1].code, LintCode::NonImageAvatarUrl);
+        assert_eq!(lints[1].path, "/items/0/author/avatar");
+
+        Ok(())
+    }
+
+    #[test]
+    fn link_posts() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "items": [
+                { "id": "1", "url": "https://example.org/1" },
+                {
+                    "id": "2",
+                    "url": "https://example.org/2",
+                    "external_url": "https://example.com/article"
+                },
+                {
+                    "id": "3",
+                    "url": "https://example.org/3",
+                    "external_url": "https://example.org/3"
+                }
+            ]
+        }))?;
+
+        let items = feed.items()?.unwrap_or_default();
+        assert!(!items[0].is_link_post()?);
+        assert_eq!(items[0].primary_link()?, Some("https://example.org/1"));
+        assert!(items[1].is_link_post()?);
+        assert_eq!(
+            items[1].primary_link()?,
+            Some("https://example.com/article")
+        );
+
+        let lints = feed.lints();
+        assert_eq!(lints.len(), 1);
+        assert_eq!(lints[0].code, LintCode::ExternalUrlSameAsUrl);
+        assert_eq!(lints[0].path, "/items/2/external_url");
+
+        Ok(())
+    }
+
+    #[test]
+    fn broken_references() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "items": [
+                {
+                    "id": "1",
+                    "url": "https://example.org/1/",
+                    "image": "https://example.org/1/cover.jpg",
+                    "banner_image": "https://example.org/1/audio.mp3",
+                    "content_html": "<p>Hi</p><IMG alt='' src=\"cover.jpg\">",
+                    "attachments": [
+                        { "url": "https://example.org/1/audio.mp3", "mime_type": "audio/mpeg" },
+                        { "url": "https://example.org/1/a.txt", "mime_type": "text/plain", "size_in_bytes": 0 }
+                    ]
+                },
+                {
+                    "id": "2",
+                    "image": "https://example.org/2/cover.jpg",
+                    "content_html": "<img src=\"https://example.org/other.jpg\">"
+                }
+            ]
+        }))?;
+
+        let lints = feed
+            .lints()
+            .into_iter()
+            .map(|lint| (lint.code, lint.path))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lints,
+            vec![
+                (
+                    LintCode::ImageIsAttachment,
+                    String::from("/items/0/banner_image")
+                ),
+                (
+                    LintCode::EmptyAttachment,
+                    String::from("/items/0/attachments/1/size_in_bytes")
+                ),
+                (
+                    LintCode::ContentRepeatsImage,
+                    String::from("/items/0/content_html")
+                ),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn future_dates() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "items": [
+                { "id": "1", "date_published": "2021-01-01T00:00:00Z" },
+                { "id": "2", "date_published": "9999-01-01T00:00:00Z" }
+            ]
+        }))?;
+
+        assert!(feed.lints().is_empty());
+
+        let lints = feed.lints_at(Timestamp::parse("2022-01-01T00:00:00Z")?)?;
+        assert_eq!(lints.len(), 1);
+        assert_eq!(lints[0].code, LintCode::FutureDated);
+        assert_eq!(lints[0].path, "/items/1/date_published");
+
+        assert!(feed
+            .lints_at(Timestamp::parse("9999-01-02T00:00:00Z")?)?
+            .is_empty());
+        assert!(matches!(feed.lints_at("tomorrow"), Err(Error::InvalidDate)));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "gravatar")]
+    #[test]
+    fn gravatar_avatar() -> Result<(), Error> {
+        let mut author = crate::Author::new();
+        author.set_avatar_from_email(" MyEmailAddress@example.com ", 512);
+        assert_eq!(
+            author.avatar()?,
+            Some("https://www.gravatar.com/avatar/84059b07d4be67b806386c0aad8070a23f18836bbaae342275dc0a83414c32ee?s=512")
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+//! Locale aware formatting of item dates for display.
+//!
+//! ```
+//! use json_feed_model::{date::Timestamp, locale::DateStyle, Item};
+//!
+//! let mut item = Item::new();
+//! item.set_date_published("2021-06-01T12:00:00+02:00");
+//!
+//! assert_eq!(
+//!     item.format_date_published("de-DE", DateStyle::Date)?.as_deref(),
+//!     Some("01.06.2021")
+//! );
+//!
+//! let now = Timestamp::parse("2021-06-01T13:00:00Z")?;
+//! assert_eq!(
+//!     item.format_date_published("en", DateStyle::Relative(now))?.as_deref(),
+//!     Some("3 hours ago")
+//! );
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+#[cfg(feature = "std")]
+use std::{format, string::String};
+
+use chrono::{DateTime, FixedOffset, Locale};
+use serde_json::{Map, Value};
+
+use crate::{date::Timestamp, ext::__private::str_prop, Error, Item, ItemMut, ItemRef};
+
+/// How a date is displayed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum DateStyle {
+    /// The locale's date format (e.g. `06/01/2021` for `en-US` or `01.06.2021` for `de-DE`).
+    Date,
+    /// The locale's date and time format.
+    DateTime,
+    /// The time relative to the given current time (e.g. "3 hours ago" or "in 2 days").
+    ///
+    /// Relative times are translated for English, French, German, and Spanish. Other
+    /// languages use English.
+    Relative(Timestamp),
+}
+
+/// Returns the locale for a POSIX (`de_DE`) or BCP 47 (`de-DE`) locale name.
+///
+/// A language without a region uses the language's main region (e.g. `de` is `de_DE`). Unknown
+/// locales use the POSIX locale.
+fn parse_locale(name: &str) -> Locale {
+    let name = name.replace('-', "_");
+    if let Ok(locale) = Locale::try_from(name.as_str()) {
+        return locale;
+    }
+    let language = name.split('_').next().unwrap_or_default();
+    let region = if language == "en" {
+        String::from("US")
+    } else {
+        language.to_ascii_uppercase()
+    };
+    Locale::try_from(format!("{}_{}", language, region).as_str()).unwrap_or(Locale::POSIX)
+}
+
+fn format_date_published(
+    map: &Map<String, Value>,
+    locale: &str,
+    style: DateStyle,
+) -> Result<Option<String>, Error> {
+    let date = match str_prop::get(map, None, "date_published")? {
+        Some(date) => Timestamp::parse(date)?,
+        None => return Ok(None),
+    };
+    if let DateStyle::Relative(now) = style {
+        return Ok(Some(crate::date::relative(locale, &date, &now)));
+    }
+
+    let offset =
+        FixedOffset::east_opt(i32::from(date.offset_minutes()) * 60).ok_or(Error::InvalidDate)?;
+    let date_time = DateTime::from_timestamp(date.unix_seconds(), date.nanosecond())
+        .ok_or(Error::InvalidDate)?
+        .with_timezone(&offset);
+    let format = match style {
+        DateStyle::DateTime => "%c",
+        _ => "%x",
+    };
+    Ok(Some(
+        date_time
+            .format_localized(format, parse_locale(locale))
+            .to_string(),
+    ))
+}
+
+macro_rules! locale_methods {
+    () => {
+        /// Formats the `date_published` for display in a locale.
+        ///
+        /// The locale is a POSIX (`de_DE`) or BCP 47 (`de-DE`) name. Unknown locales are
+        /// formatted with the POSIX locale. The date is displayed in the offset it is written
+        /// with. If the item does not have a `date_published`, then `None` is returned.
+        ///
+        /// # Errors
+        ///
+        /// If the `date_published` is not a JSON string, then `Error::UnexpectedType` is
+        /// returned. If it is not a valid date, then `Error::InvalidDate` is returned.
+        pub fn format_date_published(
+            &self,
+            locale: &str,
+            style: DateStyle,
+        ) -> Result<Option<String>, Error> {
+            format_date_published(self.as_map(), locale, style)
+        }
+    };
+}
+
+impl Item {
+    locale_methods!();
+}
+
+impl<'a> ItemMut<'a> {
+    locale_methods!();
+}
+
+impl<'a> ItemRef<'a> {
+    locale_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locale_formats() -> Result<(), Error> {
+        let mut item = Item::new();
+        assert_eq!(item.format_date_published("en-US", DateStyle::Date)?, None);
+
+        item.set_date_published("2021-06-01T23:30:00-04:00");
+        assert_eq!(
+            item.format_date_published("en-US", DateStyle::Date)?
+                .as_deref(),
+            Some("06/01/2021")
+        );
+        assert_eq!(
+            item.format_date_published("fr", DateStyle::Date)?
+                .as_deref(),
+            Some("01/06/2021")
+        );
+        assert_eq!(
+            item.format_date_published("unknown", DateStyle::Date)?
+                .as_deref(),
+            Some("06/01/21")
+        );
+
+        item.set_date_published("yesterday");
+        assert!(matches!(
+            item.format_date_published("en-US", DateStyle::Date),
+            Err(Error::InvalidDate)
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+//! Social media meta tags for items.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::string::String;
+#[cfg(feature = "std")]
+use std::string::String;
+
+use serde_json::{Map, Value};
+
+use crate::{ext::__private::str_prop, Error, FeedRef, Item, ItemMut, ItemRef};
+
+/// The maximum length of a description derived from the item's content.
+const MAX_DESCRIPTION_CHARS: usize = 200;
+
+fn push_meta(out: &mut String, attr: &str, name: &str, content: &str) {
+    out.push_str("<meta ");
+    out.push_str(attr);
+    out.push_str("=\"");
+    out.push_str(name);
+    out.push_str("\" content=\"");
+    crate::html::escape_into(content, out);
+    out.push_str("\">\n");
+}
+
+fn to_meta_tags(item: &Map<String, Value>, feed: &FeedRef<'_>) -> Result<String, Error> {
+    let mut out = String::new();
+    let get = |key| str_prop::get(item, None, key);
+
+    push_meta(&mut out, "property", "og:type", "article");
+    if let Some(title) = get("title")? {
+        push_meta(&mut out, "property", "og:title", title);
+    }
+    if let Some(description) = crate::content::summary_text(item, MAX_DESCRIPTION_CHARS)? {
+        push_meta(&mut out, "property", "og:description", &description);
+    }
+    if let Some(url) = get("url")? {
+        push_meta(&mut out, "property", "og:url", url);
+    }
+    let image = match get("image")? {
+        Some(image) => Some(image),
+        None => get("banner_image")?,
+    };
+    if let Some(image) = image {
+        push_meta(&mut out, "property", "og:image", image);
+    }
+    if let Some(site_name) = feed.title()? {
+        push_meta(&mut out, "property", "og:site_name", site_name);
+    }
+    if let Some(language) = get("language")?.or(feed.language()?) {
+        // Open Graph locales use underscores (e.g. `en_US`).
+        push_meta(
+            &mut out,
+            "property",
+            "og:locale",
+            &language.replace('-', "_"),
+        );
+    }
+    if let Some(published) = get("date_published")? {
+        push_meta(&mut out, "property", "article:published_time", published);
+    }
+    if let Some(modified) = get("date_modified")? {
+        push_meta(&mut out, "property", "article:modified_time", modified);
+    }
+    let card = if image.is_some() {
+        "summary_large_image"
+    } else {
+        "summary"
+    };
+    push_meta(&mut out, "name", "twitter:card", card);
+    if let Some(robots) = crate::ext::indexing::robots(&ItemRef::from(item))? {
+        push_meta(&mut out, "name", "robots", robots);
+    }
+    Ok(out)
+}
+
+macro_rules! meta_methods {
+    () => {
+        /// Returns [Open Graph][open_graph] and Twitter card `meta` tags for the item's page.
+        ///
+        /// The feed provides the site name and the default language. Each tag is on its own
+        /// line and the attribute values are HTML escaped. The description is the `summary` or
+        /// the start of the item's content. A `robots` tag is added if the item has flags in the
+        /// `_indexing` extension.
+        ///
+        /// # Errors
+        ///
+        /// If the item's or feed's properties are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned.
+        ///
+        /// [open_graph]: https://ogp.me/
+        pub fn to_meta_tags(&self, feed: &FeedRef<'_>) -> Result<String, Error> {
+            to_meta_tags(self.as_map(), feed)
+        }
+    };
+}
+
+impl Item {
+    meta_methods!();
+}
+
+impl<'a> ItemMut<'a> {
+    meta_methods!();
+}
+
+impl<'a> ItemRef<'a> {
+    meta_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ext::indexing::IndexingMut;
+
+    #[test]
+    fn meta_tags() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "title": "Tom & Jerry's Blog",
+            "language": "en-US",
+            "items": [{
+                "id": "1",
+                "title": "A \"quoted\" title",
+                "url": "https://example.org/1",
+                "image": "https://example.org/1.png",
+                "content_html": "<p>Some <b>bold</b> text.</p>",
+                "date_published": "2021-01-01T00:00:00Z"
+            }]
+        }))?;
+        let feed_ref = FeedRef::from(feed.as_map());
+        let items = feed.items()?.unwrap_or_default();
+
+        assert_eq!(
+            items[0].to_meta_tags(&feed_ref)?,
+            "<meta property=\"og:type\" content=\"article\">\n\
+             <meta property=\"og:title\" content=\"A &quot;quoted&quot; title\">\n\
+             <meta property=\"og:description\" content=\"Some bold text.\">\n\
+             <meta property=\"og:url\" content=\"https://example.org/1\">\n\
+             <meta property=\"og:image\" content=\"https://example.org/1.png\">\n\
+             <meta property=\"og:site_name\" content=\"Tom &amp; Jerry&#39;s Blog\">\n\
+             <meta property=\"og:locale\" content=\"en_US\">\n\
+             <meta property=\"article:published_time\" content=\"2021-01-01T00:00:00Z\">\n\
+             <meta name=\"twitter:card\" content=\"summary_large_image\">\n"
+        );
+
+        let mut item = Item::new();
+        item.set_noindex(true);
+        assert!(item
+            .to_meta_tags(&feed_ref)?
+            .ends_with("<meta name=\"robots\" content=\"noindex\">\n"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,192 @@
+//! Metrics about how a feed changes between fetches.
+//!
+//! ```
+//! let prev = json_feed_model::from_str(r#"{ "items": [{ "id": "1" }, { "id": "2" }] }"#)?;
+//! let next = json_feed_model::from_str(
+//!     r#"{ "items": [{ "id": "3" }, { "id": "2", "title": "Edited" }] }"#,
+//! )?;
+//!
+//! let metrics = json_feed_model::metrics::compare(&prev, &next)?;
+//! assert_eq!((metrics.new, metrics.updated, metrics.removed), (1, 1, 1));
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{collections::BTreeMap, string::String};
+#[cfg(feature = "std")]
+use std::{collections::BTreeMap, string::String};
+
+use core::time::Duration;
+
+use serde_json::{Map, Value};
+
+use crate::{ext::__private::str_prop, Error, Feed};
+
+/// The changes between two fetches of a feed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct ChangeMetrics {
+    /// The number of items which are only in the newer feed.
+    pub new: usize,
+    /// The number of items which are in both feeds but are different.
+    pub updated: usize,
+    /// The number of items which are only in the older feed.
+    pub removed: usize,
+    /// The number of items which are the same in both feeds.
+    pub unchanged: usize,
+    /// The fraction of items which are new, updated, or removed (from 0.0 to 1.0).
+    ///
+    /// The fraction is relative to the number of distinct items in both feeds. If neither
+    /// feed has items, then the rate is 0.0.
+    pub churn_rate: f64,
+    /// The mean duration between publications of the items in the newer feed.
+    pub average_publish_interval: Option<Duration>,
+}
+
+/// Returns the items keyed by `id`.
+///
+/// If multiple items have the same `id`, the first item is used. Items without an `id` are
+/// skipped.
+fn items_by_id(feed: &Feed) -> Result<BTreeMap<&str, &Map<String, Value>>, Error> {
+    let mut items = BTreeMap::new();
+    if let Some(values) = feed.as_map().get("items") {
+        for value in values.as_array().ok_or(Error::UnexpectedType)? {
+            let item = value.as_object().ok_or(Error::UnexpectedType)?;
+            if let Some(id) = str_prop::get(item, None, "id")? {
+                items.entry(id).or_insert(item);
+            }
+        }
+    }
+    Ok(items)
+}
+
+/// Compares two fetches of a feed.
+///
+/// Items are matched by their `id`. Items without an `id` are not counted.
+///
+/// # Errors
+///
+/// If the items or their properties are not the expected JSON type, then
+/// `Error::UnexpectedType` is returned.
+pub fn compare(prev: &Feed, next: &Feed) -> Result<ChangeMetrics, Error> {
+    let prev_items = items_by_id(prev)?;
+    let next_items = items_by_id(next)?;
+
+    let mut new = 0;
+    let mut updated = 0;
+    let mut unchanged = 0;
+    for (id, next_item) in &next_items {
+        match prev_items.get(id) {
+            None => new += 1,
+            Some(prev_item) if prev_item != next_item => updated += 1,
+            Some(_) => unchanged += 1,
+        }
+    }
+    let removed = prev_items
+        .keys()
+        .filter(|id| !next_items.contains_key(*id))
+        .count();
+
+    let total = new + updated + removed + unchanged;
+    #[allow(clippy::cast_precision_loss)]
+    let churn_rate = if total == 0 {
+        0.0
+    } else {
+        (new + updated + removed) as f64 / total as f64
+    };
+
+    Ok(ChangeMetrics {
+        new,
+        updated,
+        removed,
+        unchanged,
+        churn_rate,
+        average_publish_interval: next.publication_stats()?.mean_gap,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changes() -> Result<(), Error> {
+        let prev = crate::from_value(serde_json::json!({
+            "items": [
+                { "id": "1", "date_published": "2021-01-01T00:00:00Z" },
+                { "id": "2", "date_published": "2021-01-02T00:00:00Z" },
+                { "id": "3", "title": "Old" }
+            ]
+        }))?;
+        let next = crate::from_value(serde_json::json!({
+            "items": [
+                { "id": "2", "date_published": "2021-01-02T00:00:00Z" },
+                { "id": "3", "title": "New" },
+                { "id": "4", "date_published": "2021-01-04T00:00:00Z" },
+                { "title": "No id" }
+            ]
+        }))?;
+
+        let metrics = compare(&prev, &next)?;
+        assert_eq!(metrics.new, 1);
+        assert_eq!(metrics.updated, 1);
+        assert_eq!(metrics.removed, 1);
+        assert_eq!(metrics.unchanged, 1);
+        assert!((metrics.churn_rate - 0.75).abs() < f64::EPSILON);
+        assert_eq!(
+            metrics.average_publish_interval,
+            Some(Duration::from_secs(2 * 86_400))
+        );
+
+        let metrics = compare(&Feed::new(), &Feed::new())?;
+        assert!(metrics.churn_rate.abs() < f64::EPSILON);
+
+        Ok(())
+    }
+
+    #[test]
+    fn duplicate_ids_and_identical_feeds() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "items": [
+                { "id": "1", "title": "First" },
+                { "id": "1", "title": "Second" }
+            ]
+        }))?;
+        let next = crate::from_value(serde_json::json!({
+            "items": [{ "id": "1", "title": "First" }]
+        }))?;
+
+        let metrics = compare(&feed, &next)?;
+        assert_eq!((metrics.new, metrics.updated, metrics.removed), (0, 0, 0));
+        assert_eq!(metrics.unchanged, 1);
+        assert!(metrics.churn_rate.abs() < f64::EPSILON);
+        assert_eq!(metrics.average_publish_interval, None);
+
+        let metrics = compare(&Feed::new(), &next)?;
+        assert_eq!(metrics.new, 1);
+        assert!((metrics.churn_rate - 1.0).abs() < f64::EPSILON);
+
+        Ok(())
+    }
+
+    #[test]
+    fn unexpected_types() -> Result<(), Error> {
+        let valid = crate::from_value(serde_json::json!({ "items": [{ "id": "1" }] }))?;
+        for invalid in [
+            serde_json::json!({ "items": {} }),
+            serde_json::json!({ "items": ["1"] }),
+            serde_json::json!({ "items": [{ "id": 1 }] }),
+        ] {
+            let invalid = crate::from_value(invalid)?;
+            assert!(matches!(
+                compare(&invalid, &valid),
+                Err(Error::UnexpectedType)
+            ));
+            assert!(matches!(
+                compare(&valid, &invalid),
+                Err(Error::UnexpectedType)
+            ));
+        }
+        Ok(())
+    }
+}
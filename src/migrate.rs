@@ -0,0 +1,267 @@
+//! Renaming of extensions.
+//!
+//! A publisher which renames its extension namespace can migrate existing feeds with
+//! [`Feed::rename_extension`] or, for several extensions at once, with
+//! [`Feed::migrate_extensions`]. The extension is renamed wherever extensions can be used: in
+//! the feed, its hubs and authors, and each item and the item's authors and attachments.
+//!
+//! ```
+//! use json_feed_model::migrate::ExtensionMapping;
+//!
+//! let mut feed = json_feed_model::from_str(r#"{
+//!     "_blue_shed": { "about": "https://blueshed-podcasts.com/json-feed-extension-docs" },
+//!     "items": [{ "id": "1", "_blue_shed": { "explicit": false } }]
+//! }"#)?;
+//!
+//! let mut mapping = ExtensionMapping::new();
+//! mapping.rename("_blue_shed", "_blueshed");
+//! mapping.mark_migrated = true;
+//!
+//! let migration = feed.migrate_extensions(&mapping);
+//! assert_eq!(migration.renamed, vec!["/_blue_shed", "/items/0/_blue_shed"]);
+//! assert!(migration.conflicts.is_empty());
+//! assert_eq!(
+//!     feed.as_map()["items"][0],
+//!     serde_json::json!({
+//!         "id": "1",
+//!         "_blueshed": { "explicit": false },
+//!         "_migrated_from": { "_blueshed": "_blue_shed" }
+//!     })
+//! );
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{format, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{format, string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{Feed, FeedMut};
+
+/// The key of the object which maps renamed extension keys to their previous keys.
+pub const MIGRATED_FROM_KEY: &str = "_migrated_from";
+
+/// The extension keys to rename.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ExtensionMapping {
+    /// The old and new keys in the order the renames are applied.
+    pub renames: Vec<(String, String)>,
+    /// If true, each object with a renamed extension records the previous key in its
+    /// `_migrated_from` object.
+    pub mark_migrated: bool,
+}
+
+impl ExtensionMapping {
+    /// Instantiates an empty mapping without markers.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a rename from the old key to the new key.
+    pub fn rename<T, U>(&mut self, old_key: T, new_key: U) -> &mut Self
+    where
+        T: Into<String>,
+        U: Into<String>,
+    {
+        self.renames.push((old_key.into(), new_key.into()));
+        self
+    }
+}
+
+/// The results of renaming extensions.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ExtensionMigration {
+    /// The [JSON Pointers][json_pointer] to the old keys which were renamed.
+    ///
+    /// [json_pointer]: https://tools.ietf.org/html/rfc6901
+    pub renamed: Vec<String>,
+    /// The JSON Pointers to the old keys which were not renamed because the object already has
+    /// a value for the new key.
+    pub conflicts: Vec<String>,
+}
+
+fn pointer(path: &str, key: &str) -> String {
+    format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"))
+}
+
+/// Records the previous key of a renamed extension.
+///
+/// If the old key was itself migrated, the original key is kept.
+fn mark_migrated(map: &mut Map<String, Value>, old_key: &str, new_key: &str) {
+    let markers = match map
+        .entry(MIGRATED_FROM_KEY)
+        .or_insert_with(|| Value::Object(Map::new()))
+    {
+        Value::Object(markers) => markers,
+        _ => return,
+    };
+    let from = markers
+        .remove(old_key)
+        .unwrap_or_else(|| Value::String(String::from(old_key)));
+    markers.insert(String::from(new_key), from);
+}
+
+fn rename_in_object(
+    map: &mut Map<String, Value>,
+    path: &str,
+    mapping: &ExtensionMapping,
+    migration: &mut ExtensionMigration,
+) {
+    for (old_key, new_key) in &mapping.renames {
+        if old_key == new_key || !map.contains_key(old_key.as_str()) {
+            continue;
+        }
+        if map.contains_key(new_key.as_str()) {
+            migration.conflicts.push(pointer(path, old_key));
+            continue;
+        }
+        if let Some(value) = map.remove(old_key.as_str()) {
+            map.insert(new_key.clone(), value);
+            if mapping.mark_migrated {
+                mark_migrated(map, old_key, new_key);
+            }
+            migration.renamed.push(pointer(path, old_key));
+        }
+    }
+}
+
+/// The properties of a feed which can have objects with extensions.
+const FEED_CHILDREN: &[&str] = &["author", "authors", "hubs", "items"];
+
+/// The properties of an item which can have objects with extensions.
+const ITEM_CHILDREN: &[&str] = &["author", "authors", "attachments"];
+
+/// Renames the extensions in the object and in the objects of its listed properties.
+fn rename_in_tree(
+    map: &mut Map<String, Value>,
+    path: &str,
+    children: &[&str],
+    mapping: &ExtensionMapping,
+    migration: &mut ExtensionMigration,
+) {
+    rename_in_object(map, path, mapping, migration);
+    for key in children {
+        let child_path = pointer(path, key);
+        let grandchildren = if *key == "items" { ITEM_CHILDREN } else { &[] };
+        match map.get_mut(*key) {
+            Some(Value::Object(child)) => {
+                rename_in_tree(child, &child_path, grandchildren, mapping, migration);
+            }
+            Some(Value::Array(values)) => {
+                for (index, value) in values.iter_mut().enumerate() {
+                    if let Value::Object(child) = value {
+                        let path = format!("{}/{}", child_path, index);
+                        rename_in_tree(child, &path, grandchildren, mapping, migration);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn migrate_extensions(
+    map: &mut Map<String, Value>,
+    mapping: &ExtensionMapping,
+) -> ExtensionMigration {
+    let mut migration = ExtensionMigration::default();
+    rename_in_tree(map, "", FEED_CHILDREN, mapping, &mut migration);
+    migration
+}
+
+macro_rules! migrate_methods {
+    () => {
+        /// Renames an extension key in the feed and in its hubs, authors, items, and the items'
+        /// authors and attachments.
+        ///
+        /// An object which already has a value for the new key keeps both values and is
+        /// reported as a conflict. Values which are not the expected JSON type are skipped. No
+        /// `_migrated_from` markers are added; use an [`ExtensionMapping`] with
+        /// `mark_migrated` to add them.
+        pub fn rename_extension(&mut self, old_key: &str, new_key: &str) -> ExtensionMigration {
+            let mut mapping = ExtensionMapping::new();
+            mapping.rename(old_key, new_key);
+            migrate_extensions(self.as_map_mut(), &mapping)
+        }
+
+        /// Renames the extension keys in the mapping wherever [`rename_extension`] would.
+        ///
+        /// The renames are applied in order to each object, so a key renamed by an earlier
+        /// rename can be renamed again by a later one.
+        ///
+        /// [`rename_extension`]: Self::rename_extension
+        pub fn migrate_extensions(&mut self, mapping: &ExtensionMapping) -> ExtensionMigration {
+            migrate_extensions(self.as_map_mut(), mapping)
+        }
+    };
+}
+
+impl Feed {
+    migrate_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    migrate_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::vec;
+    use serde_json::json;
+
+    #[test]
+    fn migration() -> Result<(), crate::Error> {
+        let mut feed = crate::from_value(json!({
+            "_old": 1,
+            "author": { "_old": 2 },
+            "hubs": [{ "type": "WebSub", "url": "https://example.org/hub", "_old": 3 }],
+            "items": [
+                {
+                    "id": "1",
+                    "_old": 4,
+                    "_new": 5,
+                    "authors": [{ "_old": 6 }],
+                    "attachments": [{ "_old": 7 }]
+                },
+                "not an item"
+            ]
+        }))?;
+
+        let migration = feed.rename_extension("_old", "_new");
+        assert_eq!(
+            migration.renamed,
+            vec![
+                "/_old",
+                "/author/_old",
+                "/hubs/0/_old",
+                "/items/0/authors/0/_old",
+                "/items/0/attachments/0/_old"
+            ]
+        );
+        assert_eq!(migration.conflicts, vec!["/items/0/_old"]);
+        assert_eq!(
+            feed.as_map()["items"][0]["attachments"][0],
+            json!({ "_new": 7 })
+        );
+
+        let mut mapping = ExtensionMapping::new();
+        mapping.rename("_new", "_newer").rename("_newer", "_newest");
+        mapping.mark_migrated = true;
+        let mut feed = crate::from_value(json!({
+            "author": { "_new": 2, "_migrated_from": { "_new": "_old" } }
+        }))?;
+        assert_eq!(feed.migrate_extensions(&mapping).renamed.len(), 2);
+        assert_eq!(
+            feed.as_map()["author"],
+            json!({ "_newest": 2, "_migrated_from": { "_newest": "_old" } })
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,1797 @@
+//! The JSON Feed model types.
+//!
+//! Each type has an owned variant (e.g. [`Item`]), a variant which borrows a JSON object (e.g.
+//! [`ItemRef`]), and a variant which mutably borrows a JSON object (e.g. [`ItemMut`]). The
+//! getters and setters are also available through traits (e.g. [`ItemRead`] and
+//! [`ItemWrite`]). The types are re-exported from the crate root.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use std::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use serde_json::{Map, Value};
+
+use crate::{canonical, Error};
+
+/// Version 1 identifier (for 1.0 feeds)
+pub const VERSION_1: &str = "https://jsonfeed.org/version/1";
+
+/// Version 1.1 identifier
+pub const VERSION_1_1: &str = "https://jsonfeed.org/version/1.1";
+
+/// A JSON Feed spec version identifier
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Version<'a> {
+    /// <https://jsonfeed.org/version/1>
+    Version1,
+    /// <https://jsonfeed.org/version/1.1>
+    Version1_1,
+    /// An unknown version
+    Unknown(&'a str),
+}
+
+impl<'a> AsRef<str> for Version<'a> {
+    fn as_ref(&self) -> &str {
+        match self {
+            Version::Version1 => VERSION_1,
+            Version::Version1_1 => VERSION_1_1,
+            Version::Unknown(v) => v,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for Version<'a> {
+    fn from(value: &'a str) -> Self {
+        match value {
+            VERSION_1 => Version::Version1,
+            VERSION_1_1 => Version::Version1_1,
+            _ => Version::Unknown(value),
+        }
+    }
+}
+
+impl<'a> core::fmt::Display for Version<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+macro_rules! get_set_rm_str {
+    ([$($vis:tt)*] all, $($args:tt)*) => {
+        get_set_rm_str!([$($vis)*] get, $($args)*);
+        get_set_rm_str!([$($vis)*] set, $($args)*);
+    };
+
+    ([$($vis:tt)*] set, $key_expr:expr, $getter:ident, $getter_doc:expr, $setter:ident, $setter_doc:expr, $remover:ident, $remover_doc:expr) => {
+        #[doc=$setter_doc]
+        $($vis)* fn $setter<T>(&mut self, value: T) -> Option<Value>
+        where
+            T: ToString,
+        {
+            self.as_map_mut()
+                .insert(String::from($key_expr), Value::String(value.to_string()))
+        }
+
+        #[doc=$remover_doc]
+        $($vis)* fn $remover(&mut self) -> Option<Value> {
+            self.as_map_mut().remove($key_expr)
+        }
+    };
+
+    ([$($vis:tt)*] get, $key_expr:expr, $getter:ident, $getter_doc:expr, $($setter_and_remover:tt)*) => {
+        #[doc=$getter_doc]
+        $($vis)* fn $getter(&self) -> Result<Option<&str>, Error> {
+            self.as_map().get($key_expr).map_or_else(
+                || Ok(None),
+                |value| match value {
+                    Value::String(s) => Ok(Some(s.as_str())),
+                    _ => Err(Error::UnexpectedType),
+                },
+            )
+        }
+    };
+}
+
+macro_rules! get_set_rm_str_array {
+    ([$($vis:tt)*] all, $($args:tt)*) => {
+        get_set_rm_str_array!([$($vis)*] get, $($args)*);
+        get_set_rm_str_array!([$($vis)*] set, $($args)*);
+    };
+
+    ([$($vis:tt)*] set, $key_expr:expr, $getter:ident, $getter_doc:expr, $setter:ident, $setter_doc:expr, $remover:ident, $remover_doc:expr) => {
+        #[doc=$setter_doc]
+        $($vis)* fn $setter<I>(&mut self, values: I) -> Option<Value>
+        where
+            I: IntoIterator<Item = String>,
+        {
+            let values: Value = Value::Array(values.into_iter().map(Value::String).collect());
+            self.as_map_mut().insert(String::from($key_expr), values)
+        }
+
+        #[doc=$remover_doc]
+        $($vis)* fn $remover(&mut self) -> Option<Value> {
+            self.as_map_mut().remove($key_expr)
+        }
+    };
+
+    ([$($vis:tt)*] get, $key_expr:expr, $getter:ident, $getter_doc:expr, $($setter_and_remover:tt)*) => {
+        #[doc=$getter_doc]
+        $($vis)* fn $getter(&self) -> Result<Option<Vec<&str>>, Error> {
+            self.as_map().get($key_expr).map_or_else(
+                || Ok(None),
+                |value| match value {
+                    Value::Array(arr) => arr
+                        .iter()
+                        .map(|value| match value {
+                            Value::String(s) => Ok(s.as_str()),
+                            _ => Err(Error::UnexpectedType),
+                        })
+                        .collect::<Result<Vec<&str>, Error>>()
+                        .map(Some),
+                    _ => Err(Error::UnexpectedType),
+                },
+            )
+        }
+    };
+}
+
+macro_rules! get_set_rm_bool {
+    ([$($vis:tt)*] all, $($args:tt)*) => {
+        get_set_rm_bool!([$($vis)*] get, $($args)*);
+        get_set_rm_bool!([$($vis)*] set, $($args)*);
+    };
+
+    ([$($vis:tt)*] set, $key_expr:expr, $getter:ident, $getter_doc:expr, $setter:ident, $setter_doc:expr, $remover:ident, $remover_doc:expr) => {
+        #[doc=$setter_doc]
+        $($vis)* fn $setter<T>(&mut self, value: bool) -> Option<Value> {
+            self.as_map_mut()
+                .insert(String::from($key_expr), Value::Bool(value))
+        }
+
+        #[doc=$remover_doc]
+        $($vis)* fn $remover(&mut self) -> Option<Value> {
+            self.as_map_mut().remove($key_expr)
+        }
+    };
+
+    ([$($vis:tt)*] get, $key_expr:expr, $getter:ident, $getter_doc:expr, $($setter_and_remover:tt)*) => {
+        #[doc=$getter_doc]
+        $($vis)* fn $getter(&self) -> Result<Option<bool>, Error> {
+            self.as_map().get($key_expr).map_or_else(
+                || Ok(None),
+                |value| match value {
+                    Value::Bool(b) => Ok(Some(*b)),
+                    _ => Err(Error::UnexpectedType),
+                },
+            )
+        }
+    };
+}
+
+macro_rules! get_set_rm_u64 {
+    ([$($vis:tt)*] all, $($args:tt)*) => {
+        get_set_rm_u64!([$($vis)*] get, $($args)*);
+        get_set_rm_u64!([$($vis)*] set, $($args)*);
+    };
+
+    ([$($vis:tt)*] set, $key_expr:expr, $getter:ident, $getter_doc:expr, $setter:ident, $setter_doc:expr, $remover:ident, $remover_doc:expr) => {
+        #[doc=$setter_doc]
+        $($vis)* fn $setter<T>(&mut self, value: u64) -> Option<Value> {
+            self.as_map_mut().insert(
+                String::from($key_expr),
+                Value::Number(serde_json::Number::from(value)),
+            )
+        }
+
+        #[doc=$remover_doc]
+        $($vis)* fn $remover<T>(&mut self) -> Option<Value>
+        where
+            T: ToString,
+        {
+            self.as_map_mut().remove($key_expr)
+        }
+    };
+
+    ([$($vis:tt)*] get, $key_expr:expr, $getter:ident, $getter_doc:expr, $($setter_and_remover:tt)*) => {
+        #[doc=$getter_doc]
+        $($vis)* fn $getter(&self) -> Result<Option<u64>, Error> {
+            self.as_map().get($key_expr).map_or_else(
+                || Ok(None),
+                |value| match value {
+                    Value::Number(n) => {
+                        if let Some(n) = n.as_u64() {
+                            Ok(Some(n))
+                        } else {
+                            Err(Error::UnexpectedType)
+                        }
+                    }
+                    _ => Err(Error::UnexpectedType),
+                },
+            )
+        }
+    };
+}
+
+macro_rules! get_ref_get_ref_mut_set_rm_obj {
+    ([$($vis:tt)*] all, $($args:tt)*) => {
+        get_ref_get_ref_mut_set_rm_obj!([$($vis)*] get, $($args)*);
+        get_ref_get_ref_mut_set_rm_obj!([$($vis)*] set, $($args)*);
+    };
+
+    ([$($vis:tt)*] set, $key_expr:expr, $getter_ref:ident, $getter_ref_type:ty, $getter_ref_new:expr, $getter_ref_doc:expr,
+        $getter_ref_mut:ident, $getter_ref_mut_type:ty, $getter_ref_mut_new:expr, $getter_ref_mut_doc:expr,
+        $setter:ident, $setter_type:ty, $setter_doc:expr,
+        $remover:ident, $remover_doc:expr
+    ) => {
+        #[doc=$getter_ref_mut_doc]
+        $($vis)* fn $getter_ref_mut(&mut self) -> Result<Option<$getter_ref_mut_type>, Error> {
+            self.as_map_mut().get_mut($key_expr).map_or_else(
+                || Ok(None),
+                |value| match value {
+                    Value::Object(obj) => Ok(Some($getter_ref_mut_new(obj))),
+                    _ => Err(Error::UnexpectedType),
+                },
+            )
+        }
+
+        #[doc=$setter_doc]
+        $($vis)* fn $setter(&mut self, value: $setter_type) -> Option<Value> {
+            self.as_map_mut()
+                .insert(String::from($key_expr), Value::Object(value.value))
+        }
+
+        #[doc=$remover_doc]
+        $($vis)* fn $remover(&mut self) -> Option<Value> {
+            self.as_map_mut().remove($key_expr)
+        }
+    };
+
+    ([$($vis:tt)*] get, $key_expr:expr, $getter_ref:ident, $getter_ref_type:ty, $getter_ref_new:expr, $getter_ref_doc:expr, $($mut_args:tt)*) => {
+        #[doc=$getter_ref_doc]
+        $($vis)* fn $getter_ref(&self) -> Result<Option<$getter_ref_type>, Error> {
+            self.as_map().get($key_expr).map_or_else(
+                || Ok(None),
+                |value| match value {
+                    Value::Object(obj) => Ok(Some($getter_ref_new(obj))),
+                    _ => Err(Error::UnexpectedType),
+                },
+            )
+        }
+    };
+}
+
+macro_rules! get_ref_get_ref_mut_set_rm_obj_array {
+    ([$($vis:tt)*] all, $($args:tt)*) => {
+        get_ref_get_ref_mut_set_rm_obj_array!([$($vis)*] get, $($args)*);
+        get_ref_get_ref_mut_set_rm_obj_array!([$($vis)*] set, $($args)*);
+    };
+
+    ([$($vis:tt)*] set, $key_expr:expr, $getter_ref:ident, $getter_ref_type:ty, $getter_ref_new:expr, $getter_ref_doc:expr,
+        $getter_ref_mut:ident, $getter_ref_mut_type:ty, $getter_ref_mut_new:expr, $getter_ref_mut_doc:expr,
+        $setter:ident, $setter_type:ty, $setter_doc:expr,
+        $remover:ident, $remover_doc:expr
+    ) => {
+        #[doc=$getter_ref_mut_doc]
+        $($vis)* fn $getter_ref_mut(&mut self) -> Result<Option<Vec<$getter_ref_mut_type>>, Error> {
+            self.as_map_mut().get_mut($key_expr).map_or_else(
+                || Ok(None),
+                |value| match value {
+                    Value::Array(arr) => arr
+                        .iter_mut()
+                        .map(|value| match value {
+                            Value::Object(obj) => Ok($getter_ref_mut_new(obj)),
+                            _ => Err(Error::UnexpectedType),
+                        })
+                        .collect::<Result<Vec<$getter_ref_mut_type>, Error>>()
+                        .map(Some),
+                    _ => Err(Error::UnexpectedType),
+                },
+            )
+        }
+
+        #[doc=$setter_doc]
+        $($vis)* fn $setter<I>(&mut self, items: I) -> Option<Value>
+        where
+            I: IntoIterator<Item = $setter_type>,
+        {
+            let items: Value =
+                Value::Array(items.into_iter().map(|a| Value::Object(a.value)).collect());
+            self.as_map_mut().insert(String::from($key_expr), items)
+        }
+
+        #[doc=$remover_doc]
+        $($vis)* fn $remover(&mut self) -> Option<Value> {
+            self.as_map_mut().remove($key_expr)
+        }
+    };
+
+    ([$($vis:tt)*] get, $key_expr:expr, $getter_ref:ident, $getter_ref_type:ty, $getter_ref_new:expr, $getter_ref_doc:expr, $($mut_args:tt)*) => {
+        #[doc=$getter_ref_doc]
+        $($vis)* fn $getter_ref(&self) -> Result<Option<Vec<$getter_ref_type>>, Error> {
+            self.as_map().get($key_expr).map_or_else(
+                || Ok(None),
+                |value| match value {
+                    Value::Array(arr) => arr
+                        .iter()
+                        .map(|value| match value {
+                            Value::Object(obj) => Ok($getter_ref_new(obj)),
+                            _ => Err(Error::UnexpectedType),
+                        })
+                        .collect::<Result<Vec<$getter_ref_type>, Error>>()
+                        .map(Some),
+                    _ => Err(Error::UnexpectedType),
+                },
+            )
+        }
+    };
+}
+
+/// Declares the property methods.
+///
+/// The methods are declared with the visibility in the brackets. The mode is `all` for the
+/// getters, setters, and removers, `get` for the getters, and `set` for the setters, removers,
+/// and mutable getters.
+macro_rules! json_feed_prop_decl {
+    ([$($vis:tt)*] $mode:ident) => {};
+    ([$($vis:tt)*] $mode:ident, [str_prop, $($args:tt)*] $(,$rest:tt)*) => {
+        get_set_rm_str!([$($vis)*] $mode, $($args)*);
+        json_feed_prop_decl!([$($vis)*] $mode $(,$rest)*);
+    };
+    ([$($vis:tt)*] $mode:ident, [str_array_prop, $($args:tt)*] $(,$rest:tt)*) => {
+        get_set_rm_str_array!([$($vis)*] $mode, $($args)*);
+        json_feed_prop_decl!([$($vis)*] $mode $(,$rest)*);
+    };
+    ([$($vis:tt)*] $mode:ident, [u64_prop, $($args:tt)*] $(,$rest:tt)*) => {
+        get_set_rm_u64!([$($vis)*] $mode, $($args)*);
+        json_feed_prop_decl!([$($vis)*] $mode $(,$rest)*);
+    };
+    ([$($vis:tt)*] $mode:ident, [bool_prop, $($args:tt)*] $(,$rest:tt)*) => {
+        get_set_rm_bool!([$($vis)*] $mode, $($args)*);
+        json_feed_prop_decl!([$($vis)*] $mode $(,$rest)*);
+    };
+    ([$($vis:tt)*] $mode:ident, [obj_prop, $($args:tt)*] $(,$rest:tt)*) => {
+        get_ref_get_ref_mut_set_rm_obj!([$($vis)*] $mode, $($args)*);
+        json_feed_prop_decl!([$($vis)*] $mode $(,$rest)*);
+    };
+    ([$($vis:tt)*] $mode:ident, [obj_array_prop, $($args:tt)*] $(,$rest:tt)*) => {
+        get_ref_get_ref_mut_set_rm_obj_array!([$($vis)*] $mode, $($args)*);
+        json_feed_prop_decl!([$($vis)*] $mode $(,$rest)*);
+    };
+}
+
+macro_rules! trait_for_borrowed_type {
+    ($name:ident) => {
+        impl<'a> $name<'a> {
+            /// Returns the inner `Map` as a reference.
+            #[must_use]
+            pub fn as_map(&self) -> &Map<String, Value> {
+                self.value
+            }
+        }
+
+        impl<'a> AsRef<Map<String, Value>> for $name<'a> {
+            fn as_ref(&self) -> &Map<String, Value> {
+                self.value
+            }
+        }
+
+        impl<'a> core::fmt::Debug for $name<'a> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_struct(stringify!($name))
+                    .field("value", &self.value)
+                    .finish()
+            }
+        }
+
+        impl<'a> Eq for $name<'a> {}
+
+        impl<'a> From<&'a mut Map<String, Value>> for $name<'a> {
+            fn from(value: &'a mut Map<String, Value>) -> Self {
+                Self { value }
+            }
+        }
+
+        impl<'a> PartialEq<Map<String, Value>> for $name<'a> {
+            fn eq(&self, other: &Map<String, Value>) -> bool {
+                self.value.eq(&other)
+            }
+        }
+
+        impl<'a> PartialEq<$name<'a>> for $name<'a> {
+            fn eq(&self, other: &$name<'_>) -> bool {
+                self.value.eq(&other.value)
+            }
+        }
+    };
+}
+
+macro_rules! json_feed_map_type {
+    ($owned:ident, $owned_doc:expr, $borrowed:ident, $borrowed_doc:expr, $borrowed_mut:ident, $borrowed_mut_doc:expr, $to_owned:ident,
+        $read:ident, $read_doc:expr, $write:ident, $write_doc:expr,
+        $($rest:tt),*
+    ) => {
+        #[doc=$owned_doc]
+        pub struct $owned {
+            pub(crate) value: Map<String, Value>,
+        }
+
+        impl $owned {
+            /// Instantiates with an empty JSON object.
+            #[must_use]
+            pub fn new() -> Self {
+                Self { value: Map::new() }
+            }
+
+            /// Returns the inner `Map` as a reference.
+            #[must_use]
+            pub fn as_map(&self) -> &Map<String, Value> {
+                &self.value
+            }
+
+            /// Returns the inner `Map` as a mutable reference.
+            pub fn as_map_mut(&mut self) -> &mut Map<String, Value> {
+                &mut self.value
+            }
+
+            /// Converts the type into the inner `Map`.
+            #[must_use]
+            pub fn into_inner(self) -> Map<String, Value> {
+                self.value
+            }
+
+            json_feed_prop_decl!([pub] all, $($rest),*);
+        }
+
+        impl AsRef<Map<String,Value>> for $owned {
+            fn as_ref(&self) -> &Map<String, Value> {
+                &self.value
+            }
+        }
+
+        impl AsMut<Map<String,Value>> for $owned {
+            fn as_mut(&mut self) -> &mut Map<String, Value> {
+                &mut self.value
+            }
+        }
+
+        impl Clone for $owned {
+            fn clone(&self) -> $owned {
+                $owned {
+                    value: self.value.clone(),
+                }
+            }
+        }
+
+        impl core::fmt::Debug for $owned {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_struct(stringify!($owned))
+                    .field("value", &self.value)
+                    .finish()
+            }
+        }
+
+        impl Default for $owned {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl Eq for $owned {}
+
+        impl From<Map<String, Value>> for $owned {
+            fn from(value: Map<String, Value>) -> Self {
+                Self {
+                    value
+                }
+            }
+        }
+
+        impl TryFrom<Value> for $owned {
+            type Error = Error;
+
+            fn try_from(value: Value) -> Result<Self, Self::Error> {
+                match value {
+                    Value::Object(value) => Ok(Self { value }),
+                    _ => Err(Error::UnexpectedType),
+                }
+            }
+        }
+
+        impl PartialEq<Map<String, Value>> for $owned {
+            fn eq(&self, other: &Map<String, Value>) -> bool {
+                self.value.eq(&other)
+            }
+        }
+
+        impl PartialEq<$owned> for $owned {
+            fn eq(&self, other: &$owned) -> bool {
+                self.value.eq(&other.value)
+            }
+        }
+
+        impl serde::Serialize for $owned
+        {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                canonical::SortedMap(&self.value).serialize(serializer)
+            }
+        }
+
+        impl<'de> serde::de::Deserialize<'de> for $owned {
+            #[inline]
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::de::Deserializer<'de>,
+            {
+                let map: Map<String, Value> = Map::deserialize(deserializer)?;
+                Ok(Self { value: map })
+            }
+        }
+
+        #[doc=$borrowed_doc]
+        pub struct $borrowed<'a> {
+            pub(crate) value: &'a Map<String, Value>,
+        }
+
+        trait_for_borrowed_type!($borrowed);
+
+        impl<'a> $borrowed<'a> {
+            /// Clones the inner `Map` reference and returns an owned type.
+            #[must_use]
+            pub fn $to_owned(&self) -> $owned {
+                $owned::from(self.value.clone())
+            }
+
+            json_feed_prop_decl!([pub] get, $($rest),*);
+        }
+
+        impl<'a> Clone for $borrowed<'a> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<'a> Copy for $borrowed<'a> {}
+
+        impl<'a> From<&'a Map<String, Value>> for $borrowed<'a> {
+            fn from(value: &'a Map<String, Value>) -> Self {
+                Self { value }
+            }
+        }
+
+        impl<'a> From<&'a $owned> for $borrowed<'a> {
+            fn from(value: &'a $owned) -> Self {
+                Self { value: &value.value }
+            }
+        }
+
+        impl<'a> From<$borrowed_mut<'a>> for $borrowed<'a> {
+            fn from(value: $borrowed_mut<'a>) -> Self {
+                Self { value: value.value }
+            }
+        }
+
+        impl<'a> TryFrom<&'a Value> for $borrowed<'a> {
+            type Error = Error;
+
+            fn try_from(value: &'a Value) -> Result<Self, Self::Error> {
+                value.as_object().map(Self::from).ok_or(Error::UnexpectedType)
+            }
+        }
+
+        impl<'a> serde::Serialize for $borrowed<'a>
+        {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                canonical::SortedMap(&self.value).serialize(serializer)
+            }
+        }
+
+        #[doc=$borrowed_mut_doc]
+        pub struct $borrowed_mut<'a> {
+            pub(crate) value: &'a mut Map<String, Value>,
+        }
+
+        trait_for_borrowed_type!($borrowed_mut);
+
+        impl<'a> $borrowed_mut<'a> {
+            /// Returns the inner `Map` as a mutable reference.
+            pub fn as_map_mut(&mut self) -> &mut Map<String, Value> {
+                self.value
+            }
+
+            /// Clones the inner `Map` reference and returns an owned type.
+            #[must_use]
+            pub fn $to_owned(&self) -> $owned {
+                $owned::from(self.value.clone())
+            }
+
+            /// Replaces the inner `Map` with an empty JSON object and returns the previous data
+            /// as an owned type without cloning it.
+            pub fn take(&mut self) -> $owned {
+                $owned::from(core::mem::take(self.value))
+            }
+
+            /// Replaces the inner `Map` with the data of an owned type and returns the previous
+            /// data as an owned type without cloning it.
+            pub fn replace(&mut self, value: $owned) -> $owned {
+                $owned::from(core::mem::replace(self.value, value.value))
+            }
+
+            json_feed_prop_decl!([pub] all, $($rest),*);
+        }
+
+        impl<'a> AsMut<Map<String, Value>> for $borrowed_mut<'a> {
+            fn as_mut(&mut self) -> &mut Map<String, Value> {
+                self.value
+            }
+        }
+
+        impl<'a> From<&'a mut $owned> for $borrowed_mut<'a> {
+            fn from(value: &'a mut $owned) -> Self {
+                Self { value: &mut value.value }
+            }
+        }
+
+        impl<'a> TryFrom<&'a mut Value> for $borrowed_mut<'a> {
+            type Error = Error;
+
+            fn try_from(value: &'a mut Value) -> Result<Self, Self::Error> {
+                value.as_object_mut().map(Self::from).ok_or(Error::UnexpectedType)
+            }
+        }
+
+        impl<'a> serde::Serialize for $borrowed_mut<'a>
+        {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                canonical::SortedMap(&self.value).serialize(serializer)
+            }
+        }
+
+        #[doc=$read_doc]
+        pub trait $read {
+            /// Returns the JSON object as a reference.
+            fn as_map(&self) -> &Map<String, Value>;
+
+            json_feed_prop_decl!([] get, $($rest),*);
+        }
+
+        impl $read for $owned {
+            fn as_map(&self) -> &Map<String, Value> {
+                &self.value
+            }
+        }
+
+        impl<'a> $read for $borrowed<'a> {
+            fn as_map(&self) -> &Map<String, Value> {
+                self.value
+            }
+        }
+
+        impl<'a> $read for $borrowed_mut<'a> {
+            fn as_map(&self) -> &Map<String, Value> {
+                self.value
+            }
+        }
+
+        #[doc=$write_doc]
+        pub trait $write: $read {
+            /// Returns the JSON object as a mutable reference.
+            fn as_map_mut(&mut self) -> &mut Map<String, Value>;
+
+            json_feed_prop_decl!([] set, $($rest),*);
+        }
+
+        impl $write for $owned {
+            fn as_map_mut(&mut self) -> &mut Map<String, Value> {
+                &mut self.value
+            }
+        }
+
+        impl<'a> $write for $borrowed_mut<'a> {
+            fn as_map_mut(&mut self) -> &mut Map<String, Value> {
+                self.value
+            }
+        }
+    };
+}
+
+json_feed_map_type!(
+    Author,
+    "An author of a feed or an item in the feed.
+
+# Valid Author
+
+An `Author` must have at least one of the `name`, `url`, or `avatar` properties set.
+",
+    AuthorRef,
+    "An `Author` implemented with a borrowed reference to a JSON object.",
+    AuthorMut,
+    "An `Author` implemented with a borrowed mutable reference to a JSON object.",
+    to_author,
+    AuthorRead,
+    "The getters of an `Author`, `AuthorRef`, or `AuthorMut`.",
+    AuthorWrite,
+    "The setters and removers of an `Author` or `AuthorMut`.",
+    [
+        str_prop,
+        "name",
+        name,
+        "The optional author's name.",
+        set_name,
+        "Sets the name.",
+        remove_name,
+        "Remove the name."
+    ],
+    [
+        str_prop,
+        "url",
+        url,
+        "An optional URL for a site which represents the author.",
+        set_url,
+        "Sets the URL.",
+        remove_url,
+        "Removes the URL."
+    ],
+    [
+        str_prop,
+        "avatar",
+        avatar,
+        "An optional URL for an image which represents the author.",
+        set_avatar,
+        "Sets the avatar.",
+        remove_avatar,
+        "Removes the avatar."
+    ]
+);
+
+json_feed_map_type!(
+    Hub,
+    "A subscription endpoint which can be used to receive feed update notifications.
+
+# Valid Hub
+
+A `Hub` must have both the `type` and `url` properties set.
+",
+    HubRef,
+    "A `Hub` implemented with a borrowed reference to a JSON object.",
+    HubMut,
+    "A `Hub` implemented with a borrowed mutable reference to a JSON object.",
+    to_hub,
+    HubRead,
+    "The getters of a `Hub`, `HubRef`, or `HubMut`.",
+    HubWrite,
+    "The setters and removers of a `Hub` or `HubMut`.",
+    [
+        str_prop,
+        "type",
+        hub_type,
+        "The required protocol which is used to subscribe with.",
+        set_hub_type,
+        "Sets the type.",
+        remove_hub_type,
+        "Removes the type."
+    ],
+    [
+        str_prop,
+        "url",
+        url,
+        "A required hub type specific URL which is used to subscribe with.",
+        set_url,
+        "Sets the URL.",
+        remove_url,
+        "Removes the URL."
+    ]
+);
+
+json_feed_map_type!(
+    Item,
+    "An item is a single object (blog post, story, etc.) in the feed list.
+
+# Valid Item
+
+An `Item` must have an `id` property set and either a `content_html` or `content_text` property set.
+",
+    ItemRef,
+    "An `Item` implemented with a borrowed reference to a JSON object.",
+    ItemMut,
+    "An `Item` implemented with a borrowed mutable reference to a JSON object.",
+    to_item,
+    ItemRead,
+    "The getters of an `Item`, `ItemRef`, or `ItemMut`.",
+    ItemWrite,
+    "The setters, removers, and mutable getters of an `Item` or `ItemMut`.",
+    [str_prop, "id", id, "A required unique identifier for an item.
+
+# Important
+
+The ID should be unique across all items which have ever appeared in the feed.
+An item with the same exact ID as another item (even if it is no longer in the
+current JSON feed `items` array) are considered the same item.
+
+# Version 1.0 Incompatibility
+
+While JSON Feed 1.0 permitted values which could be coerced into JSON strings (e.g. JSON numbers), this model supports only
+JSON strings. JSON Feed 1.1 strongly suggests to only use strings. In practice, the vast majority of feeds use strings.
+
+If you wish to support non-String IDs, you can directly access the underlying `Map` with `as_map_mut` or an equivalent method and
+read the JSON value.
+", set_id, "Sets the ID.", remove_id, "Removes the ID."],
+    [str_prop, "url", url, "The optional URL which the item represents.", set_url, "Sets the URL.", remove_url, "Removes the URL."],
+    [
+        str_prop,
+        "external_url",
+        external_url,
+        "An optional related external URL to the item.",
+        set_external_url,
+        "Sets the external URL.",
+        remove_external_url,
+        "Removes the external URL."
+    ],
+    [
+        str_prop,
+        "title",
+        title,
+        "An optional title for the item.",
+        set_title,
+        "Sets the title.",
+        remove_title,
+        "Removes the title."
+    ],
+    [
+        str_prop,
+        "content_html",
+        content_html,
+        "An optional HTML string representing the content.",
+        set_content_html,
+        "Sets the HTML content.",
+        remove_content_html,
+        "Removes the HTML content."
+    ],
+    [
+        str_prop,
+        "content_text",
+        content_text,
+        "An optional plain text string representing the content.",
+        set_content_text,
+        "Sets the plain text content.",
+        remove_content_text,
+        "Removes the plain text content."
+    ],
+    [
+        str_prop,
+        "summary",
+        summary,
+        "An optional summary of the item.",
+        set_summary,
+        "Sets the summary.",
+        remove_summary,
+        "Removes the summary."
+    ],
+    [
+        str_prop,
+        "image",
+        image,
+        "An optional URL of an image representing the item.",
+        set_image,
+        "Sets the image.",
+        remove_image,
+        "Removes the image."
+    ],
+    [
+        str_prop,
+        "banner_image",
+        banner_image,
+        "An optional URL of a banner image representing the item.",
+        set_banner_image,
+        "Sets the banner image.",
+        remove_banner_image,
+        "Removes the banner image."
+    ],
+    [
+        str_prop,
+        "date_published",
+        date_published,
+        "The date which the item was published in [RFC 3339][rfc_3339] format.
+
+[rfc_3339]: https://tools.ietf.org/html/rfc3339
+",
+        set_date_published,
+        "Sets the date published.",
+        remove_date_published,
+        "Removes the date published."
+    ],
+    [
+        str_prop,
+        "date_modified",
+        date_modified,
+        "The date which the item was modified in [RFC 3339][rfc_3339] format.
+
+[rfc_3339]: https://tools.ietf.org/html/rfc3339
+",
+        set_date_modified,
+        "Sets the date modified.",
+        remove_date_modified,
+        "Removes the date modified."
+    ],
+    [
+        obj_prop,
+        "author",
+        author,
+        AuthorRef<'_>,
+        AuthorRef::from,
+        "An optional author.
+
+# Deprecation
+
+The `author` field is deprecated in favor of the `authors` field as of JSON Feed 1.1.
+",
+        author_mut,
+        AuthorMut<'_>,
+        AuthorMut::from,
+        "An optional author.
+
+# Deprecation
+
+The `author` field is deprecated in favor of the `authors` field as of JSON Feed 1.1.
+",
+        set_author,
+        Author,
+        "Sets the author.",
+        remove_author,
+        "Removes the author."
+    ],
+    [
+        obj_array_prop,
+        "authors",
+        authors,
+        AuthorRef<'_>,
+        AuthorRef::from,
+        "An optional array of authors.",
+        authors_mut,
+        AuthorMut<'_>,
+        AuthorMut::from,
+        "An optional array of authors.",
+        set_authors,
+        Author,
+        "Sets the authors.",
+        remove_authors,
+        "Removes the authors."
+    ],
+    [
+        str_array_prop,
+        "tags",
+        tags,
+        "An optional array of plain text tags.",
+        set_tags,
+        "Sets the tags.",
+        remove_tags,
+        "Removes the tags."
+    ],
+    [
+        str_prop,
+        "language",
+        language,
+        "The optional language which the feed data is written in.
+
+Valid values are from [RFC 5646][rfc_5646].
+
+[rfc_5646]: https://tools.ietf.org/html/rfc5646
+",
+        set_language,
+        "Sets the language.",
+        remove_language,
+        "Removes the language."
+    ],
+    [
+        obj_array_prop,
+        "attachments",
+        attachments,
+        AttachmentRef<'_>,
+        AttachmentRef::from,
+        "An optional array of relevant resources for the item.",
+        attachments_mut,
+        AttachmentMut<'_>,
+        AttachmentMut::from,
+        "An optional array of relevant resources for the item.",
+        set_attachments,
+        Attachment,
+        "Sets the attachments.",
+        remove_attachments,
+        "Removes the attachments."
+    ]
+);
+
+json_feed_map_type!(
+    Attachment,
+    "A relevant resource for an `Item`.
+
+# Valid Attachment
+
+An `Attachment` must have both the `url` and `mime_type` properties set.
+",
+    AttachmentRef,
+    "An `Attachment` implemented with a borrowed reference to a JSON object.",
+    AttachmentMut,
+    "An `Attachment` implemented with a borrowed mutable reference to a JSON object.",
+    to_attachment,
+    AttachmentRead,
+    "The getters of an `Attachment`, `AttachmentRef`, or `AttachmentMut`.",
+    AttachmentWrite,
+    "The setters and removers of an `Attachment` or `AttachmentMut`.",
+    [
+        str_prop,
+        "url",
+        url,
+        "The required URL for the attachment.",
+        set_url,
+        "Sets the URL.",
+        remove_url,
+        "Removes the URL."
+    ],
+    [
+        str_prop,
+        "mime_type",
+        mime_type,
+        "The required [MIME][mime] type (e.g. image/png).
+
+[mime]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Basics_of_HTTP/MIME_types
+",
+        set_mime_type,
+        "Sets the MIME type.",
+        remove_mime_type,
+        "Removes the MIME type."
+    ],
+    [
+        str_prop,
+        "title",
+        title,
+        "An optional title for the attachment.
+
+# Important
+
+Attachments with the same title are considered to be alternative representations of an attachment.
+ ",
+        set_title,
+        "Sets the title.",
+        remove_title,
+        "Removes the title."
+    ],
+    [
+        u64_prop,
+        "size_in_bytes",
+        size_in_bytes,
+        "The optional size of the attachment in bytes.",
+        set_size_in_bytes,
+        "Sets the size in bytes.",
+        remove_size_in_bytes,
+        "Removes the size in bytes."
+    ],
+    [
+        u64_prop,
+        "duration_in_seconds",
+        duration_in_seconds,
+        "The optional duration of the content in seconds.",
+        set_duration_in_seconds,
+        "Sets the duration of in seconds.",
+        remove_duration_in_seconds,
+        "Removes the duration in seconds."
+    ]
+);
+
+json_feed_map_type!(
+    Feed,
+    r#"A list of items with associated metadata.
+
+The type provides a view into a JSON object value with accessor methods for the standard properties.
+`Feed` owns the underlying JSON object data and provides methods to access the backing object itself
+with `as_map`, `as_map_mut`, and `into_inner`.
+
+The underlying data is not guaranteed to be a valid JSON Feed.
+
+# Valid Feed
+
+A `Feed` must have the `version` set to a valid JSON Feed version value, the `title` property set, and the `items`
+property set.
+
+# Example
+
+```
+use json_feed_model::{Feed};
+# fn main() -> Result<(), json_feed_model::Error> {
+let json = serde_json::json!({
+    "version": "https://jsonfeed.org/version/1.1",
+    "title": "Lorem ipsum dolor sit amet.",
+    "home_page_url": "https://example.org/",
+    "feed_url": "https://example.org/feed.json",
+    "items": [
+        {
+            "id": "cd7f0673-8e81-4e13-b273-4bd1b83967d0",
+            "content_text": "Aenean tristique dictum mauris, et.",
+            "url": "https://example.org/aenean-tristique"
+        },
+        {
+            "id": "2bcb497d-c40b-4493-b5ae-bc63c74b48fa",
+            "content_html": "Vestibulum non magna vitae tortor.",
+            "url": "https://example.org/vestibulum-non"
+        }
+    ]
+});
+let feed = json_feed_model::from_value(json).unwrap();
+assert_eq!(feed.version()?, Some(json_feed_model::VERSION_1_1));
+assert_eq!(feed.title()?, Some("Lorem ipsum dolor sit amet."));
+assert_eq!(feed.home_page_url()?, Some("https://example.org/"));
+assert_eq!(feed.feed_url()?, Some("https://example.org/feed.json"));
+
+let items = feed.items()?;
+let items = items.unwrap();
+assert_eq!(items.len(), 2);
+
+assert_eq!(items[0].id()?, Some("cd7f0673-8e81-4e13-b273-4bd1b83967d0"));
+assert_eq!(
+    items[0].content_text()?,
+    Some("Aenean tristique dictum mauris, et.")
+);
+assert_eq!(
+    items[0].url()?,
+    Some("https://example.org/aenean-tristique")
+);
+
+assert_eq!(items[1].id()?, Some("2bcb497d-c40b-4493-b5ae-bc63c74b48fa"));
+assert_eq!(
+    items[1].content_html()?,
+    Some("Vestibulum non magna vitae tortor.")
+);
+assert_eq!(items[1].url()?, Some("https://example.org/vestibulum-non"));
+# Ok(())
+# }
+```
+    "#,
+    FeedRef,
+    "A `Feed` implemented with a borrowed reference to a JSON object.",
+    FeedMut,
+    "A `Feed` implemented with a borrowed mutable reference to a JSON object.",
+    to_feed,
+    FeedRead,
+    "The getters of a `Feed`, `FeedRef`, or `FeedMut`.",
+    FeedWrite,
+    "The setters, removers, and mutable getters of a `Feed` or `FeedMut`.",
+    [
+        str_prop,
+        "version",
+        version,
+        "The required URL formatted version identifier.
+
+Identifies what version of the spec the feed is suppose to be compliant with.",
+        set_version,
+        "Sets the version identifier.",
+        remove_version,
+        "Removes the version identifier."
+    ],
+    [
+        str_prop,
+        "title",
+        title,
+        "The optional name of the feed.",
+        set_title,
+        "Sets the name of the feed.",
+        remove_title,
+        "Removes the name of the feed."
+    ],
+    [
+        str_prop,
+        "home_page_url",
+        home_page_url,
+        "The optional URL which the feed is suppose to represent.",
+        set_home_page_url,
+        "Sets the home page URL.",
+        remove_home_page_url,
+        "Removes the home page URL."
+    ],
+    [
+        str_prop,
+        "feed_url",
+        feed_url,
+        "The optional URL which this feed can be retrieived from.",
+        set_feed_url,
+        "Sets the feed URL.",
+        remove_feed_url,
+        "Removes the feed URL."
+    ],
+    [
+        str_prop,
+        "description",
+        description,
+        "An optional description of the feed.",
+        set_description,
+        "Sets the description of the feed.",
+        remove_description,
+        "Removes the description of the feed."
+    ],
+    [
+        str_prop,
+        "user_comment",
+        user_comment,
+        "An optional meta description about the feed only intended to be viewed in the raw JSON form.",
+        set_user_comment,
+        "Sets the user comment.",
+        remove_user_comment,
+        "Removes the user comment."
+    ],
+    [
+        str_prop,
+        "next_url",
+        next_url,
+        "An optional pagination URL.",
+        set_next_url,
+        "Sets the next URL.",
+        remove_next_url,
+        "Removes the next URL."
+    ],
+    [str_prop, "icon", icon, "An optional URL to an icon for use in a list of items.", set_icon, "Sets the icon.", remove_icon, "Removes the icon."],
+    [
+        str_prop,
+        "favicon",
+        favicon,
+        "An optional URL to a favicon suitable for use in a list of feeds.",
+        set_favicon,
+        "Sets the favicon URL.",
+        remove_favicon,
+        "Removes the favicon URL."
+    ],
+    [
+        obj_prop,
+        "author",
+        author,
+        AuthorRef<'_>,
+        AuthorRef::from,
+        "An optional author.
+
+# Deprecation
+
+The `author` field is deprecated in favor of the `authors` field as of JSON Feed 1.1.
+",
+        author_mut,
+        AuthorMut<'_>,
+        AuthorMut::from,
+        "An optional author.
+
+# Deprecation
+
+The `author` field is deprecated in favor of the `authors` field as of JSON Feed 1.1.
+",
+        set_author,
+        Author,
+        "Sets the author.",
+        remove_author,
+        "Removes the author."
+    ],
+    [
+        obj_array_prop,
+        "authors",
+        authors,
+        AuthorRef<'_>,
+        AuthorRef::from,
+        "An optional array of authors.",
+        authors_mut,
+        AuthorMut<'_>,
+        AuthorMut::from,
+        "An optional array of authors.",
+        set_authors,
+        Author,
+        "Sets the authors.",
+        remove_authors,
+        "Removes the authors."
+    ],
+    [
+        str_prop,
+        "language",
+        language,
+        "The optional language which the feed data is written in.
+
+Valid values are from [RFC 5646][rfc_5646].
+
+[rfc_5646]: https://tools.ietf.org/html/rfc5646
+",
+        set_language,
+        "Sets the language.",
+        remove_language,
+        "Removes the language."
+    ],
+    [
+        bool_prop,
+        "expired",
+        expired,
+        "Optionally determines if the feed will be updated in the future.
+        
+If true, the feed will not be updated in the future. If false or `None`, then the feed may be updated in the future.",
+        set_expired,
+        "Sets the expired flag.",
+        remove_expired,
+        "Removes the expired flag."
+    ],
+    [
+        obj_array_prop,
+        "hubs",
+        hubs,
+        HubRef<'_>,
+        HubRef::from,
+        "Optional subscription endpoints which can be used to received feed update notifications.",
+        hubs_mut,
+        HubMut<'_>,
+        HubMut::from,
+        "Subscription endpoints which can be used to received feed update notifications.",
+        set_hubs,
+        Hub,
+        "Sets the hubs.",
+        remove_hubs,
+        "Removes the hubs."
+    ],
+    [
+        obj_array_prop,
+        "items",
+        items,
+        ItemRef<'_>,
+        ItemRef::from,
+        "A required array of `Items`.",
+        items_mut,
+        ItemMut<'_>,
+        ItemMut::from,
+        "A required array of `Items`.",
+        set_items,
+        Item,
+        "Sets the items.",
+        remove_items,
+        "Removes the items."
+    ]
+);
+
+/// Returns the items of a feed map borrowed for the lifetime of the map.
+///
+/// Unlike `FeedRef::items`, the items do not borrow a temporary `FeedRef`.
+pub(crate) fn item_refs(map: &Map<String, Value>) -> Result<Vec<ItemRef<'_>>, Error> {
+    match map.get("items") {
+        None => Ok(Vec::new()),
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|item| match item {
+                Value::Object(obj) => Ok(ItemRef::from(obj)),
+                _ => Err(Error::UnexpectedType),
+            })
+            .collect(),
+        Some(_) => Err(Error::UnexpectedType),
+    }
+}
+
+#[cfg(feature = "gravatar")]
+fn gravatar_url(email: &str, size: u32) -> String {
+    use core::fmt::Write;
+    use sha2::{Digest, Sha256};
+
+    let email = email.trim().to_ascii_lowercase();
+    let hash = Sha256::digest(email.as_bytes());
+
+    let mut url = String::from("https://www.gravatar.com/avatar/");
+    for b in hash {
+        let _ = write!(url, "{:02x}", b);
+    }
+    let _ = write!(url, "?s={}", size);
+    url
+}
+
+#[cfg(feature = "gravatar")]
+impl Author {
+    /// Sets the avatar to the [Gravatar][gravatar] image URL for an email address.
+    ///
+    /// The email address is trimmed and lowercased before it is hashed with SHA-256. The `size`
+    /// is the requested width and height in pixels. The spec recommends large, square avatars
+    /// (e.g. 512x512).
+    ///
+    /// [gravatar]: https://docs.gravatar.com/api/avatars/images/
+    #[cfg(feature = "gravatar")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "gravatar")))]
+    pub fn set_avatar_from_email(&mut self, email: &str, size: u32) -> Option<Value> {
+        self.set_avatar(gravatar_url(email, size))
+    }
+}
+
+#[cfg(feature = "gravatar")]
+impl<'a> AuthorMut<'a> {
+    /// Sets the avatar to the [Gravatar][gravatar] image URL for an email address.
+    ///
+    /// The email address is trimmed and lowercased before it is hashed with SHA-256. The `size`
+    /// is the requested width and height in pixels. The spec recommends large, square avatars
+    /// (e.g. 512x512).
+    ///
+    /// [gravatar]: https://docs.gravatar.com/api/avatars/images/
+    #[cfg(feature = "gravatar")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "gravatar")))]
+    pub fn set_avatar_from_email(&mut self, email: &str, size: u32) -> Option<Value> {
+        self.set_avatar(gravatar_url(email, size))
+    }
+}
+
+impl Feed {
+    /// Returns true if the feed will not be updated in the future.
+    ///
+    /// A missing `expired` property is treated the same as `false`.
+    ///
+    /// # Errors
+    ///
+    /// If the `expired` property is not a JSON boolean, then `Error::UnexpectedType` is returned.
+    pub fn is_expired(&self) -> Result<bool, Error> {
+        self.expired().map(|expired| expired.unwrap_or(false))
+    }
+
+    /// Marks the feed as expired so that readers stop checking it for updates.
+    ///
+    /// An expired feed should not have `hubs` or a `next_url`. See `lints()` for the
+    /// contradictions which are reported.
+    pub fn expire(&mut self) -> Option<Value> {
+        self.set_expired::<()>(true)
+    }
+}
+
+impl<'a> FeedMut<'a> {
+    /// Returns true if the feed will not be updated in the future.
+    ///
+    /// A missing `expired` property is treated the same as `false`.
+    ///
+    /// # Errors
+    ///
+    /// If the `expired` property is not a JSON boolean, then `Error::UnexpectedType` is returned.
+    pub fn is_expired(&self) -> Result<bool, Error> {
+        self.expired().map(|expired| expired.unwrap_or(false))
+    }
+
+    /// Marks the feed as expired so that readers stop checking it for updates.
+    ///
+    /// An expired feed should not have `hubs` or a `next_url`. See `lints()` for the
+    /// contradictions which are reported.
+    pub fn expire(&mut self) -> Option<Value> {
+        self.set_expired::<()>(true)
+    }
+}
+
+impl<'a> FeedRef<'a> {
+    /// Returns true if the feed will not be updated in the future.
+    ///
+    /// A missing `expired` property is treated the same as `false`.
+    ///
+    /// # Errors
+    ///
+    /// If the `expired` property is not a JSON boolean, then `Error::UnexpectedType` is returned.
+    pub fn is_expired(&self) -> Result<bool, Error> {
+        self.expired().map(|expired| expired.unwrap_or(false))
+    }
+}
+
+fn reserve_items(map: &mut Map<String, Value>, additional: usize) -> Result<(), Error> {
+    match map
+        .entry("items")
+        .or_insert_with(|| Value::Array(Vec::new()))
+    {
+        Value::Array(items) => {
+            items.reserve(additional);
+            Ok(())
+        }
+        _ => Err(Error::UnexpectedType),
+    }
+}
+
+fn shrink_items_to_fit(map: &mut Map<String, Value>) -> Result<(), Error> {
+    match map.get_mut("items") {
+        None => Ok(()),
+        Some(Value::Array(items)) => {
+            items.shrink_to_fit();
+            Ok(())
+        }
+        Some(_) => Err(Error::UnexpectedType),
+    }
+}
+
+macro_rules! items_capacity_methods {
+    () => {
+        /// Reserves capacity for at least `additional` more items.
+        ///
+        /// If there is no `items` property, then an empty JSON array is added first. Reserving
+        /// capacity before appending many items avoids repeated reallocations.
+        ///
+        /// # Errors
+        ///
+        /// If the `items` property is not a JSON array, then `Error::UnexpectedType` is returned.
+        pub fn reserve_items(&mut self, additional: usize) -> Result<(), Error> {
+            reserve_items(self.as_map_mut(), additional)
+        }
+
+        /// Shrinks the capacity of the `items` array as much as possible.
+        ///
+        /// # Errors
+        ///
+        /// If the `items` property is not a JSON array, then `Error::UnexpectedType` is returned.
+        pub fn shrink_items_to_fit(&mut self) -> Result<(), Error> {
+            shrink_items_to_fit(self.as_map_mut())
+        }
+    };
+}
+
+impl Feed {
+    items_capacity_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    items_capacity_methods!();
+}
+
+impl Feed {
+    /// Assembles a feed from its top-level properties and the JSON objects of its items.
+    ///
+    /// Any `items` property in `metadata` is replaced. The item maps are moved into the feed
+    /// without being wrapped or copied, which is useful when converting many records directly
+    /// into a feed.
+    #[must_use]
+    pub fn from_parts(mut metadata: Map<String, Value>, items: Vec<Map<String, Value>>) -> Self {
+        metadata.insert(
+            String::from("items"),
+            Value::Array(items.into_iter().map(Value::Object).collect()),
+        );
+        Self { value: metadata }
+    }
+}
+
+/// WebSub hub type identifier
+pub const HUB_TYPE_WEBSUB: &str = "WebSub";
+
+/// rssCloud hub type identifier
+pub const HUB_TYPE_RSS_CLOUD: &str = "rssCloud";
+
+/// A `Hub` subscription protocol.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum HubType<'a> {
+    /// <https://www.w3.org/TR/websub/>
+    WebSub,
+    /// <http://home.rssboard.org/rsscloud-interface>
+    RssCloud,
+    /// An unknown hub type
+    Other(&'a str),
+}
+
+impl<'a> AsRef<str> for HubType<'a> {
+    fn as_ref(&self) -> &str {
+        match self {
+            HubType::WebSub => HUB_TYPE_WEBSUB,
+            HubType::RssCloud => HUB_TYPE_RSS_CLOUD,
+            HubType::Other(t) => t,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for HubType<'a> {
+    /// Converts the string into a hub type.
+    ///
+    /// The known identifiers are matched case-insensitively since the spec does not define
+    /// the exact values and publishers use different capitalizations.
+    fn from(value: &'a str) -> Self {
+        if value.eq_ignore_ascii_case(HUB_TYPE_WEBSUB) {
+            HubType::WebSub
+        } else if value.eq_ignore_ascii_case(HUB_TYPE_RSS_CLOUD) {
+            HubType::RssCloud
+        } else {
+            HubType::Other(value)
+        }
+    }
+}
+
+impl<'a> core::fmt::Display for HubType<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+impl Hub {
+    /// Instantiates a WebSub hub with the hub's URL.
+    #[must_use]
+    pub fn websub<T>(url: T) -> Self
+    where
+        T: ToString,
+    {
+        let mut hub = Hub::new();
+        hub.set_hub_type(HubType::WebSub);
+        hub.set_url(url);
+        hub
+    }
+
+    /// Instantiates an rssCloud hub with the hub's URL.
+    #[must_use]
+    pub fn rsscloud<T>(url: T) -> Self
+    where
+        T: ToString,
+    {
+        let mut hub = Hub::new();
+        hub.set_hub_type(HubType::RssCloud);
+        hub.set_url(url);
+        hub
+    }
+
+    /// The protocol which is used to subscribe with as a `HubType`.
+    ///
+    /// # Errors
+    ///
+    /// If the type is not a JSON string, then `Error::UnexpectedType` is returned.
+    pub fn hub_type_enum(&self) -> Result<Option<HubType<'_>>, Error> {
+        self.hub_type().map(|t| t.map(HubType::from))
+    }
+}
+
+impl<'a> HubMut<'a> {
+    /// The protocol which is used to subscribe with as a `HubType`.
+    ///
+    /// # Errors
+    ///
+    /// If the type is not a JSON string, then `Error::UnexpectedType` is returned.
+    pub fn hub_type_enum(&self) -> Result<Option<HubType<'_>>, Error> {
+        self.hub_type().map(|t| t.map(HubType::from))
+    }
+}
+
+impl<'a> HubRef<'a> {
+    /// The protocol which is used to subscribe with as a `HubType`.
+    ///
+    /// # Errors
+    ///
+    /// If the type is not a JSON string, then `Error::UnexpectedType` is returned.
+    pub fn hub_type_enum(&self) -> Result<Option<HubType<'_>>, Error> {
+        self.hub_type().map(|t| t.map(HubType::from))
+    }
+}
+
+/// Adds the hub unless a hub with the same type and URL exists.
+fn ensure_hub(map: &mut Map<String, Value>, hub: Hub) -> Result<bool, Error> {
+    let hub_ref = HubRef::from(&hub.value);
+    let (hub_type, url) = (hub_ref.hub_type_enum()?, hub_ref.url()?);
+    let hubs = match map
+        .entry("hubs")
+        .or_insert_with(|| Value::Array(Vec::new()))
+    {
+        Value::Array(hubs) => hubs,
+        _ => return Err(Error::UnexpectedType),
+    };
+    for existing in hubs.iter() {
+        let existing = HubRef::from(existing.as_object().ok_or(Error::UnexpectedType)?);
+        if existing.url()? == url && existing.hub_type_enum()? == hub_type {
+            return Ok(false);
+        }
+    }
+    hubs.push(Value::Object(hub.value));
+    Ok(true)
+}
+
+macro_rules! ensure_hub_methods {
+    () => {
+        /// Adds the hub to the `hubs` unless a hub with the same type and URL exists.
+        ///
+        /// The known hub types are compared case-insensitively. Returns true if the hub was
+        /// added.
+        ///
+        /// # Errors
+        ///
+        /// If the `hubs` are not a JSON array of JSON objects, or a hub's type or URL is not a
+        /// JSON string, then `Error::UnexpectedType` is returned. The hub is not added if an
+        /// error is returned.
+        pub fn ensure_hub(&mut self, hub: Hub) -> Result<bool, Error> {
+            ensure_hub(self.as_map_mut(), hub)
+        }
+    };
+}
+
+impl Feed {
+    ensure_hub_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    ensure_hub_methods!();
+}
+
+impl Item {
+    /// Returns true if the item is about a linked page, as in a linkblog.
+    ///
+    /// Link posts have an `external_url` to the page being discussed in addition to their own
+    /// `url`.
+    ///
+    /// # Errors
+    ///
+    /// If the `external_url` is not a JSON string, then `Error::UnexpectedType` is returned.
+    pub fn is_link_post(&self) -> Result<bool, Error> {
+        self.external_url()
+            .map(|external_url| external_url.is_some())
+    }
+
+    /// Returns the URL a reader should be sent to when following the item.
+    ///
+    /// The `external_url` is returned for link posts, otherwise the `url` is returned.
+    ///
+    /// # Errors
+    ///
+    /// If the `external_url` or `url` is not a JSON string, then `Error::UnexpectedType` is
+    /// returned.
+    pub fn primary_link(&self) -> Result<Option<&str>, Error> {
+        match self.external_url()? {
+            Some(external_url) => Ok(Some(external_url)),
+            None => self.url(),
+        }
+    }
+}
+
+impl<'a> ItemMut<'a> {
+    /// Returns true if the item is about a linked page, as in a linkblog.
+    ///
+    /// Link posts have an `external_url` to the page being discussed in addition to their own
+    /// `url`.
+    ///
+    /// # Errors
+    ///
+    /// If the `external_url` is not a JSON string, then `Error::UnexpectedType` is returned.
+    pub fn is_link_post(&self) -> Result<bool, Error> {
+        self.external_url()
+            .map(|external_url| external_url.is_some())
+    }
+
+    /// Returns the URL a reader should be sent to when following the item.
+    ///
+    /// The `external_url` is returned for link posts, otherwise the `url` is returned.
+    ///
+    /// # Errors
+    ///
+    /// If the `external_url` or `url` is not a JSON string, then `Error::UnexpectedType` is
+    /// returned.
+    pub fn primary_link(&self) -> Result<Option<&str>, Error> {
+        match self.external_url()? {
+            Some(external_url) => Ok(Some(external_url)),
+            None => self.url(),
+        }
+    }
+}
+
+impl<'a> ItemRef<'a> {
+    /// Returns true if the item is about a linked page, as in a linkblog.
+    ///
+    /// Link posts have an `external_url` to the page being discussed in addition to their own
+    /// `url`.
+    ///
+    /// # Errors
+    ///
+    /// If the `external_url` is not a JSON string, then `Error::UnexpectedType` is returned.
+    pub fn is_link_post(&self) -> Result<bool, Error> {
+        self.external_url()
+            .map(|external_url| external_url.is_some())
+    }
+
+    /// Returns the URL a reader should be sent to when following the item.
+    ///
+    /// The `external_url` is returned for link posts, otherwise the `url` is returned.
+    ///
+    /// # Errors
+    ///
+    /// If the `external_url` or `url` is not a JSON string, then `Error::UnexpectedType` is
+    /// returned.
+    pub fn primary_link(&self) -> Result<Option<&str>, Error> {
+        match self.external_url()? {
+            Some(external_url) => Ok(Some(external_url)),
+            None => self.url(),
+        }
+    }
+}
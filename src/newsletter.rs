@@ -0,0 +1,252 @@
+//! Email digests of feed items.
+//!
+//! Newsletter services send subscribers a digest of the items published since the last email.
+//! The digest is rendered as an HTML fragment for the `text/html` part and plain text for the
+//! `text/plain` alternative of a MIME message.
+//!
+//! ```
+//! use json_feed_model::newsletter::DigestOptions;
+//!
+//! let feed = json_feed_model::from_str(r#"{
+//!     "title": "My Blog",
+//!     "items": [{
+//!         "id": "1",
+//!         "title": "Hello & welcome",
+//!         "url": "https://example.org/1",
+//!         "summary": "The first post.",
+//!         "date_published": "2021-06-01T12:00:00Z"
+//!     }]
+//! }"#)?;
+//!
+//! let items = feed.items()?.unwrap_or_default();
+//! let digest = feed.render_digest_html(&items, &DigestOptions::default())?;
+//! assert!(digest.html.contains(r#"<a href="https://example.org/1">Hello &amp; welcome</a>"#));
+//! assert!(digest.text.contains("Hello & welcome\n2021-06-01\nThe first post.\nhttps://example.org/1\n"));
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::string::String;
+#[cfg(feature = "std")]
+use std::string::String;
+
+use core::fmt::Write;
+
+use serde_json::{Map, Value};
+
+use crate::{date::Timestamp, html::escape_into, Error, Feed, FeedMut, FeedRef, ItemRef};
+
+/// Options for rendering a digest.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct DigestOptions {
+    /// The maximum number of characters in each item's summary.
+    pub max_summary_chars: usize,
+    /// Includes the date each item was published.
+    pub include_dates: bool,
+    /// Includes the feed's title as a heading.
+    pub include_feed_title: bool,
+}
+
+impl Default for DigestOptions {
+    fn default() -> Self {
+        Self {
+            max_summary_chars: 280,
+            include_dates: true,
+            include_feed_title: true,
+        }
+    }
+}
+
+/// A rendered digest.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Digest {
+    /// An HTML fragment listing the items.
+    pub html: String,
+    /// A plain text alternative to the HTML.
+    pub text: String,
+}
+
+/// Returns the link if it is safe to use in an email.
+fn safe_link(link: Option<&str>) -> Option<&str> {
+    link.filter(|link| {
+        crate::url::scheme(link).map_or(false, |scheme| {
+            scheme.eq_ignore_ascii_case("https") || scheme.eq_ignore_ascii_case("http")
+        })
+    })
+}
+
+fn render_item(
+    item: &ItemRef<'_>,
+    options: &DigestOptions,
+    digest: &mut Digest,
+) -> Result<(), Error> {
+    let map: &Map<String, Value> = item.as_map();
+    let title = item.title()?;
+    let link = safe_link(item.primary_link()?);
+    let date = if options.include_dates {
+        item.date_published()?
+            .and_then(|date| Timestamp::parse(date).ok())
+            .map(|date| {
+                let (year, month, day) = date.date();
+                let mut s = String::with_capacity(10);
+                let _ = write!(s, "{:04}-{:02}-{:02}", year, month, day);
+                s
+            })
+    } else {
+        None
+    };
+    let summary = crate::content::summary_text(map, options.max_summary_chars)?;
+
+    let html = &mut digest.html;
+    let text = &mut digest.text;
+    html.push_str("<li>");
+    match (title, link) {
+        (Some(title), Some(link)) => {
+            html.push_str("<a href=\"");
+            escape_into(link, html);
+            html.push_str("\">");
+            escape_into(title, html);
+            html.push_str("</a>");
+        }
+        (Some(title), None) => {
+            html.push_str("<strong>");
+            escape_into(title, html);
+            html.push_str("</strong>");
+        }
+        (None, _) => {}
+    }
+    if let Some(title) = title {
+        text.push_str(title);
+        text.push('\n');
+    }
+    if let Some(date) = date {
+        if title.is_some() {
+            html.push_str("<br>");
+        }
+        html.push_str("<small>");
+        html.push_str(&date);
+        html.push_str("</small>");
+        text.push_str(&date);
+        text.push('\n');
+    }
+    if let Some(summary) = summary {
+        html.push_str("<p>");
+        escape_into(&summary, html);
+        html.push_str("</p>");
+        text.push_str(&summary);
+        text.push('\n');
+    }
+    if let Some(link) = link {
+        if title.is_none() {
+            html.push_str("<p><a href=\"");
+            escape_into(link, html);
+            html.push_str("\">Read more</a></p>");
+        }
+        text.push_str(link);
+        text.push('\n');
+    }
+    html.push_str("</li>\n");
+    text.push('\n');
+    Ok(())
+}
+
+fn render_digest_html(
+    map: &Map<String, Value>,
+    items: &[ItemRef<'_>],
+    options: &DigestOptions,
+) -> Result<Digest, Error> {
+    let mut digest = Digest::default();
+    if options.include_feed_title {
+        if let Some(title) = FeedRef::from(map).title()? {
+            digest.html.push_str("<h1>");
+            escape_into(title, &mut digest.html);
+            digest.html.push_str("</h1>\n");
+            digest.text.push_str(title);
+            digest.text.push_str("\n\n");
+        }
+    }
+    digest.html.push_str("<ul>\n");
+    for item in items {
+        render_item(item, options, &mut digest)?;
+    }
+    digest.html.push_str("</ul>\n");
+    Ok(digest)
+}
+
+macro_rules! digest_methods {
+    () => {
+        /// Renders a digest listing the items with their titles, dates, summaries, and links.
+        ///
+        /// The items are usually the feed's new items since the last digest. The HTML does not
+        /// include any of the item's HTML content; summaries are plain text (see
+        /// `DigestOptions`) and only `http` and `https` links are included, so the fragment is
+        /// safe to embed in an email.
+        ///
+        /// # Errors
+        ///
+        /// If the feed's title or an item's properties are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned.
+        pub fn render_digest_html(
+            &self,
+            items: &[ItemRef<'_>],
+            options: &DigestOptions,
+        ) -> Result<Digest, Error> {
+            render_digest_html(self.as_map(), items, options)
+        }
+    };
+}
+
+impl Feed {
+    digest_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    digest_methods!();
+}
+
+impl<'a> FeedRef<'a> {
+    digest_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitized_digest() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "title": "<b>Feed</b>",
+            "items": [
+                {
+                    "id": "1",
+                    "url": "javascript:alert(1)",
+                    "content_html": "<script>alert(1)</script><p>Hello <i>there</i></p>"
+                },
+                { "id": "2", "url": "https://example.org/2", "content_text": "Untitled post" }
+            ]
+        }))?;
+        let items = feed.items()?.unwrap_or_default();
+        let options = DigestOptions {
+            include_dates: false,
+            ..DigestOptions::default()
+        };
+
+        let digest = feed.render_digest_html(&items, &options)?;
+        assert_eq!(
+            digest.html,
+            "<h1>&lt;b&gt;Feed&lt;/b&gt;</h1>\n\
+             <ul>\n\
+             <li><p>Hello there</p></li>\n\
+             <li><p>Untitled post</p><p><a href=\"https://example.org/2\">Read more</a></p></li>\n\
+             </ul>\n"
+        );
+        assert_eq!(
+            digest.text,
+            "<b>Feed</b>\n\nHello there\n\nUntitled post\nhttps://example.org/2\n\n"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,288 @@
+//! Push notification payloads for feed changes.
+//!
+//! Notifier services send a notification for each new item found when a feed is fetched again.
+//! [`build_payloads`] extracts the text of each notification from a [`FeedDiff`] and keeps it
+//! within push notification size limits.
+//!
+//! ```
+//! use json_feed_model::{date::Timestamp, notifications::{build_payloads, NotificationOptions}};
+//!
+//! let prev = json_feed_model::from_value(serde_json::json!({ "items": [] }))?;
+//! let next = json_feed_model::from_value(serde_json::json!({
+//!     "items": [{
+//!         "id": "1",
+//!         "title": "Hello",
+//!         "url": "https://example.org/1",
+//!         "content_html": "<p>The <em>first</em> post.</p>",
+//!         "image": "https://example.org/1.png",
+//!         "date_published": "2021-06-01T12:00:00Z"
+//!     }]
+//! }))?;
+//! let diff = json_feed_model::diff::diff(&prev, &next)?;
+//!
+//! let mut options = NotificationOptions::new();
+//! options.now = Some(Timestamp::parse("2021-06-01T15:00:00Z")?);
+//! let payloads = build_payloads(&diff, &options)?;
+//! assert_eq!(payloads[0].title, "Hello");
+//! assert_eq!(payloads[0].snippet.as_deref(), Some("The first post."));
+//! assert_eq!(payloads[0].url.as_deref(), Some("https://example.org/1"));
+//! assert_eq!(payloads[0].image.as_deref(), Some("https://example.org/1.png"));
+//! assert_eq!(payloads[0].date.as_deref(), Some("3 hours ago"));
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+use core::fmt::Write;
+
+use crate::{
+    date::Timestamp,
+    diff::{FeedDiff, ItemSummary},
+    Error,
+};
+
+/// The maximum size of an Apple Push Notification service or Firebase Cloud Messaging payload.
+pub const DEFAULT_MAX_PAYLOAD_BYTES: usize = 4096;
+
+/// Options for building notification payloads.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct NotificationOptions {
+    /// The maximum number of characters in a title.
+    pub max_title_chars: usize,
+    /// The maximum number of characters in a snippet.
+    pub max_snippet_chars: usize,
+    /// The maximum number of bytes in all of a notification's strings.
+    pub max_payload_bytes: usize,
+    /// Builds notifications for edited items in addition to new items.
+    pub include_edited: bool,
+    /// The current time.
+    ///
+    /// If set, dates are relative to the current time (e.g. "3 hours ago"). Otherwise, dates
+    /// are formatted as `YYYY-MM-DD`.
+    pub now: Option<Timestamp>,
+}
+
+impl NotificationOptions {
+    /// Instantiates with the default options.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Default for NotificationOptions {
+    fn default() -> Self {
+        Self {
+            max_title_chars: 65,
+            max_snippet_chars: 180,
+            max_payload_bytes: DEFAULT_MAX_PAYLOAD_BYTES,
+            include_edited: false,
+            now: None,
+        }
+    }
+}
+
+/// The content of a push notification about an item.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Notification {
+    /// The item's `id`.
+    pub id: String,
+    /// The item's title or, if it does not have one, the beginning of its text.
+    pub title: String,
+    /// A plain text excerpt of the item's summary or content.
+    pub snippet: Option<String>,
+    /// The item's `url` or `external_url`.
+    pub url: Option<String>,
+    /// The item's `image` or `banner_image`.
+    pub image: Option<String>,
+    /// The item's `date_published` for display.
+    pub date: Option<String>,
+    /// True if the item was edited, false if it is new.
+    pub is_update: bool,
+}
+
+impl Notification {
+    /// Returns the number of bytes in the notification's strings.
+    #[must_use]
+    pub fn payload_bytes(&self) -> usize {
+        [&self.snippet, &self.url, &self.image, &self.date]
+            .iter()
+            .filter_map(|value| value.as_ref())
+            .map(String::len)
+            .sum::<usize>()
+            + self.id.len()
+            + self.title.len()
+    }
+
+    /// Removes or shortens the least important strings until the notification fits.
+    ///
+    /// The image is removed first, then the snippet is shortened or removed, then the URL is
+    /// removed, and finally the title is shortened.
+    fn fit(&mut self, max_bytes: usize) {
+        if self.payload_bytes() > max_bytes {
+            self.image = None;
+        }
+        if let Some(snippet) = self.snippet.take() {
+            let over = self.payload_bytes() + snippet.len();
+            self.snippet = if over <= max_bytes {
+                Some(snippet)
+            } else {
+                shorten(&snippet, (snippet.len() + max_bytes).saturating_sub(over))
+            };
+        }
+        if self.payload_bytes() > max_bytes {
+            self.url = None;
+        }
+        let over = self.payload_bytes();
+        if over > max_bytes {
+            let max = (self.title.len() + max_bytes).saturating_sub(over);
+            self.title = shorten(&self.title, max).unwrap_or_default();
+        }
+    }
+}
+
+/// The bytes of the ellipsis appended to shortened text.
+const ELLIPSIS_BYTES: usize = '\u{2026}'.len_utf8();
+
+/// Shortens the text at a word boundary to at most `max_bytes` bytes including an ellipsis.
+fn shorten(text: &str, max_bytes: usize) -> Option<String> {
+    let len = crate::html::truncate_text_len(text, max_bytes.checked_sub(ELLIPSIS_BYTES)?);
+    if len == 0 {
+        return None;
+    }
+    let mut shortened = String::from(&text[..len]);
+    shortened.push('\u{2026}');
+    Some(shortened)
+}
+
+/// Returns the link if it can be opened from a notification.
+fn web_link(link: Option<&str>) -> Option<String> {
+    link.filter(|link| {
+        crate::url::scheme(link).map_or(false, |scheme| {
+            scheme.eq_ignore_ascii_case("https") || scheme.eq_ignore_ascii_case("http")
+        })
+    })
+    .map(String::from)
+}
+
+fn build_payload(
+    summary: &ItemSummary,
+    is_update: bool,
+    options: &NotificationOptions,
+) -> Result<Notification, Error> {
+    let item = &summary.item;
+    let map = item.as_map();
+    let snippet = crate::content::summary_text(map, options.max_snippet_chars)?;
+    let title = match &summary.title {
+        Some(title) => crate::content::truncate_words(title, options.max_title_chars),
+        None => crate::content::summary_text(map, options.max_title_chars)?
+            .unwrap_or_else(|| summary.id.clone()),
+    };
+    let date = match item.date_published()?.map(Timestamp::parse).transpose()? {
+        Some(date) => Some(match options.now {
+            Some(now) => crate::date::relative(item.language()?.unwrap_or("en"), &date, &now),
+            None => {
+                let (year, month, day) = date.date();
+                let mut s = String::with_capacity(10);
+                let _ = write!(s, "{:04}-{:02}-{:02}", year, month, day);
+                s
+            }
+        }),
+        None => None,
+    };
+
+    let mut notification = Notification {
+        id: summary.id.clone(),
+        title,
+        snippet,
+        url: web_link(summary.url.as_deref()),
+        image: web_link(item.image()?.or(item.banner_image()?)),
+        date,
+        is_update,
+    };
+    notification.fit(options.max_payload_bytes);
+    Ok(notification)
+}
+
+/// Builds a notification for each new item and, optionally, each edited item.
+///
+/// Titles and snippets are plain text with HTML removed and whitespace collapsed. Only `http`
+/// and `https` URLs are included. If a notification's strings are larger than
+/// `max_payload_bytes`, the image, snippet, URL, and title are removed or shortened in that
+/// order.
+///
+/// # Errors
+///
+/// If an item's properties are not the expected JSON type, then `Error::UnexpectedType` is
+/// returned. If an item's `date_published` is not a valid date, then `Error::InvalidDate` is
+/// returned.
+pub fn build_payloads(
+    diff: &FeedDiff,
+    options: &NotificationOptions,
+) -> Result<Vec<Notification>, Error> {
+    let edited = diff
+        .edited
+        .iter()
+        .filter(|_| options.include_edited)
+        .map(|edited| (&edited.item, true));
+    diff.added
+        .iter()
+        .map(|item| (item, false))
+        .chain(edited)
+        .map(|(item, is_update)| build_payload(item, is_update, options))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size_limits() -> Result<(), Error> {
+        let prev = crate::from_value(serde_json::json!({
+            "items": [{ "id": "1", "title": "Old" }]
+        }))?;
+        let next = crate::from_value(serde_json::json!({
+            "items": [
+                { "id": "1", "title": "Old", "summary": "Fixed a typo." },
+                {
+                    "id": "2",
+                    "content_text": "No   title but a long text body about many things",
+                    "url": "javascript:alert(1)",
+                    "banner_image": "https://example.org/banner.png",
+                    "date_published": "2021-06-01T23:30:00-04:00"
+                }
+            ]
+        }))?;
+        let diff = crate::diff::diff(&prev, &next)?;
+
+        let mut options = NotificationOptions::new();
+        options.max_title_chars = 20;
+        let payloads = build_payloads(&diff, &options)?;
+        assert_eq!(payloads.len(), 1);
+        assert_eq!(payloads[0].title, "No title but a long\u{2026}");
+        assert_eq!(payloads[0].url, None);
+        assert_eq!(
+            payloads[0].image.as_deref(),
+            Some("https://example.org/banner.png")
+        );
+        assert_eq!(payloads[0].date.as_deref(), Some("2021-06-01"));
+
+        options.include_edited = true;
+        options.max_payload_bytes = 50;
+        let payloads = build_payloads(&diff, &options)?;
+        assert_eq!(payloads.len(), 2);
+        assert_eq!(payloads[0].image, None);
+        assert_eq!(payloads[0].snippet.as_deref(), Some("No title but\u{2026}"));
+        assert!(payloads[0].payload_bytes() <= 50);
+        assert!(payloads[1].is_update);
+        assert_eq!(payloads[1].title, "Old");
+        assert_eq!(payloads[1].snippet.as_deref(), Some("Fixed a typo."));
+        Ok(())
+    }
+}
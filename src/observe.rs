@@ -0,0 +1,236 @@
+//! Callbacks for observing feed processing.
+//!
+//! Metrics systems can implement [`Observer`] to count bytes, items, failures, and validation
+//! outcomes without the crate depending on a metrics library. Observers receive `&self`, so
+//! implementations typically use atomic counters or their metrics library's handles.
+//!
+//! ```
+//! use core::sync::atomic::{AtomicUsize, Ordering};
+//! use json_feed_model::{observe::{self, Observer}, Error};
+//!
+//! #[derive(Default)]
+//! struct Counters {
+//!     bytes: AtomicUsize,
+//!     items: AtomicUsize,
+//! }
+//!
+//! impl Observer for Counters {
+//!     fn bytes_read(&self, bytes: usize) {
+//!         self.bytes.fetch_add(bytes, Ordering::Relaxed);
+//!     }
+//!
+//!     fn parse_finished(&self, item_count: Option<usize>, _error: Option<&Error>) {
+//!         self.items.fetch_add(item_count.unwrap_or(0), Ordering::Relaxed);
+//!     }
+//! }
+//!
+//! let counters = Counters::default();
+//! let json = br#"{ "items": [{ "id": "1" }] }"#;
+//! let feed = observe::from_slice_with(json, &counters)?;
+//! assert_eq!(counters.bytes.load(Ordering::Relaxed), json.len());
+//! assert_eq!(counters.items.load(Ordering::Relaxed), 1);
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{Error, Feed, ValidationOptions, Version};
+
+/// Callbacks invoked while feeds are parsed and validated.
+///
+/// Every method has an empty default implementation.
+pub trait Observer {
+    /// Called before a feed is parsed.
+    fn parse_started(&self) {}
+
+    /// Called with the number of bytes which were decoded.
+    fn bytes_read(&self, bytes: usize) {
+        let _ = bytes;
+    }
+
+    /// Called after a feed is parsed.
+    ///
+    /// `item_count` is the length of the `items` array if it is a JSON array. `error` is the
+    /// error which is returned if the feed could not be parsed.
+    fn parse_finished(&self, item_count: Option<usize>, error: Option<&Error>) {
+        let _ = (item_count, error);
+    }
+
+    /// Called after a feed is validated.
+    fn validated(&self, version: &Version<'_>, valid: bool) {
+        let _ = (version, valid);
+    }
+}
+
+/// An observer which ignores every callback.
+impl Observer for () {}
+
+impl<T> Observer for &T
+where
+    T: Observer + ?Sized,
+{
+    fn parse_started(&self) {
+        (**self).parse_started();
+    }
+
+    fn bytes_read(&self, bytes: usize) {
+        (**self).bytes_read(bytes);
+    }
+
+    fn parse_finished(&self, item_count: Option<usize>, error: Option<&Error>) {
+        (**self).parse_finished(item_count, error);
+    }
+
+    fn validated(&self, version: &Version<'_>, valid: bool) {
+        (**self).validated(version, valid);
+    }
+}
+
+fn finish<O: Observer>(result: Result<Feed, Error>, observer: &O) -> Result<Feed, Error> {
+    match &result {
+        Ok(feed) => observer.parse_finished(
+            feed.as_map()
+                .get("items")
+                .and_then(Value::as_array)
+                .map(Vec::len),
+            None,
+        ),
+        Err(error) => observer.parse_finished(None, Some(error)),
+    }
+    result
+}
+
+/// Attempts to JSON decode a byte slice and return a `Feed` while notifying an observer.
+///
+/// See `from_slice`.
+///
+/// # Errors
+///
+/// If the byte slice cannot be JSON decoded, then `Error::SerdeJson(serde_json::Error)` is returned.
+///
+/// If the decoded JSON value is not an Object, then `Error::UnexpectedType` is returned.
+pub fn from_slice_with<O: Observer>(v: &[u8], observer: &O) -> Result<Feed, Error> {
+    observer.parse_started();
+    observer.bytes_read(v.len());
+    finish(crate::from_slice(v), observer)
+}
+
+/// Attempts to JSON decode a reader's data and return a `Feed` while notifying an observer.
+///
+/// See `from_reader`. The number of bytes read is reported after the data is decoded.
+///
+/// # Errors
+///
+/// If the data cannot be JSON decoded, then `Error::SerdeJson(serde_json::Error)` is returned.
+///
+/// If the decoded JSON value is not an Object, then `Error::UnexpectedType` is returned.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn from_reader_with<R, O>(reader: R, observer: &O) -> Result<Feed, Error>
+where
+    R: std::io::Read,
+    O: Observer,
+{
+    struct CountingReader<R> {
+        inner: R,
+        count: usize,
+    }
+
+    impl<R: std::io::Read> std::io::Read for CountingReader<R> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let read = self.inner.read(buf)?;
+            self.count += read;
+            Ok(read)
+        }
+    }
+
+    observer.parse_started();
+    let mut reader = CountingReader {
+        inner: reader,
+        count: 0,
+    };
+    let result = crate::from_reader(&mut reader);
+    observer.bytes_read(reader.count);
+    finish(result, observer)
+}
+
+/// Verifies if a JSON object is a valid `Feed` while notifying an observer of the outcome.
+///
+/// See `validate_feed_map`.
+#[must_use]
+pub fn validate_with<O: Observer>(
+    map: &Map<String, Value>,
+    version: &Version<'_>,
+    options: &ValidationOptions,
+    observer: &O,
+) -> bool {
+    let valid = crate::validate_feed_map(map, version, options);
+    observer.validated(version, valid);
+    valid
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use core::cell::RefCell;
+
+    #[derive(Default)]
+    struct Recorder {
+        events: RefCell<Vec<String>>,
+    }
+
+    impl Observer for Recorder {
+        fn parse_started(&self) {
+            self.events.borrow_mut().push(String::from("started"));
+        }
+
+        fn bytes_read(&self, bytes: usize) {
+            self.events.borrow_mut().push(format!("bytes {}", bytes));
+        }
+
+        fn parse_finished(&self, item_count: Option<usize>, error: Option<&Error>) {
+            self.events
+                .borrow_mut()
+                .push(format!("finished {:?} {}", item_count, error.is_some()));
+        }
+
+        fn validated(&self, version: &Version<'_>, valid: bool) {
+            self.events
+                .borrow_mut()
+                .push(format!("validated {} {}", version, valid));
+        }
+    }
+
+    #[test]
+    fn callbacks() {
+        let recorder = Recorder::default();
+        let json =
+            r#"{ "version": "https://jsonfeed.org/version/1.1", "title": "A", "items": [] }"#;
+        let feed = from_reader_with(json.as_bytes(), &recorder).unwrap();
+        assert!(validate_with(
+            feed.as_map(),
+            &Version::Version1_1,
+            &ValidationOptions::new(),
+            &recorder
+        ));
+        assert!(from_slice_with(b"[]", &recorder).is_err());
+
+        assert_eq!(
+            recorder.events.into_inner(),
+            vec![
+                String::from("started"),
+                format!("bytes {}", json.len()),
+                String::from("finished Some(0) false"),
+                String::from("validated https://jsonfeed.org/version/1.1 true"),
+                String::from("started"),
+                String::from("bytes 2"),
+                String::from("finished None true"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,165 @@
+//! Cursor based pagination over items.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{Error, Feed, FeedMut, FeedRef, ItemRef};
+
+/// Returns the items with an `id` sorted by their `id`.
+///
+/// If items have the same `id`, then `Error::DuplicateId` is returned.
+fn items_by_id(map: &Map<String, Value>) -> Result<Vec<(&str, ItemRef<'_>)>, Error> {
+    let mut items = Vec::new();
+    if let Some(values) = map.get("items") {
+        for value in values.as_array().ok_or(Error::UnexpectedType)? {
+            let item = value.as_object().ok_or(Error::UnexpectedType)?;
+            if let Some(id) = crate::ext::__private::str_prop::get(item, None, "id")? {
+                items.push((id, ItemRef::from(item)));
+            }
+        }
+    }
+    items.sort_by_key(|(id, _)| *id);
+    if items.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+        return Err(Error::DuplicateId);
+    }
+    Ok(items)
+}
+
+fn page_after<'a>(
+    map: &'a Map<String, Value>,
+    cursor: Option<&str>,
+    page_size: usize,
+) -> Result<Vec<ItemRef<'a>>, Error> {
+    let items = items_by_id(map)?;
+    let start = cursor.map_or(0, |cursor| items.partition_point(|(id, _)| *id <= cursor));
+    Ok(items
+        .into_iter()
+        .skip(start)
+        .take(page_size)
+        .map(|(_, item)| item)
+        .collect())
+}
+
+fn cursor_for(map: &Map<String, Value>, index: usize) -> Result<Option<&str>, Error> {
+    Ok(items_by_id(map)?.get(index).map(|(id, _)| *id))
+}
+
+macro_rules! pagination_methods {
+    () => {
+        /// Returns up to `page_size` items which come after the `cursor`.
+        ///
+        /// Items are paginated in the order of their `id`s, so pages are the same regardless
+        /// of the order of the items in the feed. The cursor is the `id` of the last item on
+        /// the previous page, or `None` for the first page. The cursor does not need to be the
+        /// `id` of an item in the feed, so cursors remain valid after items are removed. Items
+        /// without an `id` are not paginated.
+        ///
+        /// The `id`s must be unique. Otherwise, items with the same `id` as the cursor would be
+        /// skipped at a page boundary.
+        ///
+        /// # Errors
+        ///
+        /// If the items or their ids are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned.
+        ///
+        /// If items have the same `id`, then `Error::DuplicateId` is returned.
+        pub fn page_after(
+            &self,
+            cursor: Option<&str>,
+            page_size: usize,
+        ) -> Result<Vec<ItemRef<'_>>, Error> {
+            page_after(self.as_map(), cursor, page_size)
+        }
+
+        /// Returns the cursor for the item at `index` in pagination order.
+        ///
+        /// Passing the cursor to `page_after` returns the items after the item at `index`. If
+        /// `index` is out of bounds, then `None` is returned.
+        ///
+        /// # Errors
+        ///
+        /// If the items or their ids are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned.
+        ///
+        /// If items have the same `id`, then `Error::DuplicateId` is returned.
+        pub fn cursor_for(&self, index: usize) -> Result<Option<&str>, Error> {
+            cursor_for(self.as_map(), index)
+        }
+    };
+}
+
+impl Feed {
+    pagination_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    pagination_methods!();
+}
+
+impl<'a> FeedRef<'a> {
+    pagination_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::{string::String, vec, vec::Vec};
+
+    fn ids(items: &[ItemRef<'_>]) -> Result<Vec<String>, Error> {
+        items
+            .iter()
+            .map(|item| Ok(String::from(item.id()?.unwrap_or_default())))
+            .collect()
+    }
+
+    #[test]
+    fn pages() -> Result<(), Error> {
+        let mut feed = crate::from_value(serde_json::json!({
+            "items": [{ "id": "c" }, { "id": "a" }, { "title": "no id" }, { "id": "d" }, { "id": "b" }]
+        }))?;
+
+        assert_eq!(ids(&feed.page_after(None, 2)?)?, vec!["a", "b"]);
+        let cursor = feed.cursor_for(1)?.map(String::from);
+        assert_eq!(cursor.as_deref(), Some("b"));
+        assert_eq!(
+            ids(&feed.page_after(cursor.as_deref(), 2)?)?,
+            vec!["c", "d"]
+        );
+        assert_eq!(feed.cursor_for(4)?, None);
+        assert!(feed.page_after(Some("d"), 2)?.is_empty());
+
+        // Removing the cursor's item does not change the next page.
+        let mut items = feed
+            .items()?
+            .unwrap_or_default()
+            .iter()
+            .map(|item| item.as_map().clone())
+            .collect::<Vec<_>>();
+        items.retain(|item| item.get("id") != Some(&Value::from("b")));
+        feed.as_map_mut().insert(
+            String::from("items"),
+            Value::Array(items.into_iter().map(Value::Object).collect()),
+        );
+        assert_eq!(
+            ids(&feed.page_after(cursor.as_deref(), 2)?)?,
+            vec!["c", "d"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn duplicate_ids() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "items": [{ "id": "a" }, { "id": "b" }, { "id": "a" }]
+        }))?;
+        assert!(matches!(feed.page_after(None, 2), Err(Error::DuplicateId)));
+        assert!(matches!(feed.cursor_for(0), Err(Error::DuplicateId)));
+        Ok(())
+    }
+}
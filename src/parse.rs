@@ -0,0 +1,170 @@
+//! Decoding of JSON data into the model types.
+//!
+//! The functions are re-exported from the crate root.
+
+#[cfg(all(feature = "alloc", not(feature = "std"), feature = "tracing"))]
+use alloc::vec::Vec;
+#[cfg(all(feature = "std", feature = "tracing"))]
+use std::vec::Vec;
+
+use serde_json::Value;
+
+use crate::{Attachment, Author, Error, Feed, Hub, Item};
+
+/// Attempts to JSON decode a `std::io::Read` and return a `Feed`.
+///
+/// # Errors
+///
+/// If the data cannot be JSON decoded, then `Error::SerdeJson(serde_json::Error)` is returned.
+///
+/// If the decoded JSON value is not an Object, then `Error::UnexpectedType` is returned.
+#[cfg(feature = "std")]
+pub fn from_reader<R>(reader: R) -> Result<Feed, Error>
+where
+    R: std::io::Read,
+{
+    let _span = trace_span!("parse_feed");
+    let value = match serde_json::from_reader(reader) {
+        Ok(value) => value,
+        Err(error) => {
+            trace_event!(%error, "feed cannot be decoded");
+            return Err(Error::SerdeJson(error));
+        }
+    };
+    from_value(value)
+}
+
+/// Attempts to JSON decode a `str` and return a `Feed`.
+///
+/// # Errors
+///
+/// If the string cannot be JSON decoded, then `Error::SerdeJson(serde_json::Error)` is returned.
+///
+/// If the decoded JSON value is not an Object, then `Error::UnexpectedType` is returned.
+pub fn from_str(s: &str) -> Result<Feed, Error> {
+    from_slice(s.as_bytes())
+}
+
+/// Attempts to JSON decode a byte slice and return a `Feed`.
+///
+/// # Errors
+///
+/// If the byte slice cannot be JSON decoded, then `Error::SerdeJson(serde_json::Error)` is returned.
+///
+/// If the decoded JSON value is not an Object, then `Error::UnexpectedType` is returned.
+pub fn from_slice(v: &[u8]) -> Result<Feed, Error> {
+    let _span = trace_span!("parse_feed", bytes = v.len());
+    let value = match serde_json::from_slice(v) {
+        Ok(value) => value,
+        Err(error) => {
+            trace_event!(%error, "feed cannot be decoded");
+            return Err(Error::SerdeJson(error));
+        }
+    };
+    from_value(value)
+}
+
+/// Attempts to return a `Feed` from a JSON `Value`.
+///
+/// # Errors
+///
+/// If the JSON value is not an Object, then `Error::UnexpectedType` is returned.
+///
+/// # Example
+///
+/// If the library user wishes to save invalid JSON values, a simple check should be done
+/// before calling the function.
+///
+/// ```
+/// let value = serde_json::json!("a JSON String, not an Object");
+/// match &value {
+///     serde_json::Value::Object(_) => {
+///         let feed_result = json_feed_model::from_value(value);
+///         assert!(false, "should not have execute this code")
+///     }
+///     _ => {
+///         // handle the invalid JSON value
+///     },
+/// }
+pub fn from_value(value: Value) -> Result<Feed, Error> {
+    match value {
+        Value::Object(obj) => {
+            trace_event!(
+                items = obj
+                    .get("items")
+                    .and_then(|items| items.as_array())
+                    .map(Vec::len),
+                "parsed feed"
+            );
+            Ok(Feed { value: obj })
+        }
+        _ => {
+            trace_event!("feed is not a JSON object");
+            Err(Error::UnexpectedType)
+        }
+    }
+}
+
+macro_rules! standalone_parse_fns {
+    ($owned:ident, $name:expr, $from_str:ident, $from_slice:ident, $from_value:ident) => {
+        #[doc = concat!("Attempts to JSON decode a `str` and return ", $name, ".")]
+        ///
+        /// # Errors
+        ///
+        /// If the string cannot be JSON decoded, then `Error::SerdeJson(serde_json::Error)` is
+        /// returned.
+        ///
+        /// If the decoded JSON value is not an Object, then `Error::UnexpectedType` is returned.
+        pub fn $from_str(s: &str) -> Result<$owned, Error> {
+            $from_slice(s.as_bytes())
+        }
+
+        #[doc = concat!("Attempts to JSON decode a byte slice and return ", $name, ".")]
+        ///
+        /// # Errors
+        ///
+        /// If the byte slice cannot be JSON decoded, then `Error::SerdeJson(serde_json::Error)` is
+        /// returned.
+        ///
+        /// If the decoded JSON value is not an Object, then `Error::UnexpectedType` is returned.
+        pub fn $from_slice(v: &[u8]) -> Result<$owned, Error> {
+            let value = serde_json::from_slice(v)?;
+            $from_value(value)
+        }
+
+        #[doc = concat!("Attempts to return ", $name, " from a JSON `Value`.")]
+        ///
+        /// # Errors
+        ///
+        /// If the JSON value is not an Object, then `Error::UnexpectedType` is returned.
+        pub fn $from_value(value: Value) -> Result<$owned, Error> {
+            match value {
+                Value::Object(obj) => Ok($owned { value: obj }),
+                _ => Err(Error::UnexpectedType),
+            }
+        }
+    };
+}
+
+standalone_parse_fns!(
+    Item,
+    "an `Item`",
+    item_from_str,
+    item_from_slice,
+    item_from_value
+);
+standalone_parse_fns!(
+    Author,
+    "an `Author`",
+    author_from_str,
+    author_from_slice,
+    author_from_value
+);
+standalone_parse_fns!(
+    Attachment,
+    "an `Attachment`",
+    attachment_from_str,
+    attachment_from_slice,
+    attachment_from_value
+);
+standalone_parse_fns!(Hub, "a `Hub`", hub_from_str, hub_from_slice, hub_from_value);
@@ -0,0 +1,228 @@
+//! Permalinks generated from URL templates.
+//!
+//! Feeds generated from a database often have item URLs which follow a pattern. A template is a
+//! string with placeholders in braces which are replaced with values from each item:
+//!
+//! * `{id}` - The item's `id`, percent-encoded as a path segment.
+//! * `{slug}` - The item's slug. See `Item::slug`.
+//! * `{year}`, `{month}`, `{day}` - The date of the item's `date_published` in the offset it
+//!   is written with. The month and day are zero-padded to two digits.
+//!
+//! ```
+//! let mut feed = json_feed_model::from_str(r#"{
+//!     "items": [
+//!         { "id": "42", "title": "Hello, World!", "date_published": "2021-06-01T12:00:00Z" },
+//!         { "id": "43", "url": "https://example.org/custom" }
+//!     ]
+//! }"#)?;
+//!
+//! let assigned = feed.assign_item_urls("https://example.org/{year}/{month}/{slug}")?;
+//! assert_eq!(assigned, 1);
+//!
+//! let items = feed.items()?.unwrap();
+//! assert_eq!(items[0].url()?, Some("https://example.org/2021/06/hello-world"));
+//! assert_eq!(items[1].url()?, Some("https://example.org/custom"));
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::string::String;
+#[cfg(feature = "std")]
+use std::string::String;
+
+use core::fmt::Write;
+
+use serde_json::{Map, Value};
+
+use crate::{date::Timestamp, ext::__private::str_prop, Error, Feed, FeedMut, ItemMut};
+
+/// A piece of a URL template.
+enum Part<'a> {
+    Literal(&'a str),
+    Id,
+    Slug,
+    Year,
+    Month,
+    Day,
+}
+
+/// Splits a template into literals and placeholders.
+fn parts(template: &str) -> impl Iterator<Item = Result<Part<'_>, Error>> {
+    let mut rest = template;
+    core::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let open = match rest.find('{') {
+            Some(0) => 0,
+            Some(open) => {
+                let literal = &rest[..open];
+                rest = &rest[open..];
+                return Some(Ok(Part::Literal(literal)));
+            }
+            None => {
+                let literal = rest;
+                rest = "";
+                return Some(Ok(Part::Literal(literal)));
+            }
+        };
+        let close = match rest.find('}') {
+            Some(close) => close,
+            None => {
+                rest = "";
+                return Some(Err(Error::InvalidTemplate));
+            }
+        };
+        let name = &rest[open + 1..close];
+        rest = &rest[close + 1..];
+        Some(match name {
+            "id" => Ok(Part::Id),
+            "slug" => Ok(Part::Slug),
+            "year" => Ok(Part::Year),
+            "month" => Ok(Part::Month),
+            "day" => Ok(Part::Day),
+            _ => Err(Error::InvalidTemplate),
+        })
+    })
+}
+
+/// Renders the template for an item.
+///
+/// Returns `None` if the item does not have a value for a placeholder.
+fn render(template: &str, item: &Map<String, Value>) -> Result<Option<String>, Error> {
+    let mut url = String::with_capacity(template.len() + 32);
+    let mut date = None;
+    for part in parts(template) {
+        match part? {
+            Part::Literal(literal) => url.push_str(literal),
+            Part::Id => match str_prop::get(item, None, "id")? {
+                Some(id) => crate::url::encode_path_segment_into(id, &mut url),
+                None => return Ok(None),
+            },
+            Part::Slug => match crate::slug::item_slug(item)? {
+                Some(slug) => url.push_str(&slug),
+                None => return Ok(None),
+            },
+            part @ Part::Year | part @ Part::Month | part @ Part::Day => {
+                if date.is_none() {
+                    date = match str_prop::get(item, None, "date_published")? {
+                        Some(date) => Some(Timestamp::parse(date)?.date()),
+                        None => return Ok(None),
+                    };
+                }
+                let (year, month, day) = date.unwrap_or_default();
+                let _ = match part {
+                    Part::Year => write!(url, "{:04}", year),
+                    Part::Month => write!(url, "{:02}", month),
+                    _ => write!(url, "{:02}", day),
+                };
+            }
+        }
+    }
+    Ok(Some(url))
+}
+
+fn assign_item_urls(map: &mut Map<String, Value>, template: &str) -> Result<usize, Error> {
+    // Validate the template even if no item needs a URL.
+    for part in parts(template) {
+        part?;
+    }
+
+    let items = match map.get_mut("items") {
+        None => return Ok(0),
+        Some(Value::Array(items)) => items,
+        Some(_) => return Err(Error::UnexpectedType),
+    };
+    let mut assigned = 0;
+    for item in items {
+        let item = match item {
+            Value::Object(obj) => obj,
+            _ => return Err(Error::UnexpectedType),
+        };
+        if item.contains_key("url") {
+            continue;
+        }
+        if let Some(url) = render(template, item)? {
+            ItemMut::from(item).set_url(url);
+            assigned += 1;
+        }
+    }
+    Ok(assigned)
+}
+
+impl Feed {
+    /// Sets the `url` of items without one by filling in a URL template.
+    ///
+    /// Items which already have a `url` are not changed. Items without a value for one of the
+    /// template's placeholders (e.g. an item without a `date_published` for `{year}`) are
+    /// skipped. Returns the number of items which were assigned a URL.
+    ///
+    /// See the [module documentation](crate::permalink) for the placeholders.
+    ///
+    /// # Errors
+    ///
+    /// If the template has an unknown or unclosed placeholder, then `Error::InvalidTemplate` is
+    /// returned. If the items or their properties are not the expected JSON type, then
+    /// `Error::UnexpectedType` is returned. If a `date_published` is needed but is not a valid
+    /// date, then `Error::InvalidDate` is returned.
+    pub fn assign_item_urls(&mut self, template: &str) -> Result<usize, Error> {
+        assign_item_urls(&mut self.value, template)
+    }
+}
+
+impl<'a> FeedMut<'a> {
+    /// Sets the `url` of items without one by filling in a URL template.
+    ///
+    /// Items which already have a `url` are not changed. Items without a value for one of the
+    /// template's placeholders (e.g. an item without a `date_published` for `{year}`) are
+    /// skipped. Returns the number of items which were assigned a URL.
+    ///
+    /// See the [module documentation](crate::permalink) for the placeholders.
+    ///
+    /// # Errors
+    ///
+    /// If the template has an unknown or unclosed placeholder, then `Error::InvalidTemplate` is
+    /// returned. If the items or their properties are not the expected JSON type, then
+    /// `Error::UnexpectedType` is returned. If a `date_published` is needed but is not a valid
+    /// date, then `Error::InvalidDate` is returned.
+    pub fn assign_item_urls(&mut self, template: &str) -> Result<usize, Error> {
+        assign_item_urls(self.value, template)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn templates() -> Result<(), Error> {
+        let mut feed = crate::from_value(serde_json::json!({
+            "items": [
+                { "id": "a/b c", "date_published": "2021-01-02T23:00:00-05:00" },
+                { "id": "2" }
+            ]
+        }))?;
+
+        assert!(matches!(
+            feed.assign_item_urls("/{year}/{unknown}"),
+            Err(Error::InvalidTemplate)
+        ));
+        assert!(matches!(
+            feed.assign_item_urls("/{year"),
+            Err(Error::InvalidTemplate)
+        ));
+
+        assert_eq!(feed.assign_item_urls("/{year}/{month}/{day}/{id}")?, 1);
+        let items = feed.items()?.unwrap_or_default();
+        assert_eq!(items[0].url()?, Some("/2021/01/02/a%2Fb%20c"));
+        assert_eq!(items[1].url()?, None);
+
+        assert_eq!(feed.assign_item_urls("/items/{id}")?, 1);
+        assert_eq!(
+            feed.items()?.unwrap_or_default()[1].url()?,
+            Some("/items/2")
+        );
+
+        Ok(())
+    }
+}
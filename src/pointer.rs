@@ -0,0 +1,486 @@
+//! Access to nested values with JSON Pointers.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{
+    Attachment, AttachmentMut, AttachmentRef, Author, AuthorMut, AuthorRef, Error, Feed, FeedMut,
+    FeedRef, Hub, HubMut, HubRef, Item, ItemMut, ItemRef,
+};
+
+/// The kinds of objects defined by the spec.
+#[derive(Clone, Copy)]
+enum Kind {
+    Feed,
+    Item,
+    Author,
+    Attachment,
+    Hub,
+}
+
+/// The expected type of a value.
+#[derive(Clone, Copy)]
+enum Expected {
+    String,
+    U64,
+    Bool,
+    Object(Kind),
+    Objects(Kind),
+    Strings,
+    Any,
+}
+
+impl Kind {
+    fn member(self, key: &str) -> Expected {
+        match (self, key) {
+            (
+                Kind::Feed,
+                "version" | "title" | "home_page_url" | "feed_url" | "description" | "user_comment"
+                | "next_url" | "icon" | "favicon" | "language",
+            ) => Expected::String,
+            (Kind::Feed, "expired") => Expected::Bool,
+            (Kind::Feed, "hubs") => Expected::Objects(Kind::Hub),
+            (Kind::Feed, "items") => Expected::Objects(Kind::Item),
+            (
+                Kind::Item,
+                "id" | "url" | "external_url" | "title" | "content_html" | "content_text"
+                | "summary" | "image" | "banner_image" | "date_published" | "date_modified"
+                | "language",
+            ) => Expected::String,
+            (Kind::Item, "tags") => Expected::Strings,
+            (Kind::Item, "attachments") => Expected::Objects(Kind::Attachment),
+            (Kind::Feed | Kind::Item, "author") => Expected::Object(Kind::Author),
+            (Kind::Feed | Kind::Item, "authors") => Expected::Objects(Kind::Author),
+            (Kind::Author, "name" | "url" | "avatar")
+            | (Kind::Attachment, "url" | "mime_type" | "title")
+            | (Kind::Hub, "type" | "url") => Expected::String,
+            (Kind::Attachment, "size_in_bytes" | "duration_in_seconds") => Expected::U64,
+            // Extensions and unknown members can have any type.
+            _ => Expected::Any,
+        }
+    }
+}
+
+impl Expected {
+    fn child(self, token: &str) -> Expected {
+        match self {
+            Expected::Object(kind) => kind.member(token),
+            Expected::Objects(kind) => Expected::Object(kind),
+            Expected::Strings => Expected::String,
+            Expected::String | Expected::U64 | Expected::Bool | Expected::Any => Expected::Any,
+        }
+    }
+
+    fn matches(self, value: &Value) -> bool {
+        match (self, value) {
+            (Expected::Any, _)
+            | (Expected::String, Value::String(_))
+            | (Expected::Bool, Value::Bool(_)) => true,
+            (Expected::U64, Value::Number(n)) => n.is_u64(),
+            (Expected::Object(kind), Value::Object(obj)) => obj
+                .iter()
+                .all(|(key, value)| kind.member(key).matches(value)),
+            (Expected::Objects(kind), Value::Array(values)) => values
+                .iter()
+                .all(|value| Expected::Object(kind).matches(value)),
+            (Expected::Strings, Value::Array(values)) => values.iter().all(Value::is_string),
+            _ => false,
+        }
+    }
+}
+
+/// Splits a JSON Pointer into its unescaped reference tokens.
+fn tokens(pointer: &str) -> Result<Vec<String>, Error> {
+    let rest = pointer.strip_prefix('/').ok_or(Error::InvalidPointer)?;
+    rest.split('/')
+        .map(|token| {
+            let mut unescaped = String::with_capacity(token.len());
+            let mut chars = token.chars();
+            while let Some(c) = chars.next() {
+                if c == '~' {
+                    match chars.next() {
+                        Some('0') => unescaped.push('~'),
+                        Some('1') => unescaped.push('/'),
+                        _ => return Err(Error::InvalidPointer),
+                    }
+                } else {
+                    unescaped.push(c);
+                }
+            }
+            Ok(unescaped)
+        })
+        .collect()
+}
+
+fn index(token: &str) -> Option<usize> {
+    if token.is_empty() || (token.len() > 1 && token.starts_with('0')) {
+        return None;
+    }
+    if !token.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    token.parse().ok()
+}
+
+/// Returns the first token and the rest of the pointer.
+fn split_first(pointer: &str) -> Option<(String, &str)> {
+    let rest = pointer.strip_prefix('/')?;
+    let end = rest.find('/').unwrap_or(rest.len());
+    let first = tokens(&pointer[..=end]).ok()?.pop()?;
+    Some((first, &rest[end..]))
+}
+
+fn get_pointer<'a>(map: &'a Map<String, Value>, pointer: &str) -> Option<&'a Value> {
+    let (first, rest) = split_first(pointer)?;
+    map.get(&first)?.pointer(rest)
+}
+
+pub(crate) fn get_pointer_mut<'a>(
+    map: &'a mut Map<String, Value>,
+    pointer: &str,
+) -> Option<&'a mut Value> {
+    let (first, rest) = split_first(pointer)?;
+    map.get_mut(&first)?.pointer_mut(rest)
+}
+
+/// Removes the value which a JSON Pointer refers to.
+///
+/// An array element is removed and the following elements are shifted.
+pub(crate) fn remove_pointer(map: &mut Map<String, Value>, pointer: &str) -> Option<Value> {
+    let mut tokens = tokens(pointer).ok()?;
+    let last = tokens.pop()?;
+    let parent = match tokens.split_first() {
+        None => return map.remove(&last),
+        Some((first, rest)) => {
+            let mut parent = map.get_mut(first.as_str())?;
+            for token in rest {
+                parent = match parent {
+                    Value::Object(obj) => obj.get_mut(token.as_str()),
+                    Value::Array(values) => index(token).and_then(move |i| values.get_mut(i)),
+                    _ => None,
+                }?;
+            }
+            parent
+        }
+    };
+    match parent {
+        Value::Object(obj) => obj.remove(&last),
+        Value::Array(values) => {
+            let i = index(&last).filter(|i| *i < values.len())?;
+            Some(values.remove(i))
+        }
+        _ => None,
+    }
+}
+
+fn set_pointer(
+    map: &mut Map<String, Value>,
+    kind: Kind,
+    pointer: &str,
+    value: Value,
+) -> Result<Option<Value>, Error> {
+    let mut tokens = tokens(pointer)?;
+    let last = tokens.pop().ok_or(Error::InvalidPointer)?;
+
+    let mut expected = Expected::Object(kind);
+    for token in &tokens {
+        expected = expected.child(token);
+    }
+    if !expected.child(&last).matches(&value) {
+        return Err(Error::UnexpectedType);
+    }
+
+    let mut parent = match tokens.split_first() {
+        None => return Ok(map.insert(last, value)),
+        Some((first, rest)) => {
+            let mut parent = map.get_mut(first.as_str()).ok_or(Error::InvalidPointer)?;
+            for token in rest {
+                parent = match parent {
+                    Value::Object(obj) => obj.get_mut(token.as_str()),
+                    Value::Array(values) => index(token).and_then(move |i| values.get_mut(i)),
+                    _ => None,
+                }
+                .ok_or(Error::InvalidPointer)?;
+            }
+            parent
+        }
+    };
+    match &mut parent {
+        Value::Object(obj) => Ok(obj.insert(last, value)),
+        Value::Array(values) => {
+            let i = if last == "-" {
+                values.len()
+            } else {
+                index(&last).ok_or(Error::InvalidPointer)?
+            };
+            match i.cmp(&values.len()) {
+                core::cmp::Ordering::Less => Ok(Some(core::mem::replace(&mut values[i], value))),
+                core::cmp::Ordering::Equal => {
+                    values.push(value);
+                    Ok(None)
+                }
+                core::cmp::Ordering::Greater => Err(Error::InvalidPointer),
+            }
+        }
+        _ => Err(Error::InvalidPointer),
+    }
+}
+
+macro_rules! pointer_methods {
+    () => {
+        /// Returns the value which a [JSON Pointer][json_pointer] refers to.
+        ///
+        /// The pointer is relative to this object. For instance, `/items/0/attachments/1/url`
+        /// refers to the URL of the second attachment of the first item of a feed. If the
+        /// pointer is malformed, is empty, or does not refer to a value, then `None` is
+        /// returned.
+        ///
+        /// [json_pointer]: https://tools.ietf.org/html/rfc6901
+        #[must_use]
+        pub fn get_pointer(&self, pointer: &str) -> Option<&Value> {
+            get_pointer(self.as_map(), pointer)
+        }
+    };
+}
+
+macro_rules! pointer_mut_methods {
+    ($kind:expr) => {
+        /// Returns a mutable reference to the value which a [JSON Pointer][json_pointer]
+        /// refers to.
+        ///
+        /// The returned value can be changed to any type. Use `set_pointer` to check the type
+        /// of the value.
+        ///
+        /// [json_pointer]: https://tools.ietf.org/html/rfc6901
+        pub fn get_pointer_mut(&mut self, pointer: &str) -> Option<&mut Value> {
+            get_pointer_mut(self.as_map_mut(), pointer)
+        }
+
+        /// Sets the value which a [JSON Pointer][json_pointer] refers to and returns the
+        /// previous value.
+        ///
+        /// The parent of the value must exist. An array element can be replaced, or appended
+        /// with the index of the array's length or `-`.
+        ///
+        /// # Errors
+        ///
+        /// If the value does not have the type defined by the spec for its location, then
+        /// `Error::UnexpectedType` is returned. Objects and arrays are checked recursively, and
+        /// extensions and unknown members can have any type.
+        ///
+        /// If the pointer is malformed or its parent does not exist, then
+        /// `Error::InvalidPointer` is returned.
+        ///
+        /// [json_pointer]: https://tools.ietf.org/html/rfc6901
+        pub fn set_pointer(&mut self, pointer: &str, value: Value) -> Result<Option<Value>, Error> {
+            set_pointer(self.as_map_mut(), $kind, pointer, value)
+        }
+    };
+}
+
+macro_rules! impl_pointer {
+    ($kind:expr, $owned:ident, $borrowed:ident, $borrowed_mut:ident) => {
+        impl $owned {
+            pointer_methods!();
+            pointer_mut_methods!($kind);
+        }
+
+        impl<'a> $borrowed_mut<'a> {
+            pointer_methods!();
+            pointer_mut_methods!($kind);
+        }
+
+        impl<'a> $borrowed<'a> {
+            pointer_methods!();
+        }
+    };
+}
+
+impl_pointer!(Kind::Feed, Feed, FeedRef, FeedMut);
+impl_pointer!(Kind::Item, Item, ItemRef, ItemMut);
+impl_pointer!(Kind::Author, Author, AuthorRef, AuthorMut);
+impl_pointer!(Kind::Attachment, Attachment, AttachmentRef, AttachmentMut);
+impl_pointer!(Kind::Hub, Hub, HubRef, HubMut);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::vec;
+    use serde_json::json;
+
+    #[test]
+    fn get_and_set() -> Result<(), Error> {
+        let mut feed = crate::from_value(json!({
+            "title": "Example",
+            "items": [{
+                "id": "1",
+                "attachments": [
+                    { "url": "https://example.org/a.mp3", "mime_type": "audio/mpeg" },
+                    { "url": "https://example.org/b.mp3", "mime_type": "audio/mpeg" }
+                ],
+                "_a/b": { "c~d": 1 }
+            }]
+        }))?;
+
+        assert_eq!(
+            feed.get_pointer("/items/0/attachments/1/url"),
+            Some(&json!("https://example.org/b.mp3"))
+        );
+        assert_eq!(feed.get_pointer("/items/0/_a~1b/c~0d"), Some(&json!(1)));
+        assert_eq!(feed.get_pointer("/items/1"), None);
+        assert_eq!(feed.get_pointer(""), None);
+
+        if let Some(title) = feed.get_pointer_mut("/title") {
+            *title = json!("Renamed");
+        }
+        assert_eq!(feed.title()?, Some("Renamed"));
+
+        assert_eq!(
+            feed.set_pointer("/items/0/attachments/0/size_in_bytes", json!(100))?,
+            None
+        );
+        assert_eq!(
+            feed.set_pointer("/items/0/tags/-", json!("a"))
+                .map_err(|e| matches!(e, Error::InvalidPointer)),
+            Err(true)
+        );
+        feed.set_pointer("/items/0/tags", json!(["a"]))?;
+        feed.set_pointer("/items/0/tags/-", json!("b"))?;
+        assert_eq!(feed.items()?.unwrap()[0].tags()?, Some(vec!["a", "b"]));
+        assert_eq!(
+            feed.set_pointer("/items/0/id", json!("2"))?,
+            Some(json!("1"))
+        );
+        feed.set_pointer("/items/0/_custom", json!([1, null]))?;
+
+        assert!(matches!(
+            feed.set_pointer("/items/0/attachments/0/size_in_bytes", json!(-1)),
+            Err(Error::UnexpectedType)
+        ));
+        assert!(matches!(
+            feed.set_pointer("/items/-", json!({ "id": 3 })),
+            Err(Error::UnexpectedType)
+        ));
+        assert!(matches!(
+            feed.set_pointer("/items/5", json!({ "id": "3" })),
+            Err(Error::InvalidPointer)
+        ));
+        assert!(matches!(
+            feed.set_pointer("title", json!("x")),
+            Err(Error::InvalidPointer)
+        ));
+
+        let mut item = Item::new();
+        item.set_pointer("/author", json!({ "name": "Jane" }))?;
+        assert_eq!(item.author()?.unwrap().name()?, Some("Jane"));
+        assert!(matches!(
+            item.set_pointer("/author/name", json!(1)),
+            Err(Error::UnexpectedType)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn escapes() -> Result<(), Error> {
+        let mut feed = crate::from_value(json!({
+            "_x": { "a/b": 1, "c~d": 2, "~1": 3 }
+        }))?;
+
+        assert_eq!(feed.get_pointer("/_x/a~1b"), Some(&json!(1)));
+        assert_eq!(feed.get_pointer("/_x/c~0d"), Some(&json!(2)));
+        assert_eq!(feed.get_pointer("/_x/~01"), Some(&json!(3)));
+        assert_eq!(feed.get_pointer("/_x/a/b"), None);
+        assert_eq!(feed.get_pointer("/_x/c~2d"), None);
+
+        assert_eq!(feed.set_pointer("/_x/a~1b", json!(4))?, Some(json!(1)));
+        assert_eq!(feed.set_pointer("/_x/e~0~1f", json!(5))?, None);
+        assert_eq!(feed.as_map()["_x"]["e~/f"], json!(5));
+        feed.set_pointer("/_y~1z", json!(6))?;
+        assert_eq!(feed.as_map()["_y/z"], json!(6));
+
+        assert!(matches!(
+            feed.set_pointer("/_x/c~2d", json!(0)),
+            Err(Error::InvalidPointer)
+        ));
+        assert!(matches!(
+            feed.set_pointer("/_x/c~", json!(0)),
+            Err(Error::InvalidPointer)
+        ));
+
+        assert_eq!(
+            remove_pointer(feed.as_map_mut(), "/_x/c~0d"),
+            Some(json!(2))
+        );
+        assert_eq!(remove_pointer(feed.as_map_mut(), "/_x/c~0d"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn array_indices() -> Result<(), Error> {
+        let mut feed = crate::from_value(json!({
+            "items": [{ "id": "1", "tags": ["a", "b"] }]
+        }))?;
+
+        assert_eq!(feed.get_pointer("/items/0/tags/1"), Some(&json!("b")));
+        assert_eq!(feed.get_pointer("/items/0/tags/2"), None);
+        assert_eq!(feed.get_pointer("/items/0/tags/01"), None);
+        assert_eq!(feed.get_pointer("/items/0/tags/-"), None);
+        assert!(feed.get_pointer_mut("/items/1").is_none());
+
+        assert_eq!(
+            feed.set_pointer("/items/0/tags/0", json!("z"))?,
+            Some(json!("a"))
+        );
+        assert_eq!(feed.set_pointer("/items/0/tags/2", json!("c"))?, None);
+        assert!(matches!(
+            feed.set_pointer("/items/0/tags/4", json!("e")),
+            Err(Error::InvalidPointer)
+        ));
+        assert!(matches!(
+            feed.set_pointer("/items/0/tags/01", json!("e")),
+            Err(Error::InvalidPointer)
+        ));
+        assert!(matches!(
+            feed.set_pointer("/items/0/tags/x", json!("e")),
+            Err(Error::InvalidPointer)
+        ));
+        assert_eq!(feed.as_map()["items"][0]["tags"], json!(["z", "b", "c"]));
+
+        assert_eq!(remove_pointer(feed.as_map_mut(), "/items/0/tags/3"), None);
+        assert_eq!(
+            remove_pointer(feed.as_map_mut(), "/items/0/tags/0"),
+            Some(json!("z"))
+        );
+        assert_eq!(feed.as_map()["items"][0]["tags"], json!(["b", "c"]));
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_pointers() -> Result<(), Error> {
+        let mut feed = crate::from_value(json!({
+            "title": "Example",
+            "items": [{ "id": "1" }]
+        }))?;
+
+        for pointer in &["", "title", "/missing/title", "/items/1/id", "/title/0"] {
+            assert!(
+                matches!(
+                    feed.set_pointer(pointer, json!("x")),
+                    Err(Error::InvalidPointer)
+                ),
+                "{}",
+                pointer
+            );
+        }
+        assert_eq!(feed.get_pointer("title"), None);
+        assert_eq!(feed.get_pointer("/title/0"), None);
+        assert_eq!(remove_pointer(feed.as_map_mut(), "title"), None);
+        assert_eq!(feed.title()?, Some("Example"));
+        Ok(())
+    }
+}
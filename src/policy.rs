@@ -0,0 +1,350 @@
+//! Policies for the URLs in a republished feed.
+//!
+//! When third-party feeds are republished inside a restricted network, links to some hosts or
+//! schemes may not be allowed. A [`UrlPolicy`] has a [`UrlRule`] for each kind of URL, such as
+//! attachments or avatars. [`Feed::enforce_url_policy`] removes or proxies the URLs which a
+//! rule does not permit and reports each change.
+//!
+//! ```
+//! use json_feed_model::{
+//!     policy::{Resolution, UrlPolicy, UrlRule, ViolationAction},
+//!     urls::UrlKind,
+//! };
+//!
+//! let mut feed = json_feed_model::from_str(r#"{
+//!     "home_page_url": "https://tracker.example/",
+//!     "items": [{
+//!         "id": "1",
+//!         "url": "https://blog.example.org/1",
+//!         "attachments": [{ "url": "http://cdn.example.net/1.mp3", "mime_type": "audio/mpeg" }]
+//!     }]
+//! }"#)?;
+//!
+//! let mut policy = UrlPolicy::new();
+//! policy.default_rule.allowed_hosts = vec![String::from("example.org")];
+//! let mut attachments = UrlRule::new();
+//! attachments.allowed_schemes = vec![String::from("https")];
+//! attachments.action = ViolationAction::Proxy(String::from("https://proxy.example.org/?u={url}"));
+//! policy.rules.insert(UrlKind::Attachment, attachments);
+//!
+//! let violations = feed.enforce_url_policy(&policy)?;
+//! assert_eq!(violations.len(), 2);
+//! assert_eq!(violations[0].path, "/home_page_url");
+//! assert_eq!(violations[0].resolution, Resolution::Removed);
+//! assert_eq!(feed.home_page_url()?, None);
+//! assert_eq!(
+//!     feed.items()?.unwrap()[0].attachments()?.unwrap()[0].url()?,
+//!     Some("https://proxy.example.org/?u=http%3A%2F%2Fcdn.example.net%2F1.mp3")
+//! );
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{collections::BTreeMap, string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{urls::UrlKind, Error, Feed, FeedMut};
+
+/// What is done with a URL which a rule does not permit.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ViolationAction {
+    /// The URL is removed.
+    ///
+    /// A hub or attachment is removed with its URL since the URL is required.
+    Remove,
+    /// The URL is replaced with a URL generated from a proxy template.
+    ///
+    /// The `{url}` placeholder in the template is replaced with the percent-encoded URL.
+    Proxy(String),
+}
+
+impl Default for ViolationAction {
+    fn default() -> Self {
+        ViolationAction::Remove
+    }
+}
+
+/// The schemes and hosts which are permitted for a kind of URL.
+///
+/// Host names match the host and its subdomains case-insensitively, so `example.org` matches
+/// `cdn.example.org`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct UrlRule {
+    /// The permitted schemes. If empty, any scheme is permitted.
+    ///
+    /// If there are permitted schemes, relative URLs are not permitted.
+    pub allowed_schemes: Vec<String>,
+    /// The permitted hosts. If empty, any host is permitted.
+    ///
+    /// If there are permitted hosts, URLs without a host are not permitted.
+    pub allowed_hosts: Vec<String>,
+    /// The hosts which are not permitted even if they are in the permitted hosts.
+    pub denied_hosts: Vec<String>,
+    /// What is done with a URL which is not permitted.
+    pub action: ViolationAction,
+}
+
+fn is_host_match(host: &str, pattern: &str) -> bool {
+    let pattern = pattern.trim_end_matches('.');
+    host.len() >= pattern.len()
+        && host
+            .get(host.len() - pattern.len()..)
+            .map_or(false, |suffix| suffix.eq_ignore_ascii_case(pattern))
+        && (host.len() == pattern.len() || host[..host.len() - pattern.len()].ends_with('.'))
+}
+
+impl UrlRule {
+    /// Instantiates a rule which permits every URL.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns true if the rule permits the URL.
+    #[must_use]
+    pub fn permits(&self, url: &str) -> bool {
+        let url = url.trim();
+        if !self.allowed_schemes.is_empty() {
+            let scheme = crate::url::scheme(url).unwrap_or_default();
+            if !self
+                .allowed_schemes
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(scheme))
+            {
+                return false;
+            }
+        }
+        match crate::url::host(url) {
+            Some(host) => {
+                (self.allowed_hosts.is_empty()
+                    || self
+                        .allowed_hosts
+                        .iter()
+                        .any(|allowed| is_host_match(host, allowed)))
+                    && !self
+                        .denied_hosts
+                        .iter()
+                        .any(|denied| is_host_match(host, denied))
+            }
+            None => self.allowed_hosts.is_empty(),
+        }
+    }
+}
+
+/// The rules for the URLs in a feed.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct UrlPolicy {
+    /// The rule for the kinds of URLs which do not have their own rule.
+    pub default_rule: UrlRule,
+    /// The rules for specific kinds of URLs.
+    pub rules: BTreeMap<UrlKind, UrlRule>,
+}
+
+impl UrlPolicy {
+    /// Instantiates a policy which permits every URL.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the rule for a kind of URL.
+    #[must_use]
+    pub fn rule_for(&self, kind: UrlKind) -> &UrlRule {
+        self.rules.get(&kind).unwrap_or(&self.default_rule)
+    }
+}
+
+/// What was done with a URL which was not permitted.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Resolution {
+    /// The URL was removed.
+    Removed,
+    /// The URL was replaced with the proxy URL.
+    Proxied(String),
+}
+
+/// A URL which was not permitted by a policy.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct PolicyViolation {
+    /// The [JSON Pointer][json_pointer] to the URL in the original feed.
+    ///
+    /// [json_pointer]: https://tools.ietf.org/html/rfc6901
+    pub path: String,
+    /// The URL.
+    pub url: String,
+    /// The property which contained the URL.
+    pub kind: UrlKind,
+    /// What was done with the URL.
+    pub resolution: Resolution,
+}
+
+fn proxy_url(template: &str, url: &str) -> Result<String, Error> {
+    let placeholder = template.find("{url}").ok_or(Error::InvalidTemplate)?;
+    let mut proxied = String::with_capacity(template.len() + url.len() * 3);
+    proxied.push_str(&template[..placeholder]);
+    crate::url::encode_path_segment_into(url, &mut proxied);
+    proxied.push_str(&template[placeholder + 5..]);
+    Ok(proxied)
+}
+
+fn enforce_url_policy(
+    map: &mut Map<String, Value>,
+    policy: &UrlPolicy,
+) -> Result<Vec<PolicyViolation>, Error> {
+    let mut violations = Vec::new();
+    for found in crate::FeedRef::from(&*map).urls() {
+        let rule = policy.rule_for(found.kind);
+        if rule.permits(found.url) {
+            continue;
+        }
+        let resolution = match &rule.action {
+            ViolationAction::Remove => Resolution::Removed,
+            ViolationAction::Proxy(template) => {
+                Resolution::Proxied(proxy_url(template, found.url)?)
+            }
+        };
+        violations.push(PolicyViolation {
+            path: found.path,
+            url: String::from(found.url),
+            kind: found.kind,
+            resolution,
+        });
+    }
+
+    // Later array elements are removed first so the earlier paths stay valid.
+    for violation in violations.iter().rev() {
+        match &violation.resolution {
+            Resolution::Removed => {
+                let path = match violation.kind {
+                    UrlKind::Hub | UrlKind::Attachment => violation
+                        .path
+                        .strip_suffix("/url")
+                        .unwrap_or(&violation.path),
+                    _ => &violation.path,
+                };
+                crate::pointer::remove_pointer(map, path);
+            }
+            Resolution::Proxied(url) => {
+                if let Some(value) = crate::pointer::get_pointer_mut(map, &violation.path) {
+                    *value = Value::String(url.clone());
+                }
+            }
+        }
+    }
+    Ok(violations)
+}
+
+macro_rules! url_policy_methods {
+    () => {
+        /// Removes or proxies the URLs listed by `urls()` which the policy does not permit and
+        /// returns the changes in the order of `urls()`.
+        ///
+        /// # Errors
+        ///
+        /// If a proxy template does not contain `{url}`, then `Error::InvalidTemplate` is
+        /// returned. The feed is not changed if an error is returned.
+        pub fn enforce_url_policy(
+            &mut self,
+            policy: &UrlPolicy,
+        ) -> Result<Vec<PolicyViolation>, Error> {
+            enforce_url_policy(self.as_map_mut(), policy)
+        }
+    };
+}
+
+impl Feed {
+    url_policy_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    url_policy_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::{string::String, vec, vec::Vec};
+    use serde_json::json;
+
+    #[test]
+    fn rules() {
+        let mut rule = UrlRule::new();
+        assert!(rule.permits("/relative"));
+        rule.allowed_hosts = vec![String::from("example.org")];
+        rule.denied_hosts = vec![String::from("ads.example.org")];
+        assert!(rule.permits("https://EXAMPLE.org/"));
+        assert!(rule.permits("https://user@cdn.example.org:8080/a"));
+        assert!(!rule.permits("https://notexample.org/"));
+        assert!(!rule.permits("https://x.ads.example.org/"));
+        assert!(!rule.permits("mailto:a@example.org"));
+        rule.allowed_schemes = vec![String::from("https")];
+        assert!(!rule.permits("http://example.org/"));
+        assert!(!rule.permits("//example.org/"));
+    }
+
+    #[test]
+    fn enforcement() -> Result<(), Error> {
+        let mut feed = crate::from_value(json!({
+            "hubs": [
+                { "type": "WebSub", "url": "https://hub.example/" },
+                { "type": "WebSub", "url": "https://example.org/hub" }
+            ],
+            "items": [{
+                "id": "1",
+                "authors": [{ "name": "A", "avatar": "https://gravatar.example/a" }],
+                "_links": ["https://x.example/", "https://example.org/", "https://y.example/"]
+            }]
+        }))?;
+
+        let mut policy = UrlPolicy::new();
+        policy.default_rule.allowed_hosts = vec![String::from("example.org")];
+        let mut avatars = policy.default_rule.clone();
+        avatars.action = ViolationAction::Proxy(String::from("https://example.org/avatar"));
+        policy.rules.insert(UrlKind::Avatar, avatars);
+        assert!(matches!(
+            feed.clone().enforce_url_policy(&policy),
+            Err(Error::InvalidTemplate)
+        ));
+
+        if let Some(rule) = policy.rules.get_mut(&UrlKind::Avatar) {
+            rule.action = ViolationAction::Proxy(String::from("https://example.org/avatar/{url}"));
+        }
+        let violations = feed.enforce_url_policy(&policy)?;
+        assert_eq!(
+            violations
+                .iter()
+                .map(|v| v.path.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "/hubs/0/url",
+                "/items/0/_links/0",
+                "/items/0/_links/2",
+                "/items/0/authors/0/avatar"
+            ]
+        );
+        assert_eq!(
+            feed,
+            crate::from_value(json!({
+                "hubs": [{ "type": "WebSub", "url": "https://example.org/hub" }],
+                "items": [{
+                    "id": "1",
+                    "authors": [{
+                        "name": "A",
+                        "avatar": "https://example.org/avatar/https%3A%2F%2Fgravatar.example%2Fa"
+                    }],
+                    "_links": ["https://example.org/"]
+                }]
+            }))?
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,333 @@
+//! Publication statistics and polling interval suggestions.
+//!
+//! Aggregators which poll feeds can use the publication history of a feed to decide how often
+//! to fetch it.
+//!
+//! ```
+//! use core::time::Duration;
+//! use json_feed_model::poll::PollBounds;
+//!
+//! let feed = json_feed_model::from_str(r#"{
+//!     "items": [
+//!         { "id": "3", "date_published": "2021-01-03T00:00:00Z" },
+//!         { "id": "2", "date_published": "2021-01-02T00:00:00Z" },
+//!         { "id": "1", "date_published": "2021-01-01T00:00:00Z" }
+//!     ]
+//! }"#)?;
+//!
+//! let interval = feed.suggested_poll_interval(&PollBounds::default())?;
+//! assert_eq!(interval, Duration::from_secs(12 * 60 * 60));
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+//!
+//! Publishers can use the same history to decide how long responses may be cached:
+//!
+//! ```
+//! # let feed = json_feed_model::from_str(r#"{
+//! #     "items": [
+//! #         { "id": "3", "date_published": "2021-01-03T00:00:00Z" },
+//! #         { "id": "2", "date_published": "2021-01-02T00:00:00Z" },
+//! #         { "id": "1", "date_published": "2021-01-01T00:00:00Z" }
+//! #     ]
+//! # }"#)?;
+//! let headers = feed.suggested_cache_headers("2021-01-03T06:00:00Z")?;
+//! assert_eq!(headers.cache_control, "public, max-age=43200");
+//! assert_eq!(headers.expires, "Sun, 03 Jan 2021 18:00:00 GMT");
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{format, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{format, string::String, vec::Vec};
+
+use core::time::Duration;
+
+use serde_json::{Map, Value};
+
+use crate::{
+    date::{Timestamp, ToTimestamp},
+    Error, Feed, FeedMut, FeedRef,
+};
+
+/// How much longer the polling interval is when a feed has hubs.
+///
+/// Subscribers are notified of updates through the hub, so polling is only a fallback.
+const HUB_BACKOFF_FACTOR: u32 = 4;
+
+/// The shortest `max-age` suggested for a feed which is still updated.
+const MIN_MAX_AGE: Duration = Duration::from_secs(5 * 60);
+
+/// The longest `max-age` suggested for a feed which is still updated.
+const MAX_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The `max-age` suggested for an expired feed.
+const EXPIRED_MAX_AGE: Duration = Duration::from_secs(365 * 24 * 60 * 60);
+
+/// Statistics about when a feed's items were published.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct PublicationStats {
+    /// The number of items with a valid `date_published`.
+    pub count: usize,
+    /// The earliest `date_published`.
+    pub first: Option<Timestamp>,
+    /// The latest `date_published`.
+    pub last: Option<Timestamp>,
+    /// The median duration between consecutive publications.
+    pub median_gap: Option<Duration>,
+    /// The mean duration between consecutive publications.
+    pub mean_gap: Option<Duration>,
+}
+
+/// Suggested values for the caching headers of a feed response.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct CacheHeaders {
+    /// How long the response may be cached.
+    pub max_age: Duration,
+    /// The value of the `Cache-Control` header (e.g. `public, max-age=3600`).
+    pub cache_control: String,
+    /// The value of the `Expires` header, which is `max_age` after the current time.
+    pub expires: String,
+}
+
+/// The shortest and longest polling intervals to suggest.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct PollBounds {
+    /// The shortest interval.
+    pub min: Duration,
+    /// The longest interval.
+    pub max: Duration,
+}
+
+impl PollBounds {
+    /// Instantiates bounds with the shortest and longest intervals.
+    ///
+    /// If `max` is less than `min`, then `min` is used for both.
+    #[must_use]
+    pub fn new(min: Duration, max: Duration) -> Self {
+        Self {
+            min,
+            max: max.max(min),
+        }
+    }
+
+    fn clamp(&self, interval: Duration) -> Duration {
+        interval.max(self.min).min(self.max.max(self.min))
+    }
+}
+
+impl Default for PollBounds {
+    /// Returns bounds from 15 minutes to 1 day.
+    fn default() -> Self {
+        Self::new(
+            Duration::from_secs(15 * 60),
+            Duration::from_secs(24 * 60 * 60),
+        )
+    }
+}
+
+fn publication_stats(map: &Map<String, Value>) -> Result<PublicationStats, Error> {
+    // Items with invalid dates are ignored so one bad item does not prevent polling.
+    let mut dates = Vec::new();
+    for item in crate::item_refs(map)? {
+        if let Some(date) = item.date_published()? {
+            if let Ok(date) = Timestamp::parse(date) {
+                dates.push(date);
+            }
+        }
+    }
+    dates.sort();
+
+    let mut gaps = dates
+        .windows(2)
+        .map(|pair| pair[1].saturating_duration_since(&pair[0]))
+        .collect::<Vec<_>>();
+    gaps.sort();
+
+    let median_gap = match gaps.len() {
+        0 => None,
+        len if len % 2 == 1 => Some(gaps[len / 2]),
+        len => Some((gaps[len / 2 - 1] + gaps[len / 2]) / 2),
+    };
+    let gap_count = u32::try_from(gaps.len()).unwrap_or(u32::MAX);
+    let mean_gap = match (dates.first(), dates.last()) {
+        (Some(first), Some(last)) if gap_count > 0 => {
+            Some(last.saturating_duration_since(first) / gap_count)
+        }
+        _ => None,
+    };
+
+    Ok(PublicationStats {
+        count: dates.len(),
+        first: dates.first().copied(),
+        last: dates.last().copied(),
+        median_gap,
+        mean_gap,
+    })
+}
+
+fn suggested_poll_interval(
+    map: &Map<String, Value>,
+    bounds: &PollBounds,
+) -> Result<Duration, Error> {
+    let feed = FeedRef::from(map);
+    if feed.is_expired()? {
+        return Ok(bounds.max);
+    }
+
+    // Polling twice per typical gap catches most items within half a gap of publication.
+    let interval = match publication_stats(map)?.median_gap {
+        Some(gap) => gap / 2,
+        None => bounds.max,
+    };
+    let has_hubs = feed.hubs()?.map_or(false, |hubs| !hubs.is_empty());
+    let interval = if has_hubs {
+        interval
+            .checked_mul(HUB_BACKOFF_FACTOR)
+            .unwrap_or(bounds.max)
+    } else {
+        interval
+    };
+    Ok(bounds.clamp(interval))
+}
+
+fn suggested_cache_headers<T: ToTimestamp>(
+    map: &Map<String, Value>,
+    now: &T,
+) -> Result<CacheHeaders, Error> {
+    let now = now.to_timestamp()?;
+    let max_age = if FeedRef::from(map).is_expired()? {
+        EXPIRED_MAX_AGE
+    } else {
+        // Caching for half the typical gap keeps most new items from being hidden for long.
+        publication_stats(map)?
+            .median_gap
+            .map_or(MAX_MAX_AGE, |gap| gap / 2)
+            .max(MIN_MAX_AGE)
+            .min(MAX_MAX_AGE)
+    };
+    let seconds = max_age.as_secs();
+    let expires = Timestamp::from_unix(
+        now.unix_seconds()
+            .saturating_add(i64::try_from(seconds).unwrap_or(i64::MAX)),
+        0,
+    );
+    Ok(CacheHeaders {
+        max_age,
+        cache_control: format!("public, max-age={}", seconds),
+        expires: expires.to_http_date(),
+    })
+}
+
+macro_rules! poll_methods {
+    () => {
+        /// Returns statistics about when the items were published.
+        ///
+        /// Items without a `date_published` or with an invalid date are not counted.
+        ///
+        /// # Errors
+        ///
+        /// If the items or their dates are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned.
+        pub fn publication_stats(&self) -> Result<PublicationStats, Error> {
+            publication_stats(self.as_map())
+        }
+
+        /// Returns a suggested interval between fetches of the feed.
+        ///
+        /// The interval is half the median gap between publications. Feeds with hubs are
+        /// polled less often because updates are pushed to subscribers. Expired feeds and feeds
+        /// with fewer than two dated items are polled at the longest interval.
+        ///
+        /// # Errors
+        ///
+        /// If the items, their dates, the hubs, or the expired flag are not the expected JSON
+        /// type, then `Error::UnexpectedType` is returned.
+        pub fn suggested_poll_interval(&self, bounds: &PollBounds) -> Result<Duration, Error> {
+            suggested_poll_interval(self.as_map(), bounds)
+        }
+
+        /// Returns suggested `Cache-Control` and `Expires` header values for serving the feed.
+        ///
+        /// Like `suggested_poll_interval()`, the `max-age` is half the median gap between
+        /// publications, from 5 minutes to 1 day. Feeds with fewer than two dated items are
+        /// cached for 1 day, and expired feeds are cached for 1 year because they will not
+        /// change. Use the headers with `etag()` so caches can revalidate cheaply.
+        ///
+        /// # Errors
+        ///
+        /// If the items, their dates, or the expired flag are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned. If `now` is not a valid date, then
+        /// `Error::InvalidDate` is returned.
+        pub fn suggested_cache_headers<T: ToTimestamp>(
+            &self,
+            now: T,
+        ) -> Result<CacheHeaders, Error> {
+            suggested_cache_headers(self.as_map(), &now)
+        }
+    };
+}
+
+impl Feed {
+    poll_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    poll_methods!();
+}
+
+impl<'a> FeedRef<'a> {
+    poll_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::vec;
+
+    const HOUR: Duration = Duration::from_secs(60 * 60);
+
+    #[test]
+    fn stats_and_interval() -> Result<(), Error> {
+        let mut feed = crate::from_value(serde_json::json!({
+            "items": [
+                { "id": "1", "date_published": "2021-01-01T00:00:00Z" },
+                { "id": "2", "date_published": "2021-01-01T02:00:00Z" },
+                { "id": "3", "date_published": "not a date" },
+                { "id": "4", "date_published": "2021-01-01T04:00:00Z" },
+                { "id": "5", "date_published": "2021-01-01T12:00:00Z" }
+            ]
+        }))?;
+
+        let stats = feed.publication_stats()?;
+        assert_eq!(stats.count, 4);
+        assert_eq!(stats.median_gap, Some(HOUR * 2));
+        assert_eq!(stats.mean_gap, Some(HOUR * 4));
+
+        let bounds = PollBounds::new(HOUR / 2, HOUR * 24);
+        assert_eq!(feed.suggested_poll_interval(&bounds)?, HOUR);
+        assert_eq!(
+            feed.suggested_poll_interval(&PollBounds::new(HOUR * 2, HOUR * 24))?,
+            HOUR * 2
+        );
+
+        feed.set_hubs(vec![crate::Hub::websub("https://example.org/hub")]);
+        assert_eq!(feed.suggested_poll_interval(&bounds)?, HOUR * 4);
+
+        let headers = feed.suggested_cache_headers("2021-01-01T12:00:00Z")?;
+        assert_eq!(headers.max_age, HOUR);
+        assert_eq!(headers.expires, "Fri, 01 Jan 2021 13:00:00 GMT");
+
+        feed.expire();
+        assert_eq!(feed.suggested_poll_interval(&bounds)?, HOUR * 24);
+        let headers = feed.suggested_cache_headers("2021-01-01T12:00:00Z")?;
+        assert_eq!(headers.cache_control, "public, max-age=31536000");
+        assert_eq!(headers.expires, "Sat, 01 Jan 2022 12:00:00 GMT");
+
+        Ok(())
+    }
+}
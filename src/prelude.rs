@@ -0,0 +1,29 @@
+//! The commonly used types, traits, and functions.
+//!
+//! ```
+//! use json_feed_model::prelude::*;
+//!
+//! fn first_title<T: FeedRead>(feed: &T) -> Result<Option<String>, Error> {
+//!     Ok(feed
+//!         .items()?
+//!         .and_then(|items| items.first().and_then(|item| item.title().ok().flatten()).map(String::from)))
+//! }
+//!
+//! let feed = from_str(r#"{ "items": [{ "id": "1", "title": "Hello" }] }"#)?;
+//! assert_eq!(first_title(&feed)?.as_deref(), Some("Hello"));
+//! assert!(!feed.is_valid(&Version::Version1_1));
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(feature = "std")]
+pub use crate::from_reader;
+pub use crate::{
+    attachment_from_slice, attachment_from_str, attachment_from_value, author_from_slice,
+    author_from_str, author_from_value, from_slice, from_str, from_value, hub_from_slice,
+    hub_from_str, hub_from_value, item_from_slice, item_from_str, item_from_value,
+    validate_attachment_map, validate_author_map, validate_feed_map, validate_hub_map,
+    validate_item_map, Attachment, AttachmentMut, AttachmentRead, AttachmentRef, AttachmentWrite,
+    Author, AuthorMut, AuthorRead, AuthorRef, AuthorWrite, CoercionOptions, CrossFieldRules, Error,
+    Feed, FeedMut, FeedRead, FeedRef, FeedWrite, Hub, HubMut, HubRead, HubRef, HubType, HubWrite,
+    Item, ItemMut, ItemRead, ItemRef, ItemWrite, SerializeOptions, ValidationOptions, Version,
+};
@@ -0,0 +1,314 @@
+//! Projections which keep only selected members of a feed.
+//!
+//! Platforms which republish third-party feeds can use a projection to drop members which
+//! should not be republished, such as `user_comment` or extensions with private data.
+//!
+//! ```
+//! use json_feed_model::projection::{FieldFilter, FieldSet};
+//!
+//! let feed = json_feed_model::from_value(serde_json::json!({
+//!     "version": "https://jsonfeed.org/version/1.1",
+//!     "title": "Example",
+//!     "user_comment": "Internal notes",
+//!     "authors": [{ "name": "Jane", "_email": "jane@example.org" }],
+//!     "items": [{ "id": "1", "content_text": "Hello", "_tracking": { "campaign": "x" } }]
+//! }))?;
+//!
+//! let mut fields = FieldSet::spec();
+//! fields.feed = FieldFilter::except(["user_comment"]);
+//! let projected = feed.project(&fields)?;
+//!
+//! assert_eq!(
+//!     projected,
+//!     json_feed_model::from_value(serde_json::json!({
+//!         "version": "https://jsonfeed.org/version/1.1",
+//!         "title": "Example",
+//!         "authors": [{ "name": "Jane" }],
+//!         "items": [{ "id": "1", "content_text": "Hello" }]
+//!     }))?
+//! );
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{collections::BTreeSet, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{collections::BTreeSet, string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{Error, Feed, FeedMut, FeedRef};
+
+const FEED_KEYS: &[&str] = &[
+    "version",
+    "title",
+    "home_page_url",
+    "feed_url",
+    "description",
+    "user_comment",
+    "next_url",
+    "icon",
+    "favicon",
+    "author",
+    "authors",
+    "language",
+    "expired",
+    "hubs",
+    "items",
+];
+
+const ITEM_KEYS: &[&str] = &[
+    "id",
+    "url",
+    "external_url",
+    "title",
+    "content_html",
+    "content_text",
+    "summary",
+    "image",
+    "banner_image",
+    "date_published",
+    "date_modified",
+    "author",
+    "authors",
+    "language",
+    "tags",
+    "attachments",
+];
+
+const AUTHOR_KEYS: &[&str] = &["name", "url", "avatar"];
+
+const ATTACHMENT_KEYS: &[&str] = &[
+    "url",
+    "mime_type",
+    "title",
+    "size_in_bytes",
+    "duration_in_seconds",
+];
+
+const HUB_KEYS: &[&str] = &["type", "url"];
+
+/// Which members of an object are kept.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum FieldFilter {
+    /// Every member is kept.
+    All,
+    /// Only the members defined by version 1.1 of the spec are kept.
+    ///
+    /// Extensions and unknown members are removed.
+    Spec,
+    /// Only the named members are kept.
+    Only(BTreeSet<String>),
+    /// Every member except the named members is kept.
+    Except(BTreeSet<String>),
+}
+
+impl FieldFilter {
+    /// Instantiates a filter which keeps only the named members.
+    pub fn only<I, S>(keys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        FieldFilter::Only(keys.into_iter().map(Into::into).collect())
+    }
+
+    /// Instantiates a filter which keeps every member except the named members.
+    pub fn except<I, S>(keys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        FieldFilter::Except(keys.into_iter().map(Into::into).collect())
+    }
+
+    fn keeps(&self, key: &str, spec_keys: &[&str]) -> bool {
+        match self {
+            FieldFilter::All => true,
+            FieldFilter::Spec => spec_keys.contains(&key),
+            FieldFilter::Only(keys) => keys.contains(key),
+            FieldFilter::Except(keys) => !keys.contains(key),
+        }
+    }
+}
+
+impl Default for FieldFilter {
+    fn default() -> Self {
+        FieldFilter::All
+    }
+}
+
+/// The members which are kept for each kind of object in a feed.
+///
+/// The filters apply to the members of the feed object, item objects, author objects (in both
+/// `author` and `authors`), attachment objects, and hub objects.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct FieldSet {
+    /// The filter for the feed's members.
+    pub feed: FieldFilter,
+    /// The filter for the items' members.
+    pub item: FieldFilter,
+    /// The filter for the authors' members.
+    pub author: FieldFilter,
+    /// The filter for the attachments' members.
+    pub attachment: FieldFilter,
+    /// The filter for the hubs' members.
+    pub hub: FieldFilter,
+}
+
+impl FieldSet {
+    /// Instantiates a set which keeps every member.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Instantiates a set which keeps only the members defined by the spec.
+    #[must_use]
+    pub fn spec() -> Self {
+        Self {
+            feed: FieldFilter::Spec,
+            item: FieldFilter::Spec,
+            author: FieldFilter::Spec,
+            attachment: FieldFilter::Spec,
+            hub: FieldFilter::Spec,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Feed,
+    Item,
+    Author,
+    Attachment,
+    Hub,
+}
+
+impl Kind {
+    fn filter(self, fields: &FieldSet) -> (&FieldFilter, &'static [&'static str]) {
+        match self {
+            Kind::Feed => (&fields.feed, FEED_KEYS),
+            Kind::Item => (&fields.item, ITEM_KEYS),
+            Kind::Author => (&fields.author, AUTHOR_KEYS),
+            Kind::Attachment => (&fields.attachment, ATTACHMENT_KEYS),
+            Kind::Hub => (&fields.hub, HUB_KEYS),
+        }
+    }
+
+    /// Returns the kind of the objects in a member.
+    fn child(self, key: &str) -> Option<Kind> {
+        match (self, key) {
+            (Kind::Feed | Kind::Item, "author" | "authors") => Some(Kind::Author),
+            (Kind::Feed, "items") => Some(Kind::Item),
+            (Kind::Feed, "hubs") => Some(Kind::Hub),
+            (Kind::Item, "attachments") => Some(Kind::Attachment),
+            _ => None,
+        }
+    }
+}
+
+fn project_value(value: &Value, kind: Kind, fields: &FieldSet) -> Result<Value, Error> {
+    match value {
+        Value::Object(map) => Ok(Value::Object(project_map(map, kind, fields)?)),
+        Value::Array(values) => values
+            .iter()
+            .map(|value| match value {
+                Value::Object(map) => Ok(Value::Object(project_map(map, kind, fields)?)),
+                _ => Err(Error::UnexpectedType),
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array),
+        _ => Err(Error::UnexpectedType),
+    }
+}
+
+fn project_map(
+    map: &Map<String, Value>,
+    kind: Kind,
+    fields: &FieldSet,
+) -> Result<Map<String, Value>, Error> {
+    let (filter, spec_keys) = kind.filter(fields);
+    let mut projected = Map::new();
+    for (key, value) in map {
+        if !filter.keeps(key, spec_keys) {
+            continue;
+        }
+        let value = match kind.child(key) {
+            Some(child) => project_value(value, child, fields)?,
+            None => value.clone(),
+        };
+        projected.insert(key.clone(), value);
+    }
+    Ok(projected)
+}
+
+macro_rules! projection_methods {
+    () => {
+        /// Returns a copy of the feed with only the members kept by the `fields`.
+        ///
+        /// # Errors
+        ///
+        /// If the items, authors, attachments, or hubs are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned.
+        pub fn project(&self, fields: &FieldSet) -> Result<Feed, Error> {
+            project_map(self.as_map(), Kind::Feed, fields).map(Feed::from)
+        }
+    };
+}
+
+impl Feed {
+    projection_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    projection_methods!();
+}
+
+impl<'a> FeedRef<'a> {
+    projection_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allow_list() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "title": "Example",
+            "icon": "https://example.org/icon.png",
+            "hubs": [{ "type": "WebSub", "url": "https://hub.example.org/", "_secret": "x" }],
+            "items": [{
+                "id": "1",
+                "title": "Hello",
+                "content_html": "<p>Hi</p>",
+                "author": { "name": "Jane", "avatar": "https://example.org/jane.png" },
+                "attachments": [{ "url": "https://example.org/a.mp3", "mime_type": "audio/mpeg", "_private": true }]
+            }]
+        }))?;
+
+        let mut fields = FieldSet::new();
+        fields.feed = FieldFilter::only(["title", "items"]);
+        fields.item = FieldFilter::only(["id", "title", "author", "attachments"]);
+        fields.author = FieldFilter::except(["avatar"]);
+        fields.attachment = FieldFilter::Spec;
+
+        assert_eq!(
+            feed.project(&fields)?,
+            crate::from_value(serde_json::json!({
+                "title": "Example",
+                "items": [{
+                    "id": "1",
+                    "title": "Hello",
+                    "author": { "name": "Jane" },
+                    "attachments": [{ "url": "https://example.org/a.mp3", "mime_type": "audio/mpeg" }]
+                }]
+            }))?
+        );
+        assert_eq!(feed.project(&FieldSet::new())?, feed);
+        Ok(())
+    }
+}
@@ -0,0 +1,396 @@
+//! Lenient decoding which salvages as much data as possible.
+//!
+//! Aggregators often prefer partial data over failing on a single malformed value.
+//! [`from_value_lossy`] always returns a `Feed`. Values which have the wrong JSON type are
+//! converted when the intent is obvious, such as a numeric item `id`, and removed otherwise.
+//! Every change is reported as an [`Issue`].
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use std::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use serde_json::{Map, Value};
+
+use crate::Feed;
+
+/// What was done with a value which did not have the expected JSON type.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum IssueKind {
+    /// The value was removed.
+    Discarded,
+    /// The value was converted to the expected JSON type.
+    Coerced,
+}
+
+/// A value which was changed while salvaging a feed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Issue {
+    /// What was done with the value.
+    pub kind: IssueKind,
+    /// A [JSON Pointer][json_pointer] to the value in the original JSON data.
+    ///
+    /// [json_pointer]: https://tools.ietf.org/html/rfc6901
+    pub path: String,
+    /// A human readable description of the problem.
+    pub message: String,
+}
+
+const FEED_STRING_KEYS: &[&str] = &[
+    "version",
+    "title",
+    "home_page_url",
+    "feed_url",
+    "description",
+    "user_comment",
+    "next_url",
+    "icon",
+    "favicon",
+    "language",
+];
+
+const ITEM_STRING_KEYS: &[&str] = &[
+    "id",
+    "url",
+    "external_url",
+    "title",
+    "content_html",
+    "content_text",
+    "summary",
+    "image",
+    "banner_image",
+    "date_published",
+    "date_modified",
+    "language",
+];
+
+const AUTHOR_STRING_KEYS: &[&str] = &["name", "url", "avatar"];
+
+const ATTACHMENT_STRING_KEYS: &[&str] = &["url", "mime_type", "title"];
+
+const ATTACHMENT_NUMBER_KEYS: &[&str] = &["size_in_bytes", "duration_in_seconds"];
+
+const HUB_STRING_KEYS: &[&str] = &["type", "url"];
+
+struct Salvager {
+    issues: Vec<Issue>,
+}
+
+impl Salvager {
+    fn discarded(&mut self, path: String, message: &str) {
+        self.issues.push(Issue {
+            kind: IssueKind::Discarded,
+            path,
+            message: String::from(message),
+        });
+    }
+
+    fn coerced(&mut self, path: String, message: &str) {
+        self.issues.push(Issue {
+            kind: IssueKind::Coerced,
+            path,
+            message: String::from(message),
+        });
+    }
+
+    fn strings(&mut self, map: &mut Map<String, Value>, path: &str, keys: &[&str]) {
+        for key in keys {
+            let coerced = match map.get(*key) {
+                None | Some(Value::String(_)) => continue,
+                Some(Value::Number(n)) => Value::String(n.to_string()),
+                Some(Value::Bool(b)) => Value::String(b.to_string()),
+                Some(_) => {
+                    map.remove(*key);
+                    self.discarded(format!("{}/{}", path, key), "expected a string");
+                    continue;
+                }
+            };
+            map.insert(String::from(*key), coerced);
+            self.coerced(format!("{}/{}", path, key), "converted to a string");
+        }
+    }
+
+    fn numbers(&mut self, map: &mut Map<String, Value>, path: &str, keys: &[&str]) {
+        for key in keys {
+            let coerced = match map.get(*key) {
+                None => continue,
+                Some(Value::Number(n)) if n.is_u64() => continue,
+                Some(Value::String(s)) => s.trim().parse::<u64>().ok(),
+                Some(Value::Number(n)) => n
+                    .as_f64()
+                    .filter(|n| n.is_finite() && *n >= 0.0)
+                    .map(|n| (n + 0.5) as u64),
+                Some(_) => None,
+            };
+            if let Some(n) = coerced {
+                map.insert(String::from(*key), Value::from(n));
+                self.coerced(format!("{}/{}", path, key), "converted to an integer");
+            } else {
+                map.remove(*key);
+                self.discarded(format!("{}/{}", path, key), "expected an integer");
+            }
+        }
+    }
+
+    fn bool(&mut self, map: &mut Map<String, Value>, path: &str, key: &str) {
+        let coerced = match map.get(key) {
+            None | Some(Value::Bool(_)) => return,
+            Some(Value::String(s)) if s == "true" => Some(true),
+            Some(Value::String(s)) if s == "false" => Some(false),
+            Some(_) => None,
+        };
+        if let Some(b) = coerced {
+            map.insert(String::from(key), Value::Bool(b));
+            self.coerced(format!("{}/{}", path, key), "converted to a boolean");
+        } else {
+            map.remove(key);
+            self.discarded(format!("{}/{}", path, key), "expected a boolean");
+        }
+    }
+
+    fn object(
+        &mut self,
+        map: &mut Map<String, Value>,
+        path: &str,
+        key: &str,
+        mut f: impl FnMut(&mut Self, &mut Map<String, Value>, &str),
+    ) {
+        match map.get_mut(key) {
+            None => {}
+            Some(Value::Object(obj)) => f(self, obj, &format!("{}/{}", path, key)),
+            Some(_) => {
+                map.remove(key);
+                self.discarded(format!("{}/{}", path, key), "expected an object");
+            }
+        }
+    }
+
+    fn objects(
+        &mut self,
+        map: &mut Map<String, Value>,
+        path: &str,
+        key: &str,
+        mut f: impl FnMut(&mut Self, &mut Map<String, Value>, &str),
+    ) {
+        let values = match map.get_mut(key) {
+            None => return,
+            Some(Value::Array(values)) => values,
+            Some(_) => {
+                map.remove(key);
+                self.discarded(format!("{}/{}", path, key), "expected an array");
+                return;
+            }
+        };
+
+        let mut kept = Vec::with_capacity(values.len());
+        for (index, value) in values.drain(..).enumerate() {
+            let value_path = format!("{}/{}/{}", path, key, index);
+            match value {
+                Value::Object(mut obj) => {
+                    f(self, &mut obj, &value_path);
+                    kept.push(Value::Object(obj));
+                }
+                _ => self.discarded(value_path, "expected an object"),
+            }
+        }
+        *values = kept;
+    }
+
+    fn tags(&mut self, map: &mut Map<String, Value>, path: &str) {
+        let tags = match map.get_mut("tags") {
+            None => return,
+            Some(Value::Array(tags)) => tags,
+            Some(_) => {
+                map.remove("tags");
+                self.discarded(format!("{}/tags", path), "expected an array");
+                return;
+            }
+        };
+
+        let mut kept = Vec::with_capacity(tags.len());
+        for (index, tag) in tags.drain(..).enumerate() {
+            let tag_path = format!("{}/tags/{}", path, index);
+            match tag {
+                Value::String(_) => kept.push(tag),
+                Value::Number(n) => {
+                    kept.push(Value::String(n.to_string()));
+                    self.coerced(tag_path, "converted to a string");
+                }
+                _ => self.discarded(tag_path, "expected a string"),
+            }
+        }
+        *tags = kept;
+    }
+
+    fn author(&mut self, author: &mut Map<String, Value>, path: &str) {
+        self.strings(author, path, AUTHOR_STRING_KEYS);
+    }
+
+    fn authors(&mut self, map: &mut Map<String, Value>, path: &str) {
+        self.object(map, path, "author", Self::author);
+        self.objects(map, path, "authors", Self::author);
+    }
+
+    fn attachment(&mut self, attachment: &mut Map<String, Value>, path: &str) {
+        self.strings(attachment, path, ATTACHMENT_STRING_KEYS);
+        self.numbers(attachment, path, ATTACHMENT_NUMBER_KEYS);
+    }
+
+    fn hub(&mut self, hub: &mut Map<String, Value>, path: &str) {
+        self.strings(hub, path, HUB_STRING_KEYS);
+    }
+
+    fn item(&mut self, item: &mut Map<String, Value>, path: &str) {
+        self.strings(item, path, ITEM_STRING_KEYS);
+        self.tags(item, path);
+        self.authors(item, path);
+        self.objects(item, path, "attachments", Self::attachment);
+    }
+
+    fn feed(&mut self, feed: &mut Map<String, Value>) {
+        self.strings(feed, "", FEED_STRING_KEYS);
+        self.bool(feed, "", "expired");
+        self.authors(feed, "");
+        self.objects(feed, "", "hubs", Self::hub);
+        self.objects(feed, "", "items", Self::item);
+    }
+}
+
+/// Returns a `Feed` salvaged from a JSON `Value` and the changes which were made.
+///
+/// Unlike `from_value`, this function does not fail. If the value is not an Object, an empty
+/// `Feed` is returned. Known members with the wrong JSON type are converted when possible (for
+/// instance, a number to a string or `"true"` to a boolean) and removed otherwise. Entries in
+/// `items`, `authors`, `attachments`, and `hubs` which are not objects are removed. Unknown
+/// members, including extensions, are kept as is.
+///
+/// The returned `Feed` may still be invalid. For instance, a missing `title` is not added.
+///
+/// # Example
+///
+/// ```
+/// use json_feed_model::recover::{self, IssueKind};
+///
+/// let (feed, issues) = recover::from_value_lossy(serde_json::json!({
+///     "title": "Example",
+///     "items": [{ "id": 1 }, "not an item"]
+/// }));
+///
+/// assert_eq!(feed.items()?.unwrap()[0].id()?, Some("1"));
+/// assert_eq!(feed.items()?.unwrap().len(), 1);
+/// assert_eq!(issues.len(), 2);
+/// assert_eq!(issues[0].kind, IssueKind::Coerced);
+/// assert_eq!(issues[0].path, "/items/0/id");
+/// assert_eq!(issues[1].kind, IssueKind::Discarded);
+/// assert_eq!(issues[1].path, "/items/1");
+/// # Ok::<(), json_feed_model::Error>(())
+/// ```
+#[must_use]
+pub fn from_value_lossy(value: Value) -> (Feed, Vec<Issue>) {
+    let mut salvager = Salvager { issues: Vec::new() };
+    let map = match value {
+        Value::Object(mut map) => {
+            salvager.feed(&mut map);
+            map
+        }
+        _ => {
+            salvager.discarded(String::new(), "expected an object");
+            Map::new()
+        }
+    };
+    trace_event!(issues = salvager.issues.len(), "salvaged feed");
+    (Feed::from(map), salvager.issues)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::{vec, vec::Vec};
+
+    use crate::Version;
+
+    #[test]
+    fn salvaged_feed_is_valid() {
+        let (feed, issues) = from_value_lossy(serde_json::json!({
+            "version": "https://jsonfeed.org/version/1.1",
+            "title": 2021,
+            "expired": "false",
+            "authors": [{ "name": "Jane" }, null],
+            "hubs": { "type": "WebSub" },
+            "items": [
+                {
+                    "id": 1,
+                    "content_text": "Hello",
+                    "tags": ["a", 2, {}],
+                    "attachments": [
+                        { "url": "https://example.org/a.mp3", "mime_type": "audio/mpeg", "size_in_bytes": "42" }
+                    ],
+                    "_ext": { "kept": true }
+                },
+                []
+            ]
+        }));
+
+        assert!(feed.is_valid(&Version::Version1_1));
+        assert_eq!(
+            feed.as_map(),
+            crate::from_value(serde_json::json!({
+                "version": "https://jsonfeed.org/version/1.1",
+                "title": "2021",
+                "expired": false,
+                "authors": [{ "name": "Jane" }],
+                "items": [
+                    {
+                        "id": "1",
+                        "content_text": "Hello",
+                        "tags": ["a", "2"],
+                        "attachments": [
+                            { "url": "https://example.org/a.mp3", "mime_type": "audio/mpeg", "size_in_bytes": 42 }
+                        ],
+                        "_ext": { "kept": true }
+                    }
+                ]
+            }))
+            .unwrap()
+            .as_map()
+        );
+
+        let paths = issues
+            .iter()
+            .map(|issue| (issue.kind, issue.path.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![
+                (IssueKind::Coerced, "/title"),
+                (IssueKind::Coerced, "/expired"),
+                (IssueKind::Discarded, "/authors/1"),
+                (IssueKind::Discarded, "/hubs"),
+                (IssueKind::Coerced, "/items/0/id"),
+                (IssueKind::Coerced, "/items/0/tags/1"),
+                (IssueKind::Discarded, "/items/0/tags/2"),
+                (IssueKind::Coerced, "/items/0/attachments/0/size_in_bytes"),
+                (IssueKind::Discarded, "/items/1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn not_an_object() {
+        let (feed, issues) = from_value_lossy(serde_json::json!([]));
+        assert!(feed.as_map().is_empty());
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path, "");
+    }
+}
@@ -0,0 +1,191 @@
+//! Related item suggestions.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{collections::BTreeSet, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{collections::BTreeSet, string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{date::Timestamp, tags::TagOptions, Error, Feed, FeedMut, FeedRef, ItemRef};
+
+/// The score added for each tag shared with the item.
+const SHARED_TAG_WEIGHT: f64 = 1.0;
+
+/// The score added if the items have an author in common.
+const SAME_AUTHOR_WEIGHT: f64 = 1.0;
+
+/// The maximum score added for being published close to the item.
+///
+/// The score halves for every `DATE_HALF_LIFE_DAYS` between the publication dates.
+const DATE_PROXIMITY_WEIGHT: f64 = 0.5;
+
+const DATE_HALF_LIFE_DAYS: f64 = 30.0;
+
+struct Features {
+    tags: BTreeSet<String>,
+    authors: BTreeSet<String>,
+    published: Option<Timestamp>,
+}
+
+fn features(item: &ItemRef<'_>) -> Result<Features, Error> {
+    let options = TagOptions::normalized();
+    let tags = item
+        .tags()?
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|tag| options.normalize(tag))
+        .collect();
+
+    // Authors are identified by their URL or, if they do not have one, their name.
+    let mut authors = BTreeSet::new();
+    let all_authors = item
+        .authors()?
+        .unwrap_or_default()
+        .into_iter()
+        .chain(item.author()?);
+    for author in all_authors {
+        if let Some(id) = author.url()?.or(author.name()?) {
+            authors.insert(String::from(id));
+        }
+    }
+
+    let published = item
+        .date_published()?
+        .and_then(|date| Timestamp::parse(date).ok());
+
+    Ok(Features {
+        tags,
+        authors,
+        published,
+    })
+}
+
+fn score(target: &Features, other: &Features) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let mut score = target.tags.intersection(&other.tags).count() as f64 * SHARED_TAG_WEIGHT;
+    if target.authors.intersection(&other.authors).next().is_some() {
+        score += SAME_AUTHOR_WEIGHT;
+    }
+    if let (Some(a), Some(b)) = (target.published, other.published) {
+        #[allow(clippy::cast_precision_loss)]
+        let days = (a.seconds_since(&b).unsigned_abs() as f64) / 86_400.0;
+        score += DATE_PROXIMITY_WEIGHT / (1.0 + days / DATE_HALF_LIFE_DAYS);
+    }
+    score
+}
+
+fn related_items<'a>(
+    map: &'a Map<String, Value>,
+    id: &str,
+    limit: usize,
+) -> Result<Vec<ItemRef<'a>>, Error> {
+    let items = crate::item_refs(map)?;
+    let target = match items
+        .iter()
+        .find(|item| matches!(item.id(), Ok(Some(item_id)) if item_id == id))
+    {
+        Some(target) => features(target)?,
+        None => return Ok(Vec::new()),
+    };
+
+    let mut scored = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        if item.id()? == Some(id) {
+            continue;
+        }
+        let score = score(&target, &features(item)?);
+        if score > 0.0 {
+            scored.push((score, index));
+        }
+    }
+    // Ties keep the order of the items in the feed.
+    scored.sort_by(|(a, a_index), (b, b_index)| {
+        b.partial_cmp(a)
+            .unwrap_or(core::cmp::Ordering::Equal)
+            .then(a_index.cmp(b_index))
+    });
+
+    let mut items = items.into_iter().map(Some).collect::<Vec<_>>();
+    Ok(scored
+        .into_iter()
+        .take(limit)
+        .filter_map(|(_, index)| items[index].take())
+        .collect())
+}
+
+macro_rules! related_methods {
+    () => {
+        /// Returns up to `limit` items related to the item with the given `id`, most related
+        /// first.
+        ///
+        /// Items are scored by the number of tags they share with the item (compared after
+        /// case folding and whitespace collapsing), whether they have an author in common, and
+        /// how close their `date_published` is to the item's. Items with no tags or authors in
+        /// common and no comparable dates are not returned.
+        ///
+        /// If there is no item with the `id`, an empty list is returned.
+        ///
+        /// # Errors
+        ///
+        /// If the items or an item's properties are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned.
+        pub fn related_items(&self, id: &str, limit: usize) -> Result<Vec<ItemRef<'_>>, Error> {
+            related_items(self.as_map(), id, limit)
+        }
+    };
+}
+
+impl Feed {
+    related_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    related_methods!();
+}
+
+impl<'a> FeedRef<'a> {
+    related_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::{vec, vec::Vec};
+
+    #[test]
+    fn ranking() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "items": [
+                {
+                    "id": "1",
+                    "tags": ["Rust", "serde"],
+                    "authors": [{ "name": "Alice" }],
+                    "date_published": "2021-01-10T00:00:00Z"
+                },
+                { "id": "2", "tags": ["rust"], "date_published": "2021-01-11T00:00:00Z" },
+                { "id": "3", "tags": ["go"] },
+                {
+                    "id": "4",
+                    "tags": ["rust ", "SERDE"],
+                    "authors": [{ "name": "Alice" }],
+                    "date_published": "2020-01-10T00:00:00Z"
+                },
+                { "id": "5", "date_published": "2021-01-09T00:00:00Z" },
+            ]
+        }))?;
+
+        let related = feed.related_items("1", 10)?;
+        let ids = related
+            .iter()
+            .map(|item| item.id())
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(ids, vec![Some("4"), Some("2"), Some("5")]);
+
+        assert_eq!(feed.related_items("1", 1)?.len(), 1);
+        assert!(feed.related_items("missing", 10)?.is_empty());
+
+        Ok(())
+    }
+}
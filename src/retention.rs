@@ -0,0 +1,214 @@
+//! Retention policies which limit the items kept in a feed.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+use core::{cmp::Reverse, time::Duration};
+
+use serde_json::{Map, Value};
+
+use crate::{
+    date::{Timestamp, ToTimestamp},
+    Error, Feed, FeedMut, Item,
+};
+
+/// Rules which determine which items are kept in a feed.
+///
+/// An item's age is determined from its last update (see `Item::last_updated`). The default
+/// policy keeps every item.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct RetentionPolicy {
+    /// The maximum number of items to keep.
+    ///
+    /// The most recently updated items are kept.
+    pub max_items: Option<usize>,
+    /// The maximum time since an item was last updated.
+    pub max_age: Option<Duration>,
+    /// If true, items without a date are always kept and do not count towards `max_items`.
+    ///
+    /// If false, items without a date are considered older than every dated item.
+    pub keep_undated: bool,
+}
+
+impl RetentionPolicy {
+    /// Instantiates a policy which keeps every item.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Instantiates a policy which keeps the `max_items` most recently updated items.
+    #[must_use]
+    pub fn max_items(max_items: usize) -> Self {
+        Self {
+            max_items: Some(max_items),
+            ..Self::default()
+        }
+    }
+
+    /// Instantiates a policy which keeps items updated within `max_age`.
+    #[must_use]
+    pub fn max_age(max_age: Duration) -> Self {
+        Self {
+            max_age: Some(max_age),
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            max_items: None,
+            max_age: None,
+            keep_undated: true,
+        }
+    }
+}
+
+fn retain_recent<T: ToTimestamp>(
+    map: &mut Map<String, Value>,
+    policy: &RetentionPolicy,
+    now: &T,
+) -> Result<Vec<Item>, Error> {
+    let now = now.to_timestamp()?;
+    let dates = crate::item_refs(map)?
+        .iter()
+        .map(crate::ItemRef::last_updated)
+        .collect::<Result<Vec<Option<Timestamp>>, Error>>()?;
+
+    let mut keep = dates
+        .iter()
+        .map(|date| match (date, policy.max_age) {
+            (Some(date), Some(max_age)) => now.saturating_duration_since(date) <= max_age,
+            (Some(_), None) => true,
+            (None, _) => policy.keep_undated || policy.max_age.is_none(),
+        })
+        .collect::<Vec<_>>();
+
+    if let Some(max_items) = policy.max_items {
+        let mut ranked = (0..dates.len())
+            .filter(|&index| keep[index] && !(policy.keep_undated && dates[index].is_none()))
+            .collect::<Vec<_>>();
+        // Newest first; undated items sort after dated ones and ties keep feed order.
+        ranked.sort_by_key(|&index| Reverse(dates[index]));
+        for &index in ranked.iter().skip(max_items) {
+            keep[index] = false;
+        }
+    }
+
+    if keep.iter().all(|keep| *keep) {
+        return Ok(Vec::new());
+    }
+
+    let mut removed = Vec::new();
+    if let Some(Value::Array(items)) = map.get_mut("items") {
+        let mut keep = keep.into_iter();
+        let mut kept = Vec::with_capacity(items.len());
+        for item in items.drain(..) {
+            if keep.next().unwrap_or(true) {
+                kept.push(item);
+            } else if let Value::Object(item) = item {
+                removed.push(Item::from(item));
+            }
+        }
+        *items = kept;
+    }
+    trace_event!(removed = removed.len(), "applied retention policy");
+    Ok(removed)
+}
+
+macro_rules! retention_methods {
+    () => {
+        /// Removes the items which are not kept by the retention `policy` and returns them.
+        ///
+        /// The kept items stay in their original order. The removed items are returned in
+        /// their original order so they can be archived.
+        ///
+        /// # Errors
+        ///
+        /// If the items or their dates are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned. If a date or `now` is not a valid date, then
+        /// `Error::InvalidDate` is returned. The feed is not changed if an error is returned.
+        pub fn retain_recent<T: ToTimestamp>(
+            &mut self,
+            policy: &RetentionPolicy,
+            now: T,
+        ) -> Result<Vec<Item>, Error> {
+            retain_recent(self.as_map_mut(), policy, &now)
+        }
+    };
+}
+
+impl Feed {
+    retention_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    retention_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::{vec, vec::Vec};
+
+    fn ids(items: &[Item]) -> Vec<&str> {
+        items
+            .iter()
+            .map(|item| item.id().unwrap().unwrap())
+            .collect()
+    }
+
+    fn feed() -> Feed {
+        crate::from_value(serde_json::json!({
+            "items": [
+                { "id": "1", "date_published": "2021-01-01T00:00:00Z" },
+                { "id": "2" },
+                { "id": "3", "date_published": "2021-01-03T00:00:00Z" },
+                { "id": "4", "date_published": "2020-01-01T00:00:00Z", "date_modified": "2021-01-04T00:00:00Z" },
+                { "id": "5", "date_published": "2021-01-02T00:00:00Z" }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn max_items() -> Result<(), Error> {
+        let now = "2021-01-05T00:00:00Z";
+
+        let mut kept = feed();
+        let removed = kept.retain_recent(&RetentionPolicy::max_items(2), now)?;
+        assert_eq!(ids(&removed), vec!["1", "5"]);
+        assert_eq!(kept.items()?.unwrap().len(), 3);
+
+        let mut policy = RetentionPolicy::max_items(2);
+        policy.keep_undated = false;
+        let mut kept = feed();
+        let removed = kept.retain_recent(&policy, now)?;
+        assert_eq!(ids(&removed), vec!["1", "2", "5"]);
+        Ok(())
+    }
+
+    #[test]
+    fn max_age() -> Result<(), Error> {
+        let now = "2021-01-05T00:00:00Z";
+        let mut policy = RetentionPolicy::max_age(Duration::from_secs(3 * 86_400));
+
+        let mut kept = feed();
+        let removed = kept.retain_recent(&policy, now)?;
+        assert_eq!(ids(&removed), vec!["1"]);
+
+        policy.keep_undated = false;
+        policy.max_items = Some(1);
+        let mut kept = feed();
+        let removed = kept.retain_recent(&policy, now)?;
+        assert_eq!(ids(&removed), vec!["1", "2", "3", "5"]);
+        assert_eq!(kept.items()?.unwrap()[0].id()?, Some("4"));
+        Ok(())
+    }
+}
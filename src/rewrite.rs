@@ -0,0 +1,402 @@
+//! Rewriters for `content_html`.
+//!
+//! The functions can be used with `Feed::rewrite_content_html` to apply the same change to every
+//! item.
+//!
+//! ```
+//! use json_feed_model::rewrite;
+//!
+//! let mut feed = json_feed_model::from_value(serde_json::json!({
+//!     "items": [{
+//!         "id": "1",
+//!         "content_html": "<p><img src=\"https://example.org/a.png\"><img src=\"https://t.example/p.gif\" width=\"1\" height=\"1\"></p>"
+//!     }]
+//! }))?;
+//!
+//! feed.rewrite_content_html(|html| rewrite::lazy_load_images(&rewrite::strip_tracking_pixels(html)))?;
+//!
+//! assert_eq!(
+//!     feed.items()?.unwrap()[0].content_html()?,
+//!     Some("<p><img src=\"https://example.org/a.png\" loading=\"lazy\"></p>")
+//! );
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+use core::ops::Range;
+
+use serde_json::{Map, Value};
+
+use crate::{
+    html::{Tag, Token},
+    Error, Feed, FeedMut, FeedRef,
+};
+
+/// Attributes which contain a single URL.
+const URL_ATTRS: &[&str] = &["href", "src", "poster", "cite", "action"];
+
+/// Calls `f` with each opening tag and its source and replaces the tag with the returned string.
+fn rewrite_tags<F>(html: &str, mut f: F) -> String
+where
+    F: FnMut(&Tag<'_>, &str) -> Option<String>,
+{
+    let mut out = String::with_capacity(html.len());
+    let mut copied = 0;
+    let mut tokens = crate::html::tokens(html);
+    loop {
+        let start = tokens.offset();
+        let token = match tokens.next() {
+            Some(token) => token,
+            None => break,
+        };
+        if let Token::Tag(tag) = token {
+            if tag.is_closing {
+                continue;
+            }
+            let end = tokens.offset();
+            if let Some(replacement) = f(&tag, &html[start..end]) {
+                out.push_str(&html[copied..start]);
+                out.push_str(&replacement);
+                copied = end;
+            }
+        }
+    }
+    out.push_str(&html[copied..]);
+    out
+}
+
+/// Returns the byte range of the tag's attributes in the tag's source.
+fn attrs_range(tag: &Tag<'_>) -> Range<usize> {
+    let start = 1 + tag.name.len();
+    start..start + tag.attrs.len()
+}
+
+/// Returns the tag's source with an attribute set to a value.
+///
+/// The value is escaped. An existing attribute is replaced; otherwise, the attribute is added
+/// after the other attributes.
+fn set_attr(tag: &Tag<'_>, source: &str, name: &str, value: &str) -> String {
+    let attrs = attrs_range(tag);
+    let (range, separator) = match tag.attr_span(name) {
+        Some((span, _)) => (attrs.start + span.start..attrs.start + span.end, ""),
+        None => {
+            // Add the attribute before a self-closing slash.
+            let end = attrs.start
+                + tag
+                    .attrs
+                    .trim_end_matches(|c: char| c == '/' || c.is_ascii_whitespace())
+                    .len();
+            (end..end, " ")
+        }
+    };
+    let mut out = String::with_capacity(source.len() + name.len() + value.len() + 4);
+    out.push_str(&source[..range.start]);
+    out.push_str(separator);
+    out.push_str(name);
+    out.push_str("=\"");
+    crate::html::escape_into(value, &mut out);
+    out.push('"');
+    out.push_str(&source[range.end..]);
+    out
+}
+
+fn decoded_attr(tag: &Tag<'_>, name: &str) -> Option<String> {
+    tag.attr(name).map(|value| {
+        let mut decoded = String::with_capacity(value.len());
+        crate::html::decode_entities_into(value, &mut decoded);
+        decoded
+    })
+}
+
+/// Resolves the URLs in a `srcset` attribute.
+fn resolve_srcset(srcset: &str, base: &str) -> Option<String> {
+    let mut resolved = String::with_capacity(srcset.len());
+    for candidate in srcset.split(',') {
+        let candidate = candidate.trim();
+        if candidate.is_empty() {
+            continue;
+        }
+        let (url, descriptor) = candidate.split_at(
+            candidate
+                .find(char::is_whitespace)
+                .unwrap_or(candidate.len()),
+        );
+        if !resolved.is_empty() {
+            resolved.push_str(", ");
+        }
+        resolved.push_str(&crate::url::resolve(base, url)?);
+        resolved.push_str(descriptor);
+    }
+    Some(resolved)
+}
+
+/// Resolves the relative URLs in `href`, `src`, `srcset`, and similar attributes against `base`.
+///
+/// Fragment-only URLs are kept as is.
+#[must_use]
+pub fn absolutize_urls(html: &str, base: &str) -> String {
+    rewrite_tags(html, |tag, source| {
+        let mut rewritten: Option<String> = None;
+        for attr in URL_ATTRS {
+            let value = match decoded_attr(tag, attr) {
+                Some(value) => value,
+                None => continue,
+            };
+            let value = value.trim();
+            if value.is_empty() || value.starts_with('#') || crate::url::is_absolute(value) {
+                continue;
+            }
+            if let Some(resolved) = crate::url::resolve(base, value) {
+                rewritten = Some(rewrite_attr(tag, source, rewritten, attr, &resolved));
+            }
+        }
+        if let Some(srcset) = decoded_attr(tag, "srcset") {
+            if let Some(resolved) = resolve_srcset(&srcset, base) {
+                if resolved != srcset {
+                    rewritten = Some(rewrite_attr(tag, source, rewritten, "srcset", &resolved));
+                }
+            }
+        }
+        rewritten
+    })
+}
+
+/// Sets an attribute on a tag which may have already been rewritten.
+fn rewrite_attr(
+    tag: &Tag<'_>,
+    source: &str,
+    rewritten: Option<String>,
+    name: &str,
+    value: &str,
+) -> String {
+    match rewritten {
+        // The tag's offsets are only valid for the original source, so re-tokenize.
+        Some(rewritten) => {
+            let mut tokens = crate::html::tokens(&rewritten);
+            match tokens.next() {
+                Some(Token::Tag(tag)) => set_attr(&tag, &rewritten, name, value),
+                _ => rewritten.clone(),
+            }
+        }
+        None => set_attr(tag, source, name, value),
+    }
+}
+
+/// Adds `loading="lazy"` to `img` and `iframe` elements which do not have a `loading`
+/// attribute.
+#[must_use]
+pub fn lazy_load_images(html: &str) -> String {
+    rewrite_tags(html, |tag, source| {
+        if (tag.is("img") || tag.is("iframe")) && tag.attr("loading").is_none() {
+            Some(set_attr(tag, source, "loading", "lazy"))
+        } else {
+            None
+        }
+    })
+}
+
+/// Replaces the `src` of `img` elements with a URL generated from a proxy `template`.
+///
+/// The `{url}` placeholder in the template is replaced with the percent-encoded image URL.
+/// Only absolute `http` and `https` image URLs are proxied, so relative URLs should be
+/// resolved with [`absolutize_urls`] first. The `srcset` attribute is removed from proxied
+/// images because its URLs are not proxied.
+///
+/// # Errors
+///
+/// If the template does not contain `{url}`, then `Error::InvalidTemplate` is returned.
+pub fn proxy_images(html: &str, template: &str) -> Result<String, Error> {
+    let placeholder = template.find("{url}").ok_or(Error::InvalidTemplate)?;
+    let (prefix, suffix) = (&template[..placeholder], &template[placeholder + 5..]);
+
+    Ok(rewrite_tags(html, |tag, source| {
+        if !tag.is("img") {
+            return None;
+        }
+        let src = decoded_attr(tag, "src")?;
+        let src = src.trim();
+        let is_http = crate::url::scheme(src).map_or(false, |scheme| {
+            scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("https")
+        });
+        if !is_http {
+            return None;
+        }
+
+        let mut proxied = String::with_capacity(template.len() + src.len() * 3);
+        proxied.push_str(prefix);
+        crate::url::encode_path_segment_into(src, &mut proxied);
+        proxied.push_str(suffix);
+
+        let rewritten = set_attr(tag, source, "src", &proxied);
+        let mut tokens = crate::html::tokens(&rewritten);
+        match tokens.next() {
+            Some(Token::Tag(rewritten_tag)) => match rewritten_tag.attr_span("srcset") {
+                Some((span, _)) => {
+                    let attrs = attrs_range(&rewritten_tag);
+                    let mut start = attrs.start + span.start;
+                    while rewritten[..start].ends_with(char::is_whitespace) {
+                        start -= 1;
+                    }
+                    let mut without = String::from(&rewritten[..start]);
+                    without.push_str(&rewritten[attrs.start + span.end..]);
+                    Some(without)
+                }
+                None => Some(rewritten),
+            },
+            _ => Some(rewritten),
+        }
+    }))
+}
+
+/// Returns true if a `width` or `height` attribute is at most one pixel.
+fn is_tiny(tag: &Tag<'_>, name: &str) -> bool {
+    tag.attr(name).map_or(false, |value| {
+        let value = value.trim();
+        let value = value.strip_suffix("px").unwrap_or(value);
+        value.parse::<u32>().map_or(false, |pixels| pixels <= 1)
+    })
+}
+
+/// Removes images which are likely tracking pixels.
+///
+/// An `img` element whose `width` and `height` are both 0 or 1 is removed.
+#[must_use]
+pub fn strip_tracking_pixels(html: &str) -> String {
+    rewrite_tags(html, |tag, _| {
+        if tag.is("img") && is_tiny(tag, "width") && is_tiny(tag, "height") {
+            Some(String::new())
+        } else {
+            None
+        }
+    })
+}
+
+fn absolutize_content_urls(map: &mut Map<String, Value>) -> Result<usize, Error> {
+    let feed = FeedRef::from(&*map);
+    let feed_base = match feed.home_page_url()? {
+        Some(base) => Some(base),
+        None => feed.feed_url()?,
+    };
+    let items = crate::item_refs(map)?;
+    let mut rewritten = Vec::with_capacity(items.len());
+    for item in items {
+        let html = item.content_html()?;
+        let base = match item.url()? {
+            Some(url) => Some(url),
+            None => feed_base,
+        };
+        rewritten.push(match (html, base) {
+            (Some(html), Some(base)) => {
+                Some(absolutize_urls(html, base)).filter(|absolutized| absolutized != html)
+            }
+            _ => None,
+        });
+    }
+
+    let mut changed = 0;
+    if let Some(Value::Array(items)) = map.get_mut("items") {
+        for (item, html) in items.iter_mut().zip(rewritten) {
+            if let (Value::Object(item), Some(html)) = (item, html) {
+                item.insert(String::from("content_html"), Value::String(html));
+                changed += 1;
+            }
+        }
+    }
+    Ok(changed)
+}
+
+macro_rules! rewrite_methods {
+    () => {
+        /// Resolves the relative URLs in every item's `content_html`.
+        ///
+        /// URLs are resolved against the item's `url` or, if it does not have one, the feed's
+        /// `home_page_url` or `feed_url`. See [`absolutize_urls`].
+        ///
+        /// Returns the number of items which were changed.
+        ///
+        /// # Errors
+        ///
+        /// If the items or the URL and content properties are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned. The feed is not changed if an error is returned.
+        #[cfg_attr(docsrs, doc(cfg(feature = "rewrite")))]
+        pub fn absolutize_content_urls(&mut self) -> Result<usize, Error> {
+            absolutize_content_urls(self.as_map_mut())
+        }
+    };
+}
+
+impl Feed {
+    rewrite_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    rewrite_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn absolutize() -> Result<(), Error> {
+        let html = r##"<a href="../b?x=1&amp;y=2">B</a><img src='c.png' srcset="c.png 1x, /d.png 2x"><a href="#top">Top</a><a href="https://other.example/">O</a>"##;
+        assert_eq!(
+            absolutize_urls(html, "https://example.org/posts/a/"),
+            r##"<a href="https://example.org/posts/b?x=1&amp;y=2">B</a><img src="https://example.org/posts/a/c.png" srcset="https://example.org/posts/a/c.png 1x, https://example.org/d.png 2x"><a href="#top">Top</a><a href="https://other.example/">O</a>"##
+        );
+
+        let mut feed = crate::from_value(serde_json::json!({
+            "home_page_url": "https://example.org/",
+            "items": [
+                { "id": "1", "content_html": "<img src=\"a.png\">" },
+                { "id": "2", "url": "https://example.org/x/2", "content_html": "<img src=\"a.png\">" },
+                { "id": "3", "content_text": "a.png" }
+            ]
+        }))?;
+        assert_eq!(feed.absolutize_content_urls()?, 2);
+        let items = feed.items()?.unwrap();
+        assert_eq!(
+            items[0].content_html()?,
+            Some("<img src=\"https://example.org/a.png\">")
+        );
+        assert_eq!(
+            items[1].content_html()?,
+            Some("<img src=\"https://example.org/x/a.png\">")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn proxy() -> Result<(), Error> {
+        let html = r#"<img src="https://example.org/a b.png" srcset="x.png 2x" alt="A"><img src="data:image/gif;base64,AA"><img src="rel.png">"#;
+        assert_eq!(
+            proxy_images(html, "https://proxy.example/i?u={url}&w=600")?,
+            r#"<img src="https://proxy.example/i?u=https%3A%2F%2Fexample.org%2Fa%20b.png&amp;w=600" alt="A"><img src="data:image/gif;base64,AA"><img src="rel.png">"#
+        );
+        assert!(matches!(
+            proxy_images(html, "https://proxy.example/"),
+            Err(Error::InvalidTemplate)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn lazy_and_pixels() {
+        assert_eq!(
+            lazy_load_images(
+                r#"<img src="a.png"/><img src="b.png" loading="eager"><iframe src="v"></iframe>"#
+            ),
+            r#"<img src="a.png" loading="lazy"/><img src="b.png" loading="eager"><iframe src="v" loading="lazy"></iframe>"#
+        );
+        assert_eq!(
+            strip_tracking_pixels(
+                r#"<p>Hi<img src="t.gif" width="1" height="1px"><img src="a.png" width="1" height="100"></p>"#
+            ),
+            r#"<p>Hi<img src="a.png" width="1" height="100"></p>"#
+        );
+    }
+}
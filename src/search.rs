@@ -0,0 +1,258 @@
+//! Full-text search over the items in a feed.
+//!
+//! A `SearchIndex` is an in-memory inverted index built from the items' titles, text content,
+//! summaries, and tags. Queries are ranked with [BM25][bm25].
+//!
+//! ```
+//! let feed = json_feed_model::from_value(serde_json::json!({
+//!     "items": [
+//!         { "id": "1", "title": "Baking bread", "content_text": "Flour, water, and salt." },
+//!         { "id": "2", "title": "Gardening", "content_text": "Tomatoes need water." },
+//!         { "id": "3", "content_html": "<p>Nothing here.</p>", "tags": ["misc"] }
+//!     ]
+//! }))?;
+//!
+//! let index = feed.search_index()?;
+//! let results = index.search("water bread");
+//! assert_eq!(results.len(), 2);
+//! assert_eq!(results[0].1, 0);
+//! assert_eq!(results[1].1, 1);
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+//!
+//! [bm25]: https://en.wikipedia.org/wiki/Okapi_BM25
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{collections::BTreeMap, string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{Error, Feed, FeedMut, FeedRef, ItemRef};
+
+/// How many times more a title or tag term counts than a content term.
+const TITLE_WEIGHT: u32 = 3;
+
+const K1: f64 = 1.2;
+const B: f64 = 0.75;
+
+/// Splits text into lowercase alphanumeric terms.
+fn terms(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// An inverted index over the items in a feed.
+#[derive(Clone, Debug, Default)]
+pub struct SearchIndex {
+    /// For each term, the items containing the term and the weighted term frequency.
+    postings: BTreeMap<String, Vec<(usize, u32)>>,
+    /// The weighted number of terms in each item.
+    lengths: Vec<u32>,
+}
+
+impl SearchIndex {
+    fn add_item(&mut self, item_index: usize, item: &ItemRef<'_>) -> Result<(), Error> {
+        let mut frequencies: BTreeMap<String, u32> = BTreeMap::new();
+        let mut length = 0;
+
+        let mut add = |text: &str, weight: u32| {
+            for term in terms(text) {
+                *frequencies.entry(term).or_insert(0) += weight;
+                length += weight;
+            }
+        };
+
+        if let Some(title) = item.title()? {
+            add(title, TITLE_WEIGHT);
+        }
+        for tag in item.tags()?.unwrap_or_default() {
+            add(tag, TITLE_WEIGHT);
+        }
+        if let Some(summary) = item.summary()? {
+            add(summary, 1);
+        }
+        if let Some(text) = crate::content::plain_text(item.as_map())? {
+            add(&text, 1);
+        }
+
+        for (term, frequency) in frequencies {
+            self.postings
+                .entry(term)
+                .or_default()
+                .push((item_index, frequency));
+        }
+        self.lengths.push(length);
+        Ok(())
+    }
+
+    /// Returns the number of indexed items.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lengths.len()
+    }
+
+    /// Returns true if no items are indexed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lengths.is_empty()
+    }
+
+    /// Returns the (score, item index) pairs of the items which match any query term.
+    ///
+    /// The results are ordered from the highest to the lowest score. Items with equal scores
+    /// are ordered by their index in the feed.
+    #[must_use]
+    pub fn search(&self, query: &str) -> Vec<(f64, usize)> {
+        if self.lengths.is_empty() {
+            return Vec::new();
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let item_count = self.lengths.len() as f64;
+        #[allow(clippy::cast_precision_loss)]
+        let avg_length = self.lengths.iter().map(|l| f64::from(*l)).sum::<f64>() / item_count;
+
+        let mut query_terms = terms(query).collect::<Vec<_>>();
+        query_terms.sort();
+        query_terms.dedup();
+
+        let mut scores: BTreeMap<usize, f64> = BTreeMap::new();
+        for term in &query_terms {
+            let postings = match self.postings.get(term) {
+                Some(postings) => postings,
+                None => continue,
+            };
+            #[allow(clippy::cast_precision_loss)]
+            let matching = postings.len() as f64;
+            let idf = ln_1p((item_count - matching + 0.5) / (matching + 0.5));
+            for (item_index, frequency) in postings {
+                let frequency = f64::from(*frequency);
+                let length = f64::from(self.lengths[*item_index]);
+                let norm = K1 * (1.0 - B + B * length / avg_length.max(1.0));
+                *scores.entry(*item_index).or_insert(0.0) +=
+                    idf * frequency * (K1 + 1.0) / (frequency + norm);
+            }
+        }
+
+        let mut results = scores
+            .into_iter()
+            .map(|(item_index, score)| (score, item_index))
+            .collect::<Vec<_>>();
+        results.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(core::cmp::Ordering::Equal));
+        results
+    }
+}
+
+/// Computes `ln(1 + x)` for `x >= 0` without `std`.
+fn ln_1p(x: f64) -> f64 {
+    #[cfg(feature = "std")]
+    {
+        x.ln_1p()
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        // ln(y) = 2 * atanh((y - 1) / (y + 1)) converges for all positive y.
+        let y = 1.0 + x;
+        let z = (y - 1.0) / (y + 1.0);
+        let z2 = z * z;
+        let mut term = z;
+        let mut sum = 0.0;
+        let mut n = 1.0;
+        while n < 200.0 {
+            sum += term / n;
+            term *= z2;
+            n += 2.0;
+        }
+        2.0 * sum
+    }
+}
+
+fn search_index(map: &Map<String, Value>) -> Result<SearchIndex, Error> {
+    let mut index = SearchIndex::default();
+    for (item_index, item) in FeedRef::from(map)
+        .items()?
+        .unwrap_or_default()
+        .iter()
+        .enumerate()
+    {
+        index.add_item(item_index, item)?;
+    }
+    Ok(index)
+}
+
+impl Feed {
+    /// Builds a search index over the feed's items.
+    ///
+    /// # Errors
+    ///
+    /// If the items or an item's properties are not the expected JSON type, then
+    /// `Error::UnexpectedType` is returned.
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_index(&self) -> Result<SearchIndex, Error> {
+        search_index(&self.value)
+    }
+}
+
+impl<'a> FeedMut<'a> {
+    /// Builds a search index over the feed's items.
+    ///
+    /// # Errors
+    ///
+    /// If the items or an item's properties are not the expected JSON type, then
+    /// `Error::UnexpectedType` is returned.
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_index(&self) -> Result<SearchIndex, Error> {
+        search_index(self.value)
+    }
+}
+
+impl<'a> FeedRef<'a> {
+    /// Builds a search index over the feed's items.
+    ///
+    /// # Errors
+    ///
+    /// If the items or an item's properties are not the expected JSON type, then
+    /// `Error::UnexpectedType` is returned.
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_index(&self) -> Result<SearchIndex, Error> {
+        search_index(self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranking() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "items": [
+                { "id": "1", "content_text": "rust rust rust and some other words here" },
+                { "id": "2", "title": "Rust", "content_text": "A language." },
+                { "id": "3", "content_text": "Nothing relevant." },
+                { "id": "4", "content_text": "Ünïcode RUST", "tags": ["Systems"] }
+            ]
+        }))?;
+        let index = feed.search_index()?;
+        assert_eq!(index.len(), 4);
+
+        let results = index.search("Rust");
+        let order = results.iter().map(|(_, i)| *i).collect::<Vec<_>>();
+        assert_eq!(order, vec![1, 0, 3]);
+        assert!(results.iter().all(|(score, _)| *score > 0.0));
+
+        assert_eq!(index.search("ünïcode systems")[0].1, 3);
+        assert!(index.search("missing").is_empty());
+        assert!(index.search("").is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn ln_1p_approximation() {
+        assert!((ln_1p(1.0) - core::f64::consts::LN_2).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,135 @@
+//! URL slugs.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{format, string::String};
+#[cfg(feature = "std")]
+use std::{format, string::String};
+
+use serde_json::{Map, Value};
+
+use crate::{ext::__private::str_prop, Error, Item, ItemMut, ItemRef};
+
+/// The maximum length of a slug in bytes.
+const MAX_SLUG_LEN: usize = 80;
+
+#[cfg(feature = "transliterate")]
+fn ascii_chars(text: &str) -> impl Iterator<Item = char> + '_ {
+    text.chars().flat_map(|c| {
+        let (ascii, transliterated) = if c.is_ascii() {
+            (Some(c), "")
+        } else {
+            (None, deunicode::deunicode_char(c).unwrap_or(" "))
+        };
+        ascii.into_iter().chain(transliterated.chars())
+    })
+}
+
+#[cfg(not(feature = "transliterate"))]
+fn ascii_chars(text: &str) -> impl Iterator<Item = char> + '_ {
+    text.chars()
+}
+
+/// Returns a lowercase slug of ASCII letters and digits separated by single hyphens.
+///
+/// With the `transliterate` feature, non-ASCII characters are transliterated to ASCII first.
+/// Otherwise, they separate words.
+///
+/// Returns `None` if the text does not contain any ASCII letters or digits.
+pub(crate) fn slugify(text: &str) -> Option<String> {
+    let mut slug = String::with_capacity(text.len().min(MAX_SLUG_LEN));
+    let mut pending_hyphen = false;
+    for c in ascii_chars(text) {
+        if c.is_ascii_alphanumeric() {
+            if pending_hyphen && !slug.is_empty() {
+                if slug.len() + 1 >= MAX_SLUG_LEN {
+                    break;
+                }
+                slug.push('-');
+            }
+            if slug.len() >= MAX_SLUG_LEN {
+                break;
+            }
+            pending_hyphen = false;
+            slug.push(c.to_ascii_lowercase());
+        } else if c != '\'' && c != '\u{2019}' {
+            // Apostrophes are dropped so "don't" becomes "dont" instead of "don-t".
+            pending_hyphen = true;
+        }
+    }
+    Some(slug).filter(|slug| !slug.is_empty())
+}
+
+pub(crate) fn item_slug(map: &Map<String, Value>) -> Result<Option<String>, Error> {
+    if let Some(slug) = str_prop::get(map, None, "title")?.and_then(slugify) {
+        return Ok(Some(slug));
+    }
+    Ok(str_prop::get(map, None, "id")?
+        .map(|id| format!("{:016x}", crate::canonical::fnv1a(id.as_bytes()))))
+}
+
+macro_rules! slug_methods {
+    () => {
+        /// Returns a URL-safe slug for the item.
+        ///
+        /// The slug is derived from the `title` with lowercase ASCII letters and digits
+        /// separated by hyphens. With the `transliterate` feature, non-ASCII letters in the
+        /// title are transliterated (e.g. "Crème brûlée" becomes `creme-brulee`).
+        ///
+        /// If the item does not have a title which can be turned into a slug, then a
+        /// hexadecimal hash of the `id` is returned. If the item has neither, then `None` is
+        /// returned.
+        ///
+        /// # Errors
+        ///
+        /// If the `title` or `id` is not a JSON string, then `Error::UnexpectedType` is
+        /// returned.
+        pub fn slug(&self) -> Result<Option<String>, Error> {
+            item_slug(self.as_map())
+        }
+    };
+}
+
+impl Item {
+    slug_methods!();
+}
+
+impl<'a> ItemMut<'a> {
+    slug_methods!();
+}
+
+impl<'a> ItemRef<'a> {
+    slug_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugs() {
+        assert_eq!(
+            slugify("  Hello, World! Don't panic.  ").as_deref(),
+            Some("hello-world-dont-panic")
+        );
+        assert_eq!(
+            slugify("Rust 1.56 -- Edition 2021").as_deref(),
+            Some("rust-1-56-edition-2021")
+        );
+        assert_eq!(slugify("!!!"), None);
+        assert!(slugify(&"word ".repeat(40)).unwrap().len() <= MAX_SLUG_LEN);
+    }
+
+    #[test]
+    fn item_slugs() -> Result<(), Error> {
+        let mut item = Item::new();
+        assert_eq!(item.slug()?, None);
+        item.set_id("1");
+        assert_eq!(item.slug()?.as_deref(), Some("af63ac4c86019afc"));
+        item.set_title("Crème brûlée");
+        #[cfg(feature = "transliterate")]
+        assert_eq!(item.slug()?.as_deref(), Some("creme-brulee"));
+        #[cfg(not(feature = "transliterate"))]
+        assert_eq!(item.slug()?.as_deref(), Some("cr-me-br-l-e"));
+        Ok(())
+    }
+}
@@ -0,0 +1,424 @@
+//! Helpers for managing feed subscriptions.
+//!
+//! Users enter feed URLs in many forms. The same feed may be written with or without a scheme,
+//! with `http` instead of `https`, with or without a `www.` prefix or a trailing slash, or with
+//! the `feed:` pseudo-scheme used by some browsers.
+//!
+//! Subscriptions exported from other feed readers can be imported with [`from_url_list`] and
+//! [`from_json_export`].
+//!
+//! ```
+//! use json_feed_model::subscriptions::{feed_url_equivalent, normalize_subscription_url};
+//!
+//! assert_eq!(
+//!     normalize_subscription_url("feed://Example.org:80/feed.json#latest").as_deref(),
+//!     Some("http://example.org/feed.json")
+//! );
+//! assert!(feed_url_equivalent(
+//!     "example.org/blog/",
+//!     "https://www.example.org/blog"
+//! ));
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::Error;
+
+/// A feed subscription imported from another feed reader.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Subscription {
+    /// The title given to the feed.
+    pub title: Option<String>,
+    /// The URL of the feed.
+    pub feed_url: String,
+    /// The tags, folders, or categories the feed is in.
+    pub tags: Vec<String>,
+}
+
+impl Subscription {
+    /// Instantiates a subscription to a feed URL without a title or tags.
+    #[must_use]
+    pub fn new<T: Into<String>>(feed_url: T) -> Self {
+        Self {
+            title: None,
+            feed_url: feed_url.into(),
+            tags: Vec::new(),
+        }
+    }
+}
+
+/// Splits a line into whitespace separated words. Double quotes group words together.
+fn words(line: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_quotes = false;
+    let mut is_empty = true;
+    for c in line.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                is_empty = false;
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if !is_empty {
+                    words.push(core::mem::take(&mut word));
+                    is_empty = true;
+                }
+            }
+            c => {
+                word.push(c);
+                is_empty = false;
+            }
+        }
+    }
+    if !is_empty {
+        words.push(word);
+    }
+    words
+}
+
+/// Parses a list of feed URLs with one subscription per line.
+///
+/// Blank lines and lines starting with `#` are ignored. The first word on a line is the feed
+/// URL and the following words are tags. Words may be quoted to include whitespace. A word
+/// starting with `~` is the title, and words starting with `!` are ignored. This is compatible
+/// with plain lists of URLs and the `urls` file used by [Newsboat][newsboat].
+///
+/// ```
+/// use json_feed_model::subscriptions::from_url_list;
+///
+/// let subscriptions = from_url_list(
+///     "# My feeds\nhttps://example.org/feed.json rust \"~Example Blog\"\n\nhttps://example.com/feed.json\n",
+/// );
+/// assert_eq!(subscriptions.len(), 2);
+/// assert_eq!(subscriptions[0].title.as_deref(), Some("Example Blog"));
+/// assert_eq!(subscriptions[0].tags, vec!["rust"]);
+/// ```
+///
+/// [newsboat]: https://newsboat.org/releases/2.21/docs/newsboat.html#_the_urls_file
+#[must_use]
+pub fn from_url_list(text: &str) -> Vec<Subscription> {
+    let mut subscriptions = Vec::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut words = words(line).into_iter();
+        let mut subscription = match words.next() {
+            Some(url) if !url.is_empty() => Subscription::new(url),
+            _ => continue,
+        };
+        for word in words {
+            if let Some(title) = word.strip_prefix('~') {
+                subscription.title = Some(String::from(title));
+            } else if !word.starts_with('!') && !word.is_empty() {
+                subscription.tags.push(word);
+            }
+        }
+        subscriptions.push(subscription);
+    }
+    subscriptions
+}
+
+fn json_subscription(value: &Value) -> Result<Option<Subscription>, Error> {
+    fn str_value<'a>(map: &'a Map<String, Value>, key: &str) -> Result<Option<&'a str>, Error> {
+        map.get(key).map_or(Ok(None), |value| {
+            value.as_str().map(Some).ok_or(Error::UnexpectedType)
+        })
+    }
+
+    let map = value.as_object().ok_or(Error::UnexpectedType)?;
+
+    // Feedly identifies feeds with their URL prefixed by `feed/`.
+    let feed_url = match ["feed_url", "feedUrl", "xmlUrl", "url"]
+        .iter()
+        .find_map(|key| str_value(map, key).transpose())
+        .transpose()?
+    {
+        Some(url) => url,
+        None => match str_value(map, "id")?.and_then(|id| id.strip_prefix("feed/")) {
+            Some(url) => url,
+            None => return Ok(None),
+        },
+    };
+
+    let mut subscription = Subscription::new(feed_url);
+    subscription.title = str_value(map, "title")?.map(String::from);
+    for key in ["tags", "categories", "folders"] {
+        let tags = match map.get(key) {
+            Some(Value::Array(tags)) => tags,
+            Some(_) => return Err(Error::UnexpectedType),
+            None => continue,
+        };
+        for tag in tags {
+            let tag = match tag {
+                Value::String(tag) => tag,
+                Value::Object(tag) => match str_value(tag, "label")?.or(str_value(tag, "name")?) {
+                    Some(tag) => tag,
+                    None => continue,
+                },
+                _ => return Err(Error::UnexpectedType),
+            };
+            subscription.tags.push(String::from(tag));
+        }
+    }
+    Ok(Some(subscription))
+}
+
+/// Parses a JSON export of subscriptions.
+///
+/// The JSON must be an array of subscription objects or an object with a `subscriptions`
+/// array, as exported by readers like [Feedbin][feedbin] and [Feedly][feedly]. The feed URL is
+/// read from the `feed_url`, `feedUrl`, `xmlUrl`, or `url` property, or from an `id` starting
+/// with `feed/`. Tags are read from `tags`, `categories`, or `folders` arrays of strings or of
+/// objects with a `label` or `name`. Objects without a feed URL are ignored.
+///
+/// ```
+/// use json_feed_model::subscriptions::from_json_export;
+///
+/// let subscriptions = from_json_export(r#"[
+///     { "id": "feed/https://example.org/feed.json", "title": "Example", "categories": [{ "label": "Rust" }] },
+///     { "feed_url": "https://example.com/feed.json", "site_url": "https://example.com/" }
+/// ]"#)?;
+/// assert_eq!(subscriptions[0].feed_url, "https://example.org/feed.json");
+/// assert_eq!(subscriptions[0].tags, vec!["Rust"]);
+/// assert_eq!(subscriptions[1].title, None);
+/// # Ok::<(), json_feed_model::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// If the string cannot be JSON decoded, then `Error::SerdeJson(serde_json::Error)` is returned.
+///
+/// If the decoded JSON value or a subscription property is not the expected JSON type, then
+/// `Error::UnexpectedType` is returned.
+///
+/// [feedbin]: https://github.com/feedbin/feedbin-api/blob/master/content/subscriptions.md
+/// [feedly]: https://developer.feedly.com/v3/subscriptions/
+pub fn from_json_export(json: &str) -> Result<Vec<Subscription>, Error> {
+    let value: Value = serde_json::from_str(json)?;
+    let entries = match &value {
+        Value::Array(entries) => entries,
+        Value::Object(map) => match map.get("subscriptions") {
+            Some(Value::Array(entries)) => entries,
+            _ => return Err(Error::UnexpectedType),
+        },
+        _ => return Err(Error::UnexpectedType),
+    };
+    entries
+        .iter()
+        .filter_map(|entry| json_subscription(entry).transpose())
+        .collect()
+}
+
+/// Schemes which wrap a feed URL.
+///
+/// `feed://example.org/` is an alias for `http://example.org/`, and `feed:https://example.org/`
+/// is an alias for `https://example.org/`.
+const PSEUDO_SCHEMES: &[&str] = &["feed", "json"];
+
+/// Returns the URL without a pseudo-scheme.
+fn strip_pseudo_scheme(url: &str) -> Option<&str> {
+    let scheme = crate::url::scheme(url)?;
+    if !PSEUDO_SCHEMES
+        .iter()
+        .any(|pseudo| scheme.eq_ignore_ascii_case(pseudo))
+    {
+        return None;
+    }
+    Some(&url[scheme.len() + 1..])
+}
+
+/// Returns a subscription URL in a normalized form.
+///
+/// Surrounding whitespace and the fragment are removed. The `feed:` and `json:` pseudo-schemes
+/// are replaced. URLs without a scheme are assumed to be `https` URLs. The scheme and host are
+/// lowercased, default ports are removed, dot segments are removed, and an empty path is
+/// replaced with `/`.
+///
+/// If the URL is not an `http` or `https` URL, then `None` is returned.
+#[must_use]
+pub fn normalize_subscription_url(url: &str) -> Option<String> {
+    let mut url = url.trim();
+    let mut default_scheme = "https:";
+    while let Some(rest) = strip_pseudo_scheme(url) {
+        url = rest;
+        default_scheme = "http:";
+    }
+
+    let mut owned = String::with_capacity(url.len() + 8);
+    if !crate::url::is_absolute(url) {
+        owned.push_str(default_scheme);
+        if !url.starts_with("//") {
+            owned.push_str("//");
+        }
+    }
+    owned.push_str(url);
+
+    // Resolving an absolute reference removes its dot segments.
+    let mut resolved = crate::url::resolve(&owned, &owned)?;
+    if let Some(i) = resolved.find('#') {
+        resolved.truncate(i);
+    }
+    let scheme = crate::url::scheme(&resolved)?.to_ascii_lowercase();
+    let default_port = match scheme.as_str() {
+        "http" => ":80",
+        "https" => ":443",
+        _ => return None,
+    };
+
+    let rest = resolved[scheme.len() + 1..].strip_prefix("//")?;
+    let end = rest.find(|c| c == '/' || c == '?').unwrap_or(rest.len());
+    let (authority, path_and_query) = rest.split_at(end);
+    let (userinfo, host) = match authority.rfind('@') {
+        Some(i) => authority.split_at(i + 1),
+        None => ("", authority),
+    };
+    let host = host.strip_suffix(default_port).unwrap_or(host);
+    let host = host.strip_suffix(':').unwrap_or(host);
+    if host.is_empty() {
+        return None;
+    }
+
+    let mut normalized = String::with_capacity(resolved.len());
+    normalized.push_str(&scheme);
+    normalized.push_str("://");
+    normalized.push_str(userinfo);
+    normalized.push_str(&host.to_ascii_lowercase());
+    if !path_and_query.starts_with('/') {
+        normalized.push('/');
+    }
+    normalized.push_str(path_and_query);
+    Some(normalized)
+}
+
+/// Returns true if two subscription URLs likely refer to the same feed.
+///
+/// The URLs are normalized with [`normalize_subscription_url`]. Then `http` and `https`, hosts
+/// with and without a `www.` prefix, and paths with and without a trailing slash are considered
+/// the same.
+///
+/// If either URL is not an `http` or `https` URL, then the trimmed URLs are compared exactly.
+#[must_use]
+pub fn feed_url_equivalent(a: &str, b: &str) -> bool {
+    fn key(url: &str) -> Option<(String, String)> {
+        let url = normalize_subscription_url(url)?;
+        let rest = &url[url.find("://")? + 3..];
+        let end = rest.find(|c| c == '/' || c == '?').unwrap_or(rest.len());
+        let (authority, path_and_query) = rest.split_at(end);
+        let authority = authority.strip_prefix("www.").unwrap_or(authority);
+        let (path, query) = match path_and_query.find('?') {
+            Some(i) => path_and_query.split_at(i),
+            None => (path_and_query, ""),
+        };
+        let mut path_and_query = String::from(path.trim_end_matches('/'));
+        path_and_query.push_str(query);
+        Some((String::from(authority), path_and_query))
+    }
+
+    match (key(a), key(b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => a.trim() == b.trim(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::vec;
+
+    #[test]
+    fn normalize() {
+        assert_eq!(
+            normalize_subscription_url(" HTTPS://Example.ORG:443/a/../feed.json?x=1#top ")
+                .as_deref(),
+            Some("https://example.org/feed.json?x=1")
+        );
+        assert_eq!(
+            normalize_subscription_url("feed:https://example.org").as_deref(),
+            Some("https://example.org/")
+        );
+        assert_eq!(
+            normalize_subscription_url("json://example.org:8080/feed").as_deref(),
+            Some("http://example.org:8080/feed")
+        );
+        assert_eq!(
+            normalize_subscription_url("example.org/feed.json").as_deref(),
+            Some("https://example.org/feed.json")
+        );
+        assert_eq!(normalize_subscription_url("mailto:a@example.org"), None);
+        assert_eq!(normalize_subscription_url("mailto:ß"), None);
+        assert_eq!(
+            normalize_subscription_url("https://ß.example/feed").as_deref(),
+            Some("https://ß.example/feed")
+        );
+        assert_eq!(
+            normalize_subscription_url("feed:HTTPS://ß.example/é/../feed").as_deref(),
+            Some("https://ß.example/feed")
+        );
+        assert_eq!(normalize_subscription_url("https://"), None);
+    }
+
+    #[test]
+    fn equivalence() {
+        assert!(feed_url_equivalent(
+            "http://www.example.org/feed/",
+            "feed://example.org/feed"
+        ));
+        assert!(feed_url_equivalent(
+            "https://example.org/?format=json",
+            "example.org?format=json"
+        ));
+        assert!(!feed_url_equivalent(
+            "https://example.org/a",
+            "https://example.org/b"
+        ));
+        assert!(!feed_url_equivalent(
+            "https://example.org/?format=json",
+            "https://example.org/?format=xml"
+        ));
+        assert!(feed_url_equivalent("urn:x", " urn:x "));
+        assert!(feed_url_equivalent(
+            "https://www.ß.example/feed/",
+            "ß.example/feed"
+        ));
+        assert!(!feed_url_equivalent("mailto:ß", "mailto:ss"));
+    }
+
+    #[test]
+    fn imports() -> Result<(), Error> {
+        let list = from_url_list(
+            "  # comment\nhttps://example.org/a \"two words\" ! \"~A Title\"\n\t\nhttps://example.org/b",
+        );
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0].feed_url, "https://example.org/a");
+        assert_eq!(list[0].title.as_deref(), Some("A Title"));
+        assert_eq!(list[0].tags, vec!["two words"]);
+        assert_eq!(list[1], Subscription::new("https://example.org/b"));
+
+        let json = from_json_export(
+            r#"{ "subscriptions": [
+                { "xmlUrl": "https://example.org/a", "tags": ["x"], "folders": [{ "name": "y" }] },
+                { "title": "No URL" }
+            ] }"#,
+        )?;
+        assert_eq!(json.len(), 1);
+        assert_eq!(json[0].tags, vec!["x", "y"]);
+
+        assert!(matches!(
+            from_json_export(r#"[{ "feed_url": 1 }]"#),
+            Err(Error::UnexpectedType)
+        ));
+        assert!(matches!(from_json_export("["), Err(Error::SerdeJson(_))));
+
+        Ok(())
+    }
+}
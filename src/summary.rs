@@ -0,0 +1,191 @@
+//! Summaries of feeds for directories and subscription pickers.
+//!
+//! ```
+//! use json_feed_model::{summary::FeedSummary, FeedRef};
+//!
+//! let feed = json_feed_model::from_str(r#"{
+//!     "title": "My Blog",
+//!     "hubs": [{ "type": "WebSub", "url": "https://example.org/hub" }],
+//!     "items": [{ "id": "1", "date_published": "2021-06-01T12:00:00Z" }]
+//! }"#)?;
+//!
+//! let summary = FeedSummary::from(&FeedRef::from(feed.as_map()));
+//! assert_eq!(summary.title.as_deref(), Some("My Blog"));
+//! assert_eq!(summary.item_count, 1);
+//! assert_eq!(
+//!     serde_json::to_string(&summary)?,
+//!     r#"{"title":"My Blog","description":null,"home_page_url":null,"icon":null,"language":null,"item_count":1,"latest_item_date":"2021-06-01T12:00:00Z","hubs":["https://example.org/hub"]}"#
+//! );
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use std::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use serde::ser::SerializeStruct;
+
+use crate::{date::Timestamp, Feed, FeedMut, FeedRef};
+
+/// The properties of a feed which are displayed in feed listings.
+///
+/// Properties which are missing or are not the expected JSON type are `None`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct FeedSummary {
+    /// The feed's title.
+    pub title: Option<String>,
+    /// The feed's description.
+    pub description: Option<String>,
+    /// The feed's home page URL.
+    pub home_page_url: Option<String>,
+    /// The feed's icon URL.
+    pub icon: Option<String>,
+    /// The feed's language.
+    pub language: Option<String>,
+    /// The number of items in the feed.
+    pub item_count: usize,
+    /// The most recent `date_modified` or `date_published` of the items.
+    pub latest_item_date: Option<Timestamp>,
+    /// The URLs of the feed's hubs.
+    pub hubs: Vec<String>,
+}
+
+impl<'a> From<&FeedRef<'a>> for FeedSummary {
+    fn from(feed: &FeedRef<'a>) -> Self {
+        let owned =
+            |value: Result<Option<&str>, crate::Error>| value.ok().flatten().map(String::from);
+
+        let items = feed.items().ok().flatten().unwrap_or_default();
+        let latest_item_date = items
+            .iter()
+            .filter_map(|item| item.last_updated().ok().flatten())
+            .max();
+        let hubs = feed
+            .hubs()
+            .ok()
+            .flatten()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|hub| owned(hub.url()))
+            .collect();
+
+        Self {
+            title: owned(feed.title()),
+            description: owned(feed.description()),
+            home_page_url: owned(feed.home_page_url()),
+            icon: owned(feed.icon()),
+            language: owned(feed.language()),
+            item_count: items.len(),
+            latest_item_date,
+            hubs,
+        }
+    }
+}
+
+impl From<&Feed> for FeedSummary {
+    fn from(feed: &Feed) -> Self {
+        Self::from(&FeedRef::from(feed.as_map()))
+    }
+}
+
+impl<'a> From<&FeedMut<'a>> for FeedSummary {
+    fn from(feed: &FeedMut<'a>) -> Self {
+        Self::from(&FeedRef::from(feed.as_map()))
+    }
+}
+
+impl serde::Serialize for FeedSummary {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("FeedSummary", 8)?;
+        state.serialize_field("title", &self.title)?;
+        state.serialize_field("description", &self.description)?;
+        state.serialize_field("home_page_url", &self.home_page_url)?;
+        state.serialize_field("icon", &self.icon)?;
+        state.serialize_field("language", &self.language)?;
+        state.serialize_field("item_count", &self.item_count)?;
+        state.serialize_field(
+            "latest_item_date",
+            &self.latest_item_date.map(|date| date.to_string()),
+        )?;
+        state.serialize_field("hubs", &self.hubs)?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::vec;
+
+    #[test]
+    fn summary() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "title": "Blog",
+            "icon": 1,
+            "language": "en",
+            "items": [
+                { "id": "1", "date_published": "2021-01-01T00:00:00Z" },
+                { "id": "2", "date_modified": "2021-03-01T00:00:00+01:00" },
+                { "id": "3" }
+            ]
+        }))?;
+
+        let summary = FeedSummary::from(&feed);
+        assert_eq!(summary.title.as_deref(), Some("Blog"));
+        assert_eq!(summary.icon, None);
+        assert_eq!(summary.language.as_deref(), Some("en"));
+        assert_eq!(summary.item_count, 3);
+        assert_eq!(
+            summary.latest_item_date,
+            Some(Timestamp::parse("2021-02-28T23:00:00Z")?)
+        );
+        assert!(summary.hubs.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn unexpected_types() -> Result<(), Error> {
+        assert_eq!(FeedSummary::from(&Feed::new()), FeedSummary::default());
+
+        let feed = crate::from_value(serde_json::json!({
+            "title": ["Blog"],
+            "items": {},
+            "hubs": "https://example.org/hub"
+        }))?;
+        assert_eq!(FeedSummary::from(&feed), FeedSummary::default());
+
+        let feed = crate::from_value(serde_json::json!({
+            "hubs": [
+                { "type": "WebSub", "url": 1 },
+                { "type": "WebSub", "url": "https://example.org/hub" }
+            ],
+            "items": [
+                { "id": "1", "date_published": "yesterday" },
+                { "id": "2", "date_published": "2021-01-01T00:00:00Z" }
+            ]
+        }))?;
+        let summary = FeedSummary::from(&feed);
+        assert_eq!(summary.item_count, 2);
+        assert_eq!(
+            summary.latest_item_date,
+            Some(Timestamp::parse("2021-01-01T00:00:00Z")?)
+        );
+        assert_eq!(summary.hubs, vec![String::from("https://example.org/hub")]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,199 @@
+//! Tag analytics for building tag clouds and related tag lists.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+    vec::Vec,
+};
+
+use serde_json::{Map, Value};
+
+use crate::{Error, Feed, FeedMut, FeedRef};
+
+/// Determines how tags are normalized before they are counted.
+///
+/// Tags which are equal after normalization are counted as the same tag.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct TagOptions {
+    /// Lowercases tags with the Unicode case mapping (e.g. "ÉTÉ" and "été" are the same tag).
+    pub case_fold: bool,
+    /// Trims leading and trailing whitespace and collapses runs of Unicode whitespace into a
+    /// single space.
+    pub collapse_whitespace: bool,
+}
+
+impl TagOptions {
+    /// Counts tags exactly as they are written.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts tags after case folding and whitespace collapsing.
+    #[must_use]
+    pub fn normalized() -> Self {
+        Self {
+            case_fold: true,
+            collapse_whitespace: true,
+        }
+    }
+
+    /// Normalizes a tag. Returns `None` if the tag is empty after normalization.
+    #[must_use]
+    pub fn normalize(&self, tag: &str) -> Option<String> {
+        let tag = if self.collapse_whitespace {
+            tag.split_whitespace().collect::<Vec<_>>().join(" ")
+        } else {
+            String::from(tag)
+        };
+        let tag = if self.case_fold {
+            tag.to_lowercase()
+        } else {
+            tag
+        };
+        Some(tag).filter(|tag| !tag.is_empty())
+    }
+}
+
+/// Returns the distinct normalized tags of each item.
+fn item_tags(
+    map: &Map<String, Value>,
+    options: &TagOptions,
+) -> Result<Vec<BTreeSet<String>>, Error> {
+    FeedRef::from(map)
+        .items()?
+        .unwrap_or_default()
+        .iter()
+        .map(|item| {
+            Ok(item
+                .tags()?
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|tag| options.normalize(tag))
+                .collect())
+        })
+        .collect()
+}
+
+fn tag_frequencies(
+    map: &Map<String, Value>,
+    options: &TagOptions,
+) -> Result<BTreeMap<String, usize>, Error> {
+    let mut frequencies = BTreeMap::new();
+    for tags in item_tags(map, options)? {
+        for tag in tags {
+            *frequencies.entry(tag).or_insert(0) += 1;
+        }
+    }
+    Ok(frequencies)
+}
+
+fn tag_cooccurrence(
+    map: &Map<String, Value>,
+    options: &TagOptions,
+) -> Result<BTreeMap<(String, String), usize>, Error> {
+    let mut cooccurrence = BTreeMap::new();
+    for tags in item_tags(map, options)? {
+        let tags = tags.into_iter().collect::<Vec<_>>();
+        for (i, a) in tags.iter().enumerate() {
+            for b in &tags[i + 1..] {
+                *cooccurrence.entry((a.clone(), b.clone())).or_insert(0) += 1;
+            }
+        }
+    }
+    Ok(cooccurrence)
+}
+
+macro_rules! tag_methods {
+    () => {
+        /// Returns the number of items with each tag.
+        ///
+        /// A tag is counted at most once per item.
+        ///
+        /// # Errors
+        ///
+        /// If the items or an item's tags are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned.
+        pub fn tag_frequencies(
+            &self,
+            options: &TagOptions,
+        ) -> Result<BTreeMap<String, usize>, Error> {
+            tag_frequencies(self.as_map(), options)
+        }
+
+        /// Returns the number of items with each pair of tags.
+        ///
+        /// The tags in each pair are ordered so the first tag is less than the second tag.
+        ///
+        /// # Errors
+        ///
+        /// If the items or an item's tags are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned.
+        pub fn tag_cooccurrence(
+            &self,
+            options: &TagOptions,
+        ) -> Result<BTreeMap<(String, String), usize>, Error> {
+            tag_cooccurrence(self.as_map(), options)
+        }
+    };
+}
+
+impl Feed {
+    tag_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    tag_methods!();
+}
+
+impl<'a> FeedRef<'a> {
+    tag_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frequencies_and_cooccurrence() -> Result<(), Error> {
+        let feed = crate::from_value(serde_json::json!({
+            "items": [
+                { "id": "1", "tags": ["Rust", "rust", "Été"] },
+                { "id": "2", "tags": ["RUST ", "web"] },
+                { "id": "3", "tags": [" été", "  "] },
+                { "id": "4" }
+            ]
+        }))?;
+
+        let exact = feed.tag_frequencies(&TagOptions::new())?;
+        assert_eq!(exact.get("Rust"), Some(&1));
+        assert_eq!(exact.get("RUST "), Some(&1));
+
+        let normalized = feed.tag_frequencies(&TagOptions::normalized())?;
+        assert_eq!(normalized.len(), 3);
+        assert_eq!(normalized.get("rust"), Some(&2));
+        assert_eq!(normalized.get("été"), Some(&2));
+        assert_eq!(normalized.get("web"), Some(&1));
+
+        let pairs = feed.tag_cooccurrence(&TagOptions::normalized())?;
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(
+            pairs.get(&(String::from("rust"), String::from("web"))),
+            Some(&1)
+        );
+        assert_eq!(
+            pairs.get(&(String::from("rust"), String::from("été"))),
+            Some(&1)
+        );
+
+        Ok(())
+    }
+}
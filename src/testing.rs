@@ -0,0 +1,855 @@
+//! Helpers for testing code which builds feeds.
+//!
+//! The [`assert_feed_eq!`][crate::assert_feed_eq] macro compares a feed with expected JSON data
+//! and reports every difference with a [JSON Pointer][json_pointer] to the differing value.
+//! Object keys are compared regardless of their order.
+//!
+//! ```
+//! let mut feed = json_feed_model::Feed::new();
+//! feed.set_title("My Blog");
+//!
+//! json_feed_model::assert_feed_eq!(feed, serde_json::json!({ "title": "My Blog" }));
+//! ```
+//!
+//! [`sample_feed`] and [`sample_item`] generate deterministic, valid fixtures with a variety of
+//! optional properties, extensions, and attachments. [`mutate`] generates invalid variations of
+//! a feed for testing that code handles invalid data gracefully.
+//!
+//! [json_pointer]: https://tools.ietf.org/html/rfc6901
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{
+    collections::BTreeSet,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use std::{
+    collections::BTreeSet,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use core::fmt::Write;
+
+use serde_json::Value;
+
+use crate::{date::Timestamp, Attachment, Author, Feed, Item};
+
+/// A difference between actual and expected JSON data.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Difference {
+    /// An expected value is missing.
+    Missing {
+        /// A JSON Pointer to the value.
+        path: String,
+        /// The expected value.
+        expected: Value,
+    },
+    /// A value exists which was not expected.
+    Unexpected {
+        /// A JSON Pointer to the value.
+        path: String,
+        /// The actual value.
+        actual: Value,
+    },
+    /// A value is different than expected.
+    Changed {
+        /// A JSON Pointer to the value.
+        path: String,
+        /// The expected value.
+        expected: Value,
+        /// The actual value.
+        actual: Value,
+    },
+}
+
+impl Difference {
+    /// Returns a JSON Pointer to the differing value.
+    #[must_use]
+    pub fn path(&self) -> &str {
+        match self {
+            Difference::Missing { path, .. }
+            | Difference::Unexpected { path, .. }
+            | Difference::Changed { path, .. } => path,
+        }
+    }
+}
+
+impl core::fmt::Display for Difference {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Difference::Missing { path, expected } => {
+                write!(f, "{}: missing, expected {}", path, expected)
+            }
+            Difference::Unexpected { path, actual } => {
+                write!(f, "{}: unexpected {}", path, actual)
+            }
+            Difference::Changed {
+                path,
+                expected,
+                actual,
+            } => write!(f, "{}: expected {}, found {}", path, expected, actual),
+        }
+    }
+}
+
+fn push_token(path: &str, token: &str) -> String {
+    let mut path = String::from(path);
+    path.push('/');
+    for c in token.chars() {
+        match c {
+            '~' => path.push_str("~0"),
+            '/' => path.push_str("~1"),
+            c => path.push(c),
+        }
+    }
+    path
+}
+
+fn diff_into(path: &str, actual: &Value, expected: &Value, differences: &mut Vec<Difference>) {
+    match (actual, expected) {
+        (Value::Object(actual), Value::Object(expected)) => {
+            let keys = actual
+                .keys()
+                .chain(expected.keys())
+                .collect::<BTreeSet<_>>();
+            for key in keys {
+                let path = push_token(path, key);
+                match (actual.get(key), expected.get(key)) {
+                    (Some(actual), Some(expected)) => {
+                        diff_into(&path, actual, expected, differences);
+                    }
+                    (None, Some(expected)) => differences.push(Difference::Missing {
+                        path,
+                        expected: expected.clone(),
+                    }),
+                    (Some(actual), None) => differences.push(Difference::Unexpected {
+                        path,
+                        actual: actual.clone(),
+                    }),
+                    (None, None) => {}
+                }
+            }
+        }
+        (Value::Array(actual), Value::Array(expected)) => {
+            for index in 0..actual.len().max(expected.len()) {
+                let path = push_token(path, &index.to_string());
+                match (actual.get(index), expected.get(index)) {
+                    (Some(actual), Some(expected)) => {
+                        diff_into(&path, actual, expected, differences);
+                    }
+                    (None, Some(expected)) => differences.push(Difference::Missing {
+                        path,
+                        expected: expected.clone(),
+                    }),
+                    (Some(actual), None) => differences.push(Difference::Unexpected {
+                        path,
+                        actual: actual.clone(),
+                    }),
+                    (None, None) => {}
+                }
+            }
+        }
+        (actual, expected) if actual != expected => differences.push(Difference::Changed {
+            path: String::from(path),
+            expected: expected.clone(),
+            actual: actual.clone(),
+        }),
+        _ => {}
+    }
+}
+
+/// Returns the differences between actual and expected JSON data.
+///
+/// Differences are ordered by path with object keys sorted. An empty path refers to the whole
+/// value.
+#[must_use]
+pub fn diff(actual: &Value, expected: &Value) -> Vec<Difference> {
+    let mut differences = Vec::new();
+    diff_into("", actual, expected, &mut differences);
+    differences
+}
+
+/// Returns a report of the differences between actual and expected JSON data, or `None` if the
+/// data is equal.
+#[must_use]
+pub fn diff_report(actual: &Value, expected: &Value) -> Option<String> {
+    let differences = diff(actual, expected);
+    if differences.is_empty() {
+        return None;
+    }
+    let mut report = format!("feeds are not equal ({} differences):", differences.len());
+    for difference in differences {
+        let _ = write!(report, "\n  {}", difference);
+    }
+    Some(report)
+}
+
+/// Serializes a value for comparison.
+///
+/// # Panics
+///
+/// Panics if the value cannot be serialized to JSON.
+#[doc(hidden)]
+pub fn to_value<T: serde::Serialize + ?Sized>(value: &T) -> Value {
+    match serde_json::to_value(value) {
+        Ok(value) => value,
+        Err(error) => panic!("value cannot be serialized to JSON: {}", error),
+    }
+}
+
+/// Asserts that a feed (or another model type) is equal to expected JSON data.
+///
+/// Both arguments may be any serializable value, such as a `Feed`, an `ItemRef`, or a
+/// `serde_json::Value`. On failure, the panic message lists every difference with its path.
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+macro_rules! assert_feed_eq {
+    ($actual:expr, $expected:expr $(,)?) => {
+        if let Some(report) = $crate::testing::diff_report(
+            &$crate::testing::to_value(&$actual),
+            &$crate::testing::to_value(&$expected),
+        ) {
+            panic!("{}", report);
+        }
+    };
+}
+
+/// Compares a feed with a snapshot file.
+///
+/// If the file does not exist or the `JSON_FEED_MODEL_UPDATE_SNAPSHOTS` environment variable
+/// is set, the feed is written to the file as indented JSON. Otherwise, the file's JSON data is
+/// compared with the feed.
+///
+/// # Panics
+///
+/// Panics if the feed differs from the snapshot, or if the snapshot cannot be read, parsed, or
+/// written.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn assert_snapshot<T, P>(actual: &T, path: P)
+where
+    T: serde::Serialize + ?Sized,
+    P: AsRef<std::path::Path>,
+{
+    let path = path.as_ref();
+    let actual = to_value(actual);
+    if std::env::var_os("JSON_FEED_MODEL_UPDATE_SNAPSHOTS").is_some() || !path.exists() {
+        let json = match serde_json::to_vec_pretty(&actual) {
+            Ok(json) => json,
+            Err(error) => panic!("snapshot cannot be serialized: {}", error),
+        };
+        if let Err(error) = std::fs::write(path, json) {
+            panic!("snapshot {} cannot be written: {}", path.display(), error);
+        }
+        return;
+    }
+
+    let expected: Value = match std::fs::read(path)
+        .map_err(|error| error.to_string())
+        .and_then(|bytes| serde_json::from_slice(&bytes).map_err(|error| error.to_string()))
+    {
+        Ok(expected) => expected,
+        Err(error) => panic!("snapshot {} cannot be read: {}", path.display(), error),
+    };
+    if let Some(report) = diff_report(&actual, &expected) {
+        panic!(
+            "{}\nsnapshot: {}\nset JSON_FEED_MODEL_UPDATE_SNAPSHOTS=1 to update it",
+            report,
+            path.display()
+        );
+    }
+}
+
+/// Words used to generate titles, content, and tags.
+const WORDS: &[&str] = &[
+    "lorem",
+    "ipsum",
+    "dolor",
+    "sit",
+    "amet",
+    "consectetur",
+    "adipiscing",
+    "elit",
+    "sed",
+    "do",
+    "eiusmod",
+    "tempor",
+    "incididunt",
+    "labore",
+    "magna",
+    "aliqua",
+];
+
+const AUTHOR_NAMES: &[&str] = &["Alice", "Bob", "Carol", "Dave"];
+
+/// The earliest date of generated items (2020-01-01T00:00:00Z).
+const FIRST_DATE: i64 = 1_577_836_800;
+
+/// The range of generated item dates (3 years).
+const DATE_RANGE: u64 = 3 * 365 * 24 * 60 * 60;
+
+/// Returns the SplitMix64 finalizer of a value.
+///
+/// The function is a bijection, so different seeds always produce different values.
+fn mix(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// A deterministic pseudo-random number generator.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(1);
+        mix(self.0)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        // The modulo bias is irrelevant for fixtures.
+        #[allow(clippy::cast_possible_truncation)]
+        let value = (self.next() % n as u64) as usize;
+        value
+    }
+
+    fn chance(&mut self, percent: usize) -> bool {
+        self.below(100) < percent
+    }
+
+    fn words(&mut self, count: usize) -> String {
+        let mut text = String::new();
+        for index in 0..count {
+            if index > 0 {
+                text.push(' ');
+            }
+            text.push_str(WORDS[self.below(WORDS.len())]);
+        }
+        text
+    }
+}
+
+fn sample_author(rng: &mut Rng) -> Author {
+    let name = AUTHOR_NAMES[rng.below(AUTHOR_NAMES.len())];
+    let mut author = Author::new();
+    author.set_name(name);
+    if rng.chance(50) {
+        author.set_url(format!(
+            "https://example.org/authors/{}",
+            name.to_ascii_lowercase()
+        ));
+    }
+    if rng.chance(30) {
+        author.set_avatar(format!(
+            "https://example.org/avatars/{}.png",
+            name.to_ascii_lowercase()
+        ));
+    }
+    author
+}
+
+/// Returns a deterministic, valid item generated from a seed.
+///
+/// The same seed always produces the same item, and different seeds produce items with
+/// different ids. Optional properties, tags, authors, attachments, and an `_sample` extension
+/// are included depending on the seed.
+#[must_use]
+pub fn sample_item(seed: u64) -> Item {
+    let mut rng = Rng(mix(seed));
+    let id = format!("{:016x}", mix(seed));
+
+    let mut item = Item::new();
+    item.set_url(format!("https://example.org/posts/{}", id));
+    item.set_id(id);
+
+    let title_words = 2 + rng.below(5);
+    let title = rng.words(title_words);
+    let paragraph_words = 10 + rng.below(40);
+    let paragraph = rng.words(paragraph_words);
+    match rng.below(3) {
+        0 => {
+            item.set_content_text(paragraph);
+        }
+        1 => {
+            item.set_content_html(format!("<p>{}</p>", paragraph));
+        }
+        _ => {
+            item.set_content_html(format!("<p>{}</p>", paragraph));
+            item.set_content_text(paragraph);
+        }
+    }
+    if rng.chance(80) {
+        item.set_title(title);
+    }
+    if rng.chance(40) {
+        let summary_words = 5 + rng.below(10);
+        item.set_summary(rng.words(summary_words));
+    }
+
+    #[allow(clippy::cast_possible_wrap)]
+    let published = FIRST_DATE + (rng.next() % DATE_RANGE) as i64;
+    item.set_date_published(Timestamp::from_unix(published, 0).to_rfc3339());
+    if rng.chance(25) {
+        #[allow(clippy::cast_possible_wrap)]
+        let modified = published + (rng.next() % (30 * 24 * 60 * 60)) as i64;
+        item.set_date_modified(Timestamp::from_unix(modified, 0).to_rfc3339());
+    }
+
+    let tag_count = rng.below(4);
+    if tag_count > 0 {
+        let mut tags = Vec::new();
+        for _ in 0..tag_count {
+            let tag = String::from(WORDS[rng.below(WORDS.len())]);
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        item.set_tags(tags);
+    }
+    if rng.chance(50) {
+        let author = sample_author(&mut rng);
+        item.set_authors(core::iter::once(author));
+    }
+    if rng.chance(20) {
+        item.set_image(format!("https://example.org/images/{}.jpg", rng.below(100)));
+    }
+    if rng.chance(20) {
+        let mut attachment = Attachment::new();
+        attachment.set_url(format!("https://example.org/audio/{}.mp3", rng.below(100)));
+        attachment.set_mime_type("audio/mpeg");
+        attachment.set_size_in_bytes::<()>(1_000_000 + rng.next() % 50_000_000);
+        attachment.set_duration_in_seconds::<()>(60 + rng.next() % 3_600);
+        item.set_attachments(core::iter::once(attachment));
+    }
+    if rng.chance(15) {
+        item.as_map_mut()
+            .insert(String::from("_sample"), serde_json::json!({ "seed": seed }));
+    }
+    item
+}
+
+/// Returns a deterministic, valid version 1.1 feed generated from a seed.
+///
+/// The feed has `items` items generated with `sample_item`, sorted from newest to oldest by
+/// `date_published`.
+#[must_use]
+pub fn sample_feed(items: usize, seed: u64) -> Feed {
+    let mut rng = Rng(mix(seed) ^ 0x5eed);
+
+    let mut feed = Feed::new();
+    feed.set_version(crate::VERSION_1_1);
+    let title_words = 1 + rng.below(4);
+    feed.set_title(rng.words(title_words));
+    feed.set_home_page_url("https://example.org/");
+    feed.set_feed_url("https://example.org/feed.json");
+    if rng.chance(60) {
+        let description_words = 5 + rng.below(10);
+        feed.set_description(rng.words(description_words));
+    }
+    if rng.chance(50) {
+        feed.set_language("en-US");
+    }
+    if rng.chance(50) {
+        let author = sample_author(&mut rng);
+        feed.set_authors(core::iter::once(author));
+    }
+    if rng.chance(20) {
+        feed.set_icon("https://example.org/icon.png");
+    }
+
+    let mut generated = (0..items)
+        .map(|index| sample_item(mix(seed).wrapping_add(index as u64)))
+        .collect::<Vec<_>>();
+    generated.sort_by_cached_key(|item| {
+        core::cmp::Reverse(
+            item.date_published()
+                .ok()
+                .flatten()
+                .and_then(|date| Timestamp::parse(date).ok()),
+        )
+    });
+    feed.set_items(generated);
+    feed
+}
+
+/// A category of invalid data produced by [`mutate`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum MutationKind {
+    /// A property required by the spec is removed.
+    DropRequiredKey,
+    /// A property is replaced with a value of a different JSON type.
+    ChangeType,
+    /// A property is replaced with `null`.
+    InjectNull,
+    /// An item's `id` is replaced with the `id` of another item.
+    DuplicateId,
+    /// A string property is replaced with a very long string.
+    HugeString,
+}
+
+impl MutationKind {
+    /// Every kind of mutation in the order used by [`mutate`].
+    pub const ALL: &'static [MutationKind] = &[
+        MutationKind::DropRequiredKey,
+        MutationKind::ChangeType,
+        MutationKind::InjectNull,
+        MutationKind::DuplicateId,
+        MutationKind::HugeString,
+    ];
+
+    /// Returns a stable string identifier for the kind.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MutationKind::DropRequiredKey => "drop_required_key",
+            MutationKind::ChangeType => "change_type",
+            MutationKind::InjectNull => "inject_null",
+            MutationKind::DuplicateId => "duplicate_id",
+            MutationKind::HugeString => "huge_string",
+        }
+    }
+}
+
+impl core::fmt::Display for MutationKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A feed with a single mutation.
+#[derive(Debug, PartialEq)]
+#[non_exhaustive]
+pub struct MutationCase {
+    /// The kind of mutation.
+    pub kind: MutationKind,
+    /// A JSON Pointer to the mutated value.
+    pub path: String,
+    /// The mutated feed.
+    pub feed: Feed,
+}
+
+/// The length of the strings used for `MutationKind::HugeString` (1 MiB).
+const HUGE_STRING_LEN: usize = 1 << 20;
+
+/// A property of a spec-defined object.
+#[derive(Clone)]
+struct Member {
+    /// A JSON Pointer to the object.
+    parent: String,
+    key: String,
+    is_string: bool,
+}
+
+/// The locations in a feed which can be mutated.
+#[derive(Default)]
+struct Members {
+    /// The spec-defined properties.
+    all: Vec<Member>,
+    /// The properties required by the spec.
+    required: Vec<Member>,
+    /// The number of items.
+    items: usize,
+}
+
+impl Members {
+    fn collect(&mut self, map: &serde_json::Map<String, Value>, path: &str, required: &[&str]) {
+        for (key, value) in map {
+            if key.starts_with('_') {
+                continue;
+            }
+            let member = Member {
+                parent: String::from(path),
+                key: key.clone(),
+                is_string: value.is_string(),
+            };
+            if required.contains(&key.as_str()) {
+                self.required.push(member.clone());
+            }
+            self.all.push(member);
+
+            match (key.as_str(), value) {
+                ("author", Value::Object(author)) => {
+                    self.collect(author, &push_token(path, key), &[]);
+                }
+                ("authors" | "items" | "attachments" | "hubs", Value::Array(values)) => {
+                    let required: &[&str] = match key.as_str() {
+                        "items" => &["id"],
+                        "attachments" => &["url", "mime_type"],
+                        "hubs" => &["type", "url"],
+                        _ => &[],
+                    };
+                    let values_path = push_token(path, key);
+                    for (index, value) in values.iter().enumerate() {
+                        if let Value::Object(obj) = value {
+                            let item_path = push_token(&values_path, &index.to_string());
+                            self.collect(obj, &item_path, required);
+                            if key == "items" {
+                                self.collect_content(obj, &item_path);
+                            }
+                        }
+                    }
+                    if key == "items" && path.is_empty() {
+                        self.items = values.len();
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// An item requires either content property, so removing the only one is invalid.
+    fn collect_content(&mut self, item: &serde_json::Map<String, Value>, path: &str) {
+        let mut content = ["content_html", "content_text"]
+            .iter()
+            .filter(|key| item.contains_key(**key));
+        if let (Some(key), None) = (content.next(), content.next()) {
+            self.required.push(Member {
+                parent: String::from(path),
+                key: String::from(*key),
+                is_string: true,
+            });
+        }
+    }
+
+    fn candidates(&self, kind: MutationKind) -> Vec<&Member> {
+        match kind {
+            MutationKind::DropRequiredKey => self.required.iter().collect(),
+            MutationKind::ChangeType | MutationKind::InjectNull => self.all.iter().collect(),
+            MutationKind::HugeString => self.all.iter().filter(|m| m.is_string).collect(),
+            MutationKind::DuplicateId => Vec::new(),
+        }
+    }
+}
+
+fn object_mut<'a>(
+    map: &'a mut serde_json::Map<String, Value>,
+    path: &str,
+) -> Option<&'a mut serde_json::Map<String, Value>> {
+    if path.is_empty() {
+        return Some(map);
+    }
+    // The first token of the paths is never escaped.
+    let first_end = path[1..].find('/').map_or(path.len(), |i| i + 1);
+    map.get_mut(&path[1..first_end])?
+        .pointer_mut(&path[first_end..])?
+        .as_object_mut()
+}
+
+fn changed_type(value: &Value) -> Value {
+    match value {
+        Value::String(_) => Value::from(42),
+        Value::Number(_) => Value::from("42"),
+        Value::Bool(_) => Value::from("true"),
+        Value::Array(_) => Value::Object(serde_json::Map::new()),
+        Value::Object(_) => Value::Array(Vec::new()),
+        Value::Null => Value::Bool(false),
+    }
+}
+
+fn duplicate_id(map: &mut serde_json::Map<String, Value>, rng: &mut Rng) -> Option<String> {
+    let items = map.get_mut("items")?.as_array_mut()?;
+    let source = rng.below(items.len());
+    let source_item = items[source].clone();
+    let target = if items.len() == 1 {
+        items.push(source_item);
+        1
+    } else {
+        let target = (source + 1 + rng.below(items.len() - 1)) % items.len();
+        items[target]
+            .as_object_mut()?
+            .insert(String::from("id"), source_item.get("id")?.clone());
+        target
+    };
+    Some(format!("/items/{}/id", target))
+}
+
+fn apply_mutation(
+    feed: &Feed,
+    kind: MutationKind,
+    members: &Members,
+    rng: &mut Rng,
+) -> Option<MutationCase> {
+    let mut map = feed.as_map().clone();
+    let path = if kind == MutationKind::DuplicateId {
+        duplicate_id(&mut map, rng)?
+    } else {
+        let candidates = members.candidates(kind);
+        let member = candidates[rng.below(candidates.len())];
+        let parent = object_mut(&mut map, &member.parent)?;
+        if kind == MutationKind::DropRequiredKey {
+            parent.remove(&member.key);
+        } else {
+            let value = parent.get_mut(&member.key)?;
+            *value = match kind {
+                MutationKind::ChangeType => changed_type(value),
+                MutationKind::InjectNull => Value::Null,
+                _ => Value::String("x".repeat(HUGE_STRING_LEN)),
+            };
+        }
+        push_token(&member.parent, &member.key)
+    };
+
+    Some(MutationCase {
+        kind,
+        path,
+        feed: Feed::from(map),
+    })
+}
+
+/// Returns invalid variations of a valid feed for robustness testing.
+///
+/// Each case has a single mutation. The kinds of mutations are used in turn in the order of
+/// `MutationKind::ALL`, and the mutated locations are chosen pseudo-randomly from the
+/// properties defined by the spec. Extensions are not mutated. A kind is skipped if the feed
+/// has no location for it (for instance, `DuplicateId` for a feed without items). The same
+/// feed, `count`, and `seed` always produce the same cases.
+#[must_use]
+pub fn mutate(feed: &Feed, count: usize, seed: u64) -> Vec<MutationCase> {
+    let mut members = Members::default();
+    members.collect(feed.as_map(), "", &["version", "title", "items"]);
+    let kinds = MutationKind::ALL
+        .iter()
+        .copied()
+        .filter(|kind| match kind {
+            MutationKind::DuplicateId => members.items > 0,
+            _ => !members.candidates(*kind).is_empty(),
+        })
+        .collect::<Vec<_>>();
+    if kinds.is_empty() {
+        return Vec::new();
+    }
+
+    let mut rng = Rng(mix(seed) ^ 0x0bad);
+    (0..count)
+        .filter_map(|index| apply_mutation(feed, kinds[index % kinds.len()], &members, &mut rng))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples() {
+        let feed = sample_feed(50, 7);
+        assert!(feed.is_valid(&crate::Version::Version1_1));
+        assert_eq!(feed, sample_feed(50, 7));
+        assert_ne!(feed, sample_feed(50, 8));
+
+        let items = feed.items().unwrap().unwrap();
+        assert_eq!(items.len(), 50);
+        let ids = items
+            .iter()
+            .map(|item| item.id().unwrap())
+            .collect::<BTreeSet<_>>();
+        assert_eq!(ids.len(), 50);
+        assert!(items
+            .iter()
+            .any(|item| item.attachments().unwrap().is_some()));
+
+        assert_eq!(sample_item(3), sample_item(3));
+        assert!(sample_item(3).is_valid(&crate::Version::Version1_1));
+    }
+
+    #[test]
+    fn differences() {
+        let actual = serde_json::json!({
+            "title": "B",
+            "a/b": 1,
+            "items": [{ "id": "1" }, { "id": "2" }]
+        });
+        let expected = serde_json::json!({
+            "title": "A",
+            "items": [{ "id": "1", "url": "x" }],
+            "z~": true
+        });
+        assert_eq!(
+            diff_report(&actual, &expected).unwrap(),
+            "feeds are not equal (5 differences):\n  \
+             /a~1b: unexpected 1\n  \
+             /items/0/url: missing, expected \"x\"\n  \
+             /items/1: unexpected {\"id\":\"2\"}\n  \
+             /title: expected \"A\", found \"B\"\n  \
+             /z~0: missing, expected true"
+        );
+        assert_eq!(diff_report(&actual, &actual), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "/title: expected \"A\", found \"B\"")]
+    fn assert_macro() {
+        let mut feed = Feed::new();
+        feed.set_title("B");
+        crate::assert_feed_eq!(feed, serde_json::json!({ "title": "A" }));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn snapshots() {
+        let path = std::env::temp_dir().join(format!(
+            "json-feed-model-snapshot-{}.json",
+            std::process::id()
+        ));
+        let mut feed = Feed::new();
+        feed.set_title("A");
+        assert_snapshot(&feed, &path);
+        assert_snapshot(&feed, &path);
+
+        feed.set_title("B");
+        let result = std::panic::catch_unwind(|| assert_snapshot(&feed, &path));
+        std::fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn mutations() {
+        let feed = sample_feed(5, 1);
+        let cases = mutate(&feed, 20, 3);
+        assert_eq!(cases.len(), 20);
+        assert_eq!(cases, mutate(&feed, 20, 3));
+
+        for (index, case) in cases.iter().enumerate() {
+            assert_eq!(
+                case.kind,
+                MutationKind::ALL[index % MutationKind::ALL.len()]
+            );
+            match case.kind {
+                MutationKind::DropRequiredKey => {
+                    assert!(!case.feed.is_valid(&crate::Version::Version1_1));
+                    assert_eq!(case.feed.get_pointer(&case.path), None);
+                }
+                MutationKind::ChangeType => {
+                    assert!(!case.feed.is_valid(&crate::Version::Version1_1));
+                }
+                MutationKind::InjectNull => {
+                    assert!(!case.feed.is_valid(&crate::Version::Version1_1));
+                    assert_eq!(case.feed.get_pointer(&case.path), Some(&Value::Null));
+                }
+                MutationKind::DuplicateId => {
+                    let id = case.feed.get_pointer(&case.path).unwrap();
+                    let items = case.feed.get_pointer("/items").unwrap().as_array().unwrap();
+                    assert_eq!(
+                        items
+                            .iter()
+                            .filter(|item| item.get("id") == Some(id))
+                            .count(),
+                        2
+                    );
+                }
+                MutationKind::HugeString => {
+                    let value = case.feed.get_pointer(&case.path).unwrap();
+                    assert_eq!(value.as_str().unwrap().len(), HUGE_STRING_LEN);
+                }
+            }
+        }
+
+        assert!(mutate(&Feed::new(), 5, 0).is_empty());
+    }
+}
@@ -0,0 +1,222 @@
+//! Extraction and replacement of translatable text.
+//!
+//! Localization pipelines can send the extracted strings to translators and write the
+//! translations back without knowing the structure of a feed.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{collections::BTreeSet, format, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{collections::BTreeSet, format, string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{Error, Feed, FeedMut, FeedRef};
+
+const FEED_KEYS: &[&str] = &["title", "description"];
+
+const ITEM_KEYS: &[&str] = &["title", "summary", "content_text", "content_html"];
+
+fn push_strings(
+    map: &Map<String, Value>,
+    path: &str,
+    keys: &[&str],
+    strings: &mut Vec<(String, String)>,
+) -> Result<(), Error> {
+    for key in keys {
+        match map.get(*key) {
+            None => {}
+            Some(Value::String(text)) => strings.push((format!("{}/{}", path, key), text.clone())),
+            Some(_) => return Err(Error::UnexpectedType),
+        }
+    }
+    Ok(())
+}
+
+fn extract_translatable_strings(map: &Map<String, Value>) -> Result<Vec<(String, String)>, Error> {
+    let mut strings = Vec::new();
+    push_strings(map, "", FEED_KEYS, &mut strings)?;
+    for (item_index, item) in crate::item_refs(map)?.iter().enumerate() {
+        let path = format!("/items/{}", item_index);
+        push_strings(item.as_map(), &path, ITEM_KEYS, &mut strings)?;
+        for (tag_index, tag) in item.tags()?.unwrap_or_default().into_iter().enumerate() {
+            strings.push((format!("{}/tags/{}", path, tag_index), String::from(tag)));
+        }
+    }
+    Ok(strings)
+}
+
+fn apply_translations<I, K, V>(
+    map: &mut Map<String, Value>,
+    translations: I,
+) -> Result<usize, Error>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<str>,
+    V: Into<String>,
+{
+    let paths = extract_translatable_strings(map)?
+        .into_iter()
+        .map(|(path, _)| path)
+        .collect::<BTreeSet<_>>();
+
+    let mut feed = Value::Object(core::mem::take(map));
+    let mut applied = 0;
+    for (path, text) in translations {
+        if !paths.contains(path.as_ref()) {
+            continue;
+        }
+        if let Some(value) = feed.pointer_mut(path.as_ref()) {
+            *value = Value::String(text.into());
+            applied += 1;
+        }
+    }
+    if let Value::Object(feed) = feed {
+        *map = feed;
+    }
+    Ok(applied)
+}
+
+macro_rules! extract_methods {
+    () => {
+        /// Returns the text which should be translated with the [JSON Pointer][json_pointer] to
+        /// each string.
+        ///
+        /// The feed's `title` and `description` and the items' `title`, `summary`,
+        /// `content_text`, `content_html`, and `tags` are returned in document order. The text
+        /// in `content_html` is HTML.
+        ///
+        /// [json_pointer]: https://tools.ietf.org/html/rfc6901
+        ///
+        /// # Errors
+        ///
+        /// If the items or the text properties are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned.
+        pub fn extract_translatable_strings(&self) -> Result<Vec<(String, String)>, Error> {
+            extract_translatable_strings(self.as_map())
+        }
+    };
+}
+
+macro_rules! apply_methods {
+    () => {
+        /// Replaces translatable strings with their translations.
+        ///
+        /// The translations are pairs of a path returned by `extract_translatable_strings` and
+        /// the translated text. Paths which do not refer to a translatable string are ignored.
+        ///
+        /// Returns the number of strings which were replaced.
+        ///
+        /// # Errors
+        ///
+        /// If the items or the text properties are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned. The feed is not changed if an error is returned.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use std::collections::BTreeMap;
+        ///
+        /// let mut feed = json_feed_model::from_value(serde_json::json!({
+        ///     "title": "Hello",
+        ///     "items": [{ "id": "1", "content_text": "Good morning", "tags": ["news"] }]
+        /// }))?;
+        ///
+        /// let translations = feed
+        ///     .extract_translatable_strings()?
+        ///     .into_iter()
+        ///     .map(|(path, text)| {
+        ///         let translated = match text.as_str() {
+        ///             "Hello" => "Bonjour",
+        ///             "Good morning" => "Bon matin",
+        ///             _ => "Actualités",
+        ///         };
+        ///         (path, translated)
+        ///     })
+        ///     .collect::<BTreeMap<_, _>>();
+        ///
+        /// assert_eq!(feed.apply_translations(translations)?, 3);
+        /// assert_eq!(feed.title()?, Some("Bonjour"));
+        /// assert_eq!(feed.items()?.unwrap()[0].tags()?, Some(vec!["Actualités"]));
+        /// # Ok::<(), json_feed_model::Error>(())
+        /// ```
+        pub fn apply_translations<I, K, V>(&mut self, translations: I) -> Result<usize, Error>
+        where
+            I: IntoIterator<Item = (K, V)>,
+            K: AsRef<str>,
+            V: Into<String>,
+        {
+            apply_translations(self.as_map_mut(), translations)
+        }
+    };
+}
+
+impl Feed {
+    extract_methods!();
+    apply_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    extract_methods!();
+    apply_methods!();
+}
+
+impl<'a> FeedRef<'a> {
+    extract_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::{string::String, vec, vec::Vec};
+
+    #[test]
+    fn round_trip() -> Result<(), Error> {
+        let mut feed = crate::from_value(serde_json::json!({
+            "title": "Feed",
+            "home_page_url": "https://example.org/",
+            "items": [
+                { "id": "1", "title": "One", "content_html": "<p>One</p>", "tags": ["a", "b"] },
+                { "id": "2", "summary": "Two" }
+            ]
+        }))?;
+
+        let strings = feed.extract_translatable_strings()?;
+        let paths = strings
+            .iter()
+            .map(|(path, _)| path.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![
+                "/title",
+                "/items/0/title",
+                "/items/0/content_html",
+                "/items/0/tags/0",
+                "/items/0/tags/1",
+                "/items/1/summary"
+            ]
+        );
+
+        let mut translations = strings
+            .into_iter()
+            .map(|(path, text)| (path, text.to_uppercase()))
+            .collect::<Vec<_>>();
+        translations.push((String::from("/home_page_url"), String::from("x")));
+        translations.push((String::from("/items/1/title"), String::from("x")));
+        assert_eq!(feed.apply_translations(translations)?, 6);
+
+        assert_eq!(
+            feed,
+            crate::from_value(serde_json::json!({
+                "title": "FEED",
+                "home_page_url": "https://example.org/",
+                "items": [
+                    { "id": "1", "title": "ONE", "content_html": "<P>ONE</P>", "tags": ["A", "B"] },
+                    { "id": "2", "summary": "TWO" }
+                ]
+            }))?
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,245 @@
+//! Updates of individual items by id.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{
+    date::{Timestamp, ToTimestamp},
+    Error, Feed, FeedMut, Item, ItemMut, ItemRef,
+};
+
+fn items_mut(map: &mut Map<String, Value>) -> Result<&mut Vec<Value>, Error> {
+    match map
+        .entry("items")
+        .or_insert_with(|| Value::Array(Vec::new()))
+    {
+        Value::Array(items) => Ok(items),
+        _ => Err(Error::UnexpectedType),
+    }
+}
+
+/// Returns the index of the item with the `id`.
+fn position(items: &[Value], id: &str) -> Result<Option<usize>, Error> {
+    for (index, item) in items.iter().enumerate() {
+        let item = item.as_object().ok_or(Error::UnexpectedType)?;
+        if ItemRef::from(item).id()? == Some(id) {
+            return Ok(Some(index));
+        }
+    }
+    Ok(None)
+}
+
+fn update_item<F>(map: &mut Map<String, Value>, id: &str, f: F) -> Result<bool, Error>
+where
+    F: FnOnce(&mut ItemMut<'_>),
+{
+    if map.get("items").is_none() {
+        return Ok(false);
+    }
+    let items = items_mut(map)?;
+    match position(items, id)? {
+        Some(index) => {
+            if let Value::Object(item) = &mut items[index] {
+                f(&mut ItemMut::from(item));
+            }
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+fn upsert_item(
+    map: &mut Map<String, Value>,
+    mut item: Item,
+    date_modified: Option<Timestamp>,
+) -> Result<Option<Item>, Error> {
+    let _span = trace_span!("upsert_item");
+    let items = items_mut(map)?;
+    let index = match item.id()? {
+        Some(id) => position(items, id)?,
+        None => None,
+    };
+    match index {
+        Some(index) => {
+            if let Some(date_modified) = date_modified {
+                item.set_date_modified(date_modified.to_rfc3339());
+            }
+            trace_event!(index, "replaced item");
+            let previous = core::mem::replace(&mut items[index], Value::Object(item.into_inner()));
+            match previous {
+                Value::Object(previous) => Ok(Some(Item::from(previous))),
+                _ => Ok(None),
+            }
+        }
+        None => {
+            trace_event!(index = items.len(), "appended item");
+            items.push(Value::Object(item.into_inner()));
+            Ok(None)
+        }
+    }
+}
+
+macro_rules! update_methods {
+    () => {
+        /// Calls `f` with the item which has the `id`.
+        ///
+        /// Returns true if an item with the `id` was found.
+        ///
+        /// # Errors
+        ///
+        /// If the items or an item's `id` are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned.
+        pub fn update_item<F>(&mut self, id: &str, f: F) -> Result<bool, Error>
+        where
+            F: FnOnce(&mut ItemMut<'_>),
+        {
+            update_item(self.as_map_mut(), id, f)
+        }
+
+        /// Replaces the item with the same `id` or appends the item if there is no such item.
+        ///
+        /// The replaced item is returned. An item without an `id` is always appended. If there is
+        /// no `items` property, then an `items` array is added.
+        ///
+        /// # Errors
+        ///
+        /// If the items or an item's `id` are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned.
+        pub fn upsert_item(&mut self, item: Item) -> Result<Option<Item>, Error> {
+            upsert_item(self.as_map_mut(), item, None)
+        }
+
+        /// Replaces the item with the same `id` and sets its `date_modified` to `now`, or appends
+        /// the item unchanged if there is no such item.
+        ///
+        /// See `upsert_item()`.
+        ///
+        /// # Errors
+        ///
+        /// If the items or an item's `id` are not the expected JSON type, then
+        /// `Error::UnexpectedType` is returned.
+        ///
+        /// If `now` is not a valid date, then `Error::InvalidDate` is returned.
+        pub fn upsert_item_modified<T: ToTimestamp>(
+            &mut self,
+            item: Item,
+            now: T,
+        ) -> Result<Option<Item>, Error> {
+            let now = now.to_timestamp()?;
+            upsert_item(self.as_map_mut(), item, Some(now))
+        }
+    };
+}
+
+impl Feed {
+    update_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    update_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update_and_upsert() -> Result<(), Error> {
+        let mut feed = Feed::new();
+        assert!(!feed.update_item("1", |_| {})?);
+
+        let mut item = Item::new();
+        item.set_id("1");
+        item.set_title("First");
+        assert_eq!(feed.upsert_item(item)?, None);
+
+        assert!(feed.update_item("1", |item| {
+            item.set_title("Updated");
+        })?);
+        assert!(!feed.update_item("2", |_| {})?);
+
+        let mut item = Item::new();
+        item.set_id("1");
+        item.set_content_text("Replaced");
+        let previous = feed.upsert_item_modified(item, "2021-01-01T00:00:00Z")?;
+        assert_eq!(
+            previous
+                .map(Item::into_inner)
+                .and_then(|p| p.get("title").cloned()),
+            Some(Value::from("Updated"))
+        );
+
+        let items = feed.items()?.unwrap_or_default();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].title()?, None);
+        assert_eq!(items[0].date_modified()?, Some("2021-01-01T00:00:00Z"));
+
+        let mut item = Item::new();
+        item.set_id("2");
+        feed.upsert_item_modified(item, "2021-01-01T00:00:00Z")?;
+        let items = feed.items()?.unwrap_or_default();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1].date_modified()?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn missing_ids() -> Result<(), Error> {
+        let value = serde_json::json!({
+            "items": [{ "title": "No id" }, { "id": "1", "title": "First" }]
+        });
+        let mut feed = crate::from_value(value.clone())?;
+
+        let mut called = false;
+        assert!(!feed.update_item("2", |_| called = true)?);
+        assert!(!called);
+        assert!(!feed.update_item("", |_| called = true)?);
+        assert!(!called);
+        assert_eq!(feed, crate::from_value(value)?);
+
+        let mut item = Item::new();
+        item.set_title("Also no id");
+        assert_eq!(
+            feed.upsert_item_modified(item, "2021-01-01T00:00:00Z")?,
+            None
+        );
+        let items = feed.items()?.unwrap_or_default();
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[2].date_modified()?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn errors() -> Result<(), Error> {
+        let mut feed = crate::from_value(serde_json::json!({ "items": {} }))?;
+        assert!(matches!(
+            feed.update_item("1", |_| {}),
+            Err(Error::UnexpectedType)
+        ));
+        assert!(matches!(
+            feed.upsert_item(Item::new()),
+            Err(Error::UnexpectedType)
+        ));
+
+        let mut feed = crate::from_value(serde_json::json!({ "items": [{ "id": 1 }] }))?;
+        assert!(matches!(
+            feed.update_item("1", |_| {}),
+            Err(Error::UnexpectedType)
+        ));
+
+        let mut feed = Feed::new();
+        assert!(matches!(
+            feed.upsert_item_modified(Item::new(), "now"),
+            Err(Error::InvalidDate)
+        ));
+        assert_eq!(feed, Feed::new());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,308 @@
+//! URL helpers.
+//!
+//! The crate does not depend on a URL parsing library. The helpers only inspect the syntax
+//! needed by the model and lints.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::string::String;
+#[cfg(feature = "std")]
+use std::string::String;
+
+use core::fmt::Write;
+
+/// Returns the URL's scheme if the URL is absolute.
+///
+/// An absolute URL starts with a scheme as defined in [RFC 3986][rfc_3986] (a letter followed
+/// by letters, digits, `+`, `-`, or `.`) and a colon.
+///
+/// [rfc_3986]: https://tools.ietf.org/html/rfc3986#section-3.1
+pub(crate) fn scheme(url: &str) -> Option<&str> {
+    let colon = url.find(':')?;
+    let scheme = &url[..colon];
+    let mut chars = scheme.chars();
+    if !chars.next()?.is_ascii_alphabetic() {
+        return None;
+    }
+    if chars.all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.') {
+        Some(scheme)
+    } else {
+        None
+    }
+}
+
+/// Returns true if the URL has a scheme.
+pub(crate) fn is_absolute(url: &str) -> bool {
+    scheme(url).is_some()
+}
+
+/// Returns the host of an absolute URL without the user information and port.
+pub(crate) fn host(url: &str) -> Option<&str> {
+    let authority = Components::split(url).authority?;
+    let host = authority.rsplit('@').next().unwrap_or(authority);
+    let end = if host.starts_with('[') {
+        host.find(']').map_or(host.len(), |i| i + 1)
+    } else {
+        host.find(':').unwrap_or(host.len())
+    };
+    Some(&host[..end]).filter(|host| !host.is_empty())
+}
+
+/// Returns the extension of the last path segment without the query and fragment.
+pub(crate) fn path_extension(url: &str) -> Option<&str> {
+    let end = url.find(|c| c == '?' || c == '#').unwrap_or(url.len());
+    let path = &url[..end];
+    let path = match path.find("//") {
+        Some(authority_start) => {
+            let rest = &path[authority_start + 2..];
+            &rest[rest.find('/')?..]
+        }
+        None => path,
+    };
+    let segment = &path[path.rfind('/').map_or(0, |i| i + 1)..];
+    let dot = segment.rfind('.')?;
+    Some(&segment[dot + 1..]).filter(|ext| !ext.is_empty())
+}
+
+/// Appends a string percent-encoded for use as a path segment.
+///
+/// Characters other than the unreserved characters from [RFC 3986][rfc_3986] are encoded.
+///
+/// [rfc_3986]: https://tools.ietf.org/html/rfc3986#section-2.3
+pub(crate) fn encode_path_segment_into(segment: &str, out: &mut String) {
+    for b in segment.bytes() {
+        if b.is_ascii_alphanumeric() || b == b'-' || b == b'.' || b == b'_' || b == b'~' {
+            out.push(char::from(b));
+        } else {
+            let _ = write!(out, "%{:02X}", b);
+        }
+    }
+}
+
+/// The components of a URI reference as defined in [RFC 3986][rfc_3986].
+///
+/// [rfc_3986]: https://tools.ietf.org/html/rfc3986#appendix-B
+struct Components<'a> {
+    scheme: Option<&'a str>,
+    authority: Option<&'a str>,
+    path: &'a str,
+    query: Option<&'a str>,
+    fragment: Option<&'a str>,
+}
+
+impl<'a> Components<'a> {
+    fn split(url: &'a str) -> Self {
+        let (rest, fragment) = match url.find('#') {
+            Some(i) => (&url[..i], Some(&url[i + 1..])),
+            None => (url, None),
+        };
+        let (rest, query) = match rest.find('?') {
+            Some(i) => (&rest[..i], Some(&rest[i + 1..])),
+            None => (rest, None),
+        };
+        let scheme = scheme(rest);
+        let rest = scheme.map_or(rest, |scheme| &rest[scheme.len() + 1..]);
+        let (authority, path) = match rest.strip_prefix("//") {
+            Some(rest) => {
+                let end = rest.find('/').unwrap_or(rest.len());
+                (Some(&rest[..end]), &rest[end..])
+            }
+            None => (None, rest),
+        };
+        Self {
+            scheme,
+            authority,
+            path,
+            query,
+            fragment,
+        }
+    }
+}
+
+/// Removes `.` and `..` segments from a path.
+///
+/// See [RFC 3986][rfc_3986].
+///
+/// [rfc_3986]: https://tools.ietf.org/html/rfc3986#section-5.2.4
+fn remove_dot_segments(path: &str) -> String {
+    fn pop_segment(output: &mut String) {
+        let end = output.rfind('/').unwrap_or(0);
+        output.truncate(end);
+    }
+
+    let mut input = String::from(path);
+    let mut output = String::with_capacity(path.len());
+    while !input.is_empty() {
+        if input.starts_with("../") {
+            input.drain(..3);
+        } else if input.starts_with("./") || input.starts_with("/./") {
+            input.drain(..2);
+        } else if input == "/." {
+            input.truncate(1);
+        } else if input.starts_with("/../") {
+            input.drain(..3);
+            pop_segment(&mut output);
+        } else if input == "/.." {
+            input.truncate(1);
+            pop_segment(&mut output);
+        } else if input == "." || input == ".." {
+            input.clear();
+        } else {
+            let end = input.as_bytes()[1..]
+                .iter()
+                .position(|&b| b == b'/')
+                .map_or(input.len(), |i| i + 1);
+            output.push_str(&input[..end]);
+            input.drain(..end);
+        }
+    }
+    output
+}
+
+/// Resolves a URI reference against an absolute base URL.
+///
+/// See [RFC 3986][rfc_3986]. If the reference is absolute, then it is returned with its dot
+/// segments removed. If the reference is relative and the base is not absolute, then `None` is
+/// returned.
+///
+/// [rfc_3986]: https://tools.ietf.org/html/rfc3986#section-5.2
+pub(crate) fn resolve(base: &str, reference: &str) -> Option<String> {
+    let r = Components::split(reference);
+    let b = Components::split(base);
+
+    let (scheme, authority, path, query) = if let Some(scheme) = r.scheme {
+        (scheme, r.authority, remove_dot_segments(r.path), r.query)
+    } else {
+        let scheme = b.scheme?;
+        if r.authority.is_some() {
+            (scheme, r.authority, remove_dot_segments(r.path), r.query)
+        } else if r.path.is_empty() {
+            (
+                scheme,
+                b.authority,
+                String::from(b.path),
+                r.query.or(b.query),
+            )
+        } else if r.path.starts_with('/') {
+            (scheme, b.authority, remove_dot_segments(r.path), r.query)
+        } else {
+            let mut merged = String::with_capacity(b.path.len() + r.path.len() + 1);
+            if b.authority.is_some() && b.path.is_empty() {
+                merged.push('/');
+            } else {
+                merged.push_str(&b.path[..b.path.rfind('/').map_or(0, |i| i + 1)]);
+            }
+            merged.push_str(r.path);
+            (scheme, b.authority, remove_dot_segments(&merged), r.query)
+        }
+    };
+
+    let mut url = String::with_capacity(base.len() + reference.len());
+    url.push_str(scheme);
+    url.push(':');
+    if let Some(authority) = authority {
+        url.push_str("//");
+        url.push_str(authority);
+    }
+    url.push_str(&path);
+    if let Some(query) = query {
+        url.push('?');
+        url.push_str(query);
+    }
+    if let Some(fragment) = r.fragment {
+        url.push('#');
+        url.push_str(fragment);
+    }
+    Some(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn absolute_urls() {
+        assert_eq!(scheme("https://example.org/"), Some("https"));
+        assert_eq!(scheme("mailto:user@example.org"), Some("mailto"));
+        assert!(!is_absolute("/comments"));
+        assert!(!is_absolute("example.org/a:b"));
+        assert!(!is_absolute("1http://example.org"));
+    }
+
+    #[test]
+    fn hosts() {
+        assert_eq!(host("https://user@Example.org:8443/a"), Some("Example.org"));
+        assert_eq!(host("http://[::1]:80/"), Some("[::1]"));
+        assert_eq!(host("mailto:a@example.org"), None);
+        assert_eq!(host("https:///a"), None);
+    }
+
+    #[test]
+    fn path_extensions() {
+        assert_eq!(
+            path_extension("https://example.org/a/b.png?x=1.gif"),
+            Some("png")
+        );
+        assert_eq!(path_extension("https://example.org/avatar"), None);
+        assert_eq!(path_extension("https://example.org"), None);
+        assert_eq!(path_extension("https://example.org/a.b/"), None);
+        assert_eq!(path_extension("/images/me.JPG#top"), Some("JPG"));
+    }
+
+    #[test]
+    fn encode_path_segments() {
+        let mut out = String::new();
+        encode_path_segment_into("a b/c~é", &mut out);
+        assert_eq!(out, "a%20b%2Fc~%C3%A9");
+    }
+
+    #[test]
+    fn non_ascii_paths() {
+        assert_eq!(remove_dot_segments("é/./a"), "é/a");
+        assert_eq!(remove_dot_segments("😀"), "😀");
+        assert_eq!(
+            resolve("https://example.org/", "mailto:é@example.org").as_deref(),
+            Some("mailto:é@example.org")
+        );
+        assert_eq!(
+            resolve("https://example.org/", "urn:é").as_deref(),
+            Some("urn:é")
+        );
+        assert_eq!(
+            resolve("https://example.org/a/", "ß/../b").as_deref(),
+            Some("https://example.org/a/b")
+        );
+    }
+
+    #[test]
+    fn resolve_references() {
+        // Examples from RFC 3986 section 5.4.
+        let base = "http://a/b/c/d;p?q";
+        for (reference, expected) in &[
+            ("g:h", "g:h"),
+            ("g", "http://a/b/c/g"),
+            ("./g", "http://a/b/c/g"),
+            ("g/", "http://a/b/c/g/"),
+            ("/g", "http://a/g"),
+            ("//g", "http://g"),
+            ("?y", "http://a/b/c/d;p?y"),
+            ("g?y#s", "http://a/b/c/g?y#s"),
+            ("#s", "http://a/b/c/d;p?q#s"),
+            ("", "http://a/b/c/d;p?q"),
+            (".", "http://a/b/c/"),
+            ("..", "http://a/b/"),
+            ("../g", "http://a/b/g"),
+            ("../..", "http://a/"),
+            ("../../../g", "http://a/g"),
+            ("/./g", "http://a/g"),
+            ("g/../h", "http://a/b/c/h"),
+        ] {
+            assert_eq!(
+                resolve(base, reference).as_deref(),
+                Some(*expected),
+                "{}",
+                reference
+            );
+        }
+        assert_eq!(resolve("/relative", "g"), None);
+    }
+}
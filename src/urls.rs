@@ -0,0 +1,328 @@
+//! Every URL in a feed.
+//!
+//! Link checkers, proxies, and mirrors need the URLs of the feed, its authors and hubs, and its
+//! items and their attachments. [`Feed::urls`] lists them with their kind and location, and
+//! [`Feed::rewrite_urls`] replaces them.
+//!
+//! ```
+//! use json_feed_model::urls::UrlKind;
+//!
+//! let mut feed = json_feed_model::from_str(r#"{
+//!     "home_page_url": "http://example.org/",
+//!     "items": [{
+//!         "id": "1",
+//!         "url": "http://example.org/1",
+//!         "attachments": [{ "url": "http://cdn.example.org/1.mp3", "mime_type": "audio/mpeg" }]
+//!     }]
+//! }"#)?;
+//!
+//! let urls = feed.urls();
+//! assert_eq!(urls[2].path, "/items/0/attachments/0/url");
+//! assert_eq!(urls[2].url, "http://cdn.example.org/1.mp3");
+//! assert_eq!(urls[2].kind, UrlKind::Attachment);
+//!
+//! let rewritten = feed.rewrite_urls(|kind, url| match kind {
+//!     UrlKind::Attachment => None,
+//!     _ => url.strip_prefix("http:").map(|rest| format!("https:{}", rest)),
+//! });
+//! assert_eq!(rewritten, 2);
+//! assert_eq!(feed.home_page_url()?, Some("https://example.org/"));
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{format, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{format, string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{Feed, FeedMut, FeedRef};
+
+/// The property which contains a URL.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub enum UrlKind {
+    /// The feed's `home_page_url`.
+    HomePage,
+    /// The feed's `feed_url`.
+    Feed,
+    /// The feed's `next_url`.
+    Next,
+    /// The feed's `icon`.
+    Icon,
+    /// The feed's `favicon`.
+    Favicon,
+    /// An author's `url`.
+    Author,
+    /// An author's `avatar`.
+    Avatar,
+    /// A hub's `url`.
+    Hub,
+    /// An item's `url`.
+    Item,
+    /// An item's `external_url`.
+    External,
+    /// An item's `image`.
+    Image,
+    /// An item's `banner_image`.
+    BannerImage,
+    /// An attachment's `url`.
+    Attachment,
+    /// A string in an extension which looks like an absolute `http` or `https` URL.
+    Extension,
+}
+
+/// A URL in a feed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct FeedUrl<'a> {
+    /// The [JSON Pointer][json_pointer] to the URL.
+    ///
+    /// [json_pointer]: https://tools.ietf.org/html/rfc6901
+    pub path: String,
+    /// The URL.
+    pub url: &'a str,
+    /// The property which contains the URL.
+    pub kind: UrlKind,
+}
+
+const FEED_URL_KEYS: &[(&str, UrlKind)] = &[
+    ("home_page_url", UrlKind::HomePage),
+    ("feed_url", UrlKind::Feed),
+    ("next_url", UrlKind::Next),
+    ("icon", UrlKind::Icon),
+    ("favicon", UrlKind::Favicon),
+];
+
+const ITEM_URL_KEYS: &[(&str, UrlKind)] = &[
+    ("url", UrlKind::Item),
+    ("external_url", UrlKind::External),
+    ("image", UrlKind::Image),
+    ("banner_image", UrlKind::BannerImage),
+];
+
+const AUTHOR_URL_KEYS: &[(&str, UrlKind)] =
+    &[("url", UrlKind::Author), ("avatar", UrlKind::Avatar)];
+
+const HUB_URL_KEYS: &[(&str, UrlKind)] = &[("url", UrlKind::Hub)];
+
+const ATTACHMENT_URL_KEYS: &[(&str, UrlKind)] = &[("url", UrlKind::Attachment)];
+
+fn push_token(path: &str, token: &str) -> String {
+    format!("{}/{}", path, token.replace('~', "~0").replace('/', "~1"))
+}
+
+/// Returns true if an extension string is probably a URL.
+fn looks_like_url(value: &str) -> bool {
+    crate::url::scheme(value).map_or(false, |scheme| {
+        (scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("https"))
+            && value[scheme.len()..].starts_with("://")
+    })
+}
+
+fn extension_urls<'a>(value: &'a Value, path: String, urls: &mut Vec<FeedUrl<'a>>) {
+    match value {
+        Value::String(url) if looks_like_url(url) => urls.push(FeedUrl {
+            path,
+            url,
+            kind: UrlKind::Extension,
+        }),
+        Value::Array(values) => {
+            for (index, value) in values.iter().enumerate() {
+                extension_urls(value, format!("{}/{}", path, index), urls);
+            }
+        }
+        Value::Object(obj) => {
+            for (key, value) in obj {
+                extension_urls(value, push_token(&path, key), urls);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Adds the object's URLs and the URLs in its extensions.
+fn object_urls<'a>(
+    map: &'a Map<String, Value>,
+    path: &str,
+    keys: &[(&str, UrlKind)],
+    urls: &mut Vec<FeedUrl<'a>>,
+) {
+    for (key, kind) in keys {
+        if let Some(Value::String(url)) = map.get(*key) {
+            urls.push(FeedUrl {
+                path: push_token(path, key),
+                url,
+                kind: *kind,
+            });
+        }
+    }
+    for (key, value) in map {
+        if crate::is_extension_key(key) {
+            extension_urls(value, push_token(path, key), urls);
+        }
+    }
+}
+
+/// Adds the URLs of the objects in an object or array property.
+fn nested_urls<'a>(
+    map: &'a Map<String, Value>,
+    path: &str,
+    key: &str,
+    keys: &[(&str, UrlKind)],
+    urls: &mut Vec<FeedUrl<'a>>,
+) {
+    match map.get(key) {
+        Some(Value::Object(obj)) => object_urls(obj, &push_token(path, key), keys, urls),
+        Some(Value::Array(values)) => {
+            for (index, value) in values.iter().enumerate() {
+                if let Value::Object(obj) = value {
+                    let path = format!("{}/{}/{}", path, key, index);
+                    object_urls(obj, &path, keys, urls);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn urls(map: &Map<String, Value>) -> Vec<FeedUrl<'_>> {
+    let mut urls = Vec::new();
+    object_urls(map, "", FEED_URL_KEYS, &mut urls);
+    nested_urls(map, "", "author", AUTHOR_URL_KEYS, &mut urls);
+    nested_urls(map, "", "authors", AUTHOR_URL_KEYS, &mut urls);
+    nested_urls(map, "", "hubs", HUB_URL_KEYS, &mut urls);
+    if let Some(Value::Array(items)) = map.get("items") {
+        for (index, item) in items.iter().enumerate() {
+            if let Value::Object(item) = item {
+                let path = format!("/items/{}", index);
+                object_urls(item, &path, ITEM_URL_KEYS, &mut urls);
+                nested_urls(item, &path, "author", AUTHOR_URL_KEYS, &mut urls);
+                nested_urls(item, &path, "authors", AUTHOR_URL_KEYS, &mut urls);
+                nested_urls(item, &path, "attachments", ATTACHMENT_URL_KEYS, &mut urls);
+            }
+        }
+    }
+    urls
+}
+
+fn rewrite_urls<F>(map: &mut Map<String, Value>, mut f: F) -> usize
+where
+    F: FnMut(UrlKind, &str) -> Option<String>,
+{
+    let replacements = urls(map)
+        .into_iter()
+        .filter_map(|found| {
+            f(found.kind, found.url)
+                .filter(|url| url != found.url)
+                .map(|url| (found.path, url))
+        })
+        .collect::<Vec<_>>();
+    let mut rewritten = 0;
+    for (path, url) in replacements {
+        if let Some(value) = crate::pointer::get_pointer_mut(map, &path) {
+            *value = Value::String(url);
+            rewritten += 1;
+        }
+    }
+    rewritten
+}
+
+macro_rules! urls_methods {
+    () => {
+        /// Returns the URLs of the feed, its authors and hubs, and its items and their authors
+        /// and attachments.
+        ///
+        /// Strings in extensions which look like absolute `http` or `https` URLs are included.
+        /// The feed's URLs are listed first, followed by each item's URLs. Values which are not
+        /// the expected JSON type are skipped.
+        #[must_use]
+        pub fn urls(&self) -> Vec<FeedUrl<'_>> {
+            urls(self.as_map())
+        }
+    };
+}
+
+macro_rules! rewrite_urls_methods {
+    () => {
+        /// Replaces each URL listed by [`urls`][Self::urls] with the URL returned by `f`.
+        ///
+        /// `f` is called with the kind of the URL and the URL. If `f` returns `None`, the URL
+        /// is not changed. Returns the number of replaced URLs.
+        pub fn rewrite_urls<F>(&mut self, f: F) -> usize
+        where
+            F: FnMut(UrlKind, &str) -> Option<String>,
+        {
+            rewrite_urls(self.as_map_mut(), f)
+        }
+    };
+}
+
+impl Feed {
+    urls_methods!();
+    rewrite_urls_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    urls_methods!();
+    rewrite_urls_methods!();
+}
+
+impl<'a> FeedRef<'a> {
+    urls_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::{string::String, vec, vec::Vec};
+    use serde_json::json;
+
+    #[test]
+    fn all_urls() -> Result<(), crate::Error> {
+        let mut feed = crate::from_value(json!({
+            "feed_url": "https://example.org/feed.json",
+            "authors": [{ "name": "A", "avatar": "https://example.org/a.png" }],
+            "hubs": [{ "type": "WebSub", "url": "https://example.org/hub" }],
+            "_ext": { "links": ["https://example.org/x", "not a url", "mailto:a@example.org"] },
+            "items": [{
+                "id": "1",
+                "banner_image": "https://example.org/b.png",
+                "author": { "url": "https://example.org/author" },
+                "_a/b": "http://example.org/y"
+            }]
+        }))?;
+
+        assert_eq!(
+            feed.urls()
+                .iter()
+                .map(|found| (found.path.as_str(), found.kind))
+                .collect::<Vec<_>>(),
+            vec![
+                ("/feed_url", UrlKind::Feed),
+                ("/_ext/links/0", UrlKind::Extension),
+                ("/authors/0/avatar", UrlKind::Avatar),
+                ("/hubs/0/url", UrlKind::Hub),
+                ("/items/0/banner_image", UrlKind::BannerImage),
+                ("/items/0/_a~1b", UrlKind::Extension),
+                ("/items/0/author/url", UrlKind::Author),
+            ]
+        );
+
+        assert_eq!(
+            feed.rewrite_urls(|kind, url| match kind {
+                UrlKind::Extension => Some(url.replace("example.org", "mirror.example")),
+                _ => Some(String::from(url)),
+            }),
+            2
+        );
+        assert_eq!(feed.as_map()["items"][0]["_a/b"], "http://mirror.example/y");
+        assert_eq!(
+            feed.as_map()["_ext"]["links"][0],
+            "https://mirror.example/x"
+        );
+        Ok(())
+    }
+}
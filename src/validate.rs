@@ -0,0 +1,589 @@
+//! Validation of JSON data against the JSON Feed spec.
+//!
+//! The types and functions are re-exported from the crate root.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{collections::BTreeSet, string::String};
+#[cfg(feature = "std")]
+use std::{collections::BTreeSet, string::String};
+
+use serde_json::{Map, Value};
+
+use crate::{
+    cross_field, is_extension_key, limits, Attachment, AttachmentMut, AttachmentRef, Author,
+    AuthorMut, AuthorRef, Feed, FeedMut, FeedRef, Hub, HubMut, HubRef, Item, ItemMut, ItemRef,
+    Version,
+};
+
+/// Options which allow numbers and booleans written as JSON strings.
+///
+/// Some feeds contain values like `"size_in_bytes": "12345"` or `"expired": "true"`. By
+/// default, such values are invalid. The options are used by the `_with` getters (e.g.
+/// `size_in_bytes_with()`), by validation with `ValidationOptions::coercion`, and by
+/// `Feed::coerce()` which converts the values and records each conversion.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct CoercionOptions {
+    /// If true, strings of decimal digits (with optional surrounding whitespace) are accepted
+    /// as unsigned integers.
+    pub numeric_strings: bool,
+    /// If true, the strings `"true"` and `"false"` (case-insensitive, with optional surrounding
+    /// whitespace) are accepted as booleans.
+    pub boolean_strings: bool,
+}
+
+impl CoercionOptions {
+    /// Instantiates options which do not allow any coercion.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Instantiates options which allow every coercion.
+    #[must_use]
+    pub fn all() -> Self {
+        Self {
+            numeric_strings: true,
+            boolean_strings: true,
+        }
+    }
+}
+
+/// Checks of properties which must be consistent with each other.
+///
+/// The spec does not require the checks, so by default none are enabled. Each check only
+/// applies if the compared values are present and of the expected JSON type.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct CrossFieldRules {
+    /// If true, an item's `date_modified` must not be before its `date_published`.
+    pub date_modified_after_published: bool,
+    /// If true, the feed's `next_url` must be different from its `feed_url`.
+    pub next_url_differs_from_feed_url: bool,
+    /// If true, an expired feed must not have `hubs`.
+    pub expired_without_hubs: bool,
+    /// If true, an item's attachments with the same `title`, which are alternate
+    /// representations of the same resource, must have different MIME types.
+    pub distinct_alternate_mime_types: bool,
+}
+
+impl CrossFieldRules {
+    /// Instantiates rules without any checks.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Instantiates rules with every check.
+    #[must_use]
+    pub fn all() -> Self {
+        Self {
+            date_modified_after_published: true,
+            next_url_differs_from_feed_url: true,
+            expired_without_hubs: true,
+            distinct_alternate_mime_types: true,
+        }
+    }
+}
+
+/// Options which change how JSON data is validated.
+///
+/// The default options validate strictly according to the JSON Feed spec.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ValidationOptions {
+    /// If true, properties which are not defined by the spec and are not extensions (keys
+    /// starting with `_`) are allowed.
+    pub allow_unknown_keys: bool,
+    /// If true, an unknown `Version` is validated with the rules of the newest known version
+    /// instead of always being invalid.
+    ///
+    /// A feed which declares an unknown `version` is then valid if it complies with the newest
+    /// known version. Newer versions of the spec are expected to be backwards compatible. The
+    /// `UnknownVersion` lint reports feeds which declare an unknown version.
+    pub allow_unknown_version: bool,
+    /// The numbers and booleans written as JSON strings which are valid.
+    pub coercion: CoercionOptions,
+    /// The checks of properties which must be consistent with each other.
+    pub cross_field: CrossFieldRules,
+    /// The maximum sizes of the feed's structures.
+    pub limits: limits::StructuralLimits,
+}
+
+impl ValidationOptions {
+    /// Instantiates the default strict options.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the version whose rules are used to validate data for a requested `Version`.
+    fn rules_for<'a, 'b>(&self, version: &'a Version<'b>) -> Option<&'a Version<'b>> {
+        match version {
+            Version::Unknown(_) if self.allow_unknown_version => Some(&Version::Version1_1),
+            Version::Unknown(_) => None,
+            Version::Version1 | Version::Version1_1 => Some(version),
+        }
+    }
+}
+
+fn are_keys_valid<'a, I>(keys: I, valid_keys: &BTreeSet<&str>, options: &ValidationOptions) -> bool
+where
+    I: IntoIterator<Item = &'a String>,
+{
+    options.allow_unknown_keys
+        || keys
+            .into_iter()
+            .all(|k| valid_keys.contains(k.as_str()) || is_extension_key(k))
+}
+
+fn is_valid_attachment(
+    map: &Map<String, Value>,
+    version: &Version<'_>,
+    options: &ValidationOptions,
+) -> bool {
+    if options.rules_for(version).is_none() {
+        return false;
+    }
+    let attachment_ref = AttachmentRef::from(map);
+    let mut valid_keys = BTreeSet::new();
+    valid_keys.insert("url");
+    valid_keys.insert("mime_type");
+    valid_keys.insert("title");
+    valid_keys.insert("size_in_bytes");
+    valid_keys.insert("duration_in_seconds");
+
+    attachment_ref.url().map_or(false, |url| url.is_some())
+        && attachment_ref
+            .mime_type()
+            .map_or(false, |mime_type| mime_type.is_some())
+        && attachment_ref.title().is_ok()
+        && attachment_ref.size_in_bytes_with(&options.coercion).is_ok()
+        && attachment_ref
+            .duration_in_seconds_with(&options.coercion)
+            .is_ok()
+        && are_keys_valid(map.keys(), &valid_keys, options)
+}
+
+impl Attachment {
+    /// Verifies if the JSON data complies with a specific `Version` of the JSON Feed spec.
+    #[must_use]
+    pub fn is_valid(&self, version: &Version<'_>) -> bool {
+        is_valid_attachment(&self.value, version, &ValidationOptions::new())
+    }
+}
+
+impl<'a> AttachmentMut<'a> {
+    /// Verifies if the JSON data complies with a specific `Version` of the JSON Feed spec.
+    #[must_use]
+    pub fn is_valid(&self, version: &Version<'_>) -> bool {
+        is_valid_attachment(self.value, version, &ValidationOptions::new())
+    }
+}
+
+impl<'a> AttachmentRef<'a> {
+    /// Verifies if the JSON data complies with a specific `Version` of the JSON Feed spec.
+    #[must_use]
+    pub fn is_valid(&self, version: &Version<'_>) -> bool {
+        is_valid_attachment(self.value, version, &ValidationOptions::new())
+    }
+}
+
+fn is_valid_author(
+    map: &Map<String, Value>,
+    version: &Version<'_>,
+    options: &ValidationOptions,
+) -> bool {
+    if options.rules_for(version).is_none() {
+        return false;
+    }
+    let author_ref = AuthorRef::from(map);
+    let mut valid_keys = BTreeSet::new();
+    valid_keys.insert("name");
+    valid_keys.insert("avatar");
+    valid_keys.insert("url");
+
+    let name_result = author_ref.name();
+    let avatar_result = author_ref.avatar();
+    let url_result = author_ref.url();
+
+    name_result.is_ok()
+        && avatar_result.is_ok()
+        && url_result.is_ok()
+        && (name_result.map_or(false, |name| name.is_some())
+            || avatar_result.map_or(false, |avatar| avatar.is_some())
+            || url_result.map_or(false, |url| url.is_some()))
+        && are_keys_valid(map.keys(), &valid_keys, options)
+}
+
+impl Author {
+    /// Verifies if the JSON data complies with a specific `Version` of the JSON Feed spec.
+    #[must_use]
+    pub fn is_valid(&self, version: &Version<'_>) -> bool {
+        is_valid_author(&self.value, version, &ValidationOptions::new())
+    }
+}
+
+impl<'a> AuthorMut<'a> {
+    /// Verifies if the JSON data complies with a specific `Version` of the JSON Feed spec.
+    #[must_use]
+    pub fn is_valid(&self, version: &Version<'_>) -> bool {
+        is_valid_author(self.value, version, &ValidationOptions::new())
+    }
+}
+
+impl<'a> AuthorRef<'a> {
+    /// Verifies if the JSON data complies with a specific `Version` of the JSON Feed spec.
+    #[must_use]
+    pub fn is_valid(&self, version: &Version<'_>) -> bool {
+        is_valid_author(self.value, version, &ValidationOptions::new())
+    }
+}
+
+fn is_valid_feed(
+    map: &Map<String, Value>,
+    version: &Version<'_>,
+    options: &ValidationOptions,
+) -> bool {
+    let version = match options.rules_for(version) {
+        Some(version) => version,
+        None => return false,
+    };
+    let feed_ref = FeedRef::from(map);
+    let mut valid_keys = BTreeSet::new();
+    valid_keys.insert("version");
+    valid_keys.insert("title");
+    valid_keys.insert("home_page_url");
+    valid_keys.insert("feed_url");
+    valid_keys.insert("description");
+    valid_keys.insert("user_comment");
+    valid_keys.insert("next_url");
+    valid_keys.insert("icon");
+    valid_keys.insert("favicon");
+    valid_keys.insert("author");
+    match version {
+        Version::Version1_1 => {
+            valid_keys.insert("authors");
+            valid_keys.insert("language");
+        }
+        Version::Version1 | Version::Unknown(_) => {}
+    }
+    valid_keys.insert("expired");
+    valid_keys.insert("hubs");
+    valid_keys.insert("items");
+
+    let _span = trace_span!("validate_feed", version = %version);
+    let valid = feed_ref.version().map_or(false, |v| {
+        v.map_or(false, |v| match Version::from(v) {
+            Version::Unknown(_) => {
+                options.allow_unknown_version && matches!(version, Version::Version1_1)
+            }
+            Version::Version1 => match version {
+                Version::Version1 | Version::Version1_1 => true,
+                Version::Unknown(_) => false,
+            },
+            Version::Version1_1 => match version {
+                Version::Version1 | Version::Unknown(_) => false,
+                Version::Version1_1 => true,
+            },
+        })
+    }) && feed_ref
+        .title()
+        .map_or_else(|_| false, |title| title.is_some())
+        && feed_ref.items().map_or(false, |items| {
+            items.map_or(false, |items| {
+                items
+                    .iter()
+                    .all(|item| is_valid_item(item.value, version, options))
+            })
+        })
+        && feed_ref.hubs().map_or(false, |hubs| {
+            hubs.map_or(true, |hubs| {
+                hubs.iter()
+                    .all(|hub| is_valid_hub(hub.value, version, options))
+            })
+        })
+        && feed_ref.home_page_url().is_ok()
+        && feed_ref.feed_url().is_ok()
+        && feed_ref.description().is_ok()
+        && feed_ref.user_comment().is_ok()
+        && feed_ref.next_url().is_ok()
+        && feed_ref.icon().is_ok()
+        && feed_ref.favicon().is_ok()
+        && feed_ref.author().is_ok()
+        && feed_ref.authors().is_ok()
+        && feed_ref.language().is_ok()
+        && feed_ref.expired_with(&options.coercion).is_ok()
+        && are_keys_valid(map.keys(), &valid_keys, options)
+        && cross_field::is_consistent_feed(map, options)
+        && limits::is_within_feed_limits(map, &options.limits);
+    trace_event!(
+        valid,
+        first_invalid_path = if valid {
+            None
+        } else {
+            Some(first_invalid_path(map, version, options))
+        }
+        .as_deref(),
+        "validated feed"
+    );
+    valid
+}
+
+/// Returns a JSON Pointer to the first invalid item or hub of an invalid feed, or the root if
+/// neither is invalid.
+#[cfg(feature = "tracing")]
+fn first_invalid_path(
+    map: &Map<String, Value>,
+    version: &Version<'_>,
+    options: &ValidationOptions,
+) -> String {
+    use core::fmt::Write;
+
+    for key in &["items", "hubs"] {
+        if let Some(Value::Array(values)) = map.get(*key) {
+            for (index, value) in values.iter().enumerate() {
+                let is_valid = match value {
+                    Value::Object(obj) if *key == "items" => is_valid_item(obj, version, options),
+                    Value::Object(obj) => is_valid_hub(obj, version, options),
+                    _ => false,
+                };
+                if !is_valid {
+                    let mut path = String::new();
+                    let _ = write!(path, "/{}/{}", key, index);
+                    return path;
+                }
+            }
+        }
+    }
+    String::new()
+}
+
+impl Feed {
+    /// Verifies if the JSON data complies with a specific `Version` of the JSON Feed spec.
+    #[must_use]
+    pub fn is_valid(&self, version: &Version<'_>) -> bool {
+        is_valid_feed(&self.value, version, &ValidationOptions::new())
+    }
+}
+
+impl<'a> FeedMut<'a> {
+    /// Verifies if the JSON data complies with a specific `Version` of the JSON Feed spec.
+    #[must_use]
+    pub fn is_valid(&self, version: &Version<'_>) -> bool {
+        is_valid_feed(self.value, version, &ValidationOptions::new())
+    }
+}
+
+impl<'a> FeedRef<'a> {
+    /// Verifies if the JSON data complies with a specific `Version` of the JSON Feed spec.
+    #[must_use]
+    pub fn is_valid(&self, version: &Version<'_>) -> bool {
+        is_valid_feed(self.value, version, &ValidationOptions::new())
+    }
+}
+
+fn is_valid_hub(
+    map: &Map<String, Value>,
+    version: &Version<'_>,
+    options: &ValidationOptions,
+) -> bool {
+    if options.rules_for(version).is_none() {
+        return false;
+    }
+    let hub_ref = HubRef::from(map);
+    let mut valid_keys = BTreeSet::new();
+    valid_keys.insert("type");
+    valid_keys.insert("url");
+
+    hub_ref.url().map_or(false, |url| url.is_some())
+        && hub_ref
+            .hub_type()
+            .map_or(false, |hub_type| hub_type.is_some())
+        && are_keys_valid(map.keys(), &valid_keys, options)
+}
+
+impl Hub {
+    /// Verifies if the JSON data complies with a specific `Version` of the JSON Feed spec.
+    #[must_use]
+    pub fn is_valid(&self, version: &Version<'_>) -> bool {
+        is_valid_hub(&self.value, version, &ValidationOptions::new())
+    }
+}
+
+impl<'a> HubMut<'a> {
+    /// Verifies if the JSON data complies with a specific `Version` of the JSON Feed spec.
+    #[must_use]
+    pub fn is_valid(&self, version: &Version<'_>) -> bool {
+        is_valid_hub(self.value, version, &ValidationOptions::new())
+    }
+}
+
+impl<'a> HubRef<'a> {
+    /// Verifies if the JSON data complies with a specific `Version` of the JSON Feed spec.
+    #[must_use]
+    pub fn is_valid(&self, version: &Version<'_>) -> bool {
+        is_valid_hub(self.value, version, &ValidationOptions::new())
+    }
+}
+
+fn is_valid_item(
+    map: &Map<String, Value>,
+    version: &Version<'_>,
+    options: &ValidationOptions,
+) -> bool {
+    let version = match options.rules_for(version) {
+        Some(version) => version,
+        None => return false,
+    };
+    let item_ref = ItemRef::from(map);
+    let mut valid_keys = BTreeSet::new();
+    valid_keys.insert("id");
+    valid_keys.insert("url");
+    valid_keys.insert("external_url");
+    valid_keys.insert("title");
+    valid_keys.insert("content_html");
+    valid_keys.insert("content_text");
+    valid_keys.insert("summary");
+    valid_keys.insert("image");
+    valid_keys.insert("banner_image");
+    valid_keys.insert("date_published");
+    valid_keys.insert("date_modified");
+    valid_keys.insert("author");
+    match version {
+        Version::Version1_1 => {
+            valid_keys.insert("authors");
+            valid_keys.insert("language");
+        }
+        Version::Version1 | Version::Unknown(_) => {}
+    }
+    valid_keys.insert("tags");
+    valid_keys.insert("attachments");
+
+    let content_html_result = item_ref.content_html();
+    let content_text_result = item_ref.content_text();
+
+    item_ref.id().map_or(false, |id| id.is_some())
+        && item_ref.authors().map_or(false, |authors| {
+            authors.map_or(true, |authors| {
+                authors
+                    .iter()
+                    .all(|author| is_valid_author(author.value, version, options))
+            })
+        })
+        && item_ref.attachments().map_or(false, |attachments| {
+            attachments.map_or(true, |attachments| {
+                attachments
+                    .iter()
+                    .all(|attachment| is_valid_attachment(attachment.value, version, options))
+            })
+        })
+        && item_ref.id().is_ok()
+        && item_ref.url().is_ok()
+        && item_ref.external_url().is_ok()
+        && item_ref.title().is_ok()
+        && content_html_result.is_ok()
+        && content_text_result.is_ok()
+        && (content_text_result.map_or(false, |content| content.is_some())
+            || content_html_result.map_or(false, |content| content.is_some()))
+        && item_ref.summary().is_ok()
+        && item_ref.image().is_ok()
+        && item_ref.banner_image().is_ok()
+        && item_ref.date_published().is_ok()
+        && item_ref.date_modified().is_ok()
+        && item_ref.author().is_ok()
+        && item_ref.tags().is_ok()
+        && item_ref.language().is_ok()
+        && are_keys_valid(map.keys(), &valid_keys, options)
+        && cross_field::is_consistent_item(map, &options.cross_field)
+        && limits::is_within_item_limits(map, &options.limits)
+}
+
+impl Item {
+    /// Verifies if the JSON data complies with a specific `Version` of the JSON Feed spec.
+    #[must_use]
+    pub fn is_valid(&self, version: &Version<'_>) -> bool {
+        is_valid_item(&self.value, version, &ValidationOptions::new())
+    }
+}
+
+impl<'a> ItemMut<'a> {
+    /// Verifies if the JSON data complies with a specific `Version` of the JSON Feed spec.
+    #[must_use]
+    pub fn is_valid(&self, version: &Version<'_>) -> bool {
+        is_valid_item(self.value, version, &ValidationOptions::new())
+    }
+}
+
+impl<'a> ItemRef<'a> {
+    /// Verifies if the JSON data complies with a specific `Version` of the JSON Feed spec.
+    #[must_use]
+    pub fn is_valid(&self, version: &Version<'_>) -> bool {
+        is_valid_item(self.value, version, &ValidationOptions::new())
+    }
+}
+
+/// Verifies if a JSON object is a `Feed` which complies with a specific `Version` of the JSON
+/// Feed spec.
+///
+/// The same validation as `Feed::is_valid()` is done without wrapping the `Map`.
+#[must_use]
+pub fn validate_feed_map(
+    map: &Map<String, Value>,
+    version: &Version<'_>,
+    options: &ValidationOptions,
+) -> bool {
+    is_valid_feed(map, version, options)
+}
+
+/// Verifies if a JSON object is an `Item` which complies with a specific `Version` of the JSON
+/// Feed spec.
+///
+/// The same validation as `Item::is_valid()` is done without wrapping the `Map`.
+#[must_use]
+pub fn validate_item_map(
+    map: &Map<String, Value>,
+    version: &Version<'_>,
+    options: &ValidationOptions,
+) -> bool {
+    is_valid_item(map, version, options)
+}
+
+/// Verifies if a JSON object is an `Author` which complies with a specific `Version` of the JSON
+/// Feed spec.
+///
+/// The same validation as `Author::is_valid()` is done without wrapping the `Map`.
+#[must_use]
+pub fn validate_author_map(
+    map: &Map<String, Value>,
+    version: &Version<'_>,
+    options: &ValidationOptions,
+) -> bool {
+    is_valid_author(map, version, options)
+}
+
+/// Verifies if a JSON object is an `Attachment` which complies with a specific `Version` of the JSON
+/// Feed spec.
+///
+/// The same validation as `Attachment::is_valid()` is done without wrapping the `Map`.
+#[must_use]
+pub fn validate_attachment_map(
+    map: &Map<String, Value>,
+    version: &Version<'_>,
+    options: &ValidationOptions,
+) -> bool {
+    is_valid_attachment(map, version, options)
+}
+
+/// Verifies if a JSON object is a `Hub` which complies with a specific `Version` of the JSON
+/// Feed spec.
+///
+/// The same validation as `Hub::is_valid()` is done without wrapping the `Map`.
+#[must_use]
+pub fn validate_hub_map(
+    map: &Map<String, Value>,
+    version: &Version<'_>,
+    options: &ValidationOptions,
+) -> bool {
+    is_valid_hub(map, version, options)
+}
@@ -0,0 +1,414 @@
+//! Verification of authors' claims to a feed.
+//!
+//! An author's `url` is only a claim. If the page at the URL links back to the feed's
+//! `home_page_url` or `feed_url` with a `rel="me"` link, the author very likely controls both,
+//! and readers can show the author as trusted. The crate does not fetch pages, so an
+//! [`AuthorVerifier`] supplies the results. [`RelMeVerifier`] checks pages which are fetched by a
+//! function supplied by the caller.
+//!
+//! [`Feed::verify_authors`] records each result in the author's `_verified` extension.
+//!
+//! ```
+//! use json_feed_model::verify::{AuthorPage, RelMeVerifier};
+//!
+//! let mut feed = json_feed_model::from_str(r#"{
+//!     "home_page_url": "https://blog.example.org/",
+//!     "authors": [{ "name": "A", "url": "https://a.example.com/" }],
+//!     "items": [{ "id": "1", "author": { "name": "B", "url": "https://b.example.com/" } }]
+//! }"#)?;
+//!
+//! let mut verifier = RelMeVerifier::new(|url: &str| match url {
+//!     "https://a.example.com/" => Some(AuthorPage::new(
+//!         url,
+//!         r#"<p>I write at <a rel="me" href="https://blog.example.org">my blog</a>.</p>"#,
+//!     )),
+//!     "https://b.example.com/" => Some(AuthorPage::new(url, "<p>Hello</p>")),
+//!     _ => None,
+//! });
+//!
+//! let results = feed.verify_authors(&mut verifier)?;
+//! assert_eq!(results.len(), 2);
+//! assert_eq!(
+//!     feed.as_map()["authors"][0]["_verified"],
+//!     serde_json::json!({ "url": "https://a.example.com/", "verified": true })
+//! );
+//! assert_eq!(feed.as_map()["items"][0]["author"]["_verified"]["verified"], false);
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{collections::BTreeMap, format, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{collections::BTreeMap, format, string::String, vec::Vec};
+
+use serde_json::{Map, Value};
+
+use crate::{
+    html::{self, Token},
+    subscriptions::feed_url_equivalent,
+    Error, Feed, FeedMut, FeedRef,
+};
+
+/// The key of the extension which records the result of verifying an author.
+pub const VERIFIED_KEY: &str = "_verified";
+
+/// A page fetched from an author's URL.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct AuthorPage {
+    /// The URL of the page after any redirects. Relative links are resolved against the URL.
+    pub url: String,
+    /// The HTML of the page.
+    pub html: String,
+    /// The values of the response's `Link` headers.
+    pub link_headers: Vec<String>,
+}
+
+fn is_rel_me(rel: &str) -> bool {
+    rel.split_ascii_whitespace()
+        .any(|rel| rel.eq_ignore_ascii_case("me"))
+}
+
+/// Returns the URLs in a `Link` header value which have a `me` relation.
+fn rel_me_header_links(header: &str) -> Vec<&str> {
+    let mut links = Vec::new();
+    let mut rest = header;
+    while let Some(start) = rest.find('<') {
+        let end = match rest[start..].find('>') {
+            Some(end) => start + end,
+            None => break,
+        };
+        let url = rest[start + 1..end].trim();
+        rest = &rest[end + 1..];
+        let params_end = rest.find('<').unwrap_or(rest.len());
+        let params = rest[..params_end].trim_end().trim_end_matches(',');
+        let is_me = params.split(';').any(|param| {
+            let mut parts = param.splitn(2, '=');
+            parts.next().map(str::trim) == Some("rel")
+                && parts
+                    .next()
+                    .map_or(false, |rel| is_rel_me(rel.trim().trim_matches('"')))
+        });
+        if is_me {
+            links.push(url);
+        }
+        rest = &rest[params_end..];
+    }
+    links
+}
+
+impl AuthorPage {
+    /// Instantiates a page with its URL and HTML and without headers.
+    #[must_use]
+    pub fn new<T, U>(url: T, html: U) -> Self
+    where
+        T: Into<String>,
+        U: Into<String>,
+    {
+        Self {
+            url: url.into(),
+            html: html.into(),
+            link_headers: Vec::new(),
+        }
+    }
+
+    /// Returns the `rel="me"` links in the HTML's `a` and `link` elements and in the `Link`
+    /// headers.
+    ///
+    /// Relative links are resolved against the page's URL.
+    #[must_use]
+    pub fn rel_me_links(&self) -> Vec<String> {
+        let resolve =
+            |href: &str| crate::url::resolve(&self.url, href).unwrap_or_else(|| String::from(href));
+        let mut links = Vec::new();
+        for token in html::tokens(&self.html) {
+            if let Token::Tag(tag) = token {
+                if tag.is_closing || !(tag.is("a") || tag.is("link")) {
+                    continue;
+                }
+                if let (Some(rel), Some(href)) = (tag.attr("rel"), tag.attr("href")) {
+                    if is_rel_me(rel) {
+                        let mut decoded = String::new();
+                        html::decode_entities_into(href.trim(), &mut decoded);
+                        links.push(resolve(&decoded));
+                    }
+                }
+            }
+        }
+        for header in &self.link_headers {
+            links.extend(rel_me_header_links(header).into_iter().map(resolve));
+        }
+        links
+    }
+
+    /// Returns true if a `rel="me"` link is equivalent to one of the URLs.
+    ///
+    /// URLs are compared with `subscriptions::feed_url_equivalent`.
+    #[must_use]
+    pub fn claims(&self, urls: &[&str]) -> bool {
+        self.rel_me_links()
+            .iter()
+            .any(|link| urls.iter().any(|url| feed_url_equivalent(link, url)))
+    }
+}
+
+/// The result of verifying an author.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Verification {
+    /// The author's URL claims the feed.
+    Verified,
+    /// The author's URL does not claim the feed.
+    Unverified,
+    /// The author's URL could not be checked, for instance because the page could not be
+    /// fetched.
+    Unknown,
+}
+
+/// Checks if an author's URL claims a feed.
+pub trait AuthorVerifier {
+    /// Verifies that the page at `author_url` claims one of the feed's URLs.
+    ///
+    /// `feed_urls` contains the feed's `home_page_url` and `feed_url` which are set.
+    fn verify(&mut self, author_url: &str, feed_urls: &[&str]) -> Verification;
+}
+
+impl<T> AuthorVerifier for &mut T
+where
+    T: AuthorVerifier + ?Sized,
+{
+    fn verify(&mut self, author_url: &str, feed_urls: &[&str]) -> Verification {
+        (**self).verify(author_url, feed_urls)
+    }
+}
+
+/// A verifier which checks the `rel="me"` links of fetched pages.
+///
+/// The function returns the page at an author's URL, or `None` if the page could not be
+/// fetched.
+pub struct RelMeVerifier<F> {
+    fetch: F,
+}
+
+impl<F> RelMeVerifier<F>
+where
+    F: FnMut(&str) -> Option<AuthorPage>,
+{
+    /// Instantiates a verifier with a function which fetches pages.
+    pub fn new(fetch: F) -> Self {
+        Self { fetch }
+    }
+}
+
+impl<F> core::fmt::Debug for RelMeVerifier<F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RelMeVerifier").finish()
+    }
+}
+
+impl<F> AuthorVerifier for RelMeVerifier<F>
+where
+    F: FnMut(&str) -> Option<AuthorPage>,
+{
+    fn verify(&mut self, author_url: &str, feed_urls: &[&str]) -> Verification {
+        match (self.fetch)(author_url) {
+            Some(page) if page.claims(feed_urls) => Verification::Verified,
+            Some(_) => Verification::Unverified,
+            None => Verification::Unknown,
+        }
+    }
+}
+
+/// The result of verifying an author in a feed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct AuthorVerification {
+    /// The [JSON Pointer][json_pointer] to the author.
+    ///
+    /// [json_pointer]: https://tools.ietf.org/html/rfc6901
+    pub path: String,
+    /// The author's URL.
+    pub url: String,
+    /// The result.
+    pub result: Verification,
+}
+
+/// Adds the paths and URLs of the authors in an object's `author` and `authors` properties.
+fn author_urls(map: &Map<String, Value>, path: &str, authors: &mut Vec<(String, String)>) {
+    if let Some(Value::Object(author)) = map.get("author") {
+        if let Some(Value::String(url)) = author.get("url") {
+            authors.push((format!("{}/author", path), url.clone()));
+        }
+    }
+    if let Some(Value::Array(values)) = map.get("authors") {
+        for (index, value) in values.iter().enumerate() {
+            if let Some(Value::String(url)) = value.get("url") {
+                authors.push((format!("{}/authors/{}", path, index), url.clone()));
+            }
+        }
+    }
+}
+
+fn verify_authors<V>(
+    map: &mut Map<String, Value>,
+    verifier: &mut V,
+) -> Result<Vec<AuthorVerification>, Error>
+where
+    V: AuthorVerifier + ?Sized,
+{
+    let feed = FeedRef::from(&*map);
+    let feed_urls = [feed.home_page_url()?, feed.feed_url()?]
+        .iter()
+        .flatten()
+        .copied()
+        .map(String::from)
+        .collect::<Vec<_>>();
+    if feed_urls.is_empty() {
+        return Ok(Vec::new());
+    }
+    let feed_urls = feed_urls.iter().map(String::as_str).collect::<Vec<_>>();
+
+    let mut authors = Vec::new();
+    author_urls(map, "", &mut authors);
+    for (index, item) in crate::item_refs(map)?.iter().enumerate() {
+        author_urls(item.as_map(), &format!("/items/{}", index), &mut authors);
+    }
+
+    // The same author is usually listed on many items, so each URL is checked once.
+    let mut results = BTreeMap::new();
+    let mut verifications = Vec::with_capacity(authors.len());
+    for (path, url) in authors {
+        let result = *results
+            .entry(url.clone())
+            .or_insert_with(|| verifier.verify(&url, &feed_urls));
+        let verified = match result {
+            Verification::Verified => true,
+            Verification::Unverified => false,
+            Verification::Unknown => {
+                verifications.push(AuthorVerification { path, url, result });
+                continue;
+            }
+        };
+        if let Some(Value::Object(author)) = crate::pointer::get_pointer_mut(map, &path) {
+            let mut marker = Map::new();
+            marker.insert(String::from("url"), Value::String(url.clone()));
+            marker.insert(String::from("verified"), Value::Bool(verified));
+            author.insert(String::from(VERIFIED_KEY), Value::Object(marker));
+        }
+        verifications.push(AuthorVerification { path, url, result });
+    }
+    Ok(verifications)
+}
+
+macro_rules! verify_authors_methods {
+    () => {
+        /// Verifies the URLs of the feed's authors and the items' authors and records the
+        /// results in the authors' `_verified` extensions.
+        ///
+        /// Each distinct URL is verified once. If the result is `Verification::Unknown`, the
+        /// author is not changed. If the feed has neither a `home_page_url` nor a `feed_url`,
+        /// there is nothing which can be claimed, so no authors are verified. Values which
+        /// are not the expected JSON type are skipped.
+        ///
+        /// # Errors
+        ///
+        /// If the `home_page_url` or `feed_url` is not a JSON string or the `items` are not a
+        /// JSON array of JSON objects, then `Error::UnexpectedType` is returned.
+        pub fn verify_authors<V>(
+            &mut self,
+            verifier: &mut V,
+        ) -> Result<Vec<AuthorVerification>, Error>
+        where
+            V: AuthorVerifier + ?Sized,
+        {
+            verify_authors(self.as_map_mut(), verifier)
+        }
+    };
+}
+
+impl Feed {
+    verify_authors_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    verify_authors_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    use alloc::{string::String, vec, vec::Vec};
+    use serde_json::json;
+
+    #[test]
+    fn rel_me_links() {
+        let mut page = AuthorPage::new(
+            "https://a.example.com/about/",
+            r#"<link rel="me authn" href="/feed.json"><a rel=me href="https://x.example/?a=1&amp;b=2">x</a>
+            <a href="https://not.example/">y</a>"#,
+        );
+        page.link_headers.push(String::from(
+            r#"<https://z.example/a,b>; rel="me", <https://w.example/>; rel="next""#,
+        ));
+        assert_eq!(
+            page.rel_me_links(),
+            vec![
+                "https://a.example.com/feed.json",
+                "https://x.example/?a=1&b=2",
+                "https://z.example/a,b"
+            ]
+        );
+        assert!(page.claims(&["http://www.z.example/a,b"]));
+        assert!(!page.claims(&["https://w.example/"]));
+    }
+
+    #[test]
+    fn verification() -> Result<(), Error> {
+        let mut feed = crate::from_value(json!({
+            "feed_url": "https://blog.example.org/feed.json",
+            "items": [
+                { "id": "1", "authors": [{ "url": "https://a.example/" }, { "name": "B" }] },
+                { "id": "2", "authors": [{ "url": "https://a.example/" }, { "url": "https://c.example/" }] }
+            ]
+        }))?;
+        let mut fetched = Vec::new();
+        let mut verifier = RelMeVerifier::new(|url: &str| {
+            fetched.push(String::from(url));
+            if url == "https://a.example/" {
+                Some(AuthorPage::new(
+                    url,
+                    r#"<a rel="me" href="https://blog.example.org/feed.json">"#,
+                ))
+            } else {
+                None
+            }
+        });
+        let results = feed.verify_authors(&mut verifier)?;
+        assert_eq!(
+            results
+                .iter()
+                .map(|v| (v.path.as_str(), v.result))
+                .collect::<Vec<_>>(),
+            vec![
+                ("/items/0/authors/0", Verification::Verified),
+                ("/items/1/authors/0", Verification::Verified),
+                ("/items/1/authors/1", Verification::Unknown),
+            ]
+        );
+        assert_eq!(fetched, vec!["https://a.example/", "https://c.example/"]);
+        assert_eq!(
+            feed.as_map()["items"][1]["authors"][0]["_verified"]["verified"],
+            true
+        );
+        assert!(feed.as_map()["items"][1]["authors"][1]
+            .get("_verified")
+            .is_none());
+
+        feed.as_map_mut().remove("feed_url");
+        assert!(feed
+            .verify_authors(&mut RelMeVerifier::new(|_: &str| None))?
+            .is_empty());
+        Ok(())
+    }
+}
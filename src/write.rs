@@ -0,0 +1,154 @@
+//! Writing feeds to files.
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use serde_json::{Map, Value};
+
+use crate::{Error, Feed, FeedMut, FeedRef, SerializeOptions};
+
+/// Distinguishes temporary files created by the same process.
+static TEMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns a path for a temporary file next to `path`.
+///
+/// The file must be in the same directory so that it can be renamed over `path`.
+fn temp_path(path: &Path) -> io::Result<PathBuf> {
+    let file_name = path.file_name().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "path does not have a file name",
+        )
+    })?;
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(file_name);
+    temp_name.push(format!(
+        ".{}.{}.tmp",
+        std::process::id(),
+        TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    Ok(path.with_file_name(temp_name))
+}
+
+fn write_temp_file(bytes: &[u8], temp_path: &Path) -> io::Result<()> {
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(temp_path)?;
+    file.write_all(bytes)?;
+    file.sync_all()
+}
+
+/// Writes bytes to a temporary file next to `path` and renames it to `path`.
+pub(crate) fn write_bytes_atomic(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let temp_path = temp_path(path)?;
+    let result = write_temp_file(bytes, &temp_path).and_then(|()| fs::rename(&temp_path, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+        return result;
+    }
+
+    // Syncing the directory persists the rename. Not all platforms support opening a
+    // directory, so failures are ignored.
+    if let Some(dir) = path.parent() {
+        let dir = if dir.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            dir
+        };
+        if let Ok(dir) = File::open(dir) {
+            let _ = dir.sync_all();
+        }
+    }
+    Ok(())
+}
+
+fn write_to_path_atomic(
+    map: &Map<String, Value>,
+    path: &Path,
+    options: &SerializeOptions,
+) -> Result<(), Error> {
+    let map = crate::canonical::SortedMap(map);
+    let bytes = if options.pretty {
+        serde_json::to_vec_pretty(&map)?
+    } else {
+        serde_json::to_vec(&map)?
+    };
+    write_bytes_atomic(path, &bytes)?;
+    Ok(())
+}
+
+macro_rules! write_methods {
+    () => {
+        /// Writes the feed as JSON to a file without readers ever observing a partially written
+        /// file.
+        ///
+        /// The JSON is written to a temporary file in the same directory as `path`. The
+        /// temporary file is synced to disk and then renamed to `path`, replacing any existing
+        /// file.
+        ///
+        /// # Errors
+        ///
+        /// If the file cannot be written or renamed, then `Error::Io(std::io::Error)` is
+        /// returned.
+        ///
+        /// If the feed cannot be serialized, then `Error::SerdeJson(serde_json::Error)` is
+        /// returned.
+        pub fn write_to_path_atomic<P: AsRef<Path>>(
+            &self,
+            path: P,
+            options: &SerializeOptions,
+        ) -> Result<(), Error> {
+            write_to_path_atomic(self.as_map(), path.as_ref(), options)
+        }
+    };
+}
+
+impl Feed {
+    write_methods!();
+}
+
+impl<'a> FeedMut<'a> {
+    write_methods!();
+}
+
+impl<'a> FeedRef<'a> {
+    write_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn atomic_write() -> Result<(), Error> {
+        let dir = std::env::temp_dir().join(format!("json-feed-model-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let path = dir.join("feed.json");
+
+        let mut feed = Feed::new();
+        feed.set_title("First");
+        feed.write_to_path_atomic(&path, &SerializeOptions::new())?;
+        assert_eq!(fs::read_to_string(&path)?, r#"{"title":"First"}"#);
+
+        feed.set_title("Second");
+        feed.write_to_path_atomic(&path, &SerializeOptions::pretty())?;
+        assert_eq!(crate::from_slice(&fs::read(&path)?)?, feed);
+        assert_eq!(fs::read_dir(&dir)?.count(), 1);
+
+        assert!(matches!(
+            feed.write_to_path_atomic(
+                dir.join("missing").join("feed.json"),
+                &SerializeOptions::new()
+            ),
+            Err(Error::Io(_))
+        ));
+
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
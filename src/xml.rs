@@ -0,0 +1,188 @@
+//! Encoding text for XML documents such as RSS and Atom feeds.
+//!
+//! XML 1.0 does not allow most control characters, even as character references, so they are
+//! removed. Text in a CDATA section cannot contain `]]>`, so the section is split around it.
+//!
+//! ```
+//! use json_feed_model::Item;
+//!
+//! let mut item = Item::new();
+//! item.set_content_html("<p>a[b]]>c\u{1}</p>");
+//!
+//! assert_eq!(
+//!     item.content_html_escaped_for_xml()?.as_deref(),
+//!     Some("&lt;p&gt;a[b]]&gt;c&lt;/p&gt;")
+//! );
+//! assert_eq!(
+//!     item.content_as_cdata()?.as_deref(),
+//!     Some("<![CDATA[<p>a[b]]]]><![CDATA[>c</p>]]>")
+//! );
+//! # Ok::<(), json_feed_model::Error>(())
+//! ```
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::string::String;
+#[cfg(feature = "std")]
+use std::string::String;
+
+use serde_json::{Map, Value};
+
+use crate::{ext::__private::str_prop, Error, Item, ItemMut, ItemRef};
+
+/// Returns true if the character is allowed in an XML 1.0 document.
+///
+/// See the [`Char` production][xml_char].
+///
+/// [xml_char]: https://www.w3.org/TR/xml/#charsets
+#[must_use]
+pub fn is_valid_char(c: char) -> bool {
+    matches!(c, '\t' | '\n' | '\r' | ' '..='\u{fffd}' | '\u{10000}'..='\u{10ffff}')
+}
+
+/// Escapes the text into the output and removes characters which are not allowed in XML.
+pub(crate) fn escape_into(text: &str, out: &mut String) {
+    for c in text.chars().filter(|c| is_valid_char(*c)) {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(c),
+        }
+    }
+}
+
+/// Returns the text escaped for XML element content or attribute values.
+///
+/// `&`, `<`, `>`, `"`, and `'` are replaced with entity references, and characters which are
+/// not allowed in XML are removed.
+#[must_use]
+pub fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    escape_into(text, &mut out);
+    out
+}
+
+/// Returns the text in a CDATA section.
+///
+/// Every `]]>` in the text is split across two CDATA sections, and characters which are not
+/// allowed in XML are removed.
+#[must_use]
+pub fn cdata(text: &str) -> String {
+    // Removes invalid characters first so that removing them cannot form a `]]>`.
+    let text = text
+        .chars()
+        .filter(|c| is_valid_char(*c))
+        .collect::<String>();
+    let mut out = String::with_capacity(text.len() + 12);
+    out.push_str("<![CDATA[");
+    // Ends a section after `]]` so that `>` starts the next section.
+    out.push_str(&text.replace("]]>", "]]]]><![CDATA[>"));
+    out.push_str("]]>");
+    out
+}
+
+fn content_html_escaped_for_xml(map: &Map<String, Value>) -> Result<Option<String>, Error> {
+    Ok(str_prop::get(map, None, "content_html")?.map(escape))
+}
+
+fn content_as_cdata(map: &Map<String, Value>) -> Result<Option<String>, Error> {
+    let content = match str_prop::get(map, None, "content_html")? {
+        Some(html) => Some(html),
+        None => str_prop::get(map, None, "content_text")?,
+    };
+    Ok(content.map(cdata))
+}
+
+macro_rules! xml_methods {
+    () => {
+        /// Returns the `content_html` escaped for embedding in an XML element.
+        ///
+        /// See [`xml::escape`](crate::xml::escape).
+        ///
+        /// # Errors
+        ///
+        /// If the `content_html` is not a JSON string, then `Error::UnexpectedType` is
+        /// returned.
+        pub fn content_html_escaped_for_xml(&self) -> Result<Option<String>, Error> {
+            content_html_escaped_for_xml(self.as_map())
+        }
+
+        /// Returns the content in a CDATA section for embedding in an XML element.
+        ///
+        /// The `content_html` is preferred. Otherwise, the `content_text` is used. See
+        /// [`xml::cdata`](crate::xml::cdata).
+        ///
+        /// # Errors
+        ///
+        /// If the content is not a JSON string, then `Error::UnexpectedType` is returned.
+        pub fn content_as_cdata(&self) -> Result<Option<String>, Error> {
+            content_as_cdata(self.as_map())
+        }
+    };
+}
+
+impl Item {
+    xml_methods!();
+}
+
+impl<'a> ItemMut<'a> {
+    xml_methods!();
+}
+
+impl<'a> ItemRef<'a> {
+    xml_methods!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encoding() {
+        assert_eq!(escape("a & 'b'\u{b}\u{fffe}"), "a &amp; &apos;b&apos;");
+        assert_eq!(cdata(""), "<![CDATA[]]>");
+        assert_eq!(
+            cdata("]]>]]>\u{0}"),
+            "<![CDATA[]]]]><![CDATA[>]]]]><![CDATA[>]]>"
+        );
+        assert!(is_valid_char('\u{1f600}'));
+        assert!(!is_valid_char('\u{ffff}'));
+    }
+
+    #[test]
+    fn cdata_end_markers() {
+        assert_eq!(cdata("a]]>b"), "<![CDATA[a]]]]><![CDATA[>b]]>");
+        assert_eq!(cdata("]]]>"), "<![CDATA[]]]]]><![CDATA[>]]>");
+        assert_eq!(cdata("]]"), "<![CDATA[]]]]>");
+        assert_eq!(cdata("]]\u{1}>"), "<![CDATA[]]]]><![CDATA[>]]>");
+        assert_eq!(cdata("é]]>😀"), "<![CDATA[é]]]]><![CDATA[>😀]]>");
+    }
+
+    #[test]
+    fn content() -> Result<(), Error> {
+        let mut item = Item::new();
+        assert_eq!(item.content_as_cdata()?, None);
+        assert_eq!(item.content_html_escaped_for_xml()?, None);
+
+        item.set_content_text("a]]>b");
+        assert_eq!(
+            item.content_as_cdata()?.as_deref(),
+            Some("<![CDATA[a]]]]><![CDATA[>b]]>")
+        );
+        assert_eq!(item.content_html_escaped_for_xml()?, None);
+
+        item.as_map_mut()
+            .insert(String::from("content_html"), Value::Bool(true));
+        assert!(matches!(
+            item.content_as_cdata(),
+            Err(Error::UnexpectedType)
+        ));
+        assert!(matches!(
+            item.content_html_escaped_for_xml(),
+            Err(Error::UnexpectedType)
+        ));
+        Ok(())
+    }
+}